@@ -0,0 +1,135 @@
+use client::database::{
+    DataFormat, DataKind, convert_dir, convert_file, roundtrip_data,
+};
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage:
+  datatool export <items|npcs|shops|maps> <json|toml> <out_dir>
+  datatool import <items|npcs|shops|maps> <json|toml> <in_dir>
+  datatool convert <items|npcs|shops|maps> <input> <output>
+  datatool check <items|npcs|shops|maps> <json|toml>
+
+export writes every .bin file of the kind from ./data into text files,
+import writes text files back into ./data as .bin files, convert picks the
+direction from the file extensions and check round trips every .bin file
+through the text format to make sure nothing is lost. Maps only convert to
+json as toml can not hold their empty zone slots.";
+
+// Converts the speedy .bin data files to pretty JSON or TOML and back so data
+// changes can be reviewed in git. Run from the client folder so ./data
+// resolves.
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["export", kind, format, dir] => parse(kind, format)
+            .and_then(|(kind, format)| export(kind, format, Path::new(dir))),
+        ["import", kind, format, dir] => parse(kind, format)
+            .and_then(|(kind, format)| import(kind, format, Path::new(dir))),
+        ["convert", kind, input, output] => match DataKind::parse(kind) {
+            Some(kind) => {
+                convert_file(kind, Path::new(input), Path::new(output))
+                    .map_err(|e| e.to_string())
+            }
+            None => Err(format!("Unknown data kind {kind}")),
+        },
+        ["check", kind, format] => {
+            parse(kind, format).and_then(|(kind, format)| check(kind, format))
+        }
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            println!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse(kind: &str, format: &str) -> Result<(DataKind, DataFormat), String> {
+    let Some(kind) = DataKind::parse(kind) else {
+        return Err(format!("Unknown data kind {kind}"));
+    };
+
+    match DataFormat::parse(format) {
+        Some(DataFormat::Bin) | None => {
+            Err(format!("Unknown text format {format}, use json or toml"))
+        }
+        Some(format) => Ok((kind, format)),
+    }
+}
+
+fn export(
+    kind: DataKind,
+    format: DataFormat,
+    dir: &Path,
+) -> Result<(), String> {
+    let count = convert_dir(
+        kind,
+        Path::new(kind.folder()),
+        dir,
+        DataFormat::Bin,
+        format,
+    )
+    .map_err(|e| e.to_string())?;
+
+    println!("Exported {count} {kind} file(s) to {}", dir.display());
+    Ok(())
+}
+
+fn import(
+    kind: DataKind,
+    format: DataFormat,
+    dir: &Path,
+) -> Result<(), String> {
+    let count = convert_dir(
+        kind,
+        dir,
+        Path::new(kind.folder()),
+        format,
+        DataFormat::Bin,
+    )
+    .map_err(|e| e.to_string())?;
+
+    println!("Imported {count} {kind} file(s) from {}", dir.display());
+    Ok(())
+}
+
+fn check(kind: DataKind, format: DataFormat) -> Result<(), String> {
+    let entries =
+        std::fs::read_dir(kind.folder()).map_err(|e| e.to_string())?;
+    let mut failed = 0;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if DataFormat::from_extension(&path) != Some(DataFormat::Bin) {
+            continue;
+        }
+
+        let data = std::fs::read(&path).map_err(|e| e.to_string())?;
+
+        match roundtrip_data(kind, &data, format) {
+            Ok(true) => {}
+            Ok(false) => {
+                println!("{} changed after the round trip", path.display());
+                failed += 1;
+            }
+            Err(e) => {
+                println!("{} failed to convert: {e}", path.display());
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{failed} {kind} file(s) failed the round trip"));
+    }
+
+    println!("All {kind} files round trip through {}", format.extension());
+    Ok(())
+}
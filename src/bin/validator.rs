@@ -0,0 +1,21 @@
+use client::database::{ContentLimits, validate_data_dir};
+use std::process::ExitCode;
+
+// Checks the item, npc and shop data files for anything that would break the
+// client. Run from the client folder so ./data, ./images and ./audio resolve.
+fn main() -> ExitCode {
+    let limits = ContentLimits::from_dirs();
+    let issues = validate_data_dir(&limits);
+
+    for issue in &issues {
+        println!("{issue}");
+    }
+
+    if issues.is_empty() {
+        println!("No content issues found.");
+        ExitCode::SUCCESS
+    } else {
+        println!("{} content issue(s) found.", issues.len());
+        ExitCode::FAILURE
+    }
+}
@@ -2,8 +2,10 @@ mod mapitem_data;
 mod npc_data;
 mod player_data;
 mod shared_data;
+mod snapshot;
 
 pub use mapitem_data::*;
 pub use npc_data::*;
 pub use player_data::*;
 pub use shared_data::*;
+pub use snapshot::*;
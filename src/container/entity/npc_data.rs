@@ -1,13 +1,11 @@
-use std::collections::VecDeque;
-
 use ascending_graphics::*;
 
 use crate::{
-    AttackFrame, Attacking, DeathType, EntityName, EntityNameMap, HPBar,
-    LightData, Movement, MovementData, Physical, Position, SpriteImage,
-    SpriteIndex, Vitals,
+    AttackFrame, Attacking, DeathFade, DeathType, EntityName, EntityNameMap,
+    HPBar, LightData, Movement, Physical, Position, SnapshotBuffer,
+    SpriteImage, SpriteIndex, Vitals,
 };
-use mmap_bytey::{MByteBuffer, MByteBufferRead, MByteBufferWrite};
+use mmap_bytey::{MByteBufferRead, MByteBufferWrite};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Default)]
@@ -32,6 +30,7 @@ pub struct NpcEntity {
     // Frame
     pub last_move_frame: usize,
     pub attack_frame: AttackFrame,
+    pub death_fade: DeathFade,
 
     // Combat
     pub level: i32,
@@ -44,7 +43,7 @@ pub struct NpcEntity {
     // Movement
     pub movement: Movement,
     pub end_movement: Position,
-    pub movement_buffer: VecDeque<MovementData>,
+    pub snapshots: SnapshotBuffer,
     pub dir: u8,
 
     // Location
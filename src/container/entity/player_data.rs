@@ -1,14 +1,12 @@
-use std::collections::VecDeque;
-
-use educe::Educe;
 use ascending_graphics::*;
-use mmap_bytey::{MByteBuffer, MByteBufferRead, MByteBufferWrite};
+use educe::Educe;
+use mmap_bytey::{MByteBufferRead, MByteBufferWrite};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    AttackFrame, Attacking, DeathType, EntityName, EntityNameMap, GlobalKey,
-    HPBar, LightData, MAX_EQPT, Movement, MovementData, Physical, Position,
-    SpriteImage, SpriteIndex, Vitals, content::PlayerPvP,
+    AttackFrame, Attacking, DeathFade, DeathType, EntityName, EntityNameMap,
+    GlobalKey, HPBar, LightData, MAX_EQPT, Movement, Physical, Position,
+    SnapshotBuffer, SpriteImage, SpriteIndex, Vitals, content::PlayerPvP,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -34,6 +32,7 @@ pub struct PlayerEntity {
     // Frame
     pub last_move_frame: usize,
     pub attack_frame: AttackFrame,
+    pub death_fade: DeathFade,
 
     // Combat
     pub level: i32,
@@ -46,7 +45,7 @@ pub struct PlayerEntity {
     // Movement
     pub movement: Movement,
     pub end_movement: Position,
-    pub movement_buffer: VecDeque<MovementData>,
+    pub snapshots: SnapshotBuffer,
     pub dir: u8,
 
     // Location
@@ -54,7 +53,16 @@ pub struct PlayerEntity {
     pub pos_offset: Vec2,
 }
 
-#[derive(Copy, Clone, Debug, Default, MByteBufferRead, MByteBufferWrite)]
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Default,
+    MByteBufferRead,
+    MByteBufferWrite,
+)]
 pub enum UserAccess {
     #[default]
     None,
@@ -126,3 +134,17 @@ pub struct Equipment {
     #[educe(Default = (0..MAX_EQPT).map(|_| Item::default()).collect())]
     pub items: Vec<Item>,
 }
+
+/// Details of another player sent by the server in answer to an inspect
+/// request.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, MByteBufferRead, MByteBufferWrite,
+)]
+pub struct InspectData {
+    pub entity: GlobalKey,
+    pub username: String,
+    pub user_access: UserAccess,
+    pub level: i32,
+    pub pvp: PlayerPvP,
+    pub equipment: Equipment,
+}
@@ -1,14 +1,13 @@
-use educe::Educe;
 use ascending_graphics::*;
-use mmap_bytey::{MByteBuffer, MByteBufferRead, MByteBufferWrite};
+use educe::Educe;
+use mmap_bytey::{MByteBufferRead, MByteBufferWrite};
 use serde::{Deserialize, Serialize};
-use speedy::{Endianness, Readable, Writable};
+use speedy::{Readable, Writable};
 use std::collections::VecDeque;
 
 use crate::{Direction, GfxType, VITALS_MAX};
 
 pub enum MovementType {
-    MovementBuffer,
     Manual(u8, Option<Position>),
 }
 
@@ -137,6 +136,21 @@ pub struct AttackTimer(pub f32);
 #[derive(Copy, Clone, Debug, Default)]
 pub struct EntityLight(pub Option<Index>);
 
+/// Sprite tint an entity fades between when its `DeathType` changes.
+#[derive(Copy, Clone, Debug, Educe)]
+#[educe(Default)]
+pub struct DeathFade {
+    #[educe(Default = [255; 4])]
+    pub color: [u8; 4],
+    #[educe(Default = [255; 4])]
+    pub from: [u8; 4],
+    #[educe(Default = [255; 4])]
+    pub to: [u8; 4],
+    pub start: f32,
+    pub duration: f32,
+    pub active: bool,
+}
+
 #[derive(Copy, Clone, Debug, Default, MByteBufferRead, MByteBufferWrite)]
 pub struct AttackFrame {
     pub frame: usize,
@@ -221,3 +235,15 @@ pub enum DeathType {
     UnSpawned,
     Spawning,
 }
+
+impl DeathType {
+    /// Dead players and their spirits can't fight and show as dead.
+    pub fn is_dead(self) -> bool {
+        matches!(self, DeathType::Dead | DeathType::Spirit)
+    }
+
+    /// Whether the entity can be targeted and attacked.
+    pub fn is_targetable(self) -> bool {
+        matches!(self, DeathType::Alive | DeathType::Spawning)
+    }
+}
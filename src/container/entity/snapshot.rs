@@ -0,0 +1,250 @@
+use ascending_graphics::*;
+use std::collections::VecDeque;
+
+use crate::{Position, TILE_SIZE};
+
+/// Seconds a remote entity takes for one tile, as our own player.
+pub const STEP_TIME: f32 = 0.2;
+/// Playback delay without any jitter.
+pub const MIN_PLAYBACK_DELAY: f32 = 0.03;
+/// Upper bound of the playback delay, however bad the jitter gets.
+pub const MAX_PLAYBACK_DELAY: f32 = 0.4;
+/// Playback delay added per second of measured jitter.
+pub const JITTER_DELAY_FACTOR: f32 = 2.0;
+/// Seconds an entity keeps walking past its last snapshot when the next
+/// one is late, it then walks back onto the tile in the same time.
+pub const MAX_EXTRAPOLATION: f32 = 0.06;
+/// Moves over more tiles than this are shown as a jump.
+pub const SNAP_DISTANCE: i32 = 2;
+/// A gap between moves longer than this many steps means the entity stood
+/// still in between, it is left out of the jitter estimate.
+const STOP_STEPS: f32 = 2.0;
+/// Shortest step played back, as a share of the step time, so bursts of
+/// late packets are caught up quickly but not in a single frame.
+const MIN_STEP_SHARE: f32 = 0.5;
+/// Weight of a new sample in the jitter average.
+const SMOOTHING: f32 = 0.125;
+const SNAPSHOT_LIMIT: usize = 32;
+
+/// Tile an entity reaches at `time`, in the local clock.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Snapshot {
+    pub time: f32,
+    pub pos: Position,
+    pub dir: u8,
+}
+
+/// Where an entity is drawn at one moment, `progress` of the way from
+/// `from` to `to`. Past 1.0 while it is extrapolated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Playback {
+    pub from: Position,
+    pub to: Position,
+    pub dir: u8,
+    pub progress: f32,
+    pub moving: bool,
+}
+
+/// Share of a step an entity is drawn past its last snapshot when that is
+/// `late` seconds behind, walking on and then back onto the tile.
+fn overshoot(late: f32) -> f32 {
+    (MAX_EXTRAPOLATION - (late - MAX_EXTRAPOLATION).abs()).max(0.0) / STEP_TIME
+}
+
+fn world_tile(pos: Position) -> (i32, i32) {
+    (pos.map.x * 32 + pos.x, pos.map.y * 32 + pos.y)
+}
+
+impl Playback {
+    fn settled(snapshot: &Snapshot) -> Self {
+        Playback {
+            from: snapshot.pos,
+            to: snapshot.pos,
+            dir: snapshot.dir,
+            progress: 1.0,
+            moving: false,
+        }
+    }
+
+    fn between(from: &Snapshot, to: &Snapshot, progress: f32) -> Self {
+        let (from_x, from_y) = world_tile(from.pos);
+        let (to_x, to_y) = world_tile(to.pos);
+
+        if from.pos == to.pos
+            || from.pos.map.group != to.pos.map.group
+            || (to_x - from_x).abs() + (to_y - from_y).abs() > SNAP_DISTANCE
+        {
+            return Playback::settled(to);
+        }
+
+        Playback {
+            from: from.pos,
+            to: to.pos,
+            dir: to.dir,
+            progress,
+            moving: true,
+        }
+    }
+
+    /// Pixels from the `from` tile to where the entity is drawn.
+    pub fn offset(&self) -> Vec2 {
+        let (from_x, from_y) = world_tile(self.from);
+        let (to_x, to_y) = world_tile(self.to);
+
+        Vec2::new((to_x - from_x) as f32, (to_y - from_y) as f32)
+            * TILE_SIZE as f32
+            * self.progress
+    }
+}
+
+/// Jitter buffer of the moves received for a remote entity. Moves are
+/// played back a little later than they arrive, the delay grows with the
+/// jitter between packets so late ones still arrive in time.
+#[derive(Clone, Debug)]
+pub struct SnapshotBuffer {
+    snapshots: VecDeque<Snapshot>,
+    /// Last snapshot dropped from the front, the segment to extrapolate.
+    previous: Option<Snapshot>,
+    last_arrival: Option<f32>,
+    jitter: f32,
+}
+
+impl Default for SnapshotBuffer {
+    fn default() -> Self {
+        SnapshotBuffer {
+            snapshots: VecDeque::with_capacity(SNAPSHOT_LIMIT),
+            previous: None,
+            last_arrival: None,
+            jitter: 0.0,
+        }
+    }
+}
+
+impl SnapshotBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Measured average deviation from the expected packet times.
+    pub fn jitter(&self) -> f32 {
+        self.jitter
+    }
+
+    pub fn delay(&self) -> f32 {
+        (MIN_PLAYBACK_DELAY + self.jitter * JITTER_DELAY_FACTOR)
+            .clamp(MIN_PLAYBACK_DELAY, MAX_PLAYBACK_DELAY)
+    }
+
+    /// Forgets every move and places the entity on `pos`, after a warp.
+    pub fn reset(&mut self, now: f32, pos: Position, dir: u8) {
+        self.snapshots.clear();
+        self.previous = None;
+        self.last_arrival = None;
+        self.snapshots.push_back(Snapshot {
+            time: now,
+            pos,
+            dir,
+        });
+    }
+
+    /// A move to `pos` arrived at `now`. `current` is where the entity
+    /// stands when nothing was received yet.
+    pub fn push(
+        &mut self,
+        now: f32,
+        current: Position,
+        pos: Position,
+        dir: u8,
+    ) {
+        if self.snapshots.is_empty() {
+            self.snapshots.push_back(Snapshot {
+                time: f32::MIN,
+                pos: current,
+                dir,
+            });
+        }
+
+        let Some(last) = self.snapshots.back().copied() else {
+            return;
+        };
+
+        if last.pos == pos {
+            return;
+        }
+
+        let render = now - self.delay();
+
+        if let Some(last_arrival) = self.last_arrival.replace(now) {
+            let interval = now - last_arrival;
+
+            if interval < STEP_TIME * STOP_STEPS {
+                let deviation = (interval - STEP_TIME).abs();
+
+                self.jitter += (deviation - self.jitter) * SMOOTHING;
+            }
+        }
+
+        // Each move is played as a step that starts when it arrives. A late
+        // move continues from where the extrapolated entity is drawn.
+        let late = render - last.time;
+        let time = if late > 0.0 && late <= MAX_EXTRAPOLATION * 2.0 {
+            render + STEP_TIME * (1.0 - overshoot(late))
+        } else {
+            (now + STEP_TIME).max(last.time + STEP_TIME * MIN_STEP_SHARE)
+        };
+
+        if time - last.time > STEP_TIME {
+            self.snapshots.push_back(Snapshot {
+                time: time - STEP_TIME,
+                ..last
+            });
+        }
+
+        self.snapshots.push_back(Snapshot { time, pos, dir });
+
+        while self.snapshots.len() > SNAPSHOT_LIMIT {
+            self.previous = self.snapshots.pop_front();
+        }
+    }
+
+    /// Where the entity is drawn at `now`, or None before any move.
+    pub fn sample(&mut self, now: f32) -> Option<Playback> {
+        let render = now - self.delay();
+
+        while self.snapshots.len() > 1
+            && self
+                .snapshots
+                .get(1)
+                .is_some_and(|next| next.time <= render)
+        {
+            self.previous = self.snapshots.pop_front();
+        }
+
+        let current = self.snapshots.front()?;
+
+        if let Some(next) = self.snapshots.get(1) {
+            if render <= current.time {
+                return Some(Playback::settled(current));
+            }
+
+            let progress = (render - current.time) / (next.time - current.time);
+
+            return Some(Playback::between(current, next, progress.min(1.0)));
+        }
+
+        let late = render - current.time;
+
+        match self.previous {
+            Some(previous) if late > 0.0 && late <= MAX_EXTRAPOLATION * 2.0 => {
+                let progress = 1.0 + overshoot(late);
+
+                Some(Playback::between(&previous, current, progress))
+            }
+            _ => Some(Playback::settled(current)),
+        }
+    }
+}
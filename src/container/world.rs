@@ -1,7 +1,7 @@
-use crate::{ClientError, Result, content::MapItem};
+use crate::{ClientError, Result};
 use bytey::{ByteBuffer, ByteBufferRead, ByteBufferWrite};
 use mmap_bytey::{MByteBuffer, MByteBufferRead, MByteBufferWrite};
-use slotmap::{Key, KeyData, SecondaryMap, SlotMap, new_key_type};
+use slotmap::{Key, KeyData, SecondaryMap, new_key_type};
 
 use super::{MapItemEntity, NpcEntity, PlayerEntity};
 
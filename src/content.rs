@@ -1,17 +1,19 @@
-use crate::{Config, Result, SystemHolder, World, data_types::*, database::*};
+use crate::{Result, State, SystemHolder, World, data_types::*, database::*};
 
 pub mod game_content;
 pub mod inputs;
 pub mod menu_content;
 pub mod resource;
 
+use ascending_camera::controls::FlatControls;
+use ascending_graphics::{MapRenderer, Vec2};
+pub use ascending_time::Instant;
 pub use game_content::*;
-use ascending_graphics::MapRenderer;
 pub use inputs::*;
 use log::info;
 pub use menu_content::*;
 pub use resource::*;
-pub use ascending_time::Instant;
+use winit::dpi::PhysicalSize;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum ContentType {
@@ -44,6 +46,28 @@ impl Content {
         Ok(content)
     }
 
+    /// Lays the screen out again after the window was resized from
+    /// `old_size`.
+    pub fn resize(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+        graphics: &mut State<FlatControls>,
+        old_size: PhysicalSize<f32>,
+    ) -> Result<()> {
+        let screen = Vec2::new(systems.size.width, systems.size.height);
+        let old_screen = Vec2::new(old_size.width, old_size.height);
+
+        systems.gfx.set_size(&systems.fade.f_image, screen);
+        systems.gfx.set_size(&systems.map_fade.f_image, screen);
+        let fps = systems.fps;
+        set_statistic_area(systems, &fps, 0);
+
+        self.menu_content.resize(systems);
+        self.game_content
+            .resize(world, systems, graphics, old_screen)
+    }
+
     pub fn switch_content(
         &mut self,
         world: &mut World,
@@ -5,39 +5,48 @@ use ascending_graphics::*;
 
 use indexmap::IndexSet;
 
-pub mod content_input;
+mod content_input;
 pub mod interface;
 
-pub use content_input::*;
 pub use interface::*;
 
 use crate::{
     Direction, Entity, EntityKind, GlobalKey, IsUsingType, MapPosition,
-    MovementType, Position, Result, SystemHolder, TILE_SIZE, content::*,
-    data_types::*, database::*, logic::*, send_attack, send_pickup, systems::*,
+    MovementType, Position, Result, SystemHolder, TILE_SIZE, Tooltip,
+    content::*, send_attack, send_pickup, systems::*,
 };
 
+pub mod combat_log;
+pub mod death;
+pub mod entity_menu;
 pub mod floating_text;
 pub mod map;
+pub mod nameplate;
 pub mod npc;
+pub mod party;
 pub mod player;
 pub mod player_data;
+pub mod prediction;
+pub mod status_effect;
 pub mod target;
 
+pub use combat_log::*;
+pub use death::*;
+pub use entity_menu::*;
 pub use floating_text::*;
 pub use map::*;
+pub use nameplate::*;
 pub use npc::*;
+pub use party::*;
 pub use player::*;
 pub use player_data::*;
+pub use prediction::*;
+pub use status_effect::*;
 pub use target::*;
 
 const KEY_ATTACK: usize = 0;
-const KEY_MOVEUP: usize = 1;
-const KEY_MOVELEFT: usize = 2;
-const KEY_MOVEDOWN: usize = 3;
-const KEY_MOVERIGHT: usize = 4;
-const KEY_PICKUP: usize = 5;
-const MAX_KEY: usize = 6;
+const KEY_PICKUP: usize = 1;
+const MAX_KEY: usize = 2;
 
 pub struct GameContent {
     pub players: Rc<RefCell<IndexSet<GlobalKey, ahash::RandomState>>>,
@@ -55,6 +64,17 @@ pub struct GameContent {
     pub pick_up_timer: f32,
     pub current_music: String,
     pub float_text: FloatingText,
+    pub death_screen: DeathScreen,
+    pub combat_log: CombatLog,
+    pub prediction: MovePredictor,
+    pub move_ghosts: MoveGhosts,
+    pub npc_nameplates: NpcNameplates,
+    pub status_effects: StatusEffects,
+    pub party: Party,
+    pub entity_menu: EntityMenu,
+    /// Map item our player walks next to and picks up, from the context
+    /// menu.
+    pub pickup_target: Option<GlobalKey>,
     pub refresh_map: bool,
     pub reconnect_count: usize,
     pub move_keypressed: Vec<ControlKey>,
@@ -92,6 +112,15 @@ impl GameContent {
             pick_up_timer: 0.0,
             current_music: String::new(),
             float_text: FloatingText::new(),
+            death_screen: DeathScreen::new(),
+            combat_log: CombatLog::new(),
+            prediction: MovePredictor::new(),
+            move_ghosts: MoveGhosts::new(systems),
+            npc_nameplates: NpcNameplates::new(),
+            status_effects: StatusEffects::new(),
+            party: Party::new(),
+            entity_menu: EntityMenu::new(systems),
+            pickup_target: None,
             refresh_map: false,
             reconnect_count: 0,
             move_keypressed: Vec::with_capacity(4),
@@ -107,6 +136,12 @@ impl GameContent {
             *key = false;
         });
         self.target.recreate(systems);
+        self.move_ghosts.recreate(systems);
+        self.npc_nameplates = NpcNameplates::new();
+        self.status_effects = StatusEffects::new();
+        self.party.clear();
+        self.entity_menu.recreate(systems);
+        self.pickup_target = None;
         self.float_text.recreate();
         self.finalized = false;
     }
@@ -132,11 +167,21 @@ impl GameContent {
         self.mapitems.borrow_mut().clear();
         self.finalized = false;
         self.myentity = None;
+        self.interface.save_window_state();
         self.interface.unload(systems);
         self.target.unload(systems);
+        self.move_ghosts.unload(systems);
+        self.npc_nameplates.unload(systems);
+        self.entity_menu.unload(systems);
+        self.pickup_target = None;
         self.map.unload(systems, map_renderer);
         self.player_data.unload();
         self.float_text.unload(systems);
+        self.death_screen.hide(systems);
+        self.combat_log = CombatLog::new();
+        self.prediction = MovePredictor::new();
+        self.status_effects = StatusEffects::new();
+        self.party.clear();
         systems.caret.index = None;
         Ok(())
     }
@@ -196,17 +241,65 @@ impl GameContent {
     ) -> Result<()> {
         self.finalize_entity(world, systems)?;
 
+        if let Some(myindex) = self.myentity
+            && let Some(Entity::Player(p_data)) = world.entities.get(myindex)
+        {
+            self.interface
+                .restore_window_state(systems, &p_data.entity_name.0);
+
+            let mut size = systems.gfx.get_size(&p_data.hp_bar.bar_index);
+            size.x = get_percent(
+                p_data.vitals.vital[0],
+                p_data.vitals.vitalmax[0],
+                18,
+            ) as f32;
+            systems.gfx.set_size(&p_data.hp_bar.bar_index, size);
+            systems
+                .gfx
+                .set_visible(&p_data.hp_bar.bar_index, p_data.hp_bar.visible);
+            systems
+                .gfx
+                .set_visible(&p_data.hp_bar.bg_index, p_data.hp_bar.visible);
+        }
+
+        self.refresh_interface(world, systems)?;
+
+        systems.gfx.set_visible(&self.game_lights, true);
+
+        if let Some(music) = &get_map_music(systems, self.map.mapindex[0])
+            && self.current_music != *music
+        {
+            self.current_music.clone_from(music);
+            systems.audio.set_music(format!("./audio/{music}"))?;
+        }
+
+        self.finalized = true;
+
+        update_camera(world, self, systems, graphics)?;
+
+        Ok(())
+    }
+
+    /// Fills the interface windows with the player data already received
+    /// from the server.
+    pub fn refresh_interface(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+    ) -> Result<()> {
         self.player_data.inventory.iter().enumerate().for_each(
             |(index, item)| {
                 self.interface
-                    .inventory
+                    .windows
+                    .get_mut::<Inventory>()
                     .update_inv_slot(systems, index, item);
             },
         );
         self.player_data.storage.iter().enumerate().for_each(
             |(index, item)| {
                 self.interface
-                    .storage
+                    .windows
+                    .get_mut::<Storage>()
                     .update_storage_slot(systems, index, item);
             },
         );
@@ -219,30 +312,42 @@ impl GameContent {
             let nextexp = player_get_next_lvl_exp(world, myindex)?;
 
             if let Some(Entity::Player(p_data)) = world.entities.get(myindex) {
-                self.interface.profile.set_profile_label_value(
-                    systems,
-                    ProfileLabel::Level,
-                    p_data.level as u64,
-                );
-                self.interface.profile.set_profile_label_value(
-                    systems,
-                    ProfileLabel::Money,
-                    self.player_data.player_money,
-                );
+                self.interface
+                    .windows
+                    .get_mut::<Profile>()
+                    .set_profile_label_value(
+                        systems,
+                        ProfileLabel::Level,
+                        p_data.level as u64,
+                    );
+                self.interface
+                    .windows
+                    .get_mut::<Profile>()
+                    .set_profile_label_value(
+                        systems,
+                        ProfileLabel::Money,
+                        self.player_data.player_money,
+                    );
                 let damage =
                     p_data.physical.damage.saturating_add(weapon_damage);
-                self.interface.profile.set_profile_label_value(
-                    systems,
-                    ProfileLabel::Damage,
-                    damage as u64,
-                );
+                self.interface
+                    .windows
+                    .get_mut::<Profile>()
+                    .set_profile_label_value(
+                        systems,
+                        ProfileLabel::Damage,
+                        damage as u64,
+                    );
                 let defense =
                     p_data.physical.defense.saturating_add(armor_defense);
-                self.interface.profile.set_profile_label_value(
-                    systems,
-                    ProfileLabel::Defense,
-                    defense as u64,
-                );
+                self.interface
+                    .windows
+                    .get_mut::<Profile>()
+                    .set_profile_label_value(
+                        systems,
+                        ProfileLabel::Defense,
+                        defense as u64,
+                    );
 
                 self.interface.vitalbar.update_bar_size(
                     systems,
@@ -256,6 +361,9 @@ impl GameContent {
                     p_data.vitals.vital[2],
                     p_data.vitals.vitalmax[2],
                 );
+                self.interface
+                    .vitalbar
+                    .set_dead(systems, p_data.death_type.is_dead());
 
                 self.interface.vitalbar.update_bar_size(
                     systems,
@@ -263,47 +371,65 @@ impl GameContent {
                     self.player_data.levelexp as i32,
                     nextexp as i32,
                 );
-
-                let mut size = systems.gfx.get_size(&p_data.hp_bar.bar_index);
-                size.x = get_percent(
-                    p_data.vitals.vital[0],
-                    p_data.vitals.vitalmax[0],
-                    18,
-                ) as f32;
-                systems.gfx.set_size(&p_data.hp_bar.bar_index, size);
-                systems.gfx.set_visible(
-                    &p_data.hp_bar.bar_index,
-                    p_data.hp_bar.visible,
-                );
-                systems.gfx.set_visible(
-                    &p_data.hp_bar.bg_index,
-                    p_data.hp_bar.visible,
-                );
             }
         }
 
         for i in 0..MAX_EQPT {
-            self.interface.profile.update_equipment_slot(
-                systems,
-                i,
-                &self.player_data.equipment[i],
-            );
+            self.interface
+                .windows
+                .get_mut::<Profile>()
+                .update_equipment_slot(
+                    systems,
+                    i,
+                    &self.player_data.equipment[i],
+                );
         }
 
-        systems.gfx.set_visible(&self.game_lights, true);
+        Ok(())
+    }
 
-        if let Some(music) = &get_map_music(systems, self.map.mapindex[0])
-            && self.current_music != *music
-        {
-            self.current_music.clone_from(music);
-            systems.audio.set_music(format!("./audio/{music}"))?;
-        }
+    /// Applies a new UI scale or theme from the Setting window by rebuilding
+    /// the interface and filling it again.
+    pub fn rebuild_interface(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+        tooltip: &mut Tooltip,
+    ) -> Result<()> {
+        systems.scale = systems.os_scale * systems.config.ui_scale_factor();
 
-        self.finalized = true;
+        tooltip.hide_tooltip(systems);
+        tooltip.refresh_style(systems);
 
-        update_camera(world, self, systems, graphics)?;
+        self.interface.rebuild(systems, socket)?;
+        self.death_screen.refresh(systems);
+        self.combat_log.mark_changed();
+        self.refresh_interface(world, systems)
+    }
 
-        Ok(())
+    pub fn resize(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+        graphics: &mut State<FlatControls>,
+        old_screen: Vec2,
+    ) -> Result<()> {
+        systems.gfx.set_size(
+            &self.game_lights,
+            Vec2::new(systems.size.width, systems.size.height),
+        );
+
+        self.interface.resize(systems, old_screen);
+        self.death_screen.refresh(systems);
+
+        if !self.finalized {
+            return Ok(());
+        }
+
+        // The camera keeps the player centered, a bigger screen shows more
+        // of the maps around them at the same zoom.
+        update_camera(world, self, systems, graphics)
     }
 
     pub fn init_map(
@@ -354,7 +480,46 @@ impl GameContent {
             Direction::Up => self.map.map_pos.y += 1,
         }
 
-        self.init_map(systems, map_renderer, self.map.map_pos, buffer, false)
+        self.init_map(systems, map_renderer, self.map.map_pos, buffer, false)?;
+        self.prefetch_maps(systems, map_renderer, dir, buffer)
+    }
+
+    /// Starts loading the row of maps just past the visible ones in the
+    /// direction we are heading so they are ready before we cross into them.
+    pub fn prefetch_maps(
+        &mut self,
+        systems: &mut SystemHolder,
+        map_renderer: &mut MapRenderer,
+        dir: Direction,
+        buffer: &mut BufferTask,
+    ) -> Result<()> {
+        let center = self.map.map_pos;
+
+        for offset in -1..=1 {
+            let (x, y) = match dir {
+                Direction::Down => (center.x + offset, center.y - 2),
+                Direction::Left => (center.x - 2, center.y + offset),
+                Direction::Right => (center.x + 2, center.y + offset),
+                Direction::Up => (center.x + offset, center.y + 2),
+            };
+            let mappos = MapPosition::new(x, y, center.group);
+
+            if systems.base.mappos_key.contains_key(&mappos) {
+                continue;
+            }
+
+            let key = get_map_key(
+                systems,
+                map_renderer,
+                mappos,
+                buffer,
+                center,
+                false,
+            )?;
+            set_map_visible(systems, key, false);
+        }
+
+        Ok(())
     }
 
     pub fn handle_key_input(
@@ -374,11 +539,9 @@ impl GameContent {
                     KEY_ATTACK => {
                         self.player_attack(world, systems, socket, seconds)?
                     }
-                    KEY_PICKUP => {
-                        if self.pick_up_timer < seconds {
-                            send_pickup(socket)?;
-                            self.pick_up_timer = seconds + 1.0;
-                        }
+                    KEY_PICKUP if self.pick_up_timer < seconds => {
+                        send_pickup(socket)?;
+                        self.pick_up_timer = seconds + 1.0;
                     }
                     _ => {}
                 }
@@ -387,29 +550,156 @@ impl GameContent {
         Ok(())
     }
 
+    /// Leaves the bank or shop when a move key is pressed, the steps
+    /// themselves are taken by `predict_player_move`.
     pub fn move_player(
         &mut self,
         _world: &mut World,
         socket: &mut Poller,
         dir: Option<Direction>,
     ) -> Result<()> {
-        if let Some(_myentity) = self.myentity {
-            let dir = if let Some(dir) = dir {
-                enum_to_dir(dir)
-            } else {
-                send_move(socket, None)?;
-                return Ok(());
-            };
+        if self.myentity.is_none() || dir.is_none() {
+            return Ok(());
+        }
+
+        match self.player_data.is_using_type {
+            IsUsingType::Bank => send_closestorage(socket)?,
+            IsUsingType::Store(_) => send_closeshop(socket)?,
+            _ => {}
+        }
+
+        Ok(())
+    }
 
-            match self.player_data.is_using_type {
-                IsUsingType::Bank => send_closestorage(socket)?,
-                IsUsingType::Store(_) => send_closeshop(socket)?,
-                _ => {}
+    /// Walks our player a tile towards the held move key without waiting
+    /// for the server, which confirms the step with a `MoveAck`. A blocked
+    /// step only turns the player.
+    pub fn predict_player_move(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+    ) -> Result<()> {
+        let Some(myentity) = self.myentity else {
+            return Ok(());
+        };
+
+        let (pos, facing) = match world.entities.get(myentity) {
+            Some(Entity::Player(p_data))
+                if !p_data.attacking.0
+                    && !p_data.movement.is_moving
+                    && !p_data.death_type.is_dead() =>
+            {
+                (p_data.pos, p_data.dir)
             }
+            _ => return Ok(()),
+        };
 
-            send_move(socket, Some(dir))?;
+        if !self.prediction.can_predict() {
+            return Ok(());
         }
-        Ok(())
+
+        let dir = match self.move_keypressed.first() {
+            Some(ControlKey::MoveDown) => Direction::Down,
+            Some(ControlKey::MoveUp) => Direction::Up,
+            Some(ControlKey::MoveLeft) => Direction::Left,
+            Some(ControlKey::MoveRight) => Direction::Right,
+            _ => {
+                let Some(dir) =
+                    self.pickup_step(world, systems, socket, pos, facing)?
+                else {
+                    return Ok(());
+                };
+
+                dir_to_enum(dir)
+            }
+        };
+        let dir = enum_to_dir(dir);
+
+        if !self.move_keypressed.is_empty() {
+            self.pickup_target = None;
+        }
+
+        if !can_move(world, systems, pos, dir) {
+            if facing != dir {
+                if let Some(Entity::Player(p_data)) =
+                    world.entities.get_mut(myentity)
+                {
+                    p_data.dir = dir;
+                }
+
+                let frame = dir * PLAYER_SPRITE_FRAME_X as u8;
+                set_player_frame(world, systems, myentity, frame as usize)?;
+                send_dir(socket, dir)?;
+            }
+
+            return Ok(());
+        }
+
+        let step = self.prediction.predict(pos, dir);
+
+        send_movestep(socket, dir, step.seq)?;
+        move_player(
+            world,
+            systems,
+            myentity,
+            MovementType::Manual(dir, Some(step.pos)),
+        )
+    }
+
+    /// Next step towards the item picked with the context menu. Next to
+    /// it the player turns towards it and picks it up instead. The walk
+    /// stops when the item is gone or the way is blocked.
+    fn pickup_step(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+        pos: Position,
+        facing: u8,
+    ) -> Result<Option<u8>> {
+        let Some(item) = self.pickup_target else {
+            return Ok(None);
+        };
+        let item_pos = match world.entities.get(item) {
+            Some(Entity::MapItem(i_data)) => i_data.pos,
+            _ => {
+                self.pickup_target = None;
+                return Ok(None);
+            }
+        };
+
+        if tile_distance(pos, item_pos).is_some_and(|tiles| tiles <= 1) {
+            self.pickup_target = None;
+
+            if let Some(dir) = facing_dir(pos, item_pos)
+                && dir != facing
+                && let Some(myentity) = self.myentity
+            {
+                if let Some(Entity::Player(p_data)) =
+                    world.entities.get_mut(myentity)
+                {
+                    p_data.dir = dir;
+                }
+
+                let frame = dir * PLAYER_SPRITE_FRAME_X as u8;
+                set_player_frame(world, systems, myentity, frame as usize)?;
+                send_dir(socket, dir)?;
+            }
+
+            send_pickup(socket)?;
+            return Ok(None);
+        }
+
+        let step = step_towards(pos, item_pos, |at, dir| {
+            can_move(world, systems, at, dir)
+        });
+
+        if step.is_none() {
+            self.pickup_target = None;
+        }
+
+        Ok(step)
     }
 
     pub fn player_attack(
@@ -423,7 +713,10 @@ impl GameContent {
             let (pos, dir) = if let Some(Entity::Player(p_data)) =
                 world.entities.get(myentity)
             {
-                if p_data.attacking.0 || p_data.movement.is_moving {
+                if p_data.attacking.0
+                    || p_data.movement.is_moving
+                    || p_data.death_type.is_dead()
+                {
                     return Ok(());
                 }
 
@@ -475,14 +768,17 @@ impl GameContent {
                 world.entities.iter().find_map(|(key, entity_data)| {
                     match entity_data {
                         Entity::Player(p_data) => {
-                            if p_data.pos == target_pos {
+                            if p_data.pos == target_pos
+                                && p_data.death_type.is_targetable()
+                            {
                                 return Some(key);
                             }
                         }
-                        Entity::Npc(n_data) => {
-                            if n_data.pos == target_pos {
-                                return Some(key);
-                            }
+                        Entity::Npc(n_data)
+                            if n_data.pos == target_pos
+                                && n_data.death_type.is_targetable() =>
+                        {
+                            return Some(key);
                         }
                         _ => {}
                     }
@@ -490,6 +786,12 @@ impl GameContent {
                 });
 
             if let Some(got_target) = target_entity {
+                self.combat_log.attack(
+                    got_target,
+                    combat_name(world, systems, got_target),
+                    seconds,
+                );
+
                 let proceed_target = if let Some(t_entity) = self.target.entity
                 {
                     if t_entity != got_target {
@@ -546,6 +848,7 @@ impl GameContent {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_player(
     world: &mut World,
     systems: &mut SystemHolder,
@@ -556,14 +859,40 @@ pub fn update_player(
     seconds: f32,
     delta: f32,
 ) -> Result<()> {
+    content.predict_player_move(world, systems, socket)?;
+
     let players = content.players.clone();
     for entity in players.borrow().iter() {
-        move_player(world, systems, *entity, MovementType::MovementBuffer)?;
-        process_player_movement(
-            world, systems, socket, *entity, content, buffer, graphics, delta,
-        )?;
-        process_player_attack(world, systems, *entity, seconds)?
+        if content.myentity == Some(*entity) {
+            process_player_movement(
+                world, systems, socket, *entity, content, buffer, graphics,
+                delta,
+            )?;
+        } else {
+            play_player_snapshots(
+                world, systems, socket, *entity, content, seconds,
+            )?;
+        }
+        process_player_attack(world, systems, *entity, seconds)?;
+        process_death_fade(world, systems, *entity, seconds)?;
+    }
+
+    if let Some(myentity) = content.myentity {
+        if content.prediction.update(delta) {
+            update_player_camera(world, systems, socket, myentity, content)?;
+            update_camera(world, content, systems, graphics)?;
+        }
+
+        let pos = match world.entities.get(myentity) {
+            Some(Entity::Player(p_data)) => Some(p_data.pos),
+            _ => None,
+        };
+
+        content
+            .move_ghosts
+            .update(systems, &content.prediction, pos);
     }
+
     Ok(())
 }
 
@@ -573,14 +902,22 @@ pub fn update_npc(
     socket: &mut Poller,
     content: &mut GameContent,
     seconds: f32,
-    delta: f32,
 ) -> Result<()> {
     let npcs = content.npcs.clone();
     for entity in npcs.borrow().iter() {
-        move_npc(world, systems, *entity, MovementType::MovementBuffer)?;
-        process_npc_movement(world, systems, *entity, socket, content, delta)?;
+        play_npc_snapshots(world, systems, *entity, socket, content, seconds)?;
         process_npc_attack(world, systems, *entity, seconds)?;
+        process_death_fade(world, systems, *entity, seconds)?;
     }
+
+    let player_level = match content.myentity.map(|e| world.entities.get(e)) {
+        Some(Some(Entity::Player(p_data))) => Some(p_data.level),
+        _ => None,
+    };
+    content
+        .npc_nameplates
+        .update(world, systems, &npcs.borrow(), player_level);
+
     Ok(())
 }
 
@@ -771,6 +1108,7 @@ pub fn update_camera(
             + (Vec2::new(p_data.pos.x as f32, p_data.pos.y as f32)
                 * TILE_SIZE as f32)
             + p_data.pos_offset
+            + content.prediction.correction()
     } else {
         return Ok(());
     };
@@ -0,0 +1,300 @@
+use indexmap::IndexMap;
+use std::collections::VecDeque;
+
+use crate::{
+    DeathType, Entity, GlobalKey, SystemHolder, World, next_level_exp,
+};
+
+/// Entries kept in the combat log, the oldest are dropped first.
+pub const COMBAT_LOG_LIMIT: usize = 200;
+/// Seconds after our attack in which damage on its target counts as ours.
+/// The damage packet does not say who dealt it.
+pub const ATTACK_CREDIT_TIME: f32 = 1.5;
+/// Seconds after our last hit in which the death of a target is our kill.
+pub const KILL_CREDIT_TIME: f32 = 5.0;
+/// A pause between hits longer than this ends the fight, the pause does
+/// not count towards the DPS.
+pub const COMBAT_TIMEOUT: f32 = 5.0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CombatEvent {
+    Dealt { target: String, amount: u16 },
+    Taken { amount: u16 },
+    Missed { target: String },
+    Kill { target: String },
+    Experience { amount: u64 },
+    LevelUp { level: i32 },
+}
+
+#[derive(Clone, Debug)]
+pub struct CombatEntry {
+    /// Local time of day as `HH:MM:SS`.
+    pub time: String,
+    pub event: CombatEvent,
+}
+
+#[derive(Clone, Debug)]
+struct PendingAttack {
+    entity: GlobalKey,
+    name: String,
+    at: f32,
+}
+
+/// Combat events of this session and the statistics built from them, fed
+/// by the packet handlers and shown in the combat log window.
+#[derive(Clone, Debug, Default)]
+pub struct CombatLog {
+    entries: VecDeque<CombatEntry>,
+    pub damage_dealt: u64,
+    pub damage_taken: u64,
+    pub misses: u32,
+    pub kills: u32,
+    pub experience: u64,
+    target_damage: IndexMap<String, u64>,
+    fight_time: f32,
+    last_hit: Option<f32>,
+    session_start: Option<f32>,
+    now: f32,
+    attack: Option<PendingAttack>,
+    /// Entities we hit, with the time of the last hit.
+    damaged: Vec<(GlobalKey, String, f32)>,
+    level_exp: Option<(i32, u64)>,
+    changed: bool,
+}
+
+/// Name shown in the log for `entity`.
+pub fn combat_name(
+    world: &World,
+    systems: &SystemHolder,
+    entity: GlobalKey,
+) -> String {
+    match world.entities.get(entity) {
+        Some(Entity::Player(p_data)) => p_data.entity_name.0.clone(),
+        Some(Entity::Npc(n_data)) => systems
+            .base
+            .npc
+            .get(n_data.entity_index as usize)
+            .map(|npc| npc.name.clone())
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+impl CombatLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &CombatEntry> {
+        self.entries.iter()
+    }
+
+    /// Damage dealt to each target name, in the order they were first hit.
+    pub fn target_damage(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.target_damage
+            .iter()
+            .map(|(name, amount)| (name.as_str(), *amount))
+    }
+
+    /// Damage dealt per second of fighting. Every fight counts at least one
+    /// second so a single hit does not show an endless DPS.
+    pub fn dps(&self) -> f32 {
+        if self.fight_time <= 0.0 {
+            return 0.0;
+        }
+
+        self.damage_dealt as f32 / self.fight_time
+    }
+
+    /// Experience per hour since the session started. The first minute is
+    /// counted as a full minute to keep the rate from spiking.
+    pub fn xp_per_hour(&self) -> u64 {
+        let Some(start) = self.session_start else {
+            return 0;
+        };
+        let elapsed = (self.now - start).max(60.0);
+
+        (self.experience as f64 * 3600.0 / elapsed as f64) as u64
+    }
+
+    /// Whether anything changed since the last call.
+    pub fn take_changed(&mut self) -> bool {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Asks for the window to be filled again, after it was rebuilt.
+    pub fn mark_changed(&mut self) {
+        self.changed = true;
+    }
+
+    fn push(&mut self, event: CombatEvent) {
+        if self.entries.len() >= COMBAT_LOG_LIMIT {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(CombatEntry {
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            event,
+        });
+        self.changed = true;
+    }
+
+    fn miss(&mut self, attack: PendingAttack) {
+        self.misses = self.misses.saturating_add(1);
+        self.push(CombatEvent::Missed {
+            target: attack.name,
+        });
+    }
+
+    /// Clears the log and statistics and starts a new session now, the
+    /// experience baseline is kept.
+    pub fn reset(&mut self) {
+        *self = CombatLog {
+            session_start: self.session_start.map(|_| self.now),
+            now: self.now,
+            level_exp: self.level_exp,
+            changed: true,
+            ..CombatLog::default()
+        };
+    }
+
+    /// We attacked `entity`, damage on it in the next moment is ours. An
+    /// earlier attack that got no damage was a miss.
+    pub fn attack(&mut self, entity: GlobalKey, name: String, seconds: f32) {
+        if let Some(attack) = self.attack.take() {
+            self.miss(attack);
+        }
+
+        self.attack = Some(PendingAttack {
+            entity,
+            name,
+            at: seconds,
+        });
+    }
+
+    /// Damage or healing shown on `entity`, `is_self` when it is our own
+    /// player. Healing is not logged.
+    pub fn damage(
+        &mut self,
+        entity: GlobalKey,
+        is_self: bool,
+        amount: u16,
+        is_damage: bool,
+        seconds: f32,
+    ) {
+        if !is_damage {
+            return;
+        }
+
+        if is_self {
+            self.damage_taken += u64::from(amount);
+            self.push(CombatEvent::Taken { amount });
+            return;
+        }
+
+        let Some(attack) = self.attack.take_if(|attack| {
+            attack.entity == entity && seconds - attack.at <= ATTACK_CREDIT_TIME
+        }) else {
+            return;
+        };
+
+        if amount == 0 {
+            self.miss(attack);
+            return;
+        }
+
+        self.fight_time += match self.last_hit {
+            Some(last) if seconds - last <= COMBAT_TIMEOUT => seconds - last,
+            _ => 1.0,
+        };
+        self.last_hit = Some(seconds);
+        self.damage_dealt += u64::from(amount);
+        *self.target_damage.entry(attack.name.clone()).or_default() +=
+            u64::from(amount);
+
+        match self.damaged.iter_mut().find(|(key, ..)| *key == entity) {
+            Some(damaged) => damaged.2 = seconds,
+            None => self.damaged.push((entity, attack.name.clone(), seconds)),
+        }
+
+        self.push(CombatEvent::Dealt {
+            target: attack.name,
+            amount,
+        });
+    }
+
+    /// `entity` changed its death state, a target we hit shortly before
+    /// dying is our kill.
+    pub fn death(
+        &mut self,
+        entity: GlobalKey,
+        death_type: DeathType,
+        seconds: f32,
+    ) {
+        if !death_type.is_dead() {
+            return;
+        }
+
+        let Some(index) = self.damaged.iter().position(|(key, _, at)| {
+            *key == entity && seconds - at <= KILL_CREDIT_TIME
+        }) else {
+            return;
+        };
+
+        let (_, target, _) = self.damaged.swap_remove(index);
+
+        self.kills = self.kills.saturating_add(1);
+        self.push(CombatEvent::Kill { target });
+    }
+
+    /// New level and experience of our player. The first update of a
+    /// session is only the baseline, after that the difference is logged,
+    /// across level ups as well. Experience lost on death is not counted.
+    pub fn level(&mut self, level: i32, levelexp: u64, seconds: f32) {
+        let Some((old_level, old_exp)) =
+            self.level_exp.replace((level, levelexp))
+        else {
+            self.session_start.get_or_insert(seconds);
+            self.now = self.now.max(seconds);
+            return;
+        };
+
+        let gained = if level > old_level {
+            let rest = next_level_exp(old_level).saturating_sub(old_exp);
+            let between: u64 = (old_level + 1..level).map(next_level_exp).sum();
+
+            rest + between + levelexp
+        } else if level == old_level {
+            levelexp.saturating_sub(old_exp)
+        } else {
+            0
+        };
+
+        if gained > 0 {
+            self.experience += gained;
+            self.push(CombatEvent::Experience { amount: gained });
+        }
+
+        if level > old_level {
+            self.push(CombatEvent::LevelUp { level });
+        }
+    }
+
+    /// Called every frame, turns attacks that got no damage into misses and
+    /// forgets old hits.
+    pub fn process(&mut self, seconds: f32) {
+        self.now = seconds;
+
+        if self
+            .attack
+            .as_ref()
+            .is_some_and(|attack| seconds - attack.at > ATTACK_CREDIT_TIME)
+            && let Some(attack) = self.attack.take()
+        {
+            self.miss(attack);
+        }
+
+        self.damaged
+            .retain(|(_, _, at)| seconds - at <= KILL_CREDIT_TIME);
+    }
+}
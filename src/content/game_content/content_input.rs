@@ -4,19 +4,14 @@ use ascending_input::Key;
 use winit::{event_loop::ActiveEventLoop, keyboard::NamedKey};
 
 use crate::{
-    Alert, COLOR_RED, ContentType, Entity, EntityKind, MouseInputType,
-    SystemHolder, Tooltip,
-    content::*,
-    data_types::*,
-    socket::{self, *},
+    Alert, Entity, EntityKind, GlobalKey, MouseInputType, SystemHolder,
+    Tooltip, content::*, data_types::*, socket::*,
 };
 
-use super::{
-    KEY_ATTACK, KEY_MOVEDOWN, KEY_MOVELEFT, KEY_MOVERIGHT, KEY_MOVEUP,
-    KEY_PICKUP,
-};
+use super::{KEY_ATTACK, KEY_PICKUP};
 
 impl GameContent {
+    #[allow(clippy::too_many_arguments)]
     pub fn mouse_input(
         content: &mut Content,
         world: &mut World,
@@ -34,7 +29,41 @@ impl GameContent {
             );
         }
 
-        if Interface::mouse_input(
+        let entity_menu = &mut content.game_content.entity_menu;
+
+        if entity_menu.menu.visible {
+            match input_type {
+                MouseInputType::MouseMove => {
+                    entity_menu.menu.hover(systems, screen_pos);
+                }
+                MouseInputType::MouseLeftDown => {
+                    let in_menu = entity_menu.menu.in_menu(screen_pos);
+
+                    if let Some((target, action)) =
+                        entity_menu.click(systems, screen_pos)
+                    {
+                        trigger_menu_action(
+                            world,
+                            systems,
+                            socket,
+                            &mut content.game_content,
+                            target,
+                            action,
+                        )?;
+                    }
+
+                    if in_menu {
+                        return Ok(());
+                    }
+                }
+                MouseInputType::MouseRightDown => {
+                    entity_menu.close(systems);
+                }
+                _ => {}
+            }
+        }
+
+        let handled = Interface::mouse_input(
             &mut content.game_content.interface,
             world,
             systems,
@@ -43,79 +72,165 @@ impl GameContent {
             input_type,
             tooltip,
             screen_pos,
-        )? {
-            return Ok(());
+        )?;
+
+        if content.game_content.interface.needs_rebuild {
+            content
+                .game_content
+                .rebuild_interface(world, systems, socket, tooltip)?;
         }
 
-        if let MouseInputType::MouseLeftDown = input_type {
-            let target_entity = find_entity(
-                world,
-                systems,
-                &mut content.game_content,
-                screen_pos,
-            );
+        if handled {
+            return Ok(());
+        }
 
-            if let Some(entity) = target_entity {
-                if let Some(t_entity) = content.game_content.target.entity {
-                    let entity_data = world.entities.get_mut(t_entity);
-                    if let Some(entity_data) = entity_data {
-                        match entity_data {
-                            Entity::Player(p_data) => {
-                                content.game_content.target.clear_target(
-                                    socket,
-                                    systems,
-                                    &mut p_data.hp_bar,
-                                )?;
-                            }
-                            Entity::Npc(n_data) => {
-                                content.game_content.target.clear_target(
-                                    socket,
-                                    systems,
-                                    &mut n_data.hp_bar,
-                                )?;
-                            }
-                            _ => {}
-                        }
-                    }
+        match input_type {
+            MouseInputType::MouseLeftDown => {
+                let target_entity = find_entity(
+                    world,
+                    systems,
+                    &mut content.game_content,
+                    screen_pos,
+                );
 
-                    if t_entity == entity {
-                        return Ok(());
+                if let Some(entity) = target_entity {
+                    if content.game_content.target.entity == Some(entity) {
+                        content
+                            .game_content
+                            .clear_entity_target(world, systems, socket)?;
+                    } else {
+                        content
+                            .game_content
+                            .target_entity(world, systems, socket, entity)?;
                     }
                 }
-
-                content
-                    .game_content
-                    .target
-                    .set_target(socket, systems, entity)?;
-
-                let entity_kind = world.get_kind(entity)?;
-                match entity_kind {
-                    EntityKind::Player => {
-                        update_player_camera(
-                            world,
-                            systems,
-                            socket,
-                            entity,
-                            &mut content.game_content,
-                        )?;
-                    }
-                    EntityKind::Npc => {
-                        update_npc_camera(
+            }
+            MouseInputType::MouseRightDown => {
+                let game_content = &mut content.game_content;
+                let target =
+                    match find_entity(world, systems, game_content, screen_pos)
+                    {
+                        Some(entity) => match world.get_kind(entity)? {
+                            EntityKind::Player => {
+                                Some(MenuTarget::Player(entity))
+                            }
+                            EntityKind::Npc => Some(MenuTarget::Npc(entity)),
+                            _ => None,
+                        },
+                        None => find_map_item(
                             world,
                             systems,
-                            entity,
-                            socket,
-                            &mut content.game_content,
-                        )?;
-                    }
-                    _ => {}
+                            game_content,
+                            screen_pos,
+                        )
+                        .map(MenuTarget::MapItem),
+                    };
+
+                if let Some(target) = target {
+                    game_content
+                        .entity_menu
+                        .open(world, systems, target, screen_pos);
                 }
             }
+            _ => {}
         }
 
         Ok(())
     }
 
+    /// Clears the current target and hides its HP bar.
+    pub fn clear_entity_target(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+    ) -> Result<()> {
+        let Some(t_entity) = self.target.entity else {
+            return Ok(());
+        };
+
+        match world.entities.get_mut(t_entity) {
+            Some(Entity::Player(p_data)) => {
+                self.target
+                    .clear_target(socket, systems, &mut p_data.hp_bar)
+            }
+            Some(Entity::Npc(n_data)) => {
+                self.target
+                    .clear_target(socket, systems, &mut n_data.hp_bar)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Makes `entity` the target in place of the current one.
+    pub fn target_entity(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+        entity: GlobalKey,
+    ) -> Result<()> {
+        if self.target.entity == Some(entity) {
+            return Ok(());
+        }
+
+        self.clear_entity_target(world, systems, socket)?;
+        self.target.set_target(socket, systems, entity)?;
+
+        match world.get_kind(entity)? {
+            EntityKind::Player => {
+                update_player_camera(world, systems, socket, entity, self)
+            }
+            EntityKind::Npc => {
+                update_npc_camera(world, systems, entity, socket, self)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Targets the nearest hostile NPC in range, or the next one further out
+    /// when one of them is targeted already.
+    pub fn cycle_target(
+        &mut self,
+        world: &mut World,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+    ) -> Result<()> {
+        let my_pos = match self.myentity.and_then(|e| world.entities.get(e)) {
+            Some(Entity::Player(p_data)) => p_data.pos,
+            _ => return Ok(()),
+        };
+
+        let candidates = self
+            .npcs
+            .borrow()
+            .iter()
+            .filter_map(|entity| {
+                let Some(Entity::Npc(n_data)) = world.entities.get(*entity)
+                else {
+                    return None;
+                };
+                let npc = systems.base.npc.get(n_data.entity_index as usize)?;
+
+                if !n_data.visible
+                    || !n_data.death_type.is_targetable()
+                    || Disposition::of(npc.behaviour, n_data.mode)
+                        == Disposition::Friendly
+                {
+                    return None;
+                }
+
+                let distance = target_distance(my_pos, n_data.pos)?;
+                (distance <= TAB_TARGET_RANGE).then_some((*entity, distance))
+            })
+            .collect();
+
+        match next_tab_target(candidates, self.target.entity) {
+            Some(entity) => self.target_entity(world, systems, socket, entity),
+            None => Ok(()),
+        }
+    }
+
     pub fn key_input(
         content: &mut Content,
         world: &mut World,
@@ -130,6 +245,14 @@ impl GameContent {
             return Ok(());
         }
 
+        if pressed
+            && content.game_content.entity_menu.menu.visible
+            && let Key::Named(NamedKey::Escape) = key
+        {
+            content.game_content.entity_menu.close(systems);
+            return Ok(());
+        }
+
         Interface::key_input(
             &mut content.game_content,
             world,
@@ -139,8 +262,9 @@ impl GameContent {
             pressed,
         )?;
 
-        if content.game_content.interface.inventory.hold_slot.is_some()
-            || content.game_content.interface.storage.hold_slot.is_some()
+        let windows = &content.game_content.interface.windows;
+        if windows.get::<Inventory>().hold_slot.is_some()
+            || windows.get::<Storage>().hold_slot.is_some()
         {
             content.game_content.keyinput.iter_mut().for_each(|key| {
                 *key = false;
@@ -224,6 +348,13 @@ impl GameContent {
             Key::Named(NamedKey::Space) => {
                 content.game_content.keyinput[KEY_PICKUP] = pressed;
             }
+            Key::Named(NamedKey::Tab)
+                if pressed
+                    && content.game_content.interface.selected_textbox
+                        == SelectedTextbox::None =>
+            {
+                content.game_content.cycle_target(world, systems, socket)?;
+            }
             _ => {}
         }
 
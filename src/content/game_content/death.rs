@@ -0,0 +1,315 @@
+use crate::{
+    DeathType, Entity, GameContent, GlobalKey, ORDER_DEATH_SCREEN_BG,
+    ORDER_DEATH_SCREEN_TEXT, Result, SystemHolder, World, data_types::*,
+    label::*, systems::Poller,
+};
+use ascending_graphics::{cosmic_text::Metrics, *};
+
+/// Seconds the server keeps a dead player down before respawning them.
+pub const RESPAWN_TIME: f32 = 10.0;
+/// Seconds a dying sprite takes to fade into its corpse or spirit look.
+pub const DEATH_FADE_TIME: f32 = 1.0;
+/// Seconds a spawning or revived sprite takes to fade in.
+pub const SPAWN_FADE_TIME: f32 = 0.5;
+
+const HP_BAR_COLOR: Color = Color::rgba(180, 30, 30, 255);
+const DEAD_HP_BAR_COLOR: Color = Color::rgba(110, 110, 110, 255);
+
+/// Sprite tint of a death state and whether the name is shown with it.
+pub fn death_look(death_type: DeathType) -> ([u8; 4], bool) {
+    match death_type {
+        DeathType::Alive | DeathType::Spawning => ([255, 255, 255, 255], true),
+        DeathType::Spirit => ([170, 200, 255, 130], true),
+        DeathType::Dead => ([90, 90, 90, 150], false),
+        DeathType::UnSpawned => ([255, 255, 255, 0], false),
+    }
+}
+
+fn rgba(color: [u8; 4]) -> Color {
+    Color::rgba(color[0], color[1], color[2], color[3])
+}
+
+/// Tints the sprite and fades the name along with it, the name keeps its
+/// own colour.
+fn set_death_color(
+    systems: &mut SystemHolder,
+    sprite: GfxType,
+    name: GfxType,
+    color: [u8; 4],
+    show_name: bool,
+) {
+    systems.gfx.set_color(&sprite, rgba(color));
+
+    let name_color = systems.gfx.get_color(&name);
+    let name_alpha = if show_name { color[3] } else { 0 };
+
+    systems.gfx.set_color(
+        &name,
+        Color::rgba(name_color.r(), name_color.g(), name_color.b(), name_alpha),
+    );
+}
+
+/// Shows `death_type` on `entity`. The sprite fades to its corpse, spirit
+/// or living look, dead entities stop being the target and our own player
+/// gets the death screen and a grey HP bar. `animate` is false for
+/// entities that come into view already in that state.
+#[allow(clippy::too_many_arguments)]
+pub fn set_entity_death(
+    world: &mut World,
+    systems: &mut SystemHolder,
+    socket: &mut Poller,
+    content: &mut GameContent,
+    entity: GlobalKey,
+    death_type: DeathType,
+    seconds: f32,
+    animate: bool,
+) -> Result<()> {
+    let (fade, sprite, name, hp_bar) = match world.entities.get_mut(entity) {
+        Some(Entity::Player(p_data)) => {
+            p_data.death_type = death_type;
+            (
+                &mut p_data.death_fade,
+                p_data.sprite_index.0,
+                p_data.name_map.0,
+                &mut p_data.hp_bar,
+            )
+        }
+        Some(Entity::Npc(n_data)) => {
+            n_data.death_type = death_type;
+            (
+                &mut n_data.death_fade,
+                n_data.sprite_index.0,
+                n_data.name_map.0,
+                &mut n_data.hp_bar,
+            )
+        }
+        _ => return Ok(()),
+    };
+
+    let (to, show_name) = death_look(death_type);
+    let from = if death_type == DeathType::Spawning {
+        [to[0], to[1], to[2], 0]
+    } else {
+        fade.color
+    };
+
+    if animate && from != to {
+        fade.from = from;
+        fade.to = to;
+        fade.start = seconds;
+        fade.duration = if death_type.is_targetable() {
+            SPAWN_FADE_TIME
+        } else {
+            DEATH_FADE_TIME
+        };
+        fade.active = true;
+        set_death_color(systems, sprite, name, from, show_name);
+    } else {
+        fade.active = false;
+        fade.color = to;
+        set_death_color(systems, sprite, name, to, show_name);
+    }
+
+    let bar_color = if death_type.is_dead() {
+        DEAD_HP_BAR_COLOR
+    } else {
+        HP_BAR_COLOR
+    };
+    systems.gfx.set_color(&hp_bar.bar_index, bar_color);
+
+    if !death_type.is_targetable() && content.target.entity == Some(entity) {
+        content.target.clear_target(socket, systems, hp_bar)?;
+    }
+
+    if content.myentity == Some(entity) {
+        content
+            .interface
+            .vitalbar
+            .set_dead(systems, death_type.is_dead());
+
+        if death_type.is_dead() {
+            content.death_screen.show(systems, seconds);
+        } else {
+            content.death_screen.hide(systems);
+        }
+    }
+
+    Ok(())
+}
+
+/// Steps the death fade of `entity`, called every frame.
+pub fn process_death_fade(
+    world: &mut World,
+    systems: &mut SystemHolder,
+    entity: GlobalKey,
+    seconds: f32,
+) -> Result<()> {
+    let (fade, sprite, name, death_type) = match world.entities.get_mut(entity)
+    {
+        Some(Entity::Player(p_data)) => (
+            &mut p_data.death_fade,
+            p_data.sprite_index.0,
+            p_data.name_map.0,
+            p_data.death_type,
+        ),
+        Some(Entity::Npc(n_data)) => (
+            &mut n_data.death_fade,
+            n_data.sprite_index.0,
+            n_data.name_map.0,
+            n_data.death_type,
+        ),
+        _ => return Ok(()),
+    };
+
+    if !fade.active {
+        return Ok(());
+    }
+
+    let progress =
+        ((seconds - fade.start) / fade.duration.max(f32::EPSILON)).min(1.0);
+
+    for i in 0..4 {
+        let (from, to) = (fade.from[i] as f32, fade.to[i] as f32);
+        fade.color[i] = (from + (to - from) * progress).round() as u8;
+    }
+
+    if progress >= 1.0 {
+        fade.active = false;
+    }
+
+    let show_name = death_look(death_type).1;
+    set_death_color(systems, sprite, name, fade.color, show_name);
+
+    Ok(())
+}
+
+/// "You have died" overlay with the respawn countdown, shown while our own
+/// player is dead or a spirit.
+#[derive(Default)]
+pub struct DeathScreen {
+    gfx: Option<[GfxType; 3]>,
+    respawn_at: f32,
+    seconds_left: Option<u32>,
+}
+
+impl DeathScreen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.gfx.is_some()
+    }
+
+    /// Shows the overlay, a player already down keeps their countdown when
+    /// they turn into a spirit.
+    pub fn show(&mut self, systems: &mut SystemHolder, seconds: f32) {
+        if self.is_visible() {
+            return;
+        }
+
+        self.respawn_at = seconds + RESPAWN_TIME;
+        self.seconds_left = None;
+        self.create(systems);
+    }
+
+    pub fn hide(&mut self, systems: &mut SystemHolder) {
+        if let Some(gfx) = self.gfx.take() {
+            gfx.iter().for_each(|index| {
+                systems.gfx.remove_gfx(&mut systems.renderer, index);
+            });
+        }
+    }
+
+    /// Builds the overlay again for a new screen size, UI scale or
+    /// language.
+    pub fn refresh(&mut self, systems: &mut SystemHolder) {
+        if self.is_visible() {
+            self.hide(systems);
+            self.seconds_left = None;
+            self.create(systems);
+        }
+    }
+
+    fn create(&mut self, systems: &mut SystemHolder) {
+        let scale = systems.scale as f32;
+        let screen = Vec2::new(systems.size.width, systems.size.height);
+
+        let bg_rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(0.0, 0.0, ORDER_DEATH_SCREEN_BG),
+            screen,
+            Color::rgba(20, 0, 0, 140),
+            0,
+        );
+        let bg = systems.gfx.add_rect(
+            bg_rect,
+            4,
+            "Death Screen BG",
+            true,
+            CameraView::SubView1,
+        );
+
+        let mut labels = [GfxType::default(); 2];
+
+        for (index, (offset_y, height, font_size)) in
+            [(10.0, 40.0, 32.0), (-30.0, 24.0, 18.0)].iter().enumerate()
+        {
+            let size = Vec2::new(screen.x, (height * scale).floor());
+            let pos =
+                Vec2::new(0.0, (screen.y * 0.5 + offset_y * scale).floor());
+            let label = create_label(
+                systems,
+                Vec3::new(pos.x, pos.y, ORDER_DEATH_SCREEN_TEXT),
+                size,
+                Some(Bounds::new(pos.x, pos.y, pos.x + size.x, pos.y + size.y)),
+                Color::rgba(230, 200, 200, 255),
+            );
+
+            labels[index] = systems.gfx.add_text(
+                label,
+                5,
+                "Death Screen Text",
+                true,
+                CameraView::SubView1,
+            );
+            systems.gfx.set_text_metrics(
+                &labels[index],
+                Metrics::new(*font_size, *font_size).scale(scale),
+            );
+        }
+
+        let [title, countdown] = labels;
+
+        systems
+            .gfx
+            .set_text(&title, systems.locale.get("death.title"));
+        systems.gfx.center_text(&mut systems.renderer, &title);
+        self.gfx = Some([bg, title, countdown]);
+    }
+
+    /// Counts down to the respawn, once it runs out the overlay waits for
+    /// the server to revive the player.
+    pub fn update(&mut self, systems: &mut SystemHolder, seconds: f32) {
+        let Some([_, _, countdown]) = self.gfx else {
+            return;
+        };
+
+        let left = (self.respawn_at - seconds).max(0.0).ceil() as u32;
+
+        if self.seconds_left == Some(left) {
+            return;
+        }
+
+        self.seconds_left = Some(left);
+
+        let text = if left > 0 {
+            systems.locale.plural("death.respawn", left as u64, &[])
+        } else {
+            systems.locale.text("death.respawning")
+        };
+
+        systems.gfx.set_text(&countdown, text.as_ref());
+        systems.gfx.center_text(&mut systems.renderer, &countdown);
+    }
+}
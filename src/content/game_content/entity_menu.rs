@@ -0,0 +1,240 @@
+use ascending_graphics::*;
+
+use crate::{
+    AIBehavior, COLOR_WHITE, Command, ContextMenu, Entity, GlobalKey,
+    MessageChannel, Result, SystemHolder, TString, World,
+    content::game_content::*, send_command, send_partyinvite,
+    send_requestinspect, set_clipboard_text, systems::Poller,
+};
+
+/// What a right click opened the context menu on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuTarget {
+    Player(GlobalKey),
+    Npc(GlobalKey),
+    MapItem(GlobalKey),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuAction {
+    Trade,
+    InviteParty,
+    Whisper,
+    Inspect,
+    Ignore,
+    CopyName,
+    Examine,
+    PickUp,
+}
+
+impl MenuAction {
+    /// Options offered for `target`, in menu order.
+    pub fn for_target(target: MenuTarget) -> &'static [MenuAction] {
+        match target {
+            MenuTarget::Player(_) => &[
+                MenuAction::Trade,
+                MenuAction::InviteParty,
+                MenuAction::Whisper,
+                MenuAction::Inspect,
+                MenuAction::Ignore,
+                MenuAction::CopyName,
+            ],
+            MenuTarget::Npc(_) => &[MenuAction::Examine],
+            MenuTarget::MapItem(_) => &[MenuAction::PickUp],
+        }
+    }
+
+    /// Locale key of the option, `ignored` turns Ignore into Unignore.
+    pub fn text_key(self, ignored: bool) -> &'static str {
+        match self {
+            MenuAction::Trade => "menu.trade",
+            MenuAction::InviteParty => "menu.invite_party",
+            MenuAction::Whisper => "menu.whisper",
+            MenuAction::Inspect => "menu.inspect",
+            MenuAction::Ignore if ignored => "menu.unignore",
+            MenuAction::Ignore => "menu.ignore",
+            MenuAction::CopyName => "menu.copy_name",
+            MenuAction::Examine => "menu.examine",
+            MenuAction::PickUp => "menu.pick_up",
+        }
+    }
+}
+
+fn behaviour_key(behaviour: AIBehavior) -> &'static str {
+    match behaviour {
+        AIBehavior::Friendly => "menu.behaviour.friendly",
+        AIBehavior::Agressive => "menu.behaviour.aggressive",
+        AIBehavior::Reactive => "menu.behaviour.reactive",
+        AIBehavior::HelpReactive => "menu.behaviour.help_reactive",
+        AIBehavior::Healer => "menu.behaviour.healer",
+        AIBehavior::AgressiveHealer => "menu.behaviour.aggressive_healer",
+        AIBehavior::ReactiveHealer => "menu.behaviour.reactive_healer",
+    }
+}
+
+/// Right click menu of the players, NPCs and items on the map.
+pub struct EntityMenu {
+    pub menu: ContextMenu,
+    target: Option<MenuTarget>,
+    actions: Vec<MenuAction>,
+}
+
+impl EntityMenu {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        EntityMenu {
+            menu: ContextMenu::new(systems),
+            target: None,
+            actions: Vec::new(),
+        }
+    }
+
+    pub fn recreate(&mut self, systems: &mut SystemHolder) {
+        self.menu.recreate(systems);
+        self.target = None;
+        self.actions.clear();
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        self.menu.unload(systems);
+    }
+
+    /// Opens the menu at `screen_pos` with the options of `target`.
+    pub fn open(
+        &mut self,
+        world: &World,
+        systems: &mut SystemHolder,
+        target: MenuTarget,
+        screen_pos: Vec2,
+    ) {
+        let ignored = match (target, world.entities.get(target_key(target))) {
+            (MenuTarget::Player(_), Some(Entity::Player(p_data))) => {
+                systems.config.is_ignored(&p_data.entity_name.0)
+            }
+            _ => false,
+        };
+
+        self.actions = MenuAction::for_target(target).to_vec();
+        self.target = Some(target);
+
+        let options = self
+            .actions
+            .iter()
+            .map(|action| systems.locale.get(action.text_key(ignored)).into())
+            .collect();
+
+        self.menu.open(systems, screen_pos, options);
+    }
+
+    pub fn close(&mut self, systems: &mut SystemHolder) {
+        self.menu.close(systems);
+        self.target = None;
+    }
+
+    /// Closes the menu, returns the option clicked at `screen_pos`.
+    pub fn click(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<(MenuTarget, MenuAction)> {
+        let option = self.menu.click(systems, screen_pos);
+        let target = self.target.take()?;
+
+        option
+            .and_then(|index| self.actions.get(index).copied())
+            .map(|action| (target, action))
+    }
+}
+
+fn target_key(target: MenuTarget) -> GlobalKey {
+    match target {
+        MenuTarget::Player(key)
+        | MenuTarget::Npc(key)
+        | MenuTarget::MapItem(key) => key,
+    }
+}
+
+fn add_menu_chat(
+    content: &mut GameContent,
+    systems: &mut SystemHolder,
+    text: TString,
+) {
+    content.interface.windows.get_mut::<Chatbox>().add_chat(
+        systems,
+        (text, COLOR_WHITE),
+        None,
+        MessageChannel::Map,
+    );
+}
+
+/// Carries out `action` picked from the context menu of `target`.
+pub fn trigger_menu_action(
+    world: &mut World,
+    systems: &mut SystemHolder,
+    socket: &mut Poller,
+    content: &mut GameContent,
+    target: MenuTarget,
+    action: MenuAction,
+) -> Result<()> {
+    let key = target_key(target);
+
+    match (action, world.entities.get(key)) {
+        (MenuAction::Trade, Some(Entity::Player(_))) => {
+            content.target_entity(world, systems, socket, key)?;
+            send_command(socket, Command::Trade)?;
+        }
+        (MenuAction::InviteParty, Some(Entity::Player(_))) => {
+            send_partyinvite(socket, key)?;
+        }
+        (MenuAction::Whisper, Some(Entity::Player(p_data))) => {
+            let text = format!("@{} ", p_data.entity_name.0);
+            let textbox =
+                &mut content.interface.windows.get_mut::<Chatbox>().textbox;
+
+            textbox.set_text(systems, &text);
+            textbox.set_select(systems, true);
+            content.interface.selected_textbox = SelectedTextbox::Chatbox;
+        }
+        (MenuAction::Inspect, Some(Entity::Player(_))) => {
+            send_requestinspect(socket, key)?;
+        }
+        (MenuAction::Ignore, Some(Entity::Player(p_data))) => {
+            let name = p_data.entity_name.0.clone();
+            let key = if systems.config.toggle_ignored(&name) {
+                "menu.ignored"
+            } else {
+                "menu.unignored"
+            };
+
+            systems.config.save_config("settings.toml");
+
+            let text = systems.locale.format(key, &[("name", &name)]);
+            add_menu_chat(content, systems, text);
+        }
+        (MenuAction::CopyName, Some(Entity::Player(p_data))) => {
+            set_clipboard_text(&p_data.entity_name.0);
+        }
+        (MenuAction::Examine, Some(Entity::Npc(n_data))) => {
+            let Some(npc) = systems.base.npc.get(n_data.entity_index as usize)
+            else {
+                return Ok(());
+            };
+            let behaviour = systems.locale.get(behaviour_key(npc.behaviour));
+            let text = systems.locale.format(
+                "menu.examine_text",
+                &[
+                    ("name", &npc.name),
+                    ("level", &npc.level),
+                    ("behaviour", &behaviour),
+                ],
+            );
+
+            add_menu_chat(content, systems, text);
+        }
+        (MenuAction::PickUp, Some(Entity::MapItem(_))) => {
+            content.pickup_target = Some(key);
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -3,15 +3,14 @@ use crate::{
     TILE_SIZE, data_types::*, database::get_map_render_pos, label::*,
 };
 use ascending_graphics::{
-    cosmic_text::{Attrs, Metrics, Stretch, Style, Weight},
+    cosmic_text::{Attrs, Weight},
     *,
 };
-use rand::{Rng, RngExt, rng};
+use rand::{RngExt, rng};
 
 struct FloatingTextData {
     text_bg: GfxType,
     text: GfxType,
-    size: Vec2,
     adjust_pos: Vec2,
     float_y: f32,
     pos: Position,
@@ -175,7 +174,6 @@ pub fn add_float_text(
     content.float_text.data.push(FloatingTextData {
         text: text_index,
         text_bg,
-        size,
         pos,
         adjust_pos,
         timer: 0.0,
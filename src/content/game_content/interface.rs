@@ -1,39 +1,50 @@
 use std::collections::VecDeque;
 
 use ascending_graphics::*;
-use cosmic_text::{Attrs, Metrics};
+use cosmic_text::Metrics;
 
 use ascending_input::Key;
 use winit::keyboard::NamedKey;
 
 use crate::{
-    Alert, AlertIndex, AlertType, COLOR_WHITE, GameContent, GfxType,
-    MouseInputType, Result, SystemHolder, TradeStatus, World,
-    interface::chatbox::*, is_within_area, send_buyitem, send_closeshop,
-    send_closestorage, send_closetrade, send_command, send_message,
-    send_removetradeitem, send_submittrade, send_unequip,
-    send_updatetrademoney, send_useitem, socket, systems::Poller, widget::*,
+    Alert, GameContent, GfxType, KeyAction, MouseInputType, Result,
+    SystemHolder, TradeStatus, World, is_within_area, send_updatetrademoney,
+    systems::Poller, widget::*,
 };
 
 pub mod chatbox;
+mod combat_log;
+mod effect_bar;
+mod inspect;
 mod inventory;
 mod item_description;
+mod party_frames;
 mod profile;
 mod screen;
 mod setting;
 mod shop;
 mod storage;
+mod target_frame;
 mod trade;
+mod window;
+mod window_state;
 
 pub use chatbox::*;
-use inventory::*;
+pub use combat_log::*;
+pub use effect_bar::*;
+pub use inspect::*;
+pub use inventory::*;
 use item_description::*;
+pub use party_frames::*;
 pub use profile::*;
 use screen::*;
-use setting::*;
-use shop::*;
-use storage::*;
-use trade::*;
+pub use setting::*;
+pub use shop::{SHOP_WIDGETS, Shop};
+pub use storage::*;
+pub use target_frame::*;
+pub use trade::*;
+pub use window::*;
+pub use window_state::*;
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum Window {
@@ -44,6 +55,8 @@ pub enum Window {
     Storage,
     Shop,
     Trade,
+    CombatLog,
+    Inspect,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -61,17 +74,18 @@ pub struct Interface {
     pub frame_loop_collection: VecDeque<u64>,
     pub menu_button: [Button; 3],
     pub vitalbar: VitalBar,
+    pub target_frame: TargetFrame,
+    pub effect_bar: EffectBar,
+    pub party_frames: PartyFrames,
     did_button_click: bool,
-    pub inventory: Inventory,
-    pub storage: Storage,
-    pub shop: Shop,
-    pub trade: Trade,
-    pub profile: Profile,
+    pub windows: WindowRegistry,
     pub item_desc: ItemDescription,
-    setting: Setting,
-    pub chatbox: Chatbox,
-    window_order: Vec<(Window, usize)>,
     drag_window: Option<Window>,
+    state_owner: Option<String>,
+    state_changed: bool,
+    /// Set when a setting changed how the windows are built, the interface
+    /// is rebuilt once the current input is handled.
+    pub needs_rebuild: bool,
     pub selected_textbox: SelectedTextbox,
 }
 
@@ -104,7 +118,13 @@ impl Interface {
             CameraView::SubView1,
         );
 
-        systems.gfx.set_text(&ping_text, "Ping: 0");
+        systems.gfx.set_text(
+            &ping_text,
+            systems
+                .locale
+                .format("stats.ping", &[("ping", &0)])
+                .as_ref(),
+        );
 
         let pos = Vec3::new(
             statistic_pos.x,
@@ -126,7 +146,13 @@ impl Interface {
             CameraView::SubView1,
         );
 
-        systems.gfx.set_text(&average_ping, "Av. Ping: 0");
+        systems.gfx.set_text(
+            &average_ping,
+            systems
+                .locale
+                .format("stats.average_ping", &[("ping", &0)])
+                .as_ref(),
+        );
 
         let pos = Vec3::new(
             statistic_pos.x,
@@ -148,9 +174,15 @@ impl Interface {
             CameraView::SubView1,
         );
 
-        systems.gfx.set_text(&frame_loop, "Frame Jitter: 0");
+        systems.gfx.set_text(
+            &frame_loop,
+            systems
+                .locale
+                .format("stats.frame_jitter", &[("jitter", &0)])
+                .as_ref(),
+        );
 
-        let mut interface = Interface {
+        Interface {
             menu_button,
             ping_text,
             average_ping,
@@ -158,50 +190,51 @@ impl Interface {
             average_ping_collection: VecDeque::with_capacity(20),
             frame_loop_collection: VecDeque::with_capacity(20),
             vitalbar: VitalBar::new(systems),
+            target_frame: TargetFrame::new(systems),
+            effect_bar: EffectBar::new(systems),
+            party_frames: PartyFrames::new(systems),
             did_button_click: false,
-            inventory: Inventory::new(systems),
-            storage: Storage::new(systems),
-            shop: Shop::new(systems),
-            trade: Trade::new(systems),
-            profile: Profile::new(systems),
-            setting: Setting::new(systems),
-            chatbox: Chatbox::new(systems),
+            windows: WindowRegistry::new(vec![
+                Box::new(Chatbox::new(systems)),
+                Box::new(Inventory::new(systems)),
+                Box::new(Profile::new(systems)),
+                Box::new(Setting::new(systems)),
+                Box::new(Storage::new(systems)),
+                Box::new(Shop::new(systems)),
+                Box::new(Trade::new(systems)),
+                Box::new(CombatLogWindow::new(systems)),
+                Box::new(InspectWindow::new(systems)),
+            ]),
             item_desc: ItemDescription::new(systems),
-            window_order: Vec::with_capacity(7),
             drag_window: None,
+            state_owner: None,
+            state_changed: false,
+            needs_rebuild: false,
             selected_textbox: SelectedTextbox::None,
-        };
-
-        interface.add_window_order();
-        interface
-    }
-
-    pub fn add_window_order(&mut self) {
-        self.window_order.push((Window::Chatbox, 0));
-        self.window_order.push((Window::Inventory, 1));
-        self.window_order.push((Window::Profile, 2));
-        self.window_order.push((Window::Setting, 3));
-        self.window_order.push((Window::Storage, 4));
-        self.window_order.push((Window::Shop, 5));
-        self.window_order.push((Window::Trade, 6));
-        self.window_order.sort_by_key(|a| a.1);
+        }
     }
 
     pub fn recreate(&mut self, systems: &mut SystemHolder) {
         self.menu_button = create_menu_button(systems);
         self.vitalbar = VitalBar::new(systems);
-        self.inventory = Inventory::new(systems);
-        self.profile = Profile::new(systems);
-        self.setting = Setting::new(systems);
-        self.chatbox = Chatbox::new(systems);
-        self.storage = Storage::new(systems);
-        self.shop = Shop::new(systems);
-        self.trade = Trade::new(systems);
+        self.target_frame = TargetFrame::new(systems);
+        self.effect_bar = EffectBar::new(systems);
+        self.party_frames = PartyFrames::new(systems);
+        for window in self.windows.iter_mut() {
+            window.recreate(systems);
+        }
+        self.windows.reset_order();
         self.item_desc = ItemDescription::new(systems);
-        self.add_window_order();
         self.did_button_click = false;
         self.drag_window = None;
+        self.state_owner = None;
+        self.state_changed = false;
+        self.needs_rebuild = false;
         self.selected_textbox = SelectedTextbox::None;
+        self.show_statistics(systems);
+    }
+
+    fn show_statistics(&self, systems: &mut SystemHolder) {
         systems
             .gfx
             .set_visible(&self.ping_text, systems.config.show_ping);
@@ -213,25 +246,180 @@ impl Interface {
             .set_visible(&self.frame_loop, systems.config.show_frame_loop);
     }
 
+    /// Places the windows where `character` left them in their last session.
+    pub fn restore_window_state(
+        &mut self,
+        systems: &mut SystemHolder,
+        character: &str,
+    ) {
+        let states = WindowStates::load(character);
+        let mut order = Vec::with_capacity(states.window.len());
+
+        for (index, window) in self.windows.iter_mut().enumerate() {
+            let Some(state) = states.window.get(window.name()) else {
+                order.push((window.id(), index));
+                continue;
+            };
+
+            window.place_at(systems, Vec2::from(state.pos));
+            if window.restores_visibility() {
+                window.set_visible(systems, state.visible);
+            }
+
+            order.push((window.id(), state.order_index));
+        }
+
+        self.windows.sort_by_key(|window| {
+            order
+                .iter()
+                .find(|(id, _)| *id == window.id())
+                .map(|(_, order_index)| *order_index)
+        });
+        adjust_window_zorder(self, systems);
+
+        self.state_owner = Some(character.to_string());
+        self.state_changed = false;
+    }
+
+    pub fn save_window_state(&mut self) {
+        if !self.state_changed {
+            return;
+        }
+        self.state_changed = false;
+
+        let Some(character) = &self.state_owner else {
+            return;
+        };
+
+        let mut states = WindowStates::default();
+        for (order_index, window) in self.windows.iter().enumerate() {
+            let frame = window.frame();
+
+            states.window.insert(
+                window.name().to_string(),
+                WindowState {
+                    pos: frame.pos.to_array(),
+                    visible: frame.visible,
+                    order_index,
+                },
+            );
+        }
+
+        states.save(character);
+    }
+
+    /// Moves every window back to its default place and stacking order.
+    pub fn reset_window_state(&mut self, systems: &mut SystemHolder) {
+        for window in self.windows.iter_mut() {
+            let pos = window.frame().default_pos;
+
+            window.place_at(systems, pos);
+        }
+
+        self.windows.reset_order();
+        adjust_window_zorder(self, systems);
+        // The reset is pressed from the Setting window so keep it in front.
+        interface_set_to_first(self, systems, Window::Setting);
+
+        self.state_changed = true;
+        self.save_window_state();
+    }
+
+    /// Builds every window again at the current `systems.scale`. Windows
+    /// opened by the server can't be filled again on the client so they are
+    /// closed on the server as well.
+    pub fn rebuild(
+        &mut self,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+    ) -> Result<()> {
+        for window in self.windows.iter() {
+            if window.frame().visible {
+                window.send_close(socket)?;
+            }
+        }
+
+        self.state_changed = true;
+        self.save_window_state();
+        let state_owner = self.state_owner.clone();
+        let chatbox = self.windows.get::<Chatbox>();
+        let history = chatbox.history();
+        let selected_tab = chatbox.selected_tab;
+
+        self.unload(systems);
+        self.recreate(systems);
+
+        let fps = systems.fps;
+        for (row, label) in
+            [fps, self.ping_text, self.average_ping, self.frame_loop]
+                .iter()
+                .enumerate()
+        {
+            set_statistic_area(systems, label, row);
+        }
+
+        self.windows.get_mut::<Chatbox>().restore_history(
+            systems,
+            history,
+            selected_tab,
+        );
+        if let Some(character) = state_owner {
+            self.restore_window_state(systems, &character);
+        }
+
+        Ok(())
+    }
+
+    /// Re-anchors everything to the screen edges after the screen was
+    /// resized from `old_screen`.
+    pub fn resize(&mut self, systems: &mut SystemHolder, old_screen: Vec2) {
+        let screen = Vec2::new(systems.size.width, systems.size.height);
+
+        for button in self.menu_button.iter_mut() {
+            button.set_pos(systems, Vec2::new(screen.x, 10.0));
+        }
+        self.vitalbar
+            .move_by(systems, Vec2::new(0.0, screen.y - old_screen.y));
+        self.target_frame
+            .move_by(systems, Vec2::new(0.0, screen.y - old_screen.y));
+        self.effect_bar
+            .move_by(systems, Vec2::new(0.0, screen.y - old_screen.y));
+        self.party_frames
+            .move_by(systems, Vec2::new(0.0, screen.y - old_screen.y));
+
+        for window in self.windows.iter_mut() {
+            window.resize(systems, old_screen);
+        }
+        self.item_desc.set_visible(systems, false);
+
+        for (row, label) in [self.ping_text, self.average_ping, self.frame_loop]
+            .iter()
+            .enumerate()
+        {
+            set_statistic_area(systems, label, row + 1);
+        }
+
+        self.state_changed = true;
+    }
+
     pub fn unload(&mut self, systems: &mut SystemHolder) {
         self.menu_button.iter_mut().for_each(|button| {
             button.unload(systems);
         });
         self.vitalbar.unload(systems);
-        self.inventory.unload(systems);
-        self.profile.unload(systems);
-        self.setting.unload(systems);
-        self.chatbox.unload(systems);
-        self.storage.unload(systems);
-        self.shop.unload(systems);
-        self.trade.unload(systems);
-        self.window_order.clear();
+        self.target_frame.unload(systems);
+        self.effect_bar.unload(systems);
+        self.party_frames.unload(systems);
+        for window in self.windows.iter_mut() {
+            window.unload(systems);
+        }
         self.item_desc.unload(systems);
         systems.gfx.set_visible(&self.ping_text, false);
         systems.gfx.set_visible(&self.average_ping, false);
         systems.gfx.set_visible(&self.frame_loop, false);
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn mouse_input(
         interface: &mut Interface,
         _world: &mut World,
@@ -248,130 +436,34 @@ impl Interface {
             MouseInputType::MouseMove => {
                 let mut can_hover: bool = true;
 
-                for window in interface.window_order.iter() {
-                    if can_hover {
-                        match window.0 {
-                            Window::Chatbox => {
-                                interface
-                                    .chatbox
-                                    .hover_buttons(systems, screen_pos);
-                                interface
-                                    .chatbox
-                                    .hover_msg(systems, screen_pos);
-                                interface
-                                    .chatbox
-                                    .hover_scrollbar(systems, screen_pos);
-                                can_hover = !interface
-                                    .chatbox
-                                    .in_window(screen_pos, systems);
-                            }
-                            Window::Inventory => {
-                                interface
-                                    .inventory
-                                    .hover_buttons(systems, screen_pos);
-                                interface.inventory.hover_data(
-                                    systems,
-                                    screen_pos,
-                                    &mut interface.item_desc,
-                                );
-                                can_hover =
-                                    !interface.inventory.in_window(screen_pos);
-                            }
-                            Window::Profile => {
-                                interface
-                                    .profile
-                                    .hover_buttons(systems, screen_pos);
-                                interface.profile.hover_data(
-                                    systems,
-                                    screen_pos,
-                                    &mut interface.item_desc,
-                                );
-                                can_hover =
-                                    !interface.profile.in_window(screen_pos);
-                            }
-                            Window::Setting => {
-                                interface
-                                    .setting
-                                    .hover_buttons(systems, screen_pos);
-                                interface
-                                    .setting
-                                    .hover_scrollbar(systems, screen_pos);
-                                interface.setting.hover_checkbox(
-                                    systems, tooltip, screen_pos,
-                                );
-                                can_hover =
-                                    !interface.setting.in_window(screen_pos);
-                            }
-                            Window::Shop => {
-                                interface
-                                    .shop
-                                    .hover_buttons(systems, screen_pos);
-                                interface
-                                    .shop
-                                    .hover_scrollbar(systems, screen_pos);
-                                interface.shop.hover_data(
-                                    systems,
-                                    screen_pos,
-                                    &mut interface.item_desc,
-                                );
-                                can_hover =
-                                    !interface.shop.in_window(screen_pos);
-                            }
-                            Window::Storage => {
-                                interface
-                                    .storage
-                                    .hover_buttons(systems, screen_pos);
-                                interface.storage.hover_data(
-                                    systems,
-                                    screen_pos,
-                                    &mut interface.item_desc,
-                                );
-                                can_hover =
-                                    !interface.storage.in_window(screen_pos);
-                            }
-                            Window::Trade => {
-                                interface
-                                    .trade
-                                    .hover_buttons(systems, screen_pos);
-                                interface.trade.hover_data(
-                                    systems,
-                                    screen_pos,
-                                    &mut interface.item_desc,
-                                );
-                                can_hover =
-                                    !interface.trade.in_window(screen_pos);
-                            }
-                        }
+                for window in interface.windows.iter_mut() {
+                    window.hover_buttons(systems, screen_pos);
+                    window.hover_content(
+                        systems,
+                        tooltip,
+                        &mut interface.item_desc,
+                        screen_pos,
+                    );
+
+                    if window.in_window(systems, screen_pos) {
+                        can_hover = false;
+                        break;
                     }
                 }
 
                 if can_hover {
                     Interface::hover_buttons(interface, systems, screen_pos);
+                    interface.effect_bar.hover(systems, tooltip, screen_pos);
                 }
             }
             MouseInputType::MouseDoubleLeftDown => {
-                if interface.inventory.visible
-                    && interface.inventory.order_index == 0
-                    && let Some(slot) = interface
-                        .inventory
-                        .find_inv_slot(systems, screen_pos, false)
-                {
-                    send_useitem(socket, slot as u16)?;
-                }
-
-                if interface.profile.visible
-                    && interface.profile.order_index == 0
-                    && let Some(slot) = interface
-                        .profile
-                        .find_eq_slot(systems, screen_pos, false)
-                {
-                    send_unequip(socket, slot as u16)?;
-                }
-
-                if interface.chatbox.order_index == 0
-                    && let Some(text) = interface.chatbox.get_selected_msg()
+                if let Some(window) =
+                    interface.windows.iter_mut().find(|window| {
+                        window.frame().visible
+                            && window.frame().order_index == 0
+                    })
                 {
-                    set_clipboard_text(text.as_ref());
+                    window.double_click(systems, socket, screen_pos)?;
                 }
             }
             MouseInputType::MouseLeftDown => {
@@ -394,9 +486,12 @@ impl Interface {
                     interface.item_desc.set_visible(systems, false);
                 }
 
-                if let Some(slot) = interface.inventory.hold_slot {
+                if let Some(slot) =
+                    interface.windows.get::<Inventory>().hold_slot
+                {
                     interface
-                        .inventory
+                        .windows
+                        .get_mut::<Inventory>()
                         .move_inv_slot(systems, slot, screen_pos);
 
                     let window =
@@ -422,9 +517,11 @@ impl Interface {
 
                     return Ok(true);
                 }
-                if let Some(slot) = interface.storage.hold_slot {
+                if let Some(slot) = interface.windows.get::<Storage>().hold_slot
+                {
                     interface
-                        .storage
+                        .windows
+                        .get_mut::<Storage>()
                         .move_storage_slot(systems, slot, screen_pos);
 
                     let window =
@@ -451,168 +548,65 @@ impl Interface {
                     return Ok(true);
                 }
 
-                if let Some(window) = &interface.drag_window {
-                    match window {
-                        Window::Inventory => {
-                            interface.inventory.move_window(systems, screen_pos)
-                        }
-                        Window::Profile => {
-                            interface.profile.move_window(systems, screen_pos)
-                        }
-                        Window::Setting => {
-                            interface.setting.move_window(systems, screen_pos)
-                        }
-                        Window::Chatbox => {
-                            interface.chatbox.move_window(systems, screen_pos)
-                        }
-                        Window::Storage => {
-                            interface.storage.move_window(systems, screen_pos)
-                        }
-                        Window::Shop => {
-                            interface.shop.move_window(systems, screen_pos)
-                        }
-                        Window::Trade => {
-                            interface.trade.move_window(systems, screen_pos)
-                        }
-                    }
+                if let Some(window) = interface.drag_window {
+                    interface
+                        .windows
+                        .window_mut(window)
+                        .move_window(systems, screen_pos);
                     result = true;
                 } else {
-                    if interface.setting.visible {
-                        interface
-                            .setting
-                            .sfx_scroll
-                            .set_move_scroll(systems, screen_pos);
-                        interface
-                            .setting
-                            .bgm_scroll
-                            .set_move_scroll(systems, screen_pos);
-
-                        if interface.setting.bgm_scroll.in_hold {
-                            interface.setting.update_bgm_value(
-                                systems,
-                                interface.setting.bgm_scroll.value,
-                            );
-                            let volume = interface.setting.bgm_scroll.value
-                                as f32
-                                * 0.01;
-                            systems.audio.set_music_volume(volume);
-                            result = true;
-                        } else if interface.setting.sfx_scroll.in_hold {
-                            interface.setting.update_sfx_value(
-                                systems,
-                                interface.setting.sfx_scroll.value,
-                            );
-                            let volume = interface.setting.sfx_scroll.value
-                                as f32
-                                * 0.01;
-
-                            systems.audio.set_effect_volume(volume);
-                            result = true;
-                        }
-                    }
-
-                    interface
-                        .chatbox
-                        .scrollbar
-                        .set_move_scroll(systems, screen_pos);
-                    interface.chatbox.set_chat_scrollbar(systems, false);
-
-                    if interface.shop.visible {
-                        interface
-                            .shop
-                            .item_scroll
-                            .set_move_scroll(systems, screen_pos);
-                        interface.shop.set_shop_scroll_value(systems);
-
-                        if interface.shop.item_scroll.in_hold {
-                            result = true;
-                        }
-                    }
-
-                    if interface.chatbox.scrollbar.in_hold {
-                        result = true;
+                    for window in interface.windows.iter_mut() {
+                        result |= window.move_content(systems, screen_pos);
                     }
 
                     interface.hold_move_textbox(systems, screen_pos);
                 }
             }
             MouseInputType::MouseRelease => {
-                if let Some(slot) = interface.inventory.hold_slot {
+                if let Some(slot) =
+                    interface.windows.get::<Inventory>().hold_slot
+                {
                     release_inv_slot(
                         interface, socket, systems, alert, slot, screen_pos,
                     )?;
-                    interface.inventory.hold_slot = None;
+                    interface.windows.get_mut::<Inventory>().hold_slot = None;
                     return Ok(true);
                 }
 
-                if let Some(slot) = interface.storage.hold_slot {
+                if let Some(slot) = interface.windows.get::<Storage>().hold_slot
+                {
                     release_storage_slot(
                         interface, socket, systems, alert, slot, screen_pos,
                     )?;
-                    interface.storage.hold_slot = None;
+                    interface.windows.get_mut::<Storage>().hold_slot = None;
                     return Ok(true);
                 }
 
                 interface.reset_buttons(systems);
                 interface.release_textbox();
 
-                if let Some(window) = &interface.drag_window {
-                    match window {
-                        Window::Inventory => {
-                            interface.inventory.release_window()
-                        }
-                        Window::Profile => interface.profile.release_window(),
-                        Window::Setting => interface.setting.release_window(),
-                        Window::Chatbox => interface.chatbox.release_window(),
-                        Window::Storage => interface.storage.release_window(),
-                        Window::Shop => interface.shop.release_window(),
-                        Window::Trade => interface.trade.release_window(),
-                    }
+                if let Some(window) = interface.drag_window {
+                    interface.windows.window_mut(window).release_window();
+                    interface.state_changed = true;
                 }
 
                 interface.drag_window = None;
 
-                if interface.setting.visible {
-                    if interface.setting.bgm_scroll.in_hold {
-                        systems.config.bgm_volume =
-                            interface.setting.bgm_scroll.value as u8;
-                        systems.config.save_config("settings.toml");
-                    } else if interface.setting.sfx_scroll.in_hold {
-                        systems.config.sfx_volume =
-                            interface.setting.sfx_scroll.value as u8;
-                        systems.config.save_config("settings.toml");
+                for window in interface.windows.iter_mut() {
+                    // Rebuilding replaces the window being released, so it is
+                    // left to the caller.
+                    if window.release_content(systems, screen_pos)
+                        == WindowAction::Rebuild
+                    {
+                        interface.needs_rebuild = true;
                     }
-
-                    interface
-                        .setting
-                        .sfx_scroll
-                        .set_hold(systems, false, screen_pos);
-                    interface
-                        .setting
-                        .bgm_scroll
-                        .set_hold(systems, false, screen_pos);
-                }
-
-                interface
-                    .chatbox
-                    .scrollbar
-                    .set_hold(systems, false, screen_pos);
-
-                if interface.shop.visible {
-                    interface
-                        .shop
-                        .item_scroll
-                        .set_hold(systems, false, screen_pos);
+                    window.reset_buttons(systems);
                 }
-
-                interface.chatbox.reset_buttons(systems);
-                interface.profile.reset_buttons(systems);
-                interface.setting.reset_buttons(systems);
-                interface.inventory.reset_buttons(systems);
-                interface.storage.reset_buttons(systems);
-                interface.shop.reset_buttons(systems);
-                interface.trade.reset_buttons(systems);
-                interface.setting.reset_checkbox(systems);
+                interface.save_window_state();
+            }
+            MouseInputType::MouseRightDown => {
+                result =
+                    find_window(systems, interface, screen_pos, None).is_some();
             }
         }
 
@@ -627,8 +621,13 @@ impl Interface {
         key: &Key,
         pressed: bool,
     ) -> Result<()> {
+        if pressed && let Key::Named(NamedKey::Escape) = key {
+            game_content.interface.escape(systems, socket)?;
+            return Ok(());
+        }
+
         if pressed
-            && !game_content.interface.trade.visible
+            && !game_content.interface.windows.get::<Trade>().frame.visible
             && let Key::Named(NamedKey::Enter) = key
         {
             if game_content.interface.selected_textbox
@@ -637,39 +636,55 @@ impl Interface {
                 game_content.interface.selected_textbox = SelectedTextbox::None;
                 game_content
                     .interface
-                    .chatbox
+                    .windows
+                    .get_mut::<Chatbox>()
                     .textbox
                     .set_select(systems, false);
-                trigger_chatbox_button(
-                    &mut game_content.interface,
-                    systems,
-                    socket,
-                    2,
-                )?;
+                game_content
+                    .interface
+                    .windows
+                    .get_mut::<Chatbox>()
+                    .send_chat(systems, socket)?;
             } else {
                 game_content.interface.selected_textbox =
                     SelectedTextbox::Chatbox;
                 game_content
                     .interface
-                    .chatbox
+                    .windows
+                    .get_mut::<Chatbox>()
                     .textbox
                     .set_select(systems, true);
             }
         }
 
+        if pressed
+            && game_content.interface.selected_textbox == SelectedTextbox::None
+            && KeyAction::from_key(&systems.config.key_bindings, key)
+                == Some(KeyAction::ToggleCombatLog)
+        {
+            toggle_interface(
+                &mut game_content.interface,
+                systems,
+                Window::CombatLog,
+            );
+            return Ok(());
+        }
+
         match game_content.interface.selected_textbox {
             SelectedTextbox::Chatbox => {
                 game_content
                     .interface
-                    .chatbox
+                    .windows
+                    .get_mut::<Chatbox>()
                     .textbox
                     .enter_text(systems, key, pressed, false);
             }
             SelectedTextbox::Trade => {
                 game_content
                     .interface
-                    .trade
-                    .money_input
+                    .windows
+                    .get_mut::<Trade>()
+                    .money_input_mut()
                     .enter_text(systems, key, pressed, true);
             }
             _ => {}
@@ -677,6 +692,53 @@ impl Interface {
         Ok(())
     }
 
+    /// Leaves the selected textbox, or else closes an open dropdown or the
+    /// topmost window. Windows that are part of the HUD stay open.
+    fn escape(
+        &mut self,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+    ) -> Result<()> {
+        match self.selected_textbox {
+            SelectedTextbox::Chatbox => {
+                self.windows
+                    .get_mut::<Chatbox>()
+                    .textbox
+                    .set_select(systems, false);
+                self.selected_textbox = SelectedTextbox::None;
+                return Ok(());
+            }
+            SelectedTextbox::Trade => {
+                self.windows
+                    .get_mut::<Trade>()
+                    .money_input_mut()
+                    .set_select(systems, false);
+                self.selected_textbox = SelectedTextbox::None;
+                return Ok(());
+            }
+            SelectedTextbox::None => {}
+        }
+
+        if let Some(window) =
+            self.windows.iter_mut().find(|window| window.popup_open())
+        {
+            window.close_popup(systems);
+            return Ok(());
+        }
+
+        let Some(window) = self
+            .windows
+            .iter()
+            .find(|window| window.closes_on_escape() && window.frame().visible)
+            .map(|window| window.id())
+        else {
+            return Ok(());
+        };
+
+        close_interface(self, systems, window);
+        self.windows.window(window).send_close(socket)
+    }
+
     pub fn hover_buttons(
         interface: &mut Interface,
         systems: &mut SystemHolder,
@@ -707,355 +769,77 @@ impl Interface {
         screen_pos: Vec2,
         alert: &mut Alert,
     ) -> Result<bool> {
-        let mut did_click = false;
-
-        for index in 0..self.window_order.len() {
-            if !did_click {
-                match self.window_order[index].0 {
-                    Window::Chatbox => {
-                        if self.chatbox.in_window(screen_pos, systems) {
-                            if let Some(index) =
-                                self.chatbox.click_buttons(systems, screen_pos)
-                            {
-                                self.chatbox.did_button_click = true;
-                                trigger_chatbox_button(
-                                    self, systems, socket, index,
-                                )?;
-                            }
-
-                            self.chatbox.select_chat_tab(systems, screen_pos);
-                            self.click_textbox(
-                                systems,
-                                socket,
-                                screen_pos,
-                                SelectedTextbox::Chatbox,
-                            )?;
-
-                            if self.chatbox.scrollbar.in_scroll(screen_pos) {
-                                self.chatbox
-                                    .scrollbar
-                                    .set_hold(systems, true, screen_pos);
-                            }
-
-                            hold_interface(
-                                self,
-                                systems,
-                                Window::Chatbox,
-                                screen_pos,
-                                true,
-                                false,
-                            );
-                            did_click = true;
-                        }
-                    }
-                    Window::Inventory => {
-                        if self.inventory.in_window(screen_pos) {
-                            if let Some(index) = self
-                                .inventory
-                                .click_buttons(systems, screen_pos)
-                            {
-                                if index == 0 {
-                                    close_interface(
-                                        self,
-                                        systems,
-                                        Window::Inventory,
-                                    );
-                                    return Ok(true);
-                                }
-
-                                self.inventory.did_button_click = true;
-                            }
-
-                            hold_interface(
-                                self,
-                                systems,
-                                Window::Inventory,
-                                screen_pos,
-                                true,
-                                false,
-                            );
-                            did_click = true;
-                        }
-                    }
-                    Window::Profile => {
-                        if self.profile.in_window(screen_pos) {
-                            if let Some(index) =
-                                self.profile.click_buttons(systems, screen_pos)
-                            {
-                                if index == 0 {
-                                    close_interface(
-                                        self,
-                                        systems,
-                                        Window::Profile,
-                                    );
-                                    return Ok(true);
-                                }
-
-                                self.profile.did_button_click = true;
-                            }
-
-                            hold_interface(
-                                self,
-                                systems,
-                                Window::Profile,
-                                screen_pos,
-                                true,
-                                false,
-                            );
-                            did_click = true;
-                        }
-                    }
-                    Window::Setting => {
-                        if self.setting.in_window(screen_pos) {
-                            if let Some(index) =
-                                self.setting.click_buttons(systems, screen_pos)
-                            {
-                                if index == 0 {
-                                    close_interface(
-                                        self,
-                                        systems,
-                                        Window::Setting,
-                                    );
-                                    return Ok(true);
-                                }
-
-                                self.setting.did_button_click = true;
-                            }
+        // An open dropdown list takes the click, picking an option or
+        // closing the list when the click is outside of it.
+        let popup = self
+            .windows
+            .iter()
+            .find(|window| window.popup_open())
+            .map(|window| window.id());
+        if let Some(window) = popup {
+            let action = self
+                .windows
+                .window_mut(window)
+                .click_content(systems, socket, alert, screen_pos)?;
+
+            self.apply_window_action(systems, socket, window, action)?;
+            return Ok(true);
+        }
 
-                            if self.setting.sfx_scroll.in_scroll(screen_pos) {
-                                self.setting
-                                    .sfx_scroll
-                                    .set_hold(systems, true, screen_pos);
-                            }
+        let Some(window) = self.windows.topmost_at(systems, screen_pos) else {
+            return Ok(false);
+        };
 
-                            if self.setting.bgm_scroll.in_scroll(screen_pos) {
-                                self.setting
-                                    .bgm_scroll
-                                    .set_hold(systems, true, screen_pos);
-                            }
+        let wndw = self.windows.window_mut(window);
+        if let Some(index) = wndw.click_buttons(systems, screen_pos) {
+            wndw.frame_mut().did_button_click = true;
 
-                            if let Some(index) =
-                                self.setting.click_checkbox(systems, screen_pos)
-                            {
-                                self.setting.did_checkbox_click = true;
-                                self.setting.trigger_checkbox(
-                                    systems,
-                                    index,
-                                    &self.ping_text,
-                                    &self.average_ping,
-                                    &self.frame_loop,
-                                );
-                            }
-
-                            hold_interface(
-                                self,
-                                systems,
-                                Window::Setting,
-                                screen_pos,
-                                true,
-                                false,
-                            );
-                            did_click = true;
-                        }
-                    }
-                    Window::Shop => {
-                        if self.shop.in_window(screen_pos) {
-                            if let Some(index) =
-                                self.shop.click_buttons(systems, screen_pos)
-                            {
-                                match index {
-                                    0 => {
-                                        close_interface(
-                                            self,
-                                            systems,
-                                            Window::Shop,
-                                        );
-                                        send_closeshop(socket)?;
-                                        return Ok(true);
-                                    } // Close
-                                    1 => {
-                                        // Scroll Up
-                                        if self.shop.item_scroll.max_value == 0
-                                        {
-                                            return Ok(true);
-                                        }
-
-                                        let scrollbar_value = self
-                                            .shop
-                                            .item_scroll
-                                            .value
-                                            .saturating_sub(1);
-
-                                        self.shop.item_scroll.set_value(
-                                            systems,
-                                            scrollbar_value,
-                                        );
-                                        self.shop
-                                            .set_shop_scroll_value(systems);
-                                    }
-                                    2 => {
-                                        // Scroll Down
-                                        if self.shop.item_scroll.max_value == 0
-                                        {
-                                            return Ok(true);
-                                        }
-
-                                        let scrollbar_value = self
-                                            .shop
-                                            .item_scroll
-                                            .value
-                                            .saturating_add(1)
-                                            .min(
-                                                self.shop.item_scroll.max_value,
-                                            );
-
-                                        self.shop.item_scroll.set_value(
-                                            systems,
-                                            scrollbar_value,
-                                        );
-                                        self.shop
-                                            .set_shop_scroll_value(systems);
-                                    }
-                                    3..=7 => {
-                                        let button_index =
-                                            self.shop.shop_start_pos
-                                                + index.saturating_sub(3);
-
-                                        send_buyitem(
-                                            socket,
-                                            button_index as u16,
-                                        )?;
-                                    }
-                                    _ => {}
-                                }
-
-                                self.shop.did_button_click = true;
-                            }
+            let action = wndw.trigger_button(systems, socket, index)?;
+            if self.apply_window_action(systems, socket, window, action)? {
+                return Ok(true);
+            }
+        }
 
-                            if self.shop.item_scroll.in_scroll(screen_pos) {
-                                self.shop
-                                    .item_scroll
-                                    .set_hold(systems, true, screen_pos);
-                            }
+        let textbox = self.windows.window(window).textbox();
+        if textbox != SelectedTextbox::None {
+            self.click_textbox(systems, socket, screen_pos, textbox)?;
+        }
 
-                            hold_interface(
-                                self,
-                                systems,
-                                Window::Shop,
-                                screen_pos,
-                                true,
-                                false,
-                            );
-                            did_click = true;
-                        }
-                    }
-                    Window::Storage => {
-                        if self.storage.in_window(screen_pos) {
-                            if let Some(index) =
-                                self.storage.click_buttons(systems, screen_pos)
-                            {
-                                if index == 0 {
-                                    close_interface(
-                                        self,
-                                        systems,
-                                        Window::Storage,
-                                    );
-                                    send_closestorage(socket)?;
-                                    return Ok(true);
-                                }
-                                self.storage.did_button_click = true;
-                            }
+        let action = self
+            .windows
+            .window_mut(window)
+            .click_content(systems, socket, alert, screen_pos)?;
+        if self.apply_window_action(systems, socket, window, action)? {
+            return Ok(true);
+        }
 
-                            hold_interface(
-                                self,
-                                systems,
-                                Window::Storage,
-                                screen_pos,
-                                true,
-                                false,
-                            );
-                            did_click = true;
-                        }
-                    }
-                    Window::Trade => {
-                        if self.trade.in_window(screen_pos) {
-                            if let Some(index) =
-                                self.trade.click_buttons(systems, screen_pos)
-                            {
-                                match index {
-                                    0 | 2 => {
-                                        close_interface(
-                                            self,
-                                            systems,
-                                            Window::Trade,
-                                        );
-                                        send_closetrade(socket)?;
-                                        return Ok(true);
-                                    }
-                                    1 => {
-                                        if matches!(
-                                            self.trade.trade_status,
-                                            TradeStatus::None
-                                                | TradeStatus::Accepted
-                                        ) {
-                                            send_submittrade(socket)?;
-                                        }
-                                    }
-                                    _ => {}
-                                }
-
-                                self.trade.did_button_click = true;
-                            }
-                            self.click_textbox(
-                                systems,
-                                socket,
-                                screen_pos,
-                                SelectedTextbox::Trade,
-                            )?;
-
-                            if self.trade.trade_status == TradeStatus::None
-                                && let Some(slot) = self
-                                    .trade
-                                    .find_mytrade_slot(systems, screen_pos)
-                                && self.trade.my_items[slot].got_data
-                            {
-                                if self.trade.my_items[slot].count_data > 1 {
-                                    alert.show_alert(
-                                        systems,
-                                        AlertType::Input,
-                                        "",
-                                        "Enter the amount to remove",
-                                        250,
-                                        AlertIndex::RemoveTradeItem(
-                                            slot as u16,
-                                        ),
-                                        true,
-                                    );
-                                } else {
-                                    send_removetradeitem(
-                                        socket,
-                                        slot as u16,
-                                        1,
-                                    )?;
-                                }
-                            }
+        hold_interface(self, systems, window, screen_pos, true, false);
+        Ok(true)
+    }
 
-                            hold_interface(
-                                self,
-                                systems,
-                                Window::Trade,
-                                screen_pos,
-                                true,
-                                false,
-                            );
-                            did_click = true;
-                        }
-                    }
-                }
+    /// Carries out what a window asked for after handling input, returns
+    /// true when no further click handling should happen.
+    fn apply_window_action(
+        &mut self,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+        window: Window,
+        action: WindowAction,
+    ) -> Result<bool> {
+        match action {
+            WindowAction::None => {}
+            WindowAction::Close => {
+                close_interface(self, systems, window);
+                self.windows.window(window).send_close(socket)?;
+                return Ok(true);
             }
+            WindowAction::Stop => return Ok(true),
+            WindowAction::ResetLayout => self.reset_window_state(systems),
+            WindowAction::Rebuild => self.needs_rebuild = true,
+            WindowAction::ShowStatistics => self.show_statistics(systems),
         }
 
-        Ok(did_click)
+        Ok(false)
     }
 
     pub fn click_buttons(
@@ -1107,61 +891,112 @@ impl Interface {
                 if is_within_area(
                     screen_pos,
                     Vec2::new(
-                        self.chatbox.textbox.base_pos.x
-                            + (self.chatbox.textbox.adjust_pos.x
+                        self.windows.get::<Chatbox>().textbox.base_pos.x
+                            + (self
+                                .windows
+                                .get_mut::<Chatbox>()
+                                .textbox
+                                .adjust_pos
+                                .x
                                 * systems.scale as f32)
                                 .floor(),
-                        self.chatbox.textbox.base_pos.y
-                            + (self.chatbox.textbox.adjust_pos.y
+                        self.windows.get::<Chatbox>().textbox.base_pos.y
+                            + (self
+                                .windows
+                                .get_mut::<Chatbox>()
+                                .textbox
+                                .adjust_pos
+                                .y
                                 * systems.scale as f32)
                                 .floor(),
                     ),
-                    (self.chatbox.textbox.size * systems.scale as f32).floor(),
+                    (self.windows.get::<Chatbox>().textbox.size
+                        * systems.scale as f32)
+                        .floor(),
                 ) {
-                    self.chatbox.textbox.set_select(systems, true);
-                    self.chatbox.textbox.set_hold(true);
-                    self.chatbox.textbox.select_text(systems, screen_pos);
+                    self.windows
+                        .get_mut::<Chatbox>()
+                        .textbox
+                        .set_select(systems, true);
+                    self.windows.get_mut::<Chatbox>().textbox.set_hold(true);
+                    self.windows
+                        .get_mut::<Chatbox>()
+                        .textbox
+                        .select_text(systems, screen_pos);
                     self.selected_textbox = SelectedTextbox::Chatbox;
                     return Ok(());
                 }
             }
-            SelectedTextbox::Trade => {
-                if self.trade.visible
+            SelectedTextbox::Trade
+                if self.windows.get::<Trade>().frame.visible
                     & is_within_area(
                         screen_pos,
                         Vec2::new(
-                            self.trade.money_input.base_pos.x
-                                + (self.trade.money_input.adjust_pos.x
+                            self.windows
+                                .get::<Trade>()
+                                .money_input()
+                                .base_pos
+                                .x
+                                + (self
+                                    .windows
+                                    .get::<Trade>()
+                                    .money_input()
+                                    .adjust_pos
+                                    .x
                                     * systems.scale as f32)
                                     .floor(),
-                            self.trade.money_input.base_pos.y
-                                + (self.trade.money_input.adjust_pos.y
+                            self.windows
+                                .get::<Trade>()
+                                .money_input()
+                                .base_pos
+                                .y
+                                + (self
+                                    .windows
+                                    .get::<Trade>()
+                                    .money_input()
+                                    .adjust_pos
+                                    .y
                                     * systems.scale as f32)
                                     .floor(),
                         ),
-                        self.trade.money_input.size,
-                    )
-                {
-                    self.trade.money_input.set_select(systems, true);
-                    self.trade.money_input.set_hold(true);
-                    self.trade.money_input.select_text(systems, screen_pos);
-                    self.selected_textbox = SelectedTextbox::Trade;
-                    return Ok(());
-                }
+                        self.windows.get::<Trade>().money_input().size,
+                    ) =>
+            {
+                self.windows
+                    .get_mut::<Trade>()
+                    .money_input_mut()
+                    .set_select(systems, true);
+                self.windows
+                    .get_mut::<Trade>()
+                    .money_input_mut()
+                    .set_hold(true);
+                self.windows
+                    .get_mut::<Trade>()
+                    .money_input_mut()
+                    .select_text(systems, screen_pos);
+                self.selected_textbox = SelectedTextbox::Trade;
+                return Ok(());
             }
             _ => {}
         }
 
         match self.selected_textbox {
-            SelectedTextbox::Chatbox => {
-                self.chatbox.textbox.set_select(systems, false)
-            }
+            SelectedTextbox::Chatbox => self
+                .windows
+                .get_mut::<Chatbox>()
+                .textbox
+                .set_select(systems, false),
             SelectedTextbox::Trade => {
-                self.trade.money_input.set_select(systems, false);
-                if self.trade.trade_status == TradeStatus::None {
+                self.windows
+                    .get_mut::<Trade>()
+                    .money_input_mut()
+                    .set_select(systems, false);
+                if self.windows.get::<Trade>().trade_status == TradeStatus::None
+                {
                     let amount = self
-                        .trade
-                        .money_input
+                        .windows
+                        .get_mut::<Trade>()
+                        .money_input_mut()
                         .text
                         .parse::<u64>()
                         .unwrap_or_default();
@@ -1178,10 +1013,13 @@ impl Interface {
     pub fn release_textbox(&mut self) {
         match self.selected_textbox {
             SelectedTextbox::Chatbox => {
-                self.chatbox.textbox.set_hold(false);
+                self.windows.get_mut::<Chatbox>().textbox.set_hold(false);
             }
             SelectedTextbox::Trade => {
-                self.trade.money_input.set_hold(false);
+                self.windows
+                    .get_mut::<Trade>()
+                    .money_input_mut()
+                    .set_hold(false);
             }
             _ => {}
         }
@@ -1194,10 +1032,16 @@ impl Interface {
     ) {
         match self.selected_textbox {
             SelectedTextbox::Chatbox => {
-                self.chatbox.textbox.hold_move(systems, screen_pos);
+                self.windows
+                    .get_mut::<Chatbox>()
+                    .textbox
+                    .hold_move(systems, screen_pos);
             }
             SelectedTextbox::Trade => {
-                self.trade.money_input.hold_move(systems, screen_pos);
+                self.windows
+                    .get_mut::<Trade>()
+                    .money_input_mut()
+                    .hold_move(systems, screen_pos);
             }
             _ => {}
         }
@@ -1209,152 +1053,36 @@ fn trigger_button(
     systems: &mut SystemHolder,
     index: usize,
 ) {
-    match index {
-        0 => {
-            if interface.profile.visible {
-                close_interface(interface, systems, Window::Profile);
-            } else {
-                open_interface(interface, systems, Window::Profile);
-            }
-        }
-        1 => {
-            if interface.inventory.visible {
-                close_interface(interface, systems, Window::Inventory);
-            } else {
-                open_interface(interface, systems, Window::Inventory);
-            }
-        }
-        2 => {
-            if interface.setting.visible {
-                close_interface(interface, systems, Window::Setting);
-            } else {
-                open_interface(interface, systems, Window::Setting);
-            }
-        }
-        _ => {}
-    }
-}
-
-fn trigger_chatbox_button(
-    interface: &mut Interface,
-    systems: &mut SystemHolder,
-    socket: &mut Poller,
-    index: usize,
-) -> Result<()> {
-    match index {
-        0 => {
-            // Scroll Up
-            if interface.chatbox.scrollbar.max_value == 0 {
-                return Ok(());
-            }
-
-            let scrollbar_value = interface
-                .chatbox
-                .scrollbar
-                .value
-                .saturating_add(1)
-                .min(interface.chatbox.scrollbar.max_value);
-
-            interface
-                .chatbox
-                .scrollbar
-                .set_value(systems, scrollbar_value);
-            interface.chatbox.set_chat_scrollbar(systems, true);
-        }
-        1 => {
-            // Scroll Down
-            if interface.chatbox.scrollbar.max_value == 0 {
-                return Ok(());
-            }
-
-            let scrollbar_value =
-                interface.chatbox.scrollbar.value.saturating_sub(1);
-
-            interface
-                .chatbox
-                .scrollbar
-                .set_value(systems, scrollbar_value);
-            interface.chatbox.set_chat_scrollbar(systems, true);
-        }
-        2 => {
-            send_chat(interface, systems, socket)?;
-        }
-        _ => {}
-    }
-    Ok(())
-}
-
-fn can_find_window(window: Window, exception: Option<Window>) -> bool {
-    if let Some(x_window) = exception
-        && window == x_window
-    {
-        return false;
-    }
-
-    true
+    let window = match index {
+        0 => Window::Profile,
+        1 => Window::Inventory,
+        2 => Window::Setting,
+        _ => return,
+    };
+
+    toggle_interface(interface, systems, window);
 }
 
 fn find_window(
-    systems: &mut SystemHolder,
-    interface: &mut Interface,
+    systems: &SystemHolder,
+    interface: &Interface,
     screen_pos: Vec2,
     exception: Option<Window>,
 ) -> Option<Window> {
     let mut max_z_order: f32 = 0.0;
     let mut selected_window = None;
 
-    if interface.inventory.in_window(screen_pos)
-        && can_find_window(Window::Inventory, exception)
-    {
-        max_z_order = interface.inventory.z_order;
-        selected_window = Some(Window::Inventory);
-    }
-
-    if interface.profile.in_window(screen_pos)
-        && can_find_window(Window::Profile, exception)
-        && interface.profile.z_order > max_z_order
-    {
-        max_z_order = interface.profile.z_order;
-        selected_window = Some(Window::Profile);
-    }
-
-    if interface.setting.in_window(screen_pos)
-        && can_find_window(Window::Setting, exception)
-        && interface.setting.z_order > max_z_order
-    {
-        max_z_order = interface.setting.z_order;
-        selected_window = Some(Window::Setting);
-    }
-
-    if interface.chatbox.in_window(screen_pos, systems)
-        && can_find_window(Window::Chatbox, exception)
-        && interface.chatbox.z_order > max_z_order
-    {
-        max_z_order = interface.chatbox.z_order;
-        selected_window = Some(Window::Chatbox);
-    }
-
-    if interface.storage.in_window(screen_pos)
-        && can_find_window(Window::Storage, exception)
-        && interface.storage.z_order > max_z_order
-    {
-        max_z_order = interface.storage.z_order;
-        selected_window = Some(Window::Storage);
-    }
-
-    if interface.shop.in_window(screen_pos)
-        && can_find_window(Window::Shop, exception)
-        && interface.shop.z_order > max_z_order
-    {
-        max_z_order = interface.shop.z_order;
-        selected_window = Some(Window::Shop);
-    }
+    for wndw in interface.windows.iter() {
+        if exception == Some(wndw.id()) {
+            continue;
+        }
 
-    if interface.trade.in_window(screen_pos)
-        && can_find_window(Window::Trade, exception)
-        && interface.trade.z_order > max_z_order
-    {
-        selected_window = Some(Window::Trade);
+        if wndw.in_window(systems, screen_pos)
+            && (selected_window.is_none() || wndw.frame().z_order > max_z_order)
+        {
+            max_z_order = wndw.frame().z_order;
+            selected_window = Some(wndw.id());
+        }
     }
 
     selected_window
@@ -1365,52 +1093,14 @@ pub fn open_interface(
     systems: &mut SystemHolder,
     window: Window,
 ) {
-    match window {
-        Window::Inventory => {
-            if interface.inventory.visible {
-                return;
-            }
-
-            interface.inventory.set_visible(systems, true);
-        }
-        Window::Profile => {
-            if interface.profile.visible {
-                return;
-            }
-
-            interface.profile.set_visible(systems, true);
-        }
-        Window::Setting => {
-            if interface.setting.visible {
-                return;
-            }
-
-            interface.setting.set_visible(systems, true);
-        }
-        Window::Storage => {
-            if interface.storage.visible {
-                return;
-            }
-
-            interface.storage.set_visible(systems, true);
-        }
-        Window::Shop => {
-            if interface.shop.visible {
-                return;
-            }
-
-            interface.shop.set_visible(systems, true);
-        }
-        Window::Trade => {
-            if interface.trade.visible {
-                return;
-            }
+    let wndw = interface.windows.window_mut(window);
 
-            interface.trade.set_visible(systems, true);
-        }
-        _ => {}
+    if wndw.frame().visible {
+        return;
     }
 
+    wndw.set_visible(systems, true);
+    interface.state_changed = true;
     interface_set_to_first(interface, systems, window);
 }
 
@@ -1419,60 +1109,30 @@ pub fn close_interface(
     systems: &mut SystemHolder,
     window: Window,
 ) {
-    match window {
-        Window::Inventory => {
-            if !interface.inventory.visible {
-                return;
-            }
+    let wndw = interface.windows.window_mut(window);
 
-            interface.inventory.set_visible(systems, false);
-            interface.item_desc.set_visible(systems, false);
-        }
-        Window::Profile => {
-            if !interface.profile.visible {
-                return;
-            }
-
-            interface.profile.set_visible(systems, false);
-            interface.item_desc.set_visible(systems, false);
-        }
-        Window::Setting => {
-            if !interface.setting.visible {
-                return;
-            }
-
-            interface.setting.set_visible(systems, false);
-        }
-        Window::Storage => {
-            if !interface.storage.visible {
-                return;
-            }
-
-            interface.storage.set_visible(systems, false);
-            interface.item_desc.set_visible(systems, false);
-        }
-        Window::Shop => {
-            if !interface.shop.visible {
-                return;
-            }
-
-            interface.shop.set_visible(systems, false);
-            interface.item_desc.set_visible(systems, false);
-        }
-        Window::Trade => {
-            if !interface.trade.visible {
-                return;
-            }
-
-            interface.trade.set_visible(systems, false);
-            interface.item_desc.set_visible(systems, false);
-        }
-        _ => {}
+    if !wndw.frame().visible {
+        return;
     }
 
+    wndw.set_visible(systems, false);
+    interface.item_desc.set_visible(systems, false);
+    interface.state_changed = true;
     interface_set_to_last(interface, systems, window);
 }
 
+pub fn toggle_interface(
+    interface: &mut Interface,
+    systems: &mut SystemHolder,
+    window: Window,
+) {
+    if interface.windows.window(window).frame().visible {
+        close_interface(interface, systems, window);
+    } else {
+        open_interface(interface, systems, window);
+    }
+}
+
 fn hold_interface(
     interface: &mut Interface,
     systems: &mut SystemHolder,
@@ -1483,80 +1143,20 @@ fn hold_interface(
 ) {
     interface_set_to_first(interface, systems, window);
 
-    match window {
-        Window::Inventory => {
-            if interface.inventory.can_hold(systems, screen_pos) && !hold_check
-            {
-                interface.inventory.hold_window(screen_pos);
-            } else if let Some(slot) = interface
-                .inventory
-                .find_inv_slot(systems, screen_pos, false)
-            {
-                if check_content {
-                    interface
-                        .inventory
-                        .hold_inv_slot(systems, slot, screen_pos);
-                }
-
-                return;
-            } else {
-                return;
-            }
-        }
-        Window::Profile => {
-            if !interface.profile.can_hold(systems, screen_pos) {
-                return;
-            }
-
-            interface.profile.hold_window(screen_pos);
-        }
-        Window::Setting => {
-            if !interface.setting.can_hold(screen_pos) {
-                return;
-            }
-
-            interface.setting.hold_window(screen_pos);
-        }
-        Window::Chatbox => {
-            if !interface.chatbox.can_hold(screen_pos) {
-                return;
-            }
-
-            interface.chatbox.hold_window(screen_pos);
-        }
-        Window::Storage => {
-            if interface.storage.can_hold(systems, screen_pos) && !hold_check {
-                interface.storage.hold_window(screen_pos);
-            } else if let Some(slot) = interface
-                .storage
-                .find_storage_slot(systems, screen_pos, false)
-            {
-                if check_content {
-                    interface
-                        .storage
-                        .hold_storage_slot(systems, slot, screen_pos);
-                }
+    let wndw = interface.windows.window_mut(window);
+    let can_hold = wndw.can_hold(systems, screen_pos);
 
-                return;
-            } else {
-                return;
-            }
-        }
-        Window::Shop => {
-            if !interface.shop.can_hold(systems, screen_pos) {
-                return;
-            }
-
-            interface.shop.hold_window(screen_pos);
-        }
-        Window::Trade => {
-            if !interface.trade.can_hold(systems, screen_pos) {
-                return;
-            }
-
-            interface.trade.hold_window(screen_pos);
+    if wndw.holds_content() && (!can_hold || hold_check) {
+        if check_content {
+            wndw.hold_content(systems, screen_pos);
         }
+        return;
     }
+    if !can_hold {
+        return;
+    }
+
+    wndw.hold_window(screen_pos);
     interface.drag_window = Some(window);
 }
 
@@ -1565,24 +1165,9 @@ fn interface_set_to_first(
     systems: &mut SystemHolder,
     window: Window,
 ) {
-    if let Some(index) = interface
-        .window_order
-        .iter()
-        .position(|&wndw| wndw.0 == window)
-    {
-        if interface.window_order[index].1 == 0 {
-            return;
-        }
-
-        for i in 0..index {
-            interface.window_order[i].1 = i.saturating_add(1);
-        }
-
-        interface.window_order[index].1 = 0;
+    if interface.windows.move_to_front(window) {
+        adjust_window_zorder(interface, systems);
     }
-
-    interface.window_order.sort_by_key(|a| a.1);
-    adjust_window_zorder(interface, systems);
 }
 
 fn interface_set_to_last(
@@ -1590,54 +1175,44 @@ fn interface_set_to_last(
     systems: &mut SystemHolder,
     window: Window,
 ) {
-    let last_index = interface.window_order.len() - 1;
-
-    if let Some(index) = interface
-        .window_order
-        .iter()
-        .position(|&wndw| wndw.0 == window)
-    {
-        if interface.window_order[index].1 == last_index {
-            return;
-        }
-
-        for i in index..(last_index + 1) {
-            interface.window_order[i].1 = i.saturating_sub(1);
-        }
-
-        interface.window_order[index].1 = last_index;
+    if interface.windows.move_to_back(window) {
+        adjust_window_zorder(interface, systems);
     }
-
-    interface.window_order.sort_by_key(|a| a.1);
-    adjust_window_zorder(interface, systems);
 }
 
 fn adjust_window_zorder(interface: &mut Interface, systems: &mut SystemHolder) {
     let mut order = 0.99;
 
-    for wndw in interface.window_order.iter() {
-        match wndw.0 {
-            Window::Inventory => {
-                interface.inventory.set_z_order(systems, order, wndw.1)
-            }
-            Window::Profile => {
-                interface.profile.set_z_order(systems, order, wndw.1)
-            }
-            Window::Setting => {
-                interface.setting.set_z_order(systems, order, wndw.1)
-            }
-            Window::Chatbox => {
-                interface.chatbox.set_z_order(systems, order, wndw.1)
-            }
-            Window::Storage => {
-                interface.storage.set_z_order(systems, order, wndw.1)
-            }
-            Window::Shop => interface.shop.set_z_order(systems, order, wndw.1),
-            Window::Trade => {
-                interface.trade.set_z_order(systems, order, wndw.1)
-            }
-        }
-
+    for (order_index, window) in interface.windows.iter_mut().enumerate() {
+        window.set_z_order(systems, order, order_index);
         order -= 0.01;
     }
+
+    interface.state_changed = true;
+}
+
+/// Moves a statistic label onto `row`, counted down from the top right corner
+/// of the screen, and sizes its text to the current scale. Row 0 is the fps.
+pub fn set_statistic_area(
+    systems: &mut SystemHolder,
+    label: &GfxType,
+    row: usize,
+) {
+    let scale = systems.scale as f32;
+    let size = (Vec2::new(150.0, 20.0) * scale).floor();
+    let pos = Vec2::new(
+        systems.size.width - size.x,
+        systems.size.height - (25.0 * (row + 1) as f32 * scale).floor(),
+    );
+
+    let z = systems.gfx.get_pos(label).z;
+    systems.gfx.set_pos(label, Vec3::new(pos.x, pos.y, z));
+    systems.gfx.set_size(label, size);
+    systems.gfx.set_bound(
+        label,
+        Some(Bounds::new(pos.x, pos.y, pos.x + size.x, pos.y + size.y)),
+    );
+    systems
+        .gfx
+        .set_text_metrics(label, Metrics::new(16.0, 16.0).scale(scale));
 }
@@ -1,13 +1,17 @@
 use ascending_graphics::*;
-use cosmic_text::{Attrs, Metrics};
+use cosmic_text::Attrs;
 
 use crate::{
-    Interface, MapPosition, Position, Result, SystemHolder, data_types::*,
-    is_within_area, logic::*, send_command, send_message, systems::Poller,
+    MapPosition, Position, Result, SystemHolder, data_types::*, is_within_area,
+    logic::*, send_command, send_leaveparty, send_message, systems::Poller,
     widget::*,
 };
 
-const MAX_CHAT_LINE: usize = 8;
+use super::{
+    GameWindow, ItemDescription, SelectedTextbox, Window, WindowAction,
+    WindowFrame,
+};
+
 const VISIBLE_SIZE: f32 = 160.0;
 const MAX_CHAT: usize = 100;
 
@@ -18,6 +22,16 @@ pub struct Chat {
     size: Vec2,
     adjust_y: f32,
     channel: MessageChannel,
+    line: ChatLine,
+}
+
+/// A chat message as it was added, kept so a rebuilt chatbox can show it
+/// again.
+#[derive(Debug, Clone)]
+pub struct ChatLine {
+    pub msg: (TString, Color),
+    pub header: Option<(TString, Color)>,
+    pub channel: MessageChannel,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -46,12 +60,12 @@ impl ChatTab {
             &mut systems.renderer,
             Vec3::new(pos.x, pos.y, z_order[0]),
             (size * systems.scale as f32).floor(),
-            Color::rgba(100, 100, 100, 255),
+            systems.theme.chat.tab.normal,
             0,
         );
         bg_rect
-            .set_border_width(1.0)
-            .set_border_color(Color::rgba(40, 40, 40, 255));
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(systems.theme.chat.tab_border_color);
         let bg = systems.gfx.add_rect(
             bg_rect,
             0,
@@ -70,7 +84,7 @@ impl ChatTab {
                 pos.x + (size.x * systems.scale as f32).floor(),
                 pos.y + (20.0 * systems.scale as f32).floor(),
             )),
-            Color::rgba(255, 255, 255, 255),
+            systems.theme.chat.tab_text,
         );
         let text = systems.gfx.add_text(
             text_data,
@@ -111,15 +125,7 @@ impl ChatTab {
         }
         self.in_hover = in_hover;
         if !self.is_selected {
-            if self.in_hover {
-                systems
-                    .gfx
-                    .set_color(&self.bg, Color::rgba(150, 150, 150, 255));
-            } else {
-                systems
-                    .gfx
-                    .set_color(&self.bg, Color::rgba(100, 100, 100, 255));
-            }
+            self.update_color(systems);
         }
     }
 
@@ -128,19 +134,20 @@ impl ChatTab {
             return;
         }
         self.is_selected = is_selected;
-        if self.is_selected {
-            systems
-                .gfx
-                .set_color(&self.bg, Color::rgba(65, 65, 65, 255));
+        self.update_color(systems);
+    }
+
+    fn update_color(&self, systems: &mut SystemHolder) {
+        let style = systems.theme.chat.tab;
+        let color = if self.is_selected {
+            style.click
         } else if self.in_hover {
-            systems
-                .gfx
-                .set_color(&self.bg, Color::rgba(150, 150, 150, 255));
+            style.hover
         } else {
-            systems
-                .gfx
-                .set_color(&self.bg, Color::rgba(100, 100, 100, 255));
-        }
+            style.normal
+        };
+
+        systems.gfx.set_color(&self.bg, color);
     }
 
     fn unload(&mut self, systems: &mut SystemHolder) {
@@ -148,6 +155,11 @@ impl ChatTab {
         systems.gfx.remove_gfx(&mut systems.renderer, &self.text);
     }
 
+    fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        systems.gfx.set_visible(&self.bg, visible);
+        systems.gfx.set_visible(&self.text, visible);
+    }
+
     fn set_z_order(&mut self, systems: &mut SystemHolder, z_order: [f32; 2]) {
         let pos = systems.gfx.get_pos(&self.bg);
         systems
@@ -187,14 +199,14 @@ impl ChatTab {
 }
 
 pub struct Chatbox {
+    pub frame: WindowFrame,
     window: GfxType,
     textbox_bg: GfxType,
     chatarea_bg: GfxType,
     pub textbox: Textbox,
     button: [Button; 3],
-    pub did_button_click: bool,
     pub scrollbar: Scrollbar,
-    pub chat_tab: [ChatTab; 3],
+    pub chat_tab: [ChatTab; 4],
     msg_selection: GfxType,
     msg_select_index: Option<usize>,
     pub selected_tab: usize,
@@ -205,16 +217,6 @@ pub struct Chatbox {
     chat_bounds: Bounds,
     chat_line_size: f32,
     chat_scroll_value: usize,
-
-    pub pos: Vec2,
-    pub size: Vec2,
-    pub z_order: f32,
-    pub order_index: usize,
-    in_hold: bool,
-    hold_pos: Vec2,
-
-    min_bound: Vec2,
-    max_bound: Vec2,
 }
 
 impl Chatbox {
@@ -231,12 +233,12 @@ impl Chatbox {
             &mut systems.renderer,
             w_pos,
             w_size,
-            Color::rgba(120, 120, 120, 255),
+            systems.theme.window.color,
             0,
         );
         window_rect
-            .set_border_width(1.0)
-            .set_border_color(Color::rgba(40, 40, 40, 255));
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(systems.theme.window.border_color);
         let window = systems.gfx.add_rect(
             window_rect,
             0,
@@ -257,7 +259,7 @@ impl Chatbox {
                 w_size.x - (75.0 * systems.scale as f32).floor(),
                 (24.0 * systems.scale as f32).floor(),
             ),
-            Color::rgba(80, 80, 80, 255),
+            systems.theme.chat.input_color,
             0,
         );
         let textbox_bg = systems.gfx.add_rect(
@@ -282,7 +284,7 @@ impl Chatbox {
             &mut systems.renderer,
             Vec3::new(chat_area_pos.x, chat_area_pos.y, chatarea_zorder),
             chat_areasize,
-            Color::rgba(140, 140, 140, 255),
+            systems.theme.chat.area_color,
             0,
         );
 
@@ -301,38 +303,30 @@ impl Chatbox {
             chat_area_pos.y + chat_areasize.y,
         );
 
+        let textbox_style = systems.theme.textbox;
         let textbox = Textbox::new(
             systems,
             Vec3::new(w_pos.x, w_pos.y, detail_2),
             Vec2::new(7.0, 7.0),
             (0.0001, 5),
             Vec2::new(o_size.x - 79.0, 20.0),
-            Color::rgba(200, 200, 200, 255),
+            textbox_style.text,
             1,
             255,
-            Color::rgba(120, 120, 120, 255),
-            Color::rgba(10, 10, 150, 255),
+            textbox_style.background,
+            textbox_style.selection,
             false,
             true,
             None,
             vec![],
         );
 
+        let button_style = systems.theme.button;
+        let send_text = systems.locale.text("chat.send");
         let button = [
             Button::new(
                 systems,
-                ButtonType::Rect(ButtonRect {
-                    rect_color: Color::rgba(80, 80, 80, 255),
-                    got_border: false,
-                    border_color: Color::rgba(0, 0, 0, 0),
-                    border_radius: 0.0,
-                    hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                        150, 150, 150, 255,
-                    )),
-                    click_change: ButtonChangeType::ColorChange(Color::rgba(
-                        80, 80, 80, 255,
-                    )),
-                }),
+                ButtonType::Rect(button_style.rect()),
                 ButtonContentType::Image(ButtonContentImg {
                     res: systems.resource.vertical_arrow.allocation,
                     pos: Vec2::ZERO,
@@ -352,18 +346,7 @@ impl Chatbox {
             ),
             Button::new(
                 systems,
-                ButtonType::Rect(ButtonRect {
-                    rect_color: Color::rgba(80, 80, 80, 255),
-                    got_border: false,
-                    border_color: Color::rgba(0, 0, 0, 0),
-                    border_radius: 0.0,
-                    hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                        150, 150, 150, 255,
-                    )),
-                    click_change: ButtonChangeType::ColorChange(Color::rgba(
-                        80, 80, 80, 255,
-                    )),
-                }),
+                ButtonType::Rect(button_style.rect()),
                 ButtonContentType::Image(ButtonContentImg {
                     res: systems.resource.vertical_arrow.allocation,
                     pos: Vec2::ZERO,
@@ -383,30 +366,12 @@ impl Chatbox {
             ),
             Button::new(
                 systems,
-                ButtonType::Rect(ButtonRect {
-                    rect_color: Color::rgba(80, 80, 80, 255),
-                    got_border: false,
-                    border_color: Color::rgba(0, 0, 0, 0),
-                    border_radius: 0.0,
-                    hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                        150, 150, 150, 255,
-                    )),
-                    click_change: ButtonChangeType::ColorChange(Color::rgba(
-                        80, 80, 80, 255,
-                    )),
-                }),
-                ButtonContentType::Text(ButtonContentText {
-                    text: "Send".into(),
-                    pos: Vec2::new(0.0, 2.0),
-                    color: Color::rgba(255, 255, 255, 255),
-                    render_layer: 1,
-                    hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                        255, 255, 255, 255,
-                    )),
-                    click_change: ButtonChangeType::ColorChange(Color::rgba(
-                        255, 255, 255, 255,
-                    )),
-                }),
+                ButtonType::Rect(button_style.rect()),
+                ButtonContentType::Text(button_style.text(
+                    send_text,
+                    Vec2::new(0.0, 2.0),
+                    1,
+                )),
                 Vec2::new(w_pos.x, w_pos.y),
                 Vec2::new(o_size.x - 65.0, 5.0),
                 detail_1,
@@ -418,6 +383,7 @@ impl Chatbox {
             ),
         ];
 
+        let scrollbar_style = systems.theme.scrollbar;
         let scrollbar = Scrollbar::new(
             systems,
             Vec2::new(w_pos.x, w_pos.y),
@@ -427,22 +393,8 @@ impl Chatbox {
             true,
             detail_1,
             (0.0001, 5),
-            ScrollbarRect {
-                color: Color::rgba(190, 190, 190, 255),
-                render_layer: 0,
-                got_border: true,
-                border_color: Color::rgba(50, 50, 50, 255),
-                hover_color: Color::rgba(240, 240, 240, 255),
-                hold_color: Color::rgba(50, 50, 50, 255),
-                radius: 5.0,
-            },
-            Some(ScrollbarBackground {
-                color: Color::rgba(80, 80, 80, 255),
-                render_layer: 0,
-                got_border: false,
-                border_color: Color::rgba(0, 0, 0, 0),
-                radius: 0.0,
-            }),
+            scrollbar_style.bar(0),
+            Some(scrollbar_style.background_rect(0)),
             0,
             20.0,
             true,
@@ -450,6 +402,9 @@ impl Chatbox {
             None,
         );
 
+        let [all_text, map_text, global_text, party_text] =
+            ["chat.all", "chat.map", "chat.global", "chat.party"]
+                .map(|key| systems.locale.get(key).to_string());
         let mut chat_tab = [
             ChatTab::new(
                 systems,
@@ -457,7 +412,7 @@ impl Chatbox {
                 Vec2::new(0.0, o_size.y - 1.0),
                 Vec2::new(70.0, 24.0),
                 [w_pos.z, detail_1],
-                "All".into(),
+                all_text,
             ),
             ChatTab::new(
                 systems,
@@ -465,7 +420,7 @@ impl Chatbox {
                 Vec2::new(69.0, o_size.y - 1.0),
                 Vec2::new(70.0, 24.0),
                 [w_pos.z, detail_1],
-                "Map".into(),
+                map_text,
             ),
             ChatTab::new(
                 systems,
@@ -473,7 +428,15 @@ impl Chatbox {
                 Vec2::new(138.0, o_size.y - 1.0),
                 Vec2::new(70.0, 24.0),
                 [w_pos.z, detail_1],
-                "Global".into(),
+                global_text,
+            ),
+            ChatTab::new(
+                systems,
+                Vec2::new(w_pos.x, w_pos.y),
+                Vec2::new(207.0, o_size.y - 1.0),
+                Vec2::new(70.0, 24.0),
+                [w_pos.z, detail_1],
+                party_text,
             ),
         ];
         chat_tab[0].set_select(systems, false);
@@ -482,7 +445,7 @@ impl Chatbox {
             &mut systems.renderer,
             Vec3::new(0.0, 0.0, detail_3),
             Vec2::ZERO,
-            Color::rgba(60, 60, 60, 255),
+            systems.theme.chat.selection,
             0,
         );
         let msg_selection = systems.gfx.add_rect(
@@ -494,13 +457,17 @@ impl Chatbox {
         );
         systems.gfx.set_visible(&msg_selection, false);
 
+        let mut frame =
+            WindowFrame::new(systems, Vec2::new(w_pos.x, w_pos.y), w_size, 0.0);
+        frame.visible = true;
+
         Chatbox {
+            frame,
             window,
             textbox_bg,
             chatarea_bg,
             textbox,
             button,
-            did_button_click: false,
             scrollbar,
             chat: Vec::new(),
             chat_areasize,
@@ -508,232 +475,19 @@ impl Chatbox {
             chat_bounds,
             chat_line_size: 0.0,
             chat_scroll_value: 0,
-            pos: Vec2::new(w_pos.x, w_pos.y),
-            size: w_size,
-            z_order: w_pos.z,
-            order_index: 0,
-            in_hold: false,
-            hold_pos: Vec2::ZERO,
             chat_tab,
             selected_tab: 0,
             msg_selection,
             msg_select_index: None,
-
-            min_bound: Vec2::new(
-                systems.size.width - w_size.x,
-                systems.size.height - w_size.y,
-            ),
-            max_bound: Vec2::ZERO,
         }
     }
 
-    pub fn unload(&mut self, systems: &mut SystemHolder) {
-        systems.gfx.remove_gfx(&mut systems.renderer, &self.window);
-        systems
-            .gfx
-            .remove_gfx(&mut systems.renderer, &self.textbox_bg);
-        systems
-            .gfx
-            .remove_gfx(&mut systems.renderer, &self.chatarea_bg);
-        self.textbox.unload(systems);
-        self.button.iter_mut().for_each(|button| {
-            button.unload(systems);
-        });
-        self.chat.iter().for_each(|chat| {
-            systems.gfx.remove_gfx(&mut systems.renderer, &chat.text);
-        });
-        self.scrollbar.unload(systems);
-        self.chat_tab.iter_mut().for_each(|tab| {
-            tab.unload(systems);
-        });
-        systems
-            .gfx
-            .remove_gfx(&mut systems.renderer, &self.msg_selection);
-    }
-
-    pub fn can_hold(&mut self, screen_pos: Vec2) -> bool {
-        if self.scrollbar.in_scroll(screen_pos) {
-            return false;
-        }
-        for button in self.button.iter() {
-            let target_pos = button.base_pos + button.adjust_pos;
-            if is_within_area(screen_pos, target_pos, button.size) {
-                return false;
-            }
-        }
-        if is_within_area(
-            screen_pos,
-            Vec2::new(self.textbox.base_pos.x, self.textbox.base_pos.y)
-                + self.textbox.adjust_pos,
-            self.textbox.size,
-        ) {
-            return false;
-        }
-        if !is_within_area(screen_pos, self.pos, self.size) {
-            return false;
-        }
-        true
-    }
-
-    pub fn in_window(
-        &mut self,
-        screen_pos: Vec2,
-        systems: &mut SystemHolder,
-    ) -> bool {
-        let chatbox_size =
-            self.size + (Vec2::new(0.0, 24.0) * systems.scale as f32).floor();
-        is_within_area(screen_pos, self.pos, chatbox_size)
-    }
-
-    pub fn hold_window(&mut self, screen_pos: Vec2) {
-        if self.in_hold {
-            return;
-        }
-        self.in_hold = true;
-        self.hold_pos = screen_pos - self.pos;
-    }
-
-    pub fn release_window(&mut self) {
-        self.in_hold = false;
-    }
-
-    pub fn set_z_order(
-        &mut self,
-        systems: &mut SystemHolder,
-        z_order: f32,
-        order_index: usize,
-    ) {
-        if self.z_order == z_order {
-            return;
-        }
-        self.z_order = z_order;
-        self.order_index = order_index;
-
-        let detail_origin = ORDER_GUI_WINDOW.sub_f32(self.z_order, 3);
-        let detail_1 = detail_origin.sub_f32(0.001, 3);
-        let detail_2 = detail_origin.sub_f32(0.002, 3);
-        let detail_3 = detail_origin.sub_f32(0.002, 3);
-
-        let pos = systems.gfx.get_pos(&self.window);
-        systems
-            .gfx
-            .set_pos(&self.window, Vec3::new(pos.x, pos.y, detail_origin));
-        let pos = systems.gfx.get_pos(&self.textbox_bg);
-        systems
-            .gfx
-            .set_pos(&self.textbox_bg, Vec3::new(pos.x, pos.y, detail_1));
-        let pos = systems.gfx.get_pos(&self.chatarea_bg);
-        self.chat_zorder = detail_3;
-        systems
-            .gfx
-            .set_pos(&self.chatarea_bg, Vec3::new(pos.x, pos.y, detail_1));
-        self.textbox.set_z_order(systems, detail_2);
-        self.button.iter_mut().for_each(|button| {
-            button.set_z_order(systems, detail_1);
-        });
-        self.scrollbar.set_z_order(systems, detail_1);
-
-        for chat in self.chat.iter() {
-            let pos = systems.gfx.get_pos(&chat.text);
-            systems
-                .gfx
-                .set_pos(&chat.text, Vec3::new(pos.x, pos.y, self.chat_zorder));
-        }
-
-        let pos = systems.gfx.get_pos(&self.msg_selection);
-        systems
-            .gfx
-            .set_pos(&self.msg_selection, Vec3::new(pos.x, pos.y, detail_2));
-
-        self.chat_tab.iter_mut().for_each(|tab| {
-            tab.set_z_order(systems, [detail_origin, detail_1]);
-        })
-    }
-
-    pub fn move_window(
-        &mut self,
-        systems: &mut SystemHolder,
-        screen_pos: Vec2,
-    ) {
-        if !self.in_hold {
-            return;
-        }
-        self.pos = (screen_pos - self.hold_pos)
-            .max(self.max_bound)
-            .min(self.min_bound);
-
-        let pos = systems.gfx.get_pos(&self.window);
-        systems
-            .gfx
-            .set_pos(&self.window, Vec3::new(self.pos.x, self.pos.y, pos.z));
-        let pos = systems.gfx.get_pos(&self.textbox_bg);
-        systems.gfx.set_pos(
-            &self.textbox_bg,
-            Vec3::new(
-                self.pos.x + (5.0 * systems.scale as f32).floor(),
-                self.pos.y + (5.0 * systems.scale as f32).floor(),
-                pos.z,
-            ),
-        );
-        let pos = systems.gfx.get_pos(&self.chatarea_bg);
-        let chat_area_pos = Vec2::new(
-            self.pos.x + (5.0 * systems.scale as f32).floor(),
-            self.pos.y + (34.0 * systems.scale as f32).floor(),
-        );
-        self.chat_bounds = Bounds::new(
-            chat_area_pos.x,
-            chat_area_pos.y,
-            chat_area_pos.x + self.chat_areasize.x,
-            chat_area_pos.y + self.chat_areasize.y,
-        );
-        systems.gfx.set_pos(
-            &self.chatarea_bg,
-            Vec3::new(chat_area_pos.x, chat_area_pos.y, pos.z),
-        );
-        self.textbox.set_pos(systems, self.pos);
-        self.button.iter_mut().for_each(|button| {
-            button.set_pos(systems, self.pos);
-        });
-        self.scrollbar.set_pos(systems, self.pos);
-
-        let scroll_y = self.chat_scroll_value
-            * (16.0 * systems.scale as f32).floor() as usize;
-        for data in self.chat.iter_mut() {
-            let start_pos = Vec2::new(
-                self.chat_bounds.left,
-                self.chat_bounds.bottom - self.chat_areasize.y,
-            );
-            systems.gfx.set_pos(
-                &data.text,
-                Vec3::new(
-                    start_pos.x,
-                    (start_pos.y
-                        + (2.0 * systems.scale as f32).floor()
-                        + data.adjust_y)
-                        - scroll_y as f32,
-                    self.chat_zorder,
-                ),
-            );
-            systems.gfx.set_bound(&data.text, Some(self.chat_bounds));
-        }
-
-        let pos = systems.gfx.get_pos(&self.msg_selection);
-        systems
-            .gfx
-            .set_pos(&self.msg_selection, Vec3::new(0.0, 0.0, pos.z));
-        systems.gfx.set_visible(&self.msg_selection, false);
-
-        self.chat_tab.iter_mut().for_each(|tab| {
-            tab.move_pos(systems, self.pos);
-        })
-    }
-
     pub fn hover_scrollbar(
         &mut self,
         systems: &mut SystemHolder,
         screen_pos: Vec2,
     ) {
-        if self.order_index != 0 {
+        if self.frame.order_index != 0 {
             return;
         }
 
@@ -744,28 +498,11 @@ impl Chatbox {
         }
     }
 
-    pub fn hover_buttons(
+    pub fn hover_chat_tab(
         &mut self,
         systems: &mut SystemHolder,
         screen_pos: Vec2,
     ) {
-        for button in self.button.iter_mut() {
-            if is_within_area(
-                screen_pos,
-                Vec2::new(
-                    button.base_pos.x
-                        + (button.adjust_pos.x * systems.scale as f32).floor(),
-                    button.base_pos.y
-                        + (button.adjust_pos.y * systems.scale as f32).floor(),
-                ),
-                (button.size * systems.scale as f32).floor(),
-            ) {
-                button.set_hover(systems, true);
-            } else {
-                button.set_hover(systems, false);
-            }
-        }
-
         for tab in self.chat_tab.iter_mut() {
             let in_area = tab.in_area(systems, screen_pos);
             tab.set_hover(systems, in_area);
@@ -840,30 +577,6 @@ impl Chatbox {
         None
     }
 
-    pub fn click_buttons(
-        &mut self,
-        systems: &mut SystemHolder,
-        screen_pos: Vec2,
-    ) -> Option<usize> {
-        let mut button_found = None;
-        for (index, button) in self.button.iter_mut().enumerate() {
-            if is_within_area(
-                screen_pos,
-                Vec2::new(
-                    button.base_pos.x
-                        + (button.adjust_pos.x * systems.scale as f32).floor(),
-                    button.base_pos.y
-                        + (button.adjust_pos.y * systems.scale as f32).floor(),
-                ),
-                (button.size * systems.scale as f32).floor(),
-            ) {
-                button.set_click(systems, true);
-                button_found = Some(index)
-            }
-        }
-        button_found
-    }
-
     pub fn select_chat_tab(
         &mut self,
         systems: &mut SystemHolder,
@@ -884,17 +597,6 @@ impl Chatbox {
         }
     }
 
-    pub fn reset_buttons(&mut self, systems: &mut SystemHolder) {
-        if !self.did_button_click {
-            return;
-        }
-        self.did_button_click = false;
-
-        self.button.iter_mut().for_each(|button| {
-            button.set_click(systems, false);
-        });
-    }
-
     pub fn set_chat_scrollbar(
         &mut self,
         systems: &mut SystemHolder,
@@ -936,12 +638,18 @@ impl Chatbox {
         header_msg: Option<(TString, Color)>,
         channel: MessageChannel,
     ) {
+        let line = ChatLine {
+            msg: msg.clone(),
+            header: header_msg.clone(),
+            channel,
+        };
+
         let mut text_data = create_label(
             systems,
             Vec3::new(0.0, 0.0, 0.0),
             self.chat_areasize,
             Some(self.chat_bounds),
-            Color::rgba(255, 255, 255, 255),
+            systems.theme.chat.text,
         );
         text_data
             .set_buffer_size(
@@ -983,11 +691,12 @@ impl Chatbox {
             size,
             adjust_y: size.y,
             channel,
+            line,
         };
 
         systems.gfx.set_visible(
             &chat.text,
-            can_channel_show(channel, self.selected_tab),
+            self.frame.visible && can_channel_show(channel, self.selected_tab),
         );
 
         if self.chat.len() >= MAX_CHAT
@@ -1045,6 +754,34 @@ impl Chatbox {
         }
     }
 
+    /// Every message in the chatbox from the oldest to the newest.
+    pub fn history(&self) -> Vec<ChatLine> {
+        self.chat
+            .iter()
+            .rev()
+            .map(|chat| chat.line.clone())
+            .collect()
+    }
+
+    pub fn restore_history(
+        &mut self,
+        systems: &mut SystemHolder,
+        history: Vec<ChatLine>,
+        selected_tab: usize,
+    ) {
+        if selected_tab != self.selected_tab
+            && selected_tab < self.chat_tab.len()
+        {
+            self.chat_tab[self.selected_tab].set_select(systems, false);
+            self.chat_tab[selected_tab].set_select(systems, true);
+            self.selected_tab = selected_tab;
+        }
+
+        for line in history {
+            self.add_chat(systems, line.msg, line.header, line.channel);
+        }
+    }
+
     pub fn switch_tab(&mut self, systems: &mut SystemHolder) {
         let start_pos = Vec2::new(
             self.chat_bounds.left,
@@ -1092,90 +829,430 @@ impl Chatbox {
         }
         self.chat_scroll_value = self.scrollbar.value;
     }
-}
 
-pub fn can_channel_show(channel: MessageChannel, selected_tab: usize) -> bool {
-    match channel {
-        MessageChannel::Global => selected_tab == 2 || selected_tab == 0,
-        MessageChannel::Map => selected_tab == 1 || selected_tab == 0,
-        _ => selected_tab == 0,
-    }
-}
+    /// Scrolls the chat by one line towards older messages when `up`.
+    fn scroll_chat(&mut self, systems: &mut SystemHolder, up: bool) {
+        if self.scrollbar.max_value == 0 {
+            return;
+        }
 
-pub fn send_chat(
-    interface: &mut Interface,
-    systems: &mut SystemHolder,
-    socket: &mut Poller,
-) -> Result<()> {
-    if interface.chatbox.textbox.text.is_empty() {
-        return Ok(());
+        let value = if up {
+            self.scrollbar
+                .value
+                .saturating_add(1)
+                .min(self.scrollbar.max_value)
+        } else {
+            self.scrollbar.value.saturating_sub(1)
+        };
+
+        self.scrollbar.set_value(systems, value);
+        self.set_chat_scrollbar(systems, true);
     }
 
-    if let Some(char) = interface.chatbox.textbox.text.chars().next() {
-        match char {
-            '@' => {
-                let msg = &interface.chatbox.textbox.text[1..];
-                if let Some(index) = msg.find(' ') {
-                    let (name, message) = msg.split_at(index);
-                    send_message(
-                        socket,
-                        crate::MessageChannel::Private,
-                        message,
-                        name,
-                    )?;
-                } else {
-                    interface.chatbox.add_chat(
-                        systems,
-                        ("Invalid Command".into(), COLOR_WHITE),
-                        None,
-                        crate::MessageChannel::Map,
-                    );
-                }
-            }
-            '/' => {
-                let msg = &interface.chatbox.textbox.text[1..];
-                match msg {
-                    "trade" => {
-                        send_command(socket, crate::Command::Trade)?;
-                    }
-                    "warp" => send_command(
-                        socket,
-                        crate::Command::WarpTo(Position {
-                            x: 10,
-                            y: 10,
-                            map: MapPosition {
-                                x: 0,
-                                y: -1,
-                                group: 0,
-                            },
-                        }),
-                    )?,
-                    _ => {
-                        interface.chatbox.add_chat(
+    pub fn send_chat(
+        &mut self,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+    ) -> Result<()> {
+        if self.textbox.text.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(char) = self.textbox.text.chars().next() {
+            match char {
+                '@' => {
+                    let msg = &self.textbox.text[1..];
+                    if let Some(index) = msg.find(' ') {
+                        let (name, message) = msg.split_at(index);
+                        send_message(
+                            socket,
+                            crate::MessageChannel::Private,
+                            message,
+                            name,
+                        )?;
+                    } else {
+                        let msg = systems.locale.text("chat.invalid_command");
+                        self.add_chat(
                             systems,
-                            ("Invalid Command".into(), COLOR_WHITE),
+                            (msg, COLOR_WHITE),
                             None,
                             crate::MessageChannel::Map,
                         );
                     }
                 }
+                '/' => {
+                    let msg = &self.textbox.text[1..];
+                    match msg {
+                        _ if msg.starts_with("p ") => send_message(
+                            socket,
+                            crate::MessageChannel::Party,
+                            &msg[2..],
+                            "",
+                        )?,
+                        "trade" => {
+                            send_command(socket, crate::Command::Trade)?;
+                        }
+                        "leave" => send_leaveparty(socket)?,
+                        "warp" => send_command(
+                            socket,
+                            crate::Command::WarpTo(Position {
+                                x: 10,
+                                y: 10,
+                                map: MapPosition {
+                                    x: 0,
+                                    y: -1,
+                                    group: 0,
+                                },
+                            }),
+                        )?,
+                        _ => {
+                            let msg =
+                                systems.locale.text("chat.invalid_command");
+                            self.add_chat(
+                                systems,
+                                (msg, COLOR_WHITE),
+                                None,
+                                crate::MessageChannel::Map,
+                            );
+                        }
+                    }
+                }
+                _ => {
+                    let channel = match self.selected_tab {
+                        2 => crate::MessageChannel::Global,
+                        3 => crate::MessageChannel::Party,
+                        _ => crate::MessageChannel::Map,
+                    };
+                    send_message(socket, channel, &self.textbox.text, "")?;
+                }
             }
-            _ => {
-                let channel = match interface.chatbox.selected_tab {
-                    2 => crate::MessageChannel::Global,
-                    _ => crate::MessageChannel::Map,
-                };
-                send_message(
-                    socket,
-                    channel,
-                    &interface.chatbox.textbox.text,
-                    "",
-                )?;
+        }
+
+        self.textbox.set_text(systems, "");
+
+        Ok(())
+    }
+}
+
+impl GameWindow for Chatbox {
+    fn id(&self) -> Window {
+        Window::Chatbox
+    }
+
+    fn name(&self) -> &'static str {
+        "chatbox"
+    }
+
+    fn frame(&self) -> &WindowFrame {
+        &self.frame
+    }
+
+    fn frame_mut(&mut self) -> &mut WindowFrame {
+        &mut self.frame
+    }
+
+    fn buttons(&mut self) -> &mut [Button] {
+        &mut self.button
+    }
+
+    fn unload(&mut self, systems: &mut SystemHolder) {
+        systems.gfx.remove_gfx(&mut systems.renderer, &self.window);
+        systems
+            .gfx
+            .remove_gfx(&mut systems.renderer, &self.textbox_bg);
+        systems
+            .gfx
+            .remove_gfx(&mut systems.renderer, &self.chatarea_bg);
+        self.textbox.unload(systems);
+        self.button.iter_mut().for_each(|button| {
+            button.unload(systems);
+        });
+        self.chat.iter().for_each(|chat| {
+            systems.gfx.remove_gfx(&mut systems.renderer, &chat.text);
+        });
+        self.scrollbar.unload(systems);
+        self.chat_tab.iter_mut().for_each(|tab| {
+            tab.unload(systems);
+        });
+        systems
+            .gfx
+            .remove_gfx(&mut systems.renderer, &self.msg_selection);
+    }
+
+    fn recreate(&mut self, systems: &mut SystemHolder) {
+        *self = Chatbox::new(systems);
+    }
+
+    fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        if self.frame.visible == visible {
+            return;
+        }
+        self.frame.visible = visible;
+        self.frame.z_order = 0.0;
+        systems.gfx.set_visible(&self.window, visible);
+        systems.gfx.set_visible(&self.textbox_bg, visible);
+        systems.gfx.set_visible(&self.chatarea_bg, visible);
+        self.textbox.set_visible(systems, visible);
+        self.button.iter_mut().for_each(|button| {
+            button.set_visible(systems, visible);
+        });
+        self.scrollbar.set_visible(systems, visible);
+        self.chat_tab.iter_mut().for_each(|tab| {
+            tab.set_visible(systems, visible);
+        });
+        for chat in self.chat.iter() {
+            systems.gfx.set_visible(
+                &chat.text,
+                visible && can_channel_show(chat.channel, self.selected_tab),
+            );
+        }
+        self.msg_select_index = None;
+        systems.gfx.set_visible(&self.msg_selection, false);
+    }
+
+    fn can_hold(&self, _systems: &SystemHolder, screen_pos: Vec2) -> bool {
+        if !self.frame.visible {
+            return false;
+        }
+        if self.scrollbar.in_scroll(screen_pos) {
+            return false;
+        }
+        for button in self.button.iter() {
+            let target_pos = button.base_pos + button.adjust_pos;
+            if is_within_area(screen_pos, target_pos, button.size) {
+                return false;
             }
         }
+        if is_within_area(
+            screen_pos,
+            Vec2::new(self.textbox.base_pos.x, self.textbox.base_pos.y)
+                + self.textbox.adjust_pos,
+            self.textbox.size,
+        ) {
+            return false;
+        }
+        if !is_within_area(screen_pos, self.frame.pos, self.frame.size) {
+            return false;
+        }
+        true
     }
 
-    interface.chatbox.textbox.set_text(systems, "");
+    /// The chat tabs sit on top of the chat box.
+    fn in_window(&self, systems: &SystemHolder, screen_pos: Vec2) -> bool {
+        if !self.frame.visible {
+            return false;
+        }
+        let chatbox_size = self.frame.size
+            + (Vec2::new(0.0, 24.0) * systems.scale as f32).floor();
+        is_within_area(screen_pos, self.frame.pos, chatbox_size)
+    }
+
+    fn update_pos(&mut self, systems: &mut SystemHolder) {
+        let pos = systems.gfx.get_pos(&self.window);
+        systems.gfx.set_pos(
+            &self.window,
+            Vec3::new(self.frame.pos.x, self.frame.pos.y, pos.z),
+        );
+        let pos = systems.gfx.get_pos(&self.textbox_bg);
+        systems.gfx.set_pos(
+            &self.textbox_bg,
+            Vec3::new(
+                self.frame.pos.x + (5.0 * systems.scale as f32).floor(),
+                self.frame.pos.y + (5.0 * systems.scale as f32).floor(),
+                pos.z,
+            ),
+        );
+        let pos = systems.gfx.get_pos(&self.chatarea_bg);
+        let chat_area_pos = Vec2::new(
+            self.frame.pos.x + (5.0 * systems.scale as f32).floor(),
+            self.frame.pos.y + (34.0 * systems.scale as f32).floor(),
+        );
+        self.chat_bounds = Bounds::new(
+            chat_area_pos.x,
+            chat_area_pos.y,
+            chat_area_pos.x + self.chat_areasize.x,
+            chat_area_pos.y + self.chat_areasize.y,
+        );
+        systems.gfx.set_pos(
+            &self.chatarea_bg,
+            Vec3::new(chat_area_pos.x, chat_area_pos.y, pos.z),
+        );
+        self.textbox.set_pos(systems, self.frame.pos);
+        self.button.iter_mut().for_each(|button| {
+            button.set_pos(systems, self.frame.pos);
+        });
+        self.scrollbar.set_pos(systems, self.frame.pos);
 
-    Ok(())
+        let scroll_y = self.chat_scroll_value
+            * (16.0 * systems.scale as f32).floor() as usize;
+        for data in self.chat.iter_mut() {
+            let start_pos = Vec2::new(
+                self.chat_bounds.left,
+                self.chat_bounds.bottom - self.chat_areasize.y,
+            );
+            systems.gfx.set_pos(
+                &data.text,
+                Vec3::new(
+                    start_pos.x,
+                    (start_pos.y
+                        + (2.0 * systems.scale as f32).floor()
+                        + data.adjust_y)
+                        - scroll_y as f32,
+                    self.chat_zorder,
+                ),
+            );
+            systems.gfx.set_bound(&data.text, Some(self.chat_bounds));
+        }
+
+        let pos = systems.gfx.get_pos(&self.msg_selection);
+        systems
+            .gfx
+            .set_pos(&self.msg_selection, Vec3::new(0.0, 0.0, pos.z));
+        systems.gfx.set_visible(&self.msg_selection, false);
+
+        self.chat_tab.iter_mut().for_each(|tab| {
+            tab.move_pos(systems, self.frame.pos);
+        })
+    }
+
+    fn update_z_order(
+        &mut self,
+        systems: &mut SystemHolder,
+        detail_origin: f32,
+    ) {
+        let detail_1 = detail_origin.sub_f32(0.001, 3);
+        let detail_2 = detail_origin.sub_f32(0.002, 3);
+        let detail_3 = detail_origin.sub_f32(0.002, 3);
+
+        let pos = systems.gfx.get_pos(&self.window);
+        systems
+            .gfx
+            .set_pos(&self.window, Vec3::new(pos.x, pos.y, detail_origin));
+        let pos = systems.gfx.get_pos(&self.textbox_bg);
+        systems
+            .gfx
+            .set_pos(&self.textbox_bg, Vec3::new(pos.x, pos.y, detail_1));
+        let pos = systems.gfx.get_pos(&self.chatarea_bg);
+        self.chat_zorder = detail_3;
+        systems
+            .gfx
+            .set_pos(&self.chatarea_bg, Vec3::new(pos.x, pos.y, detail_1));
+        self.textbox.set_z_order(systems, detail_2);
+        self.button.iter_mut().for_each(|button| {
+            button.set_z_order(systems, detail_1);
+        });
+        self.scrollbar.set_z_order(systems, detail_1);
+
+        for chat in self.chat.iter() {
+            let pos = systems.gfx.get_pos(&chat.text);
+            systems
+                .gfx
+                .set_pos(&chat.text, Vec3::new(pos.x, pos.y, self.chat_zorder));
+        }
+
+        let pos = systems.gfx.get_pos(&self.msg_selection);
+        systems
+            .gfx
+            .set_pos(&self.msg_selection, Vec3::new(pos.x, pos.y, detail_2));
+
+        self.chat_tab.iter_mut().for_each(|tab| {
+            tab.set_z_order(systems, [detail_origin, detail_1]);
+        })
+    }
+
+    fn closes_on_escape(&self) -> bool {
+        false
+    }
+
+    fn textbox(&self) -> SelectedTextbox {
+        SelectedTextbox::Chatbox
+    }
+
+    fn trigger_button(
+        &mut self,
+        systems: &mut SystemHolder,
+        socket: &mut Poller,
+        index: usize,
+    ) -> Result<WindowAction> {
+        match index {
+            0 => self.scroll_chat(systems, true),
+            1 => self.scroll_chat(systems, false),
+            2 => self.send_chat(systems, socket)?,
+            _ => {}
+        }
+
+        Ok(WindowAction::None)
+    }
+
+    fn hover_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        _tooltip: &mut Tooltip,
+        _item_desc: &mut ItemDescription,
+        screen_pos: Vec2,
+    ) {
+        self.hover_chat_tab(systems, screen_pos);
+        self.hover_msg(systems, screen_pos);
+        self.hover_scrollbar(systems, screen_pos);
+    }
+
+    fn click_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        _alert: &mut Alert,
+        screen_pos: Vec2,
+    ) -> Result<WindowAction> {
+        self.select_chat_tab(systems, screen_pos);
+
+        if self.scrollbar.in_scroll(screen_pos) {
+            self.scrollbar.set_hold(systems, true, screen_pos);
+        }
+
+        Ok(WindowAction::None)
+    }
+
+    /// Copies the selected message.
+    fn double_click(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        _screen_pos: Vec2,
+    ) -> Result<()> {
+        if let Some(text) = self.get_selected_msg() {
+            set_clipboard_text(text.as_ref());
+        }
+
+        Ok(())
+    }
+
+    fn move_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> bool {
+        self.scrollbar.set_move_scroll(systems, screen_pos);
+        self.set_chat_scrollbar(systems, false);
+
+        self.scrollbar.in_hold
+    }
+
+    fn release_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> WindowAction {
+        self.scrollbar.set_hold(systems, false, screen_pos);
+
+        WindowAction::None
+    }
+}
+
+pub fn can_channel_show(channel: MessageChannel, selected_tab: usize) -> bool {
+    match channel {
+        MessageChannel::Global => selected_tab == 2 || selected_tab == 0,
+        MessageChannel::Map => selected_tab == 1 || selected_tab == 0,
+        MessageChannel::Party => selected_tab == 3 || selected_tab == 0,
+        _ => selected_tab == 0,
+    }
 }
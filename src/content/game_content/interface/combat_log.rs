@@ -0,0 +1,337 @@
+use ascending_graphics::*;
+
+use crate::{
+    CombatEvent, CombatLog, Locale, Result, SystemHolder, data_types::*,
+    is_within_area, systems::Poller, widget::*,
+};
+
+use super::{GameWindow, ItemDescription, Window, WindowAction, WindowFrame};
+
+const COMBAT_LOG_LAYOUT: &str =
+    include_str!("../../../../data/ui/combat_log.toml");
+
+/// Widgets the Combat Log looks up by id, a layout file without them is
+/// replaced by the built in one.
+pub const COMBAT_LOG_WIDGETS: &[(LayoutKind, &str)] = &[
+    (LayoutKind::Button, "close"),
+    (LayoutKind::Button, "reset"),
+    (LayoutKind::ListView, "log"),
+    (LayoutKind::ListView, "targets"),
+];
+
+/// Seconds between summary refreshes while nothing is logged, the XP per
+/// hour keeps moving with the session time.
+const SUMMARY_REFRESH_TIME: f32 = 1.0;
+
+fn entry_text(locale: &Locale, time: &str, event: &CombatEvent) -> String {
+    let text = match event {
+        CombatEvent::Dealt { target, amount } => locale.format(
+            "combat.dealt",
+            &[("time", &time), ("target", target), ("amount", amount)],
+        ),
+        CombatEvent::Taken { amount } => locale
+            .format("combat.taken", &[("time", &time), ("amount", amount)]),
+        CombatEvent::Missed { target } => locale
+            .format("combat.missed", &[("time", &time), ("target", target)]),
+        CombatEvent::Kill { target } => {
+            locale.format("combat.kill", &[("time", &time), ("target", target)])
+        }
+        CombatEvent::Experience { amount } => locale.format(
+            "combat.experience",
+            &[("time", &time), ("amount", amount)],
+        ),
+        CombatEvent::LevelUp { level } => locale
+            .format("combat.level_up", &[("time", &time), ("level", level)]),
+    };
+
+    text.as_ref().to_string()
+}
+
+pub struct CombatLogWindow {
+    pub frame: WindowFrame,
+    widgets: LayoutWidgets,
+    log_list: usize,
+    next_summary: f32,
+    /// Set by the reset button, the log itself is cleared on the next
+    /// update.
+    reset_requested: bool,
+}
+
+impl CombatLogWindow {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        let layout =
+            UiLayout::load("combat_log", COMBAT_LOG_LAYOUT, COMBAT_LOG_WIDGETS);
+        let w_size = layout.window.scaled_size(systems);
+        let pos = layout.window.default_pos(systems);
+
+        let widgets =
+            LayoutWidgets::new(systems, layout, pos, ORDER_GUI_WINDOW);
+        let log_list = widgets.list_view_index("log").unwrap_or(0);
+
+        CombatLogWindow {
+            frame: WindowFrame::new(systems, pos, w_size, 1.0),
+            widgets,
+            log_list,
+            next_summary: 0.0,
+            reset_requested: false,
+        }
+    }
+
+    pub fn button_id(&self, index: usize) -> &str {
+        self.widgets.button_id(index)
+    }
+
+    pub fn request_reset(&mut self) {
+        self.reset_requested = true;
+    }
+
+    /// Fills the window from `log` when it changed, or refreshes the
+    /// summary once in a while.
+    pub fn update(
+        &mut self,
+        systems: &mut SystemHolder,
+        log: &mut CombatLog,
+        seconds: f32,
+    ) {
+        if std::mem::take(&mut self.reset_requested) {
+            log.reset();
+        }
+
+        if log.take_changed() {
+            self.set_entries(systems, log);
+        } else if !self.frame.visible || seconds < self.next_summary {
+            return;
+        }
+
+        self.set_summary(systems, log);
+        self.next_summary = seconds + SUMMARY_REFRESH_TIME;
+    }
+
+    /// Lists every entry, following the newest one when the list was
+    /// scrolled to the bottom.
+    fn set_entries(&mut self, systems: &mut SystemHolder, log: &CombatLog) {
+        let items = log
+            .entries()
+            .map(|entry| entry_text(&systems.locale, &entry.time, &entry.event))
+            .collect();
+
+        let Some(list) = self.widgets.list_view.get_mut(self.log_list) else {
+            return;
+        };
+        let at_bottom = list.scrollbar.value >= list.scrollbar.max_value;
+
+        list.set_items(systems, items);
+
+        if at_bottom {
+            list.scroll_by(systems, isize::MAX);
+        }
+    }
+
+    fn set_summary(&mut self, systems: &mut SystemHolder, log: &CombatLog) {
+        let locale = &systems.locale;
+        let dps = format!("{:.1}", log.dps());
+        let labels = [
+            ("dps", locale.format("combat.dps", &[("value", &dps)])),
+            (
+                "damage_dealt",
+                locale.format(
+                    "combat.damage_dealt",
+                    &[("value", &log.damage_dealt)],
+                ),
+            ),
+            (
+                "damage_taken",
+                locale.format(
+                    "combat.damage_taken",
+                    &[("value", &log.damage_taken)],
+                ),
+            ),
+            (
+                "misses",
+                locale.format("combat.misses", &[("value", &log.misses)]),
+            ),
+            (
+                "kills",
+                locale.format("combat.kills", &[("value", &log.kills)]),
+            ),
+            (
+                "session_xp",
+                locale
+                    .format("combat.session_xp", &[("value", &log.experience)]),
+            ),
+            (
+                "xp_per_hour",
+                locale.format(
+                    "combat.xp_per_hour",
+                    &[("value", &log.xp_per_hour())],
+                ),
+            ),
+        ];
+        let targets = log
+            .target_damage()
+            .map(|(target, amount)| {
+                locale
+                    .format(
+                        "combat.target_damage",
+                        &[("target", &target), ("amount", &amount)],
+                    )
+                    .as_ref()
+                    .to_string()
+            })
+            .collect();
+
+        for (id, text) in labels {
+            self.widgets.set_label_text(systems, id, text.as_ref());
+        }
+
+        self.widgets.set_list_items(systems, "targets", targets);
+    }
+
+    pub fn hover_data(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        self.widgets.hover_tab(systems, screen_pos);
+        self.widgets.hover_list_view(systems, screen_pos);
+    }
+
+    pub fn click_data(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        self.widgets.click_tab(systems, screen_pos);
+        self.widgets.click_list_view(systems, screen_pos);
+    }
+
+    /// Drags a held list scrollbar, returns true while one is held.
+    pub fn move_scroll(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> bool {
+        self.widgets.move_list_view(systems, screen_pos)
+    }
+
+    pub fn release_scroll(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) {
+        self.widgets.release_list_view(systems, screen_pos);
+    }
+}
+
+impl GameWindow for CombatLogWindow {
+    fn id(&self) -> Window {
+        Window::CombatLog
+    }
+
+    fn name(&self) -> &'static str {
+        "combat_log"
+    }
+
+    fn frame(&self) -> &WindowFrame {
+        &self.frame
+    }
+
+    fn frame_mut(&mut self) -> &mut WindowFrame {
+        &mut self.frame
+    }
+
+    fn buttons(&mut self) -> &mut [Button] {
+        &mut self.widgets.button
+    }
+
+    fn unload(&mut self, systems: &mut SystemHolder) {
+        self.widgets.unload(systems);
+    }
+
+    fn recreate(&mut self, systems: &mut SystemHolder) {
+        *self = CombatLogWindow::new(systems);
+    }
+
+    fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        if self.frame.visible == visible {
+            return;
+        }
+        self.frame.visible = visible;
+        self.frame.z_order = 0.0;
+        self.widgets.set_visible(systems, visible);
+        self.next_summary = 0.0;
+    }
+
+    fn can_hold(&self, systems: &SystemHolder, screen_pos: Vec2) -> bool {
+        if !self.frame.visible {
+            return false;
+        }
+
+        let (header_pos, header_size) =
+            self.widgets.header_area(systems, self.frame.pos);
+
+        is_within_area(screen_pos, header_pos, header_size)
+    }
+
+    fn update_pos(&mut self, systems: &mut SystemHolder) {
+        self.widgets.set_pos(systems, self.frame.pos);
+    }
+
+    fn update_z_order(
+        &mut self,
+        systems: &mut SystemHolder,
+        detail_origin: f32,
+    ) {
+        self.widgets.set_z_order(systems, detail_origin);
+    }
+    fn restores_visibility(&self) -> bool {
+        true
+    }
+
+    fn trigger_button(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        index: usize,
+    ) -> Result<WindowAction> {
+        match self.button_id(index) {
+            "close" => return Ok(WindowAction::Close),
+            "reset" => self.request_reset(),
+            _ => {}
+        }
+
+        Ok(WindowAction::None)
+    }
+
+    fn hover_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        _tooltip: &mut Tooltip,
+        _item_desc: &mut ItemDescription,
+        screen_pos: Vec2,
+    ) {
+        self.hover_data(systems, screen_pos);
+    }
+
+    fn click_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        _alert: &mut Alert,
+        screen_pos: Vec2,
+    ) -> Result<WindowAction> {
+        self.click_data(systems, screen_pos);
+
+        Ok(WindowAction::None)
+    }
+
+    fn move_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> bool {
+        self.frame.visible && self.move_scroll(systems, screen_pos)
+    }
+
+    fn release_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> WindowAction {
+        self.release_scroll(systems, screen_pos);
+
+        WindowAction::None
+    }
+}
@@ -0,0 +1,223 @@
+use ascending_graphics::*;
+
+use crate::{
+    ActiveEffect, EffectKind, SystemHolder, data_types::*, effect_time_left,
+    is_within_area, widget::*,
+};
+
+/// Size of an effect icon before scaling.
+const ICON_SIZE: f32 = 24.0;
+/// Space between two icons.
+const ICON_GAP: f32 = 4.0;
+/// Height of the countdown under an icon.
+const TIMER_HEIGHT: f32 = 16.0;
+/// Height of the VitalBar the icons are placed under.
+const VITAL_BAR_HEIGHT: f32 = 68.0;
+
+struct EffectIcon {
+    kind: EffectKind,
+    icon: GfxType,
+    letter: GfxType,
+    timer: GfxType,
+    shown_left: Option<(&'static str, u32)>,
+}
+
+/// Icons of the effects on our player with their countdown, in a row under
+/// the VitalBar.
+pub struct EffectBar {
+    icons: Vec<EffectIcon>,
+    pos: Vec2,
+}
+
+impl EffectBar {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        let scale = systems.scale as f32;
+        let pos = Vec2::new(
+            10.0,
+            systems.size.height
+                - ((VITAL_BAR_HEIGHT * scale).floor() + 10.0)
+                - ((ICON_GAP + ICON_SIZE) * scale).floor(),
+        );
+
+        EffectBar {
+            icons: Vec::new(),
+            pos,
+        }
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        for icon in self.icons.drain(..) {
+            for gfx in [icon.icon, icon.letter, icon.timer] {
+                systems.gfx.remove_gfx(&mut systems.renderer, &gfx);
+            }
+        }
+    }
+
+    pub fn move_by(&mut self, systems: &mut SystemHolder, offset: Vec2) {
+        for icon in self.icons.iter() {
+            for gfx in [icon.icon, icon.letter, icon.timer] {
+                systems.gfx.move_by(&gfx, offset);
+            }
+        }
+        self.pos += offset;
+    }
+
+    fn icon_area(&self, systems: &SystemHolder, index: usize) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+        let size = (Vec2::splat(ICON_SIZE) * scale).floor();
+
+        (
+            self.pos
+                + Vec2::new(
+                    ((ICON_SIZE + ICON_GAP) * scale).floor() * index as f32,
+                    0.0,
+                ),
+            size,
+        )
+    }
+
+    fn add_icon(
+        &self,
+        systems: &mut SystemHolder,
+        index: usize,
+        kind: EffectKind,
+    ) -> EffectIcon {
+        let scale = systems.scale as f32;
+        let (pos, size) = self.icon_area(systems, index);
+        let style = systems.theme.hud;
+        let border = if kind.is_harmful() {
+            style.harmful_effect_border
+        } else {
+            style.effect_border
+        };
+
+        let mut rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(pos.x, pos.y, ORDER_VITAL_HPBG),
+            size,
+            kind.icon_color(),
+            0,
+        );
+        rect.set_border_width(2.0).set_border_color(border);
+        let icon = systems.gfx.add_rect(
+            rect,
+            0,
+            "Effect Icon",
+            true,
+            CameraView::SubView1,
+        );
+
+        let name = systems.locale.text(kind.locale_key());
+        let letter = name.as_ref().chars().next().unwrap_or(' ').to_string();
+        let label = create_label(
+            systems,
+            Vec3::new(pos.x, pos.y, ORDER_VITAL_HP),
+            size,
+            Some(Bounds::new(pos.x, pos.y, pos.x + size.x, pos.y + size.y)),
+            style.effect_text,
+        );
+        let letter_gfx = systems.gfx.add_text(
+            label,
+            1,
+            "Effect Letter",
+            true,
+            CameraView::SubView1,
+        );
+        systems.gfx.set_text(&letter_gfx, &letter);
+        systems.gfx.center_text(&mut systems.renderer, &letter_gfx);
+
+        let timer_pos =
+            Vec2::new(pos.x, pos.y - (TIMER_HEIGHT * scale).floor());
+        let timer_size = Vec2::new(size.x, (TIMER_HEIGHT * scale).floor());
+        let label = create_label(
+            systems,
+            Vec3::new(timer_pos.x, timer_pos.y, ORDER_VITAL_HP),
+            timer_size,
+            Some(Bounds::new(
+                timer_pos.x,
+                timer_pos.y,
+                timer_pos.x + timer_size.x,
+                timer_pos.y + timer_size.y,
+            )),
+            style.map_text,
+        );
+        let timer = systems.gfx.add_text(
+            label,
+            1,
+            "Effect Timer",
+            true,
+            CameraView::SubView1,
+        );
+
+        EffectIcon {
+            kind,
+            icon,
+            letter: letter_gfx,
+            timer,
+            shown_left: None,
+        }
+    }
+
+    /// Follows the `effects` on our player, icons are rebuilt when the set
+    /// of effects changes and only the countdowns otherwise.
+    pub fn update(
+        &mut self,
+        systems: &mut SystemHolder,
+        effects: &[ActiveEffect],
+        seconds: f32,
+    ) {
+        if !self
+            .icons
+            .iter()
+            .map(|icon| icon.kind)
+            .eq(effects.iter().map(|effect| effect.kind))
+        {
+            self.unload(systems);
+
+            for (index, effect) in effects.iter().enumerate() {
+                let icon = self.add_icon(systems, index, effect.kind);
+                self.icons.push(icon);
+            }
+        }
+
+        for (icon, effect) in self.icons.iter_mut().zip(effects) {
+            let left = effect_time_left(effect.ends_at - seconds);
+
+            if icon.shown_left == Some(left) {
+                continue;
+            }
+            icon.shown_left = Some(left);
+
+            let text = systems.locale.format(left.0, &[("time", &left.1)]);
+            systems.gfx.set_text(&icon.timer, text.as_ref());
+            systems.gfx.center_text(&mut systems.renderer, &icon.timer);
+        }
+    }
+
+    /// Shows the name and description of the effect under the mouse.
+    pub fn hover(
+        &self,
+        systems: &mut SystemHolder,
+        tooltip: &mut Tooltip,
+        screen_pos: Vec2,
+    ) {
+        let Some(icon) = (0..self.icons.len())
+            .find(|index| {
+                let (pos, size) = self.icon_area(systems, *index);
+                is_within_area(screen_pos, pos, size)
+            })
+            .map(|index| &self.icons[index])
+        else {
+            return;
+        };
+
+        let key = icon.kind.locale_key();
+        let msg = format!(
+            "{}\n{}",
+            systems.locale.get(key),
+            systems.locale.get(&format!("{key}_desc"))
+        );
+
+        tooltip.init_tooltip(systems, screen_pos, &msg);
+    }
+}
@@ -0,0 +1,322 @@
+use ascending_graphics::*;
+
+use crate::{
+    GlobalKey, InspectData, Item, Result, SystemHolder, UserAccess,
+    content::PlayerPvP, data_types::*, is_within_area, systems::Poller,
+    widget::*,
+};
+
+use super::{GameWindow, ItemDescription, Window, WindowAction, WindowFrame};
+
+const INSPECT_LAYOUT: &str = include_str!("../../../../data/ui/inspect.toml");
+
+/// Widgets the Inspect window looks up by id, a layout file without them
+/// is replaced by the built in one.
+pub const INSPECT_WIDGETS: &[(LayoutKind, &str)] = &[
+    (LayoutKind::Button, "close"),
+    (LayoutKind::Label, "name"),
+    (LayoutKind::Label, "level"),
+    (LayoutKind::Label, "status"),
+    (LayoutKind::SlotGrid, "equipment"),
+];
+
+/// Size of the item images drawn in the middle of the equipment slots.
+const ITEM_SIZE: f32 = 20.0;
+
+/// Name colour of a player, staff stand out from everyone else.
+pub fn access_color(theme: &ThemeAccess, access: UserAccess) -> Color {
+    match access {
+        UserAccess::None => theme.player,
+        UserAccess::Monitor => theme.monitor,
+        UserAccess::Admin => theme.admin,
+    }
+}
+
+/// Locale key describing the PvP state of a player.
+pub fn pvp_status_key(pvp: PlayerPvP) -> &'static str {
+    if pvp.pk {
+        "inspect.pk"
+    } else if pvp.pvpon {
+        "inspect.pvp_on"
+    } else {
+        "inspect.pvp_off"
+    }
+}
+
+#[derive(Clone, Copy)]
+struct EqData {
+    img: GfxType,
+    index: usize,
+}
+
+/// Equipment, level and PvP state of another player, filled from the
+/// server's answer to an inspect request.
+pub struct InspectWindow {
+    pub frame: WindowFrame,
+    widgets: LayoutWidgets,
+    /// Player the window was last filled with.
+    pub entity: Option<GlobalKey>,
+    eq_data: [Option<EqData>; MAX_EQPT],
+}
+
+impl InspectWindow {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        let layout = UiLayout::load("inspect", INSPECT_LAYOUT, INSPECT_WIDGETS);
+        let w_size = layout.window.scaled_size(systems);
+        let pos = layout.window.default_pos(systems);
+
+        InspectWindow {
+            frame: WindowFrame::new(systems, pos, w_size, 1.0),
+            widgets: LayoutWidgets::new(systems, layout, pos, ORDER_GUI_WINDOW),
+            entity: None,
+            eq_data: [None; MAX_EQPT],
+        }
+    }
+
+    pub fn button_id(&self, index: usize) -> &str {
+        self.widgets.button_id(index)
+    }
+
+    pub fn set_data(&mut self, systems: &mut SystemHolder, data: &InspectData) {
+        let level = systems
+            .locale
+            .format("inspect.level", &[("level", &data.level)]);
+        let status = systems.locale.text(pvp_status_key(data.pvp));
+
+        self.widgets.set_label_color(
+            systems,
+            "name",
+            access_color(&systems.theme.access, data.user_access),
+        );
+        self.widgets.set_label_text(systems, "name", &data.username);
+        self.widgets
+            .set_label_text(systems, "level", level.as_ref());
+        self.widgets
+            .set_label_text(systems, "status", status.as_ref());
+
+        for slot in 0..MAX_EQPT {
+            let item = data.equipment.items.get(slot).copied();
+
+            self.set_equipment_slot(systems, slot, item.unwrap_or_default());
+        }
+
+        self.entity = Some(data.entity);
+    }
+
+    /// Screen area of equipment slot `slot`.
+    fn slot_area(
+        &self,
+        systems: &SystemHolder,
+        slot: usize,
+    ) -> Option<(Vec2, Vec2)> {
+        let grid = self.widgets.slot_grid("equipment")?;
+        let scale = systems.scale as f32;
+
+        Some((
+            self.frame.pos + (grid.slot_pos(slot) * scale).floor(),
+            (Vec2::from(grid.slot_size) * scale).floor(),
+        ))
+    }
+
+    fn item_pos(&self, systems: &SystemHolder, slot: usize) -> Vec2 {
+        let Some((pos, size)) = self.slot_area(systems, slot) else {
+            return self.frame.pos;
+        };
+        let item_size = (ITEM_SIZE * systems.scale as f32).floor();
+
+        (pos + (size - item_size) * 0.5).floor()
+    }
+
+    /// Item images sit one layer above the slots.
+    fn item_z(&self, detail_origin: f32) -> f32 {
+        let layer = self.widgets.slot_grid("equipment").map_or(1, |g| g.layer);
+
+        layer_z(detail_origin, layer + 1)
+    }
+
+    fn set_equipment_slot(
+        &mut self,
+        systems: &mut SystemHolder,
+        slot: usize,
+        item: Item,
+    ) {
+        if let Some(data) = self.eq_data[slot].take() {
+            systems.gfx.remove_gfx(&mut systems.renderer, &data.img);
+        }
+
+        if item.val == 0 {
+            return;
+        }
+
+        let Some(item_base) = systems.base.item.get(item.num as usize) else {
+            return;
+        };
+
+        let pos = self.item_pos(systems, slot);
+        let img = Image::new(
+            Some(systems.resource.items[item_base.sprite as usize].allocation),
+            &mut systems.renderer,
+            Vec3::new(pos.x, pos.y, self.item_z(self.frame.detail_origin())),
+            (Vec2::new(ITEM_SIZE, ITEM_SIZE) * systems.scale as f32).floor(),
+            Vec4::new(0.0, 0.0, ITEM_SIZE, ITEM_SIZE),
+            0,
+        );
+        let img = systems.gfx.add_image(
+            img,
+            0,
+            "Inspect EQ Image",
+            self.frame.visible,
+            CameraView::SubView1,
+        );
+
+        self.eq_data[slot] = Some(EqData {
+            img,
+            index: item.num as usize,
+        });
+    }
+
+    pub fn find_eq_slot(
+        &self,
+        systems: &SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        (0..MAX_EQPT).find(|slot| {
+            self.eq_data[*slot].is_some()
+                && self.slot_area(systems, *slot).is_some_and(|(pos, size)| {
+                    is_within_area(screen_pos, pos, size)
+                })
+        })
+    }
+
+    pub fn hover_data(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+        itemdesc: &mut ItemDescription,
+    ) {
+        if !self.frame.visible || self.frame.order_index != 0 {
+            return;
+        }
+
+        if let Some(data) = self
+            .find_eq_slot(systems, screen_pos)
+            .and_then(|slot| self.eq_data[slot])
+        {
+            itemdesc.set_visible(systems, true);
+            itemdesc.set_data(systems, data.index);
+            itemdesc.set_position(systems, screen_pos);
+        } else {
+            itemdesc.set_visible(systems, false);
+        }
+    }
+}
+
+impl GameWindow for InspectWindow {
+    fn id(&self) -> Window {
+        Window::Inspect
+    }
+
+    fn name(&self) -> &'static str {
+        "inspect"
+    }
+
+    fn frame(&self) -> &WindowFrame {
+        &self.frame
+    }
+
+    fn frame_mut(&mut self) -> &mut WindowFrame {
+        &mut self.frame
+    }
+
+    fn buttons(&mut self) -> &mut [Button] {
+        &mut self.widgets.button
+    }
+
+    fn unload(&mut self, systems: &mut SystemHolder) {
+        self.widgets.unload(systems);
+
+        for data in self.eq_data.iter_mut() {
+            if let Some(data) = data.take() {
+                systems.gfx.remove_gfx(&mut systems.renderer, &data.img);
+            }
+        }
+    }
+
+    fn recreate(&mut self, systems: &mut SystemHolder) {
+        *self = InspectWindow::new(systems);
+    }
+
+    fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        if self.frame.visible == visible {
+            return;
+        }
+        self.frame.visible = visible;
+        self.frame.z_order = 0.0;
+        self.widgets.set_visible(systems, visible);
+
+        for data in self.eq_data.iter().flatten() {
+            systems.gfx.set_visible(&data.img, visible);
+        }
+    }
+
+    fn can_hold(&self, systems: &SystemHolder, screen_pos: Vec2) -> bool {
+        if !self.frame.visible {
+            return false;
+        }
+
+        let (header_pos, header_size) =
+            self.widgets.header_area(systems, self.frame.pos);
+
+        is_within_area(screen_pos, header_pos, header_size)
+    }
+
+    fn update_pos(&mut self, systems: &mut SystemHolder) {
+        self.widgets.set_pos(systems, self.frame.pos);
+
+        for slot in 0..MAX_EQPT {
+            if let Some(data) = self.eq_data[slot] {
+                let pos = self.item_pos(systems, slot);
+                let z = systems.gfx.get_pos(&data.img).z;
+
+                systems.gfx.set_pos(&data.img, Vec3::new(pos.x, pos.y, z));
+            }
+        }
+    }
+
+    fn update_z_order(
+        &mut self,
+        systems: &mut SystemHolder,
+        detail_origin: f32,
+    ) {
+        self.widgets.set_z_order(systems, detail_origin);
+
+        let z = self.item_z(detail_origin);
+
+        for data in self.eq_data.iter().flatten() {
+            let pos = systems.gfx.get_pos(&data.img);
+
+            systems.gfx.set_pos(&data.img, Vec3::new(pos.x, pos.y, z));
+        }
+    }
+    fn trigger_button(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        index: usize,
+    ) -> Result<WindowAction> {
+        Ok(match self.button_id(index) {
+            "close" => WindowAction::Close,
+            _ => WindowAction::None,
+        })
+    }
+
+    fn hover_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        _tooltip: &mut Tooltip,
+        item_desc: &mut ItemDescription,
+        screen_pos: Vec2,
+    ) {
+        self.hover_data(systems, screen_pos, item_desc);
+    }
+}
@@ -31,12 +31,12 @@ impl ItemDescription {
             &mut systems.renderer,
             Vec3::new(0.0, 0.0, 0.0),
             Vec2::ZERO,
-            Color::rgba(110, 110, 110, 255),
+            systems.theme.window.color,
             0,
         );
         bg_rect
-            .set_border_width(1.0)
-            .set_border_color(Color::rgba(40, 40, 40, 255));
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(systems.theme.window.border_color);
         let bg = systems.gfx.add_rect(
             bg_rect,
             0,
@@ -97,24 +97,45 @@ impl ItemDescription {
         match item_base.itemtype {
             ItemTypes::Consume => {
                 if item_base.data[0] > 0 {
-                    text_holder.push(format!("HP + {}", item_base.data[0]))
+                    text_holder.push(
+                        systems
+                            .locale
+                            .format("item.hp", &[("value", &item_base.data[0])])
+                            .to_string(),
+                    )
                 }
                 if item_base.data[1] > 0 {
-                    text_holder.push(format!("MP + {}", item_base.data[1]))
+                    text_holder.push(
+                        systems
+                            .locale
+                            .format("item.mp", &[("value", &item_base.data[1])])
+                            .to_string(),
+                    )
                 }
                 if item_base.data[2] > 0 {
-                    text_holder.push(format!("SP + {}", item_base.data[2]))
+                    text_holder.push(
+                        systems
+                            .locale
+                            .format("item.sp", &[("value", &item_base.data[2])])
+                            .to_string(),
+                    )
                 }
             }
-            ItemTypes::Weapon => {
-                text_holder.push(format!("Damage: {}", item_base.data[0]))
-            }
+            ItemTypes::Weapon => text_holder.push(
+                systems
+                    .locale
+                    .format("item.damage", &[("value", &item_base.data[0])])
+                    .to_string(),
+            ),
             ItemTypes::Accessory
             | ItemTypes::Helmet
             | ItemTypes::Armor
-            | ItemTypes::Trouser => {
-                text_holder.push(format!("Defense: {}", item_base.data[0]))
-            }
+            | ItemTypes::Trouser => text_holder.push(
+                systems
+                    .locale
+                    .format("item.defense", &[("value", &item_base.data[0])])
+                    .to_string(),
+            ),
             _ => {}
         }
 
@@ -160,7 +181,7 @@ impl ItemDescription {
                 tpos.x + text_size.x,
                 tpos.y + text_size.y,
             )))
-            .set_default_color(Color::rgba(250, 250, 250, 255));
+            .set_default_color(systems.theme.window.highlight_text);
         let name = systems.gfx.add_text(
             name_text,
             1,
@@ -194,7 +215,7 @@ impl ItemDescription {
                         n_pos.x + text_size.x,
                         n_pos.y + text_size.y,
                     )),
-                    Color::rgba(200, 200, 200, 255),
+                    systems.theme.window.text,
                 );
                 let text_index = systems.gfx.add_text(
                     text,
@@ -0,0 +1,359 @@
+use ascending_graphics::*;
+
+use crate::{
+    Entity, GlobalKey, MemberLocation, Party, PartyMember, Position,
+    SystemHolder, World, data_types::*, get_percent, member_location,
+    widget::*,
+};
+
+const PARTY_FRAMES_LAYOUT: &str =
+    include_str!("../../../../data/ui/party_frames.toml");
+
+/// Widgets the party frames place their parts on, a layout file without
+/// them is replaced by the built in one.
+pub const PARTY_FRAMES_WIDGETS: &[(LayoutKind, &str)] = &[
+    (LayoutKind::Label, "shared_exp"),
+    (LayoutKind::Rect, "member"),
+    (LayoutKind::Rect, "hp"),
+    (LayoutKind::Label, "name"),
+    (LayoutKind::Label, "location"),
+];
+
+/// Height of the VitalBar and the effect icons with their countdown, the
+/// layout offset is measured from their bottom.
+const HUD_HEIGHT: f32 = 68.0 + 4.0 + 24.0 + 16.0;
+
+/// Scaled position from the shared experience line and size of each part
+/// of the first member frame.
+#[derive(Clone, Copy)]
+struct MemberAreas {
+    frame: (Vec2, Vec2),
+    hp: (Vec2, Vec2),
+    name: (Vec2, Vec2),
+    location: (Vec2, Vec2),
+}
+
+struct MemberFrame {
+    entity: GlobalKey,
+    bg: GfxType,
+    name: GfxType,
+    bar_bg: GfxType,
+    bar: GfxType,
+    location: GfxType,
+    shown_name: Option<(i32, bool)>,
+    shown_hp: Option<(i32, i32)>,
+    shown_location: Option<MemberLocation>,
+}
+
+impl MemberFrame {
+    fn gfx(&self) -> [GfxType; 5] {
+        [self.bg, self.name, self.bar_bg, self.bar, self.location]
+    }
+}
+
+/// Level, position and vitals of `member`, from its entity while it is in
+/// view and from the server's report otherwise.
+fn member_state(
+    world: &World,
+    member: &PartyMember,
+) -> (i32, Position, [i32; VITALS_MAX], [i32; VITALS_MAX]) {
+    match world.entities.get(member.entity) {
+        Some(Entity::Player(p_data)) => (
+            p_data.level,
+            p_data.pos,
+            p_data.vitals.vital,
+            p_data.vitals.vitalmax,
+        ),
+        _ => (member.level, member.pos, member.vital, member.vitalmax),
+    }
+}
+
+/// Name, level, health and location of the other members of our party,
+/// stacked under the effect icons with the shared experience on top.
+pub struct PartyFrames {
+    header: GfxType,
+    frames: Vec<MemberFrame>,
+    pos: Vec2,
+    areas: MemberAreas,
+    shown_exp: Option<u64>,
+}
+
+impl PartyFrames {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        let layout = UiLayout::load(
+            "party_frames",
+            PARTY_FRAMES_LAYOUT,
+            PARTY_FRAMES_WIDGETS,
+        );
+        let scale = systems.scale as f32;
+        let pos = layout.window.default_pos(systems)
+            - Vec2::new(0.0, (HUD_HEIGHT * scale).floor());
+        let scaled = |(offset, size): (Vec2, Vec2)| {
+            ((offset * scale).floor(), (size * scale).floor())
+        };
+        let areas = MemberAreas {
+            frame: scaled(layout.rect_area("member").unwrap_or_default()),
+            hp: scaled(layout.rect_area("hp").unwrap_or_default()),
+            name: scaled(layout.label_area("name").unwrap_or_default()),
+            location: scaled(layout.label_area("location").unwrap_or_default()),
+        };
+
+        let (offset, size) =
+            scaled(layout.label_area("shared_exp").unwrap_or_default());
+        let label_pos = pos + offset;
+        let label = create_label(
+            systems,
+            Vec3::new(label_pos.x, label_pos.y, ORDER_VITAL_HP),
+            size,
+            Some(Bounds::new(
+                label_pos.x,
+                label_pos.y,
+                label_pos.x + size.x,
+                label_pos.y + size.y,
+            )),
+            systems.theme.hud.map_text,
+        );
+        let header = systems.gfx.add_text(
+            label,
+            1,
+            "Party Header",
+            false,
+            CameraView::SubView1,
+        );
+
+        PartyFrames {
+            header,
+            frames: Vec::new(),
+            pos,
+            areas,
+            shown_exp: None,
+        }
+    }
+
+    fn unload_frames(&mut self, systems: &mut SystemHolder) {
+        for frame in self.frames.drain(..) {
+            for gfx in frame.gfx() {
+                systems.gfx.remove_gfx(&mut systems.renderer, &gfx);
+            }
+        }
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        self.unload_frames(systems);
+        systems.gfx.remove_gfx(&mut systems.renderer, &self.header);
+    }
+
+    pub fn move_by(&mut self, systems: &mut SystemHolder, offset: Vec2) {
+        systems.gfx.move_by(&self.header, offset);
+        for frame in self.frames.iter() {
+            for gfx in frame.gfx() {
+                systems.gfx.move_by(&gfx, offset);
+            }
+        }
+        self.pos += offset;
+    }
+
+    fn add_frame(
+        &self,
+        systems: &mut SystemHolder,
+        index: usize,
+        entity: GlobalKey,
+    ) -> MemberFrame {
+        let style = systems.theme.hud;
+        let areas = self.areas;
+        // Every member sits as far under the one before as the first one
+        // sits under the shared experience line.
+        let pos = self.pos + Vec2::new(0.0, areas.frame.0.y * index as f32);
+        let (frame_pos, frame_size) = (pos + areas.frame.0, areas.frame.1);
+
+        let mut bg_rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(frame_pos.x, frame_pos.y, ORDER_VITAL_BG),
+            frame_size,
+            style.color,
+            0,
+        );
+        bg_rect
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(style.border_color);
+        let bg = systems.gfx.add_rect(
+            bg_rect,
+            0,
+            "Party Frame",
+            true,
+            CameraView::SubView1,
+        );
+
+        let mut labels = [GfxType::default(); 2];
+        for (index, ((offset, label_size), color)) in [
+            (areas.name, style.text),
+            (areas.location, style.detail_text),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let label_pos = pos + offset;
+            let label = create_label(
+                systems,
+                Vec3::new(label_pos.x, label_pos.y, ORDER_VITAL_HP),
+                label_size,
+                Some(Bounds::new(
+                    label_pos.x,
+                    label_pos.y,
+                    label_pos.x + label_size.x,
+                    label_pos.y + label_size.y,
+                )),
+                color,
+            );
+
+            labels[index] = systems.gfx.add_text(
+                label,
+                1,
+                "Party Frame Text",
+                true,
+                CameraView::SubView1,
+            );
+        }
+        let [name, location] = labels;
+
+        let (bar_pos, bar_size) = (pos + areas.hp.0, areas.hp.1);
+        let mut bar_bg_rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(bar_pos.x, bar_pos.y, ORDER_VITAL_HPBG),
+            bar_size,
+            style.bar_background,
+            0,
+        );
+        bar_bg_rect
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(style.bar_border_color);
+        let bar_bg = systems.gfx.add_rect(
+            bar_bg_rect,
+            0,
+            "Party HP BG",
+            true,
+            CameraView::SubView1,
+        );
+
+        let bar_rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(bar_pos.x + 1.0, bar_pos.y + 1.0, ORDER_VITAL_HP),
+            bar_size - Vec2::splat(2.0),
+            style.hp,
+            0,
+        );
+        let bar = systems.gfx.add_rect(
+            bar_rect,
+            0,
+            "Party HP Bar",
+            true,
+            CameraView::SubView1,
+        );
+
+        MemberFrame {
+            entity,
+            bg,
+            name,
+            bar_bg,
+            bar,
+            location,
+            shown_name: None,
+            shown_hp: None,
+            shown_location: None,
+        }
+    }
+
+    /// Follows the members of `party` other than our player, frames are
+    /// rebuilt when the members change and only their values otherwise.
+    pub fn update(
+        &mut self,
+        world: &World,
+        systems: &mut SystemHolder,
+        party: &Party,
+        myentity: Option<GlobalKey>,
+    ) {
+        let members: Vec<&PartyMember> = party
+            .members()
+            .iter()
+            .filter(|member| Some(member.entity) != myentity)
+            .collect();
+
+        if !self
+            .frames
+            .iter()
+            .map(|frame| frame.entity)
+            .eq(members.iter().map(|member| member.entity))
+        {
+            self.unload_frames(systems);
+
+            for (index, member) in members.iter().enumerate() {
+                let frame = self.add_frame(systems, index, member.entity);
+                self.frames.push(frame);
+            }
+        }
+
+        let shown_exp = party.in_party().then_some(party.shared_exp);
+        if self.shown_exp != shown_exp {
+            if let Some(exp) = shown_exp {
+                let text =
+                    systems.locale.format("party.shared_exp", &[("exp", &exp)]);
+                systems.gfx.set_text(&self.header, text.as_ref());
+            }
+            systems.gfx.set_visible(&self.header, shown_exp.is_some());
+            self.shown_exp = shown_exp;
+        }
+
+        let my_pos = match myentity.and_then(|key| world.entities.get(key)) {
+            Some(Entity::Player(p_data)) => Some(p_data.pos),
+            _ => None,
+        };
+
+        for (frame, member) in self.frames.iter_mut().zip(members) {
+            let (level, pos, vital, vitalmax) = member_state(world, member);
+            let is_leader = party.leader == Some(member.entity);
+
+            if frame.shown_name != Some((level, is_leader)) {
+                frame.shown_name = Some((level, is_leader));
+
+                let color = if is_leader {
+                    systems.theme.hud.leader_text
+                } else {
+                    systems.theme.hud.text
+                };
+                let text = systems.locale.format(
+                    "party.member",
+                    &[("name", &member.name), ("level", &level)],
+                );
+                systems.gfx.set_color(&frame.name, color);
+                systems.gfx.set_text(&frame.name, text.as_ref());
+            }
+
+            let hp = (vital[0], vitalmax[0]);
+            if frame.shown_hp != Some(hp) {
+                frame.shown_hp = Some(hp);
+
+                let bar_width = self.areas.hp.1.x - 2.0;
+                let mut size = systems.gfx.get_size(&frame.bar);
+                size.x = get_percent(hp.0, hp.1, bar_width as i32) as f32;
+                systems.gfx.set_size(&frame.bar, size);
+            }
+
+            let location = member_location(my_pos, pos);
+            if frame.shown_location != Some(location) {
+                frame.shown_location = Some(location);
+
+                let text = match location {
+                    MemberLocation::Nearby(distance) => systems.locale.plural(
+                        "party.nearby",
+                        distance as u64,
+                        &[],
+                    ),
+                    MemberLocation::Away(map) => systems
+                        .locale
+                        .format("party.map", &[("x", &map.x), ("y", &map.y)]),
+                };
+                systems.gfx.set_text(&frame.location, text.as_ref());
+            }
+        }
+    }
+}
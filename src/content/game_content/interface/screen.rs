@@ -10,6 +10,7 @@ pub struct VitalBar {
 
 impl VitalBar {
     pub fn new(systems: &mut SystemHolder) -> Self {
+        let theme = systems.theme.hud;
         let size = (Vec2::new(200.0, 68.0) * systems.scale as f32).floor();
         let pos = Vec3::new(
             10.0,
@@ -21,12 +22,12 @@ impl VitalBar {
             &mut systems.renderer,
             Vec3::new(pos.x - 1.0, pos.y - 1.0, pos.z),
             Vec2::new(size.x + 2.0, size.y + 2.0),
-            Color::rgba(180, 180, 180, 255),
+            theme.color,
             0,
         );
         bg_rect
             .set_border_width(1.0)
-            .set_border_color(Color::rgba(40, 40, 40, 255));
+            .set_border_color(theme.border_color);
         let bg = systems.gfx.add_rect(
             bg_rect,
             0,
@@ -41,9 +42,9 @@ impl VitalBar {
         let mut bar = [GfxType::default(); 3];
         for i in 0..3 {
             let (add_y, color, height) = match i {
-                0 => (38.0, Color::rgba(200, 80, 80, 255), 20.0),
-                1 => (13.0, Color::rgba(80, 80, 200, 255), 20.0),
-                _ => (0.0, Color::rgba(100, 200, 80, 255), 8.0),
+                0 => (38.0, theme.hp, 20.0),
+                1 => (13.0, theme.mp, 20.0),
+                _ => (0.0, theme.sp, 8.0),
             };
 
             let mut bg_rect = Rect::new(
@@ -57,12 +58,12 @@ impl VitalBar {
                     size.x - (10.0 * systems.scale as f32).floor(),
                     (height * systems.scale as f32).floor(),
                 ),
-                Color::rgba(100, 100, 100, 255),
+                theme.bar_background,
                 0,
             );
             bg_rect
                 .set_border_width(1.0)
-                .set_border_color(Color::rgba(60, 60, 60, 255));
+                .set_border_color(theme.bar_border_color);
             bar_bg[i] = systems.gfx.add_rect(
                 bg_rect,
                 0,
@@ -112,6 +113,27 @@ impl VitalBar {
         });
     }
 
+    pub fn move_by(&self, systems: &mut SystemHolder, offset: Vec2) {
+        for gfx in [self.bg]
+            .iter()
+            .chain(self.bar_bg.iter())
+            .chain(self.bar.iter())
+        {
+            systems.gfx.move_by(gfx, offset);
+        }
+    }
+
+    /// Greys out the HP bar while the player is dead.
+    pub fn set_dead(&self, systems: &mut SystemHolder, dead: bool) {
+        let color = if dead {
+            systems.theme.hud.dead
+        } else {
+            systems.theme.hud.hp
+        };
+
+        systems.gfx.set_color(&self.bar[0], color);
+    }
+
     pub fn update_bar_size(
         &mut self,
         systems: &mut SystemHolder,
@@ -127,16 +149,8 @@ impl VitalBar {
 
 pub fn create_menu_button(systems: &mut SystemHolder) -> [Button; 3] {
     let button_properties = ButtonRect {
-        rect_color: Color::rgba(80, 80, 80, 255),
         got_border: true,
-        border_color: Color::rgba(40, 40, 40, 255),
-        border_radius: 0.0,
-        hover_change: ButtonChangeType::ColorChange(Color::rgba(
-            135, 135, 135, 255,
-        )),
-        click_change: ButtonChangeType::ColorChange(Color::rgba(
-            200, 200, 200, 255,
-        )),
+        ..systems.theme.button.rect()
     };
     let mut image_properties = ButtonContentImg {
         res: systems.resource.button_icon.allocation,
@@ -1,992 +1,631 @@
 use ascending_graphics::*;
 
 use crate::{
-    Interface, SystemHolder, data_types::*, is_within_area, logic::*, widget::*,
+    ClientAdapterPowerSettings, ClientGPUInstances, ClientPresentMode,
+    ClientWindowMode, FPS_LIMITS, GRAPHIC_BACKENDS, Locale, Result,
+    SystemHolder, UI_SCALE_MAX, UI_SCALE_MIN, data_types::*, is_within_area,
+    systems::Poller, widget::*,
 };
 
+use super::{GameWindow, ItemDescription, Window, WindowAction, WindowFrame};
+
+const SETTING_LAYOUT: &str = include_str!("../../../../data/ui/setting.toml");
+
+/// Widgets the Setting window looks up by id, a layout file without them
+/// is replaced by the built in one.
+pub const SETTING_WIDGETS: &[(LayoutKind, &str)] = &[
+    (LayoutKind::Button, "close"),
+    (LayoutKind::Slider, "sfx_volume"),
+    (LayoutKind::Slider, "bgm_volume"),
+    (LayoutKind::Slider, "ui_scale"),
+];
+
 pub struct Setting {
-    pub visible: bool,
-    bg: GfxType,
-    header: GfxType,
-    header_text: GfxType,
-    pub sfx_scroll: Scrollbar,
-    pub bgm_scroll: Scrollbar,
-    button: Vec<Button>,
-    checkbox: Vec<Checkbox>,
-    label: Vec<GfxType>,
-    sfx_label: GfxType,
-    bgm_label: GfxType,
-
-    pub pos: Vec2,
-    pub size: Vec2,
-    pub z_order: f32,
-    pub order_index: usize,
-    in_hold: bool,
-    hold_pos: Vec2,
-    header_pos: Vec2,
-    header_size: Vec2,
-    pub did_button_click: bool,
-    pub did_checkbox_click: bool,
-
-    min_bound: Vec2,
-    max_bound: Vec2,
+    pub frame: WindowFrame,
+    widgets: LayoutWidgets,
+    sfx_slider: usize,
+    bgm_slider: usize,
+    ui_scale_slider: usize,
+    graphic_backends: Vec<String>,
+    fps_limits: Vec<u16>,
+    themes: Vec<String>,
+    languages: Vec<String>,
 }
 
-impl Setting {
-    pub fn new(systems: &mut SystemHolder) -> Self {
-        let orig_size = Vec2::new(200.0, 267.0);
-        let w_size = (orig_size * systems.scale as f32).floor();
-        let w_pos = Vec3::new(
-            systems.size.width - w_size.x - 10.0,
-            60.0,
-            ORDER_GUI_WINDOW,
-        );
-        let pos = Vec2::new(w_pos.x, w_pos.y);
+/// Index of `value` in `values`. A value that is not one of the choices,
+/// from an edited settings.toml, is kept as an extra option.
+fn option_index<T: PartialEq>(
+    names: &mut Vec<String>,
+    values: &mut Vec<T>,
+    value: T,
+    name: String,
+) -> usize {
+    values
+        .iter()
+        .position(|data| *data == value)
+        .unwrap_or_else(|| {
+            names.push(name);
+            values.push(value);
+            values.len() - 1
+        })
+}
 
-        let detail_1 = w_pos.z.sub_f32(0.001, 3);
-        let detail_2 = w_pos.z.sub_f32(0.002, 3);
+fn fps_limit_name(locale: &Locale, limit: u16) -> String {
+    match limit {
+        0 => locale.get("setting.fps_unlimited").to_string(),
+        limit => format!("{limit}"),
+    }
+}
 
-        let mut rect = Rect::new(
-            &mut systems.renderer,
-            Vec3::new(w_pos.x - 1.0, w_pos.y - 1.0, w_pos.z),
-            w_size + 2.0,
-            Color::rgba(110, 110, 110, 255),
-            0,
+impl Setting {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        let layout = UiLayout::load("setting", SETTING_LAYOUT, SETTING_WIDGETS);
+        let w_size = layout.window.scaled_size(systems);
+        let pos = layout.window.default_pos(systems);
+
+        let mut widgets =
+            LayoutWidgets::new(systems, layout, pos, ORDER_GUI_WINDOW);
+
+        // Checked by UiLayout::load against SETTING_WIDGETS.
+        let sfx_slider = widgets.slider_index("sfx_volume").unwrap_or(0);
+        let bgm_slider = widgets.slider_index("bgm_volume").unwrap_or(0);
+        let ui_scale_slider = widgets.slider_index("ui_scale").unwrap_or(0);
+
+        widgets.slider[sfx_slider]
+            .set_value(systems, systems.config.sfx_volume as usize);
+        widgets.slider[bgm_slider]
+            .set_value(systems, systems.config.bgm_volume as usize);
+        widgets.slider[ui_scale_slider]
+            .set_value(systems, systems.config.ui_scale as usize);
+
+        let mut backend_names: Vec<String> = GRAPHIC_BACKENDS
+            .iter()
+            .map(|(key, _)| systems.locale.get(key).to_string())
+            .collect();
+        let mut graphic_backends: Vec<String> = GRAPHIC_BACKENDS
+            .iter()
+            .map(|(_, value)| value.to_string())
+            .collect();
+        let backend = option_index(
+            &mut backend_names,
+            &mut graphic_backends,
+            systems.config.graphic_backend.clone(),
+            systems.config.graphic_backend.clone(),
         );
-        rect.set_border_width(1.0)
-            .set_border_color(Color::rgba(20, 20, 20, 255));
-        let bg = systems.gfx.add_rect(
-            rect,
-            0,
-            "Settings BG",
-            false,
-            CameraView::SubView1,
+        widgets.set_dropdown_options(
+            systems,
+            "graphic_backend",
+            backend_names,
+            backend,
         );
 
-        let header_pos = Vec2::new(
-            w_pos.x,
-            w_pos.y + (237.0 * systems.scale as f32).floor(),
-        );
-        let header_size = Vec2::new(orig_size.x, 30.0);
-        let header_zpos = detail_1;
-        let header_rect = Rect::new(
-            &mut systems.renderer,
-            Vec3::new(header_pos.x, header_pos.y, header_zpos),
-            (header_size * systems.scale as f32).floor(),
-            Color::rgba(70, 70, 70, 255),
-            0,
+        let mut fps_names: Vec<String> = FPS_LIMITS
+            .iter()
+            .map(|limit| fps_limit_name(&systems.locale, *limit))
+            .collect();
+        let mut fps_limits = FPS_LIMITS.to_vec();
+        let fps_limit = option_index(
+            &mut fps_names,
+            &mut fps_limits,
+            systems.config.fps_limit,
+            fps_limit_name(&systems.locale, systems.config.fps_limit),
         );
-
-        let header = systems.gfx.add_rect(
-            header_rect,
-            0,
-            "Settings Header",
-            false,
-            CameraView::SubView1,
+        widgets.set_dropdown_options(
+            systems,
+            "fps_limit",
+            fps_names,
+            fps_limit,
         );
 
-        let text = create_label(
-            systems,
-            Vec3::new(
-                w_pos.x,
-                w_pos.y + (242.0 * systems.scale as f32).floor(),
-                detail_2,
-            ),
-            Vec2::new(w_size.x, (20.0 * systems.scale as f32).floor()),
-            Some(Bounds::new(
-                w_pos.x,
-                w_pos.y + (242.0 * systems.scale as f32).floor(),
-                w_pos.x + w_size.x,
-                w_pos.y + (262.0 * systems.scale as f32).floor(),
-            )),
-            Color::rgba(200, 200, 200, 255),
+        let mut themes = Theme::list();
+        let mut theme_names: Vec<String> =
+            themes.iter().map(|id| Theme::load(id).name).collect();
+        let theme = option_index(
+            &mut theme_names,
+            &mut themes,
+            systems.theme.id.clone(),
+            systems.theme.name.clone(),
         );
-        let header_text = systems.gfx.add_text(
-            text,
-            1,
-            "Settings Header Text",
-            false,
-            CameraView::SubView1,
+        widgets.set_dropdown_options(systems, "theme", theme_names, theme);
+
+        let mut languages = Locale::list();
+        let mut language_names: Vec<String> =
+            languages.iter().map(|id| Locale::load(id).name).collect();
+        let language = option_index(
+            &mut language_names,
+            &mut languages,
+            systems.locale.id.clone(),
+            systems.locale.name.clone(),
         );
-        systems.gfx.set_text(&header_text, "Setting");
-        systems.gfx.center_text(&mut systems.renderer, &header_text);
-
-        let mut sfx_scroll = Scrollbar::new(
+        widgets.set_dropdown_options(
             systems,
-            Vec2::new(w_pos.x, w_pos.y),
-            Vec2::new(orig_size.x - 110.0, orig_size.y - 90.0),
-            100.0,
-            20.0,
-            false,
-            detail_1,
-            (0.0001, 4),
-            ScrollbarRect {
-                color: Color::rgba(70, 70, 70, 255),
-                render_layer: 0,
-                got_border: false,
-                border_color: Color::rgba(0, 0, 0, 0),
-                hover_color: Color::rgba(100, 100, 100, 255),
-                hold_color: Color::rgba(40, 40, 40, 255),
-                radius: 0.0,
-            },
-            Some(ScrollbarBackground {
-                color: Color::rgba(150, 150, 150, 255),
-                render_layer: 0,
-                got_border: false,
-                border_color: Color::rgba(0, 0, 0, 0),
-                radius: 0.0,
-            }),
-            100,
-            20.0,
-            false,
-            false,
-            None,
+            "language",
+            language_names,
+            language,
         );
-        sfx_scroll.set_value(systems, systems.config.sfx_volume as usize);
 
-        let mut bgm_scroll = Scrollbar::new(
+        let names = ClientWindowMode::ALL
+            .map(|data| systems.locale.get(data.text_key()).to_string())
+            .to_vec();
+        widgets.set_dropdown_options(
             systems,
-            Vec2::new(w_pos.x, w_pos.y),
-            Vec2::new(orig_size.x - 110.0, orig_size.y - 60.0),
-            100.0,
-            20.0,
-            false,
-            detail_1,
-            (0.0001, 4),
-            ScrollbarRect {
-                color: Color::rgba(70, 70, 70, 255),
-                render_layer: 0,
-                got_border: false,
-                border_color: Color::rgba(0, 0, 0, 0),
-                hover_color: Color::rgba(100, 100, 100, 255),
-                hold_color: Color::rgba(40, 40, 40, 255),
-                radius: 0.0,
-            },
-            Some(ScrollbarBackground {
-                color: Color::rgba(150, 150, 150, 255),
-                render_layer: 0,
-                got_border: false,
-                border_color: Color::rgba(0, 0, 0, 0),
-                radius: 0.0,
-            }),
-            100,
-            20.0,
-            false,
-            false,
-            None,
+            "window_mode",
+            names,
+            ClientWindowMode::ALL
+                .iter()
+                .position(|data| *data == systems.config.window_mode)
+                .unwrap_or_default(),
         );
-        bgm_scroll.set_value(systems, systems.config.bgm_volume as usize);
-
-        let mut button = Vec::with_capacity(1);
-        let close_button = Button::new(
+        let names = ClientPresentMode::ALL
+            .map(|data| systems.locale.get(data.text_key()).to_string())
+            .to_vec();
+        widgets.set_dropdown_options(
             systems,
-            ButtonType::Rect(ButtonRect {
-                rect_color: Color::rgba(70, 70, 70, 255),
-                got_border: false,
-                border_color: Color::rgba(0, 0, 0, 255),
-                border_radius: 0.0,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    50, 50, 50, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    150, 150, 150, 255,
-                )),
-            }),
-            ButtonContentType::Image(ButtonContentImg {
-                res: systems.resource.window_button_icon.allocation,
-                pos: Vec2::ZERO,
-                uv: Vec2::ZERO,
-                size: Vec2::new(20.0, 20.0),
-                hover_change: ButtonChangeType::None,
-                click_change: ButtonChangeType::None,
-            }),
-            Vec2::new(w_pos.x, w_pos.y),
-            Vec2::new(header_size.x - 25.0, 242.0),
-            detail_2,
-            (0.0001, 4),
-            Vec2::new(20.0, 20.0),
-            0,
-            false,
-            None,
-        );
-        button.push(close_button);
-
-        let mut label = Vec::with_capacity(2);
-        for i in 0..2 {
-            let (msg, ypos) = match i {
-                0 => ("BGM", w_size.y - (60.0 * systems.scale as f32).floor()),
-                _ => ("SFX", w_size.y - (90.0 * systems.scale as f32).floor()),
-            };
-            let tpos = Vec2::new(
-                w_pos.x + (10.0 * systems.scale as f32).floor(),
-                w_pos.y + ypos,
-            );
-            let text = create_label(
-                systems,
-                Vec3::new(tpos.x, tpos.y, detail_1),
-                (Vec2::new(100.0, 20.0) * systems.scale as f32).floor(),
-                Some(Bounds::new(
-                    tpos.x,
-                    tpos.y,
-                    tpos.x + (100.0 * systems.scale as f32).floor(),
-                    tpos.y + (20.0 * systems.scale as f32).floor(),
-                )),
-                Color::rgba(200, 200, 200, 255),
-            );
-            let label_index = systems.gfx.add_text(
-                text,
-                1,
-                "Settings Label",
-                false,
-                CameraView::SubView1,
-            );
-            systems.gfx.set_text(&label_index, msg);
-            label.push(label_index);
-        }
-
-        let tpos = Vec3::new(
-            w_pos.x + (50.0 * systems.scale as f32).floor(),
-            w_pos.y + w_size.y - (60.0 * systems.scale as f32).floor(),
-            detail_1,
+            "present_mode",
+            names,
+            ClientPresentMode::ALL
+                .iter()
+                .position(|data| *data == systems.config.present_mode)
+                .unwrap_or_default(),
         );
-        let tsize = (Vec2::new(50.0, 20.0) * systems.scale as f32).floor();
-        let slabel = create_label(
+        let names = ClientAdapterPowerSettings::ALL
+            .map(|data| systems.locale.get(data.text_key()).to_string())
+            .to_vec();
+        widgets.set_dropdown_options(
             systems,
-            tpos,
-            tsize,
-            Some(Bounds::new(
-                tpos.x,
-                tpos.y,
-                tpos.x + tsize.x,
-                tpos.y + tsize.y,
-            )),
-            Color::rgba(200, 200, 200, 255),
-        );
-        let bgm_label = systems.gfx.add_text(
-            slabel,
-            1,
-            "Settings BGM Label",
-            false,
-            CameraView::SubView1,
+            "power_settings",
+            names,
+            ClientAdapterPowerSettings::ALL
+                .iter()
+                .position(|data| *data == systems.config.power_settings)
+                .unwrap_or_default(),
         );
-        systems
-            .gfx
-            .set_text(&bgm_label, &format!("{}", systems.config.bgm_volume));
-
-        let tpos = Vec3::new(
-            w_pos.x + (50.0 * systems.scale as f32).floor(),
-            w_pos.y + w_size.y - (90.0 * systems.scale as f32).floor(),
-            detail_1,
-        );
-        let slabel = create_label(
+        let names = ClientGPUInstances::ALL
+            .map(|data| systems.locale.get(data.text_key()).to_string())
+            .to_vec();
+        widgets.set_dropdown_options(
             systems,
-            tpos,
-            tsize,
-            Some(Bounds::new(
-                tpos.x,
-                tpos.y,
-                tpos.x + tsize.x,
-                tpos.y + tsize.y,
-            )),
-            Color::rgba(200, 200, 200, 255),
-        );
-        let sfx_label = systems.gfx.add_text(
-            slabel,
-            1,
-            "Settings SFX Label",
-            false,
-            CameraView::SubView1,
+            "gpu_instance",
+            names,
+            ClientGPUInstances::ALL
+                .iter()
+                .position(|data| *data == systems.config.gpu_instance)
+                .unwrap_or_default(),
         );
-        systems
-            .gfx
-            .set_text(&sfx_label, &format!("{}", systems.config.sfx_volume));
-
-        let mut checkbox = vec![
-            Checkbox::new(
-                systems,
-                CheckboxType::Rect(CheckboxRect {
-                    rect_color: Color::rgba(100, 100, 100, 255),
-                    got_border: true,
-                    border_color: Color::rgba(50, 50, 50, 255),
-                    border_radius: 2.0,
-                    hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        140, 140, 140, 255,
-                    )),
-                    click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        70, 70, 70, 255,
-                    )),
-                }),
-                CheckType::SetRect(CheckRect {
-                    rect_color: Color::rgba(200, 200, 200, 255),
-                    got_border: false,
-                    border_color: Color::rgba(255, 255, 255, 255),
-                    border_radius: 2.0,
-                    pos: Vec2::new(5.0, 5.0),
-                    size: Vec2::new(14.0, 14.0),
-                }),
-                Vec2::new(w_pos.x, w_pos.y),
-                Vec2::new(10.0, orig_size.y - 130.0),
-                detail_1,
-                (0.0001, 4),
-                Vec2::new(24.0, 24.0),
-                0,
-                Some(CheckboxText {
-                    text: "Show FPS?".into(),
-                    offset_pos: Vec2::new(3.0, 2.0),
-                    render_layer: 1,
-                    label_size: Vec2::new(180.0, 20.0),
-                    color: Color::rgba(200, 200, 200, 255),
-                    hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        240, 240, 240, 255,
-                    )),
-                    click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        80, 80, 80, 255,
-                    )),
-                }),
-                false,
-                None,
-            ),
-            Checkbox::new(
-                systems,
-                CheckboxType::Rect(CheckboxRect {
-                    rect_color: Color::rgba(100, 100, 100, 255),
-                    got_border: true,
-                    border_color: Color::rgba(50, 50, 50, 255),
-                    border_radius: 2.0,
-                    hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        140, 140, 140, 255,
-                    )),
-                    click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        70, 70, 70, 255,
-                    )),
-                }),
-                CheckType::SetRect(CheckRect {
-                    rect_color: Color::rgba(200, 200, 200, 255),
-                    got_border: false,
-                    border_color: Color::rgba(255, 255, 255, 255),
-                    border_radius: 2.0,
-                    pos: Vec2::new(5.0, 5.0),
-                    size: Vec2::new(14.0, 14.0),
-                }),
-                Vec2::new(w_pos.x, w_pos.y),
-                Vec2::new(10.0, orig_size.y - 154.0),
-                detail_1,
-                (0.0001, 4),
-                Vec2::new(24.0, 24.0),
-                0,
-                Some(CheckboxText {
-                    text: "Show Ping?".into(),
-                    offset_pos: Vec2::new(3.0, 2.0),
-                    render_layer: 1,
-                    label_size: Vec2::new(180.0, 20.0),
-                    color: Color::rgba(200, 200, 200, 255),
-                    hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        240, 240, 240, 255,
-                    )),
-                    click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        80, 80, 80, 255,
-                    )),
-                }),
-                false,
-                None,
-            ),
-            Checkbox::new(
-                systems,
-                CheckboxType::Rect(CheckboxRect {
-                    rect_color: Color::rgba(100, 100, 100, 255),
-                    got_border: true,
-                    border_color: Color::rgba(50, 50, 50, 255),
-                    border_radius: 2.0,
-                    hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        140, 140, 140, 255,
-                    )),
-                    click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        70, 70, 70, 255,
-                    )),
-                }),
-                CheckType::SetRect(CheckRect {
-                    rect_color: Color::rgba(200, 200, 200, 255),
-                    got_border: false,
-                    border_color: Color::rgba(255, 255, 255, 255),
-                    border_radius: 2.0,
-                    pos: Vec2::new(5.0, 5.0),
-                    size: Vec2::new(14.0, 14.0),
-                }),
-                Vec2::new(w_pos.x, w_pos.y),
-                Vec2::new(10.0, orig_size.y - 178.0),
-                detail_1,
-                (0.0001, 4),
-                Vec2::new(24.0, 24.0),
-                0,
-                Some(CheckboxText {
-                    text: "Show Average Ping?".into(),
-                    offset_pos: Vec2::new(3.0, 2.0),
-                    render_layer: 1,
-                    label_size: Vec2::new(180.0, 20.0),
-                    color: Color::rgba(200, 200, 200, 255),
-                    hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        240, 240, 240, 255,
-                    )),
-                    click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        80, 80, 80, 255,
-                    )),
-                }),
-                false,
-                None,
-            ),
-            Checkbox::new(
-                systems,
-                CheckboxType::Rect(CheckboxRect {
-                    rect_color: Color::rgba(100, 100, 100, 255),
-                    got_border: true,
-                    border_color: Color::rgba(50, 50, 50, 255),
-                    border_radius: 2.0,
-                    hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        140, 140, 140, 255,
-                    )),
-                    click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        70, 70, 70, 255,
-                    )),
-                }),
-                CheckType::SetRect(CheckRect {
-                    rect_color: Color::rgba(200, 200, 200, 255),
-                    got_border: false,
-                    border_color: Color::rgba(255, 255, 255, 255),
-                    border_radius: 2.0,
-                    pos: Vec2::new(5.0, 5.0),
-                    size: Vec2::new(14.0, 14.0),
-                }),
-                Vec2::new(w_pos.x, w_pos.y),
-                Vec2::new(10.0, orig_size.y - 202.0),
-                detail_1,
-                (0.0001, 4),
-                Vec2::new(24.0, 24.0),
-                0,
-                Some(CheckboxText {
-                    text: "Show Frame Jitter?".into(),
-                    offset_pos: Vec2::new(3.0, 2.0),
-                    render_layer: 1,
-                    label_size: Vec2::new(180.0, 20.0),
-                    color: Color::rgba(200, 200, 200, 255),
-                    hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        240, 240, 240, 255,
-                    )),
-                    click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                        80, 80, 80, 255,
-                    )),
-                }),
-                false,
-                None,
-            ),
-        ];
-        checkbox[0].set_value(systems, systems.config.show_fps);
-        checkbox[1].set_value(systems, systems.config.show_ping);
-        checkbox[2].set_value(systems, systems.config.show_average_ping);
-        checkbox[3].set_value(systems, systems.config.show_frame_loop);
 
-        Setting {
-            visible: false,
-            bg,
-            header,
-            header_text,
-            sfx_scroll,
-            bgm_scroll,
-            button,
-            checkbox,
-            label,
-            bgm_label,
-            sfx_label,
-
-            pos,
-            size: w_size,
-            z_order: 0.0,
-            order_index: 0,
-            in_hold: false,
-            hold_pos: Vec2::ZERO,
-            header_pos,
-            header_size,
-            did_button_click: false,
-            did_checkbox_click: false,
-
-            min_bound: Vec2::new(
-                systems.size.width - w_size.x - 1.0,
-                systems.size.height - w_size.y - 1.0,
-            ),
-            max_bound: Vec2::new(1.0, 1.0),
+        for (id, value) in [
+            ("show_fps", systems.config.show_fps),
+            ("show_ping", systems.config.show_ping),
+            ("show_average_ping", systems.config.show_average_ping),
+            ("show_frame_loop", systems.config.show_frame_loop),
+            ("show_move_ghosts", systems.config.show_move_ghosts),
+            ("show_npc_ranges", systems.config.show_npc_ranges),
+        ] {
+            widgets.set_checkbox_value(systems, id, value);
         }
-    }
 
-    pub fn unload(&mut self, systems: &mut SystemHolder) {
-        systems.gfx.remove_gfx(&mut systems.renderer, &self.bg);
-        systems.gfx.remove_gfx(&mut systems.renderer, &self.header);
-        systems
-            .gfx
-            .remove_gfx(&mut systems.renderer, &self.header_text);
-        self.sfx_scroll.unload(systems);
-        self.bgm_scroll.unload(systems);
-        self.button.iter_mut().for_each(|button| {
-            button.unload(systems);
-        });
-        self.checkbox.iter_mut().for_each(|checkbox| {
-            checkbox.unload(systems);
-        });
-        self.label.iter().for_each(|text| {
-            systems.gfx.remove_gfx(&mut systems.renderer, text);
-        });
-        self.button.clear();
-        systems
-            .gfx
-            .remove_gfx(&mut systems.renderer, &self.bgm_label);
-        systems
-            .gfx
-            .remove_gfx(&mut systems.renderer, &self.sfx_label);
-    }
-
-    pub fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
-        if self.visible == visible {
-            return;
-        }
-        self.visible = visible;
-        self.z_order = 0.0;
-        systems.gfx.set_visible(&self.bg, visible);
-        systems.gfx.set_visible(&self.header, visible);
-        systems.gfx.set_visible(&self.header_text, visible);
-        self.sfx_scroll.set_visible(systems, visible);
-        self.bgm_scroll.set_visible(systems, visible);
-        self.button.iter_mut().for_each(|button| {
-            button.set_visible(systems, visible);
-        });
-        self.checkbox.iter_mut().for_each(|checkbox| {
-            checkbox.set_visible(systems, visible);
-        });
-        self.label.iter().for_each(|text| {
-            systems.gfx.set_visible(text, visible);
-        });
-        systems.gfx.set_visible(&self.bgm_label, visible);
-        systems.gfx.set_visible(&self.sfx_label, visible);
-    }
-
-    pub fn can_hold(&mut self, screen_pos: Vec2) -> bool {
-        if !self.visible {
-            return false;
+        Setting {
+            frame: WindowFrame::new(systems, pos, w_size, 1.0),
+            widgets,
+            sfx_slider,
+            bgm_slider,
+            ui_scale_slider,
+            graphic_backends,
+            fps_limits,
+            themes,
+            languages,
         }
-        is_within_area(screen_pos, self.header_pos, self.header_size)
     }
 
-    pub fn in_window(&mut self, screen_pos: Vec2) -> bool {
-        if !self.visible {
-            return false;
-        }
-        is_within_area(screen_pos, self.pos, self.size)
+    pub fn sfx_slider(&mut self) -> &mut Slider {
+        &mut self.widgets.slider[self.sfx_slider]
     }
 
-    pub fn hold_window(&mut self, screen_pos: Vec2) {
-        if self.in_hold {
-            return;
-        }
-        self.in_hold = true;
-        self.hold_pos = screen_pos - self.pos;
+    pub fn bgm_slider(&mut self) -> &mut Slider {
+        &mut self.widgets.slider[self.bgm_slider]
     }
 
-    pub fn release_window(&mut self) {
-        self.in_hold = false;
+    pub fn ui_scale_slider(&mut self) -> &mut Slider {
+        &mut self.widgets.slider[self.ui_scale_slider]
     }
 
-    pub fn set_z_order(
-        &mut self,
-        systems: &mut SystemHolder,
-        z_order: f32,
-        order_index: usize,
-    ) {
-        if self.z_order == z_order {
-            return;
-        }
-        self.z_order = z_order;
-        self.order_index = order_index;
-
-        let detail_origin = ORDER_GUI_WINDOW.sub_f32(self.z_order, 3);
-        let detail_1 = detail_origin.sub_f32(0.001, 3);
-        let detail_2 = detail_origin.sub_f32(0.002, 3);
-
-        let mut pos = systems.gfx.get_pos(&self.bg);
-        pos.z = detail_origin;
-        systems.gfx.set_pos(&self.bg, pos);
-
-        let mut pos = systems.gfx.get_pos(&self.header);
-        let header_zpos = detail_1;
-        pos.z = header_zpos;
-        systems.gfx.set_pos(&self.header, pos);
-
-        let mut pos = systems.gfx.get_pos(&self.header_text);
-        pos.z = detail_2;
-        systems.gfx.set_pos(&self.header_text, pos);
-
-        self.sfx_scroll.set_z_order(systems, detail_1);
-        self.bgm_scroll.set_z_order(systems, detail_1);
-
-        self.button.iter_mut().for_each(|button| {
-            button.set_z_order(systems, detail_2);
-        });
-
-        self.checkbox.iter_mut().for_each(|checkbox| {
-            checkbox.set_z_order(systems, detail_1);
-        });
+    /// UI scale in percent the slider currently points at.
+    pub fn ui_scale_value(&mut self) -> u16 {
+        let value = self.ui_scale_slider().value() as u16;
+        value.clamp(UI_SCALE_MIN, UI_SCALE_MAX)
+    }
 
-        self.label.iter().for_each(|text| {
-            let mut pos = systems.gfx.get_pos(text);
-            pos.z = detail_1;
-            systems.gfx.set_pos(text, pos);
-        });
+    pub fn button_id(&self, index: usize) -> &str {
+        self.widgets.button_id(index)
+    }
 
-        let mut pos = systems.gfx.get_pos(&self.bgm_label);
-        pos.z = detail_1;
-        systems.gfx.set_pos(&self.bgm_label, pos);
+    pub fn hover_tab(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        self.widgets.hover_tab(systems, screen_pos);
+    }
 
-        let mut pos = systems.gfx.get_pos(&self.sfx_label);
-        pos.z = detail_1;
-        systems.gfx.set_pos(&self.sfx_label, pos);
+    pub fn click_tab(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> bool {
+        self.widgets.click_tab(systems, screen_pos)
     }
 
-    pub fn move_window(
+    pub fn hold_slider(
         &mut self,
         systems: &mut SystemHolder,
         screen_pos: Vec2,
     ) {
-        if !self.in_hold {
-            return;
+        if let Some(slider) = self
+            .widgets
+            .slider
+            .iter_mut()
+            .find(|slider| slider.in_slider(screen_pos))
+        {
+            slider.set_hold(systems, true, screen_pos);
         }
-        self.pos = (screen_pos - self.hold_pos)
-            .max(self.max_bound)
-            .min(self.min_bound);
-
-        let pos = systems.gfx.get_pos(&self.bg);
-        systems.gfx.set_pos(
-            &self.bg,
-            Vec3::new(self.pos.x - 1.0, self.pos.y - 1.0, pos.z),
-        );
-        let pos = systems.gfx.get_pos(&self.header);
-        self.header_pos = Vec2::new(self.pos.x, self.pos.y + 237.0);
-        systems.gfx.set_pos(
-            &self.header,
-            Vec3::new(
-                self.pos.x,
-                self.pos.y + (237.0 * systems.scale as f32).floor(),
-                pos.z,
-            ),
-        );
-        let pos = systems.gfx.get_pos(&self.header_text);
-        systems.gfx.set_pos(
-            &self.header_text,
-            Vec3::new(
-                self.pos.x,
-                self.pos.y + (242.0 * systems.scale as f32).floor(),
-                pos.z,
-            ),
-        );
-        systems.gfx.set_bound(
-            &self.header_text,
-            Some(Bounds::new(
-                self.pos.x,
-                self.pos.y + (242.0 * systems.scale as f32).floor(),
-                self.pos.x + self.size.x,
-                self.pos.y + (262.0 * systems.scale as f32).floor(),
-            )),
-        );
-        systems
-            .gfx
-            .center_text(&mut systems.renderer, &self.header_text);
-
-        self.button.iter_mut().for_each(|button| {
-            button.set_pos(systems, self.pos);
-        });
-
-        self.checkbox.iter_mut().for_each(|checkbox| {
-            checkbox.set_pos(systems, self.pos);
-        });
-
-        self.sfx_scroll.set_pos(systems, self.pos);
-        self.bgm_scroll.set_pos(systems, self.pos);
-
-        self.label.iter().enumerate().for_each(|(index, text)| {
-            let ypos = match index {
-                0 => self.size.y - (60.0 * systems.scale as f32).floor(),
-                _ => self.size.y - (90.0 * systems.scale as f32).floor(),
-            };
-            let tpos = Vec2::new(
-                self.pos.x + (10.0 * systems.scale as f32).floor(),
-                self.pos.y + ypos,
-            );
-
-            let pos = systems.gfx.get_pos(text);
-            systems.gfx.set_pos(text, Vec3::new(tpos.x, tpos.y, pos.z));
-            systems.gfx.set_bound(
-                text,
-                Some(Bounds::new(
-                    tpos.x,
-                    tpos.y,
-                    tpos.x + (100.0 * systems.scale as f32).floor(),
-                    tpos.y + (20.0 * systems.scale as f32).floor(),
-                )),
-            );
-        });
-
-        let tpos = Vec2::new(
-            self.pos.x + (50.0 * systems.scale as f32).floor(),
-            self.pos.y + self.size.y - (60.0 * systems.scale as f32).floor(),
-        );
-        let tsize = (Vec2::new(50.0, 20.0) * systems.scale as f32).floor();
-        let pos = systems.gfx.get_pos(&self.bgm_label);
-        systems
-            .gfx
-            .set_pos(&self.bgm_label, Vec3::new(tpos.x, tpos.y, pos.z));
-        systems.gfx.set_bound(
-            &self.bgm_label,
-            Some(Bounds::new(
-                tpos.x,
-                tpos.y,
-                tpos.x + tsize.x,
-                tpos.y + tsize.y,
-            )),
-        );
-
-        let tpos = Vec2::new(
-            self.pos.x + (50.0 * systems.scale as f32).floor(),
-            self.pos.y + self.size.y - (90.0 * systems.scale as f32).floor(),
-        );
-        let pos = systems.gfx.get_pos(&self.sfx_label);
-        systems
-            .gfx
-            .set_pos(&self.sfx_label, Vec3::new(tpos.x, tpos.y, pos.z));
-        systems.gfx.set_bound(
-            &self.sfx_label,
-            Some(Bounds::new(
-                tpos.x,
-                tpos.y,
-                tpos.x + tsize.x,
-                tpos.y + tsize.y,
-            )),
-        );
     }
 
-    pub fn hover_scrollbar(
+    pub fn hover_slider(
         &mut self,
         systems: &mut SystemHolder,
         screen_pos: Vec2,
     ) {
-        if !self.visible || self.order_index != 0 {
+        if !self.frame.visible || self.frame.order_index != 0 {
             return;
         }
 
-        if self.sfx_scroll.in_scroll(screen_pos) {
-            self.sfx_scroll.set_hover(systems, true);
-        } else {
-            self.sfx_scroll.set_hover(systems, false);
-        }
-        if self.bgm_scroll.in_scroll(screen_pos) {
-            self.bgm_scroll.set_hover(systems, true);
-        } else {
-            self.bgm_scroll.set_hover(systems, false);
-        }
+        self.widgets.hover_slider(systems, screen_pos);
     }
 
-    pub fn hover_buttons(
+    pub fn hover_checkbox(
         &mut self,
         systems: &mut SystemHolder,
+        tooltip: &mut Tooltip,
         screen_pos: Vec2,
     ) {
-        if !self.visible {
-            return;
-        }
-
-        for button in self.button.iter_mut() {
-            if is_within_area(
-                screen_pos,
-                Vec2::new(
-                    button.base_pos.x
-                        + (button.adjust_pos.x * systems.scale as f32).floor(),
-                    button.base_pos.y
-                        + (button.adjust_pos.y * systems.scale as f32).floor(),
-                ),
-                (button.size * systems.scale as f32).floor(),
-            ) {
-                button.set_hover(systems, true);
-            } else {
-                button.set_hover(systems, false);
-            }
-        }
+        self.widgets.hover_checkbox(systems, tooltip, screen_pos);
     }
 
-    pub fn click_buttons(
+    pub fn click_checkbox(
         &mut self,
         systems: &mut SystemHolder,
         screen_pos: Vec2,
     ) -> Option<usize> {
-        if !self.visible {
-            return None;
-        }
+        let index = self.widgets.click_checkbox(systems, screen_pos)?;
+        self.widgets.did_checkbox_click = true;
+        Some(index)
+    }
 
-        let mut button_found = None;
-        for (index, button) in self.button.iter_mut().enumerate() {
-            if is_within_area(
-                screen_pos,
-                Vec2::new(
-                    button.base_pos.x
-                        + (button.adjust_pos.x * systems.scale as f32).floor(),
-                    button.base_pos.y
-                        + (button.adjust_pos.y * systems.scale as f32).floor(),
-                ),
-                (button.size * systems.scale as f32).floor(),
-            ) {
-                button.set_click(systems, true);
-                button_found = Some(index)
+    /// Saves the value of the checkbox at `index`, the interface shows or
+    /// hides its statistics afterwards.
+    pub fn trigger_checkbox(
+        &mut self,
+        systems: &mut SystemHolder,
+        index: usize,
+    ) {
+        let value = self.widgets.checkbox[index].value;
+
+        match self.widgets.checkbox_id(index) {
+            "show_fps" => {
+                systems.config.show_fps = value;
+                systems
+                    .gfx
+                    .set_visible(&systems.fps, systems.config.show_fps);
             }
+            "show_ping" => systems.config.show_ping = value,
+            "show_average_ping" => systems.config.show_average_ping = value,
+            "show_frame_loop" => systems.config.show_frame_loop = value,
+            "show_move_ghosts" => {
+                systems.config.show_move_ghosts = value;
+            }
+            "show_npc_ranges" => {
+                systems.config.show_npc_ranges = value;
+            }
+            _ => return,
         }
-        button_found
-    }
 
-    pub fn reset_buttons(&mut self, systems: &mut SystemHolder) {
-        if !self.did_button_click || !self.visible {
-            return;
-        }
-        self.did_button_click = false;
+        systems.config.save_config("settings.toml");
+    }
 
-        self.button.iter_mut().for_each(|button| {
-            button.set_click(systems, false);
-        });
+    pub fn reset_checkbox(&mut self, systems: &mut SystemHolder) {
+        self.widgets.reset_checkbox(systems);
     }
 
-    pub fn hover_checkbox(
+    pub fn hover_dropdown(
         &mut self,
         systems: &mut SystemHolder,
         tooltip: &mut Tooltip,
         screen_pos: Vec2,
     ) {
-        for checkbox in self.checkbox.iter_mut() {
-            if is_within_area(
-                screen_pos,
-                Vec2::new(
-                    checkbox.base_pos.x
-                        + (checkbox.adjust_pos.x * systems.scale as f32)
-                            .floor(),
-                    checkbox.base_pos.y
-                        + (checkbox.adjust_pos.y * systems.scale as f32)
-                            .floor(),
-                ),
-                (Vec2::new(
-                    checkbox.box_size.x + checkbox.adjust_x,
-                    checkbox.box_size.y,
-                ) * systems.scale as f32)
-                    .floor(),
-            ) {
-                checkbox.set_hover(systems, true);
-
-                if let Some(msg) = &checkbox.tooltip {
-                    tooltip.init_tooltip(systems, screen_pos, msg.as_ref());
-                }
-            } else {
-                checkbox.set_hover(systems, false);
-            }
+        if !self.frame.visible {
+            return;
         }
+
+        self.widgets.hover_dropdown(systems, tooltip, screen_pos);
     }
 
-    pub fn click_checkbox(
+    pub fn dropdown_open(&self) -> bool {
+        self.widgets.dropdown_open()
+    }
+
+    pub fn close_dropdowns(&mut self, systems: &mut SystemHolder) {
+        self.widgets.close_dropdowns(systems);
+    }
+
+    /// Opens a dropdown or picks from the open list, the picked value is
+    /// applied right away unless it needs a restart and is saved. Returns
+    /// true when the interface has to be rebuilt for it.
+    pub fn click_dropdown(
         &mut self,
         systems: &mut SystemHolder,
         screen_pos: Vec2,
-    ) -> Option<usize> {
-        let mut checkbox_found = None;
-        for (index, checkbox) in self.checkbox.iter_mut().enumerate() {
-            if is_within_area(
-                screen_pos,
-                Vec2::new(
-                    checkbox.base_pos.x
-                        + (checkbox.adjust_pos.x * systems.scale as f32)
-                            .floor(),
-                    checkbox.base_pos.y
-                        + (checkbox.adjust_pos.y * systems.scale as f32)
-                            .floor(),
-                ),
-                (Vec2::new(
-                    checkbox.box_size.x + checkbox.adjust_x,
-                    checkbox.box_size.y,
-                ) * systems.scale as f32)
-                    .floor(),
-            ) {
-                checkbox.set_click(systems, true);
-                checkbox_found = Some(index)
+    ) -> bool {
+        let Some(index) = self.widgets.click_dropdown(systems, screen_pos)
+        else {
+            return false;
+        };
+        let selected = self.widgets.dropdown[index].selected;
+        let config = &mut systems.config;
+        let mut rebuild = false;
+
+        match self.widgets.dropdown_id(index) {
+            "theme" => {
+                let Some(id) = self.themes.get(selected) else {
+                    return false;
+                };
+                if *id == systems.theme.id {
+                    return false;
+                }
+
+                systems.theme = Theme::load(id);
+                systems.theme.apply_font(&mut systems.renderer.font_sys);
+                config.theme = id.clone();
+                rebuild = true;
             }
-        }
-        checkbox_found
-    }
+            "language" => {
+                let Some(id) = self.languages.get(selected) else {
+                    return false;
+                };
+                if *id == systems.locale.id {
+                    return false;
+                }
 
-    pub fn trigger_checkbox(
-        &mut self,
-        systems: &mut SystemHolder,
-        index: usize,
-        ping_index: &GfxType,
-        average_ping_index: &GfxType,
-        frame_jitter_index: &GfxType,
-    ) {
-        #[allow(clippy::single_match)]
-        match index {
-            0 => {
-                systems.config.show_fps = self.checkbox[index].value;
-                systems
-                    .gfx
-                    .set_visible(&systems.fps, systems.config.show_fps);
-                systems.config.save_config("settings.toml");
+                systems.locale = Locale::load(id);
+                config.language = id.clone();
+                rebuild = true;
             }
-            1 => {
-                systems.config.show_ping = self.checkbox[index].value;
-                systems
-                    .gfx
-                    .set_visible(ping_index, systems.config.show_ping);
-                systems.config.save_config("settings.toml");
+            "window_mode" => {
+                let Some(mode) = ClientWindowMode::ALL.get(selected) else {
+                    return false;
+                };
+                let window = systems.renderer.window();
+
+                config.window_mode = *mode;
+                window
+                    .set_fullscreen(mode.fullscreen(window.current_monitor()));
             }
-            2 => {
-                systems.config.show_average_ping = self.checkbox[index].value;
-                systems.gfx.set_visible(
-                    average_ping_index,
-                    systems.config.show_average_ping,
-                );
-                systems.config.save_config("settings.toml");
+            "present_mode" => {
+                let Some(mode) = ClientPresentMode::ALL.get(selected) else {
+                    return false;
+                };
+                config.present_mode = *mode;
             }
-            3 => {
-                systems.config.show_frame_loop = self.checkbox[index].value;
-                systems.gfx.set_visible(
-                    frame_jitter_index,
-                    systems.config.show_frame_loop,
-                );
-                systems.config.save_config("settings.toml");
+            "fps_limit" => {
+                let Some(limit) = self.fps_limits.get(selected) else {
+                    return false;
+                };
+                config.fps_limit = *limit;
+            }
+            "graphic_backend" => {
+                let Some(backend) = self.graphic_backends.get(selected) else {
+                    return false;
+                };
+                config.graphic_backend = backend.clone();
+            }
+            "power_settings" => {
+                let Some(power) = ClientAdapterPowerSettings::ALL.get(selected)
+                else {
+                    return false;
+                };
+                config.power_settings = *power;
             }
-            _ => {}
+            "gpu_instance" => {
+                let Some(flags) = ClientGPUInstances::ALL.get(selected) else {
+                    return false;
+                };
+                config.gpu_instance = *flags;
+            }
+            _ => return false,
         }
+
+        config.save_config("settings.toml");
+        rebuild
+    }
+}
+
+impl GameWindow for Setting {
+    fn id(&self) -> Window {
+        Window::Setting
     }
 
-    pub fn reset_checkbox(&mut self, systems: &mut SystemHolder) {
-        if !self.did_checkbox_click {
+    fn name(&self) -> &'static str {
+        "setting"
+    }
+
+    fn frame(&self) -> &WindowFrame {
+        &self.frame
+    }
+
+    fn frame_mut(&mut self) -> &mut WindowFrame {
+        &mut self.frame
+    }
+
+    fn buttons(&mut self) -> &mut [Button] {
+        &mut self.widgets.button
+    }
+
+    fn unload(&mut self, systems: &mut SystemHolder) {
+        self.widgets.unload(systems);
+    }
+
+    fn recreate(&mut self, systems: &mut SystemHolder) {
+        *self = Setting::new(systems);
+    }
+
+    fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        if self.frame.visible == visible {
             return;
         }
-        self.did_checkbox_click = false;
+        self.frame.visible = visible;
+        self.frame.z_order = 0.0;
+        self.widgets.set_visible(systems, visible);
+    }
+
+    fn can_hold(&self, systems: &SystemHolder, screen_pos: Vec2) -> bool {
+        if !self.frame.visible {
+            return false;
+        }
+
+        let (header_pos, header_size) =
+            self.widgets.header_area(systems, self.frame.pos);
+
+        is_within_area(screen_pos, header_pos, header_size)
+    }
 
-        self.checkbox.iter_mut().for_each(|checkbox| {
-            checkbox.set_click(systems, false);
-        });
+    fn update_pos(&mut self, systems: &mut SystemHolder) {
+        self.widgets.set_pos(systems, self.frame.pos);
     }
 
-    pub fn update_bgm_value(
+    fn update_z_order(
         &mut self,
         systems: &mut SystemHolder,
-        value: usize,
+        detail_origin: f32,
     ) {
-        systems.gfx.set_text(&self.bgm_label, &format!("{value}"));
+        self.widgets.set_z_order(systems, detail_origin);
+    }
+    fn restores_visibility(&self) -> bool {
+        true
+    }
+
+    fn popup_open(&self) -> bool {
+        self.dropdown_open()
     }
 
-    pub fn update_sfx_value(
+    fn close_popup(&mut self, systems: &mut SystemHolder) {
+        self.close_dropdowns(systems);
+    }
+
+    fn trigger_button(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        index: usize,
+    ) -> Result<WindowAction> {
+        Ok(match self.button_id(index) {
+            "close" => WindowAction::Close,
+            "reset_layout" => WindowAction::ResetLayout,
+            _ => WindowAction::None,
+        })
+    }
+
+    fn hover_content(
         &mut self,
         systems: &mut SystemHolder,
-        value: usize,
+        tooltip: &mut Tooltip,
+        _item_desc: &mut ItemDescription,
+        screen_pos: Vec2,
     ) {
-        systems.gfx.set_text(&self.sfx_label, &format!("{value}"));
+        self.hover_tab(systems, screen_pos);
+        self.hover_slider(systems, screen_pos);
+        self.hover_checkbox(systems, tooltip, screen_pos);
+        self.hover_dropdown(systems, tooltip, screen_pos);
+    }
+
+    fn click_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        _alert: &mut Alert,
+        screen_pos: Vec2,
+    ) -> Result<WindowAction> {
+        // An open dropdown list takes the whole click.
+        let was_open = self.dropdown_open();
+        if self.click_dropdown(systems, screen_pos) {
+            return Ok(WindowAction::Rebuild);
+        }
+        if was_open {
+            return Ok(WindowAction::None);
+        }
+
+        self.click_tab(systems, screen_pos);
+        self.hold_slider(systems, screen_pos);
+
+        if let Some(index) = self.click_checkbox(systems, screen_pos) {
+            self.trigger_checkbox(systems, index);
+            return Ok(WindowAction::ShowStatistics);
+        }
+
+        Ok(WindowAction::None)
+    }
+
+    fn move_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> bool {
+        if !self.frame.visible {
+            return false;
+        }
+
+        if self.bgm_slider().set_move(systems, screen_pos) {
+            let volume = self.bgm_slider().value() as f32 * 0.01;
+            systems.audio.set_music_volume(volume);
+        }
+
+        if self.sfx_slider().set_move(systems, screen_pos) {
+            let volume = self.sfx_slider().value() as f32 * 0.01;
+            systems.audio.set_effect_volume(volume);
+        }
+
+        self.ui_scale_slider().set_move(systems, screen_pos);
+
+        self.bgm_slider().in_hold()
+            || self.sfx_slider().in_hold()
+            || self.ui_scale_slider().in_hold()
+    }
+
+    /// Saves the value of the released slider, a new UI scale needs the
+    /// interface rebuilt.
+    fn release_content(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> WindowAction {
+        let mut action = WindowAction::None;
+
+        if self.frame.visible {
+            if self.bgm_slider().in_hold() {
+                systems.config.bgm_volume = self.bgm_slider().value() as u8;
+                systems.config.save_config("settings.toml");
+            } else if self.sfx_slider().in_hold() {
+                systems.config.sfx_volume = self.sfx_slider().value() as u8;
+                systems.config.save_config("settings.toml");
+            } else if self.ui_scale_slider().in_hold() {
+                let ui_scale = self.ui_scale_value();
+                if ui_scale != systems.config.ui_scale {
+                    systems.config.ui_scale = ui_scale;
+                    systems.config.save_config("settings.toml");
+                    action = WindowAction::Rebuild;
+                }
+            }
+
+            self.sfx_slider().set_hold(systems, false, screen_pos);
+            self.bgm_slider().set_hold(systems, false, screen_pos);
+            self.ui_scale_slider().set_hold(systems, false, screen_pos);
+        }
+
+        self.reset_checkbox(systems);
+        action
     }
 }
@@ -0,0 +1,424 @@
+use ascending_graphics::*;
+
+use crate::{
+    Entity, GlobalKey, Position, SystemHolder, Vitals, World, combat_name,
+    data_types::*, get_percent, target_distance, widget::*,
+};
+
+const TARGET_FRAME_LAYOUT: &str =
+    include_str!("../../../../data/ui/target_frame.toml");
+
+/// Widgets the target frame places its parts on, a layout file without
+/// them is replaced by the built in one.
+pub const TARGET_FRAME_WIDGETS: &[(LayoutKind, &str)] = &[
+    (LayoutKind::Rect, "portrait"),
+    (LayoutKind::Rect, "hp"),
+    (LayoutKind::Rect, "mp"),
+    (LayoutKind::Rect, "sp"),
+    (LayoutKind::Label, "name"),
+    (LayoutKind::Label, "level"),
+    (LayoutKind::Label, "distance"),
+    (LayoutKind::Label, "of_target"),
+];
+
+/// Width of the VitalBar, the layout offset is measured from its right edge.
+const VITAL_BAR_WIDTH: f32 = 200.0;
+/// Size of one frame of an entity's sprite sheet, shown as the portrait.
+const PORTRAIT_SIZE: f32 = 40.0;
+
+/// Level, position and vitals of a player or NPC.
+fn entity_state(
+    world: &World,
+    entity: GlobalKey,
+) -> Option<(i32, Position, Vitals)> {
+    match world.entities.get(entity)? {
+        Entity::Player(p_data) => {
+            Some((p_data.level, p_data.pos, p_data.vitals))
+        }
+        Entity::Npc(n_data) => Some((n_data.level, n_data.pos, n_data.vitals)),
+        _ => None,
+    }
+}
+
+/// Name, level, portrait, vitals and distance of the current target and
+/// the name of whoever it targets in turn.
+pub struct TargetFrame {
+    bg: GfxType,
+    portrait_bg: GfxType,
+    portrait: Option<GfxType>,
+    portrait_offset: Vec2,
+    portrait_size: Vec2,
+    name: GfxType,
+    level: GfxType,
+    distance: GfxType,
+    of_target: GfxType,
+    bar_bg: [GfxType; 3],
+    bar: [GfxType; 3],
+    bar_size: [f32; 3],
+    pos: Vec2,
+    /// Entity the frame shows, None while it is hidden.
+    pub entity: Option<GlobalKey>,
+    /// Who `entity` targets, as the server last told us.
+    target_of_target: Option<GlobalKey>,
+    shown_level: Option<i32>,
+    shown_distance: Option<Option<i32>>,
+    shown_of_target: Option<String>,
+}
+
+impl TargetFrame {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        let layout = UiLayout::load(
+            "target_frame",
+            TARGET_FRAME_LAYOUT,
+            TARGET_FRAME_WIDGETS,
+        );
+        let style = systems.theme.hud;
+        let scale = systems.scale as f32;
+        let size = layout.window.scaled_size(systems);
+        let pos = layout.window.default_pos(systems)
+            + Vec2::new((VITAL_BAR_WIDTH * scale).floor(), 0.0);
+        let scaled = |(offset, size): (Vec2, Vec2)| {
+            (pos + (offset * scale).floor(), (size * scale).floor())
+        };
+
+        let mut bg_rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(pos.x - 1.0, pos.y - 1.0, ORDER_VITAL_BG),
+            Vec2::new(size.x + 2.0, size.y + 2.0),
+            style.color,
+            0,
+        );
+        bg_rect
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(style.border_color);
+        let bg = systems.gfx.add_rect(
+            bg_rect,
+            0,
+            "Target Frame",
+            false,
+            CameraView::SubView1,
+        );
+
+        let portrait_area =
+            scaled(layout.rect_area("portrait").unwrap_or_default());
+        let mut portrait_rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(portrait_area.0.x, portrait_area.0.y, ORDER_VITAL_HPBG),
+            portrait_area.1,
+            style.bar_background,
+            0,
+        );
+        portrait_rect
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(style.bar_border_color);
+        let portrait_bg = systems.gfx.add_rect(
+            portrait_rect,
+            0,
+            "Target Portrait BG",
+            false,
+            CameraView::SubView1,
+        );
+
+        let mut labels = [GfxType::default(); 4];
+        for (index, (id, color)) in [
+            ("name", style.text),
+            ("level", style.detail_text),
+            ("distance", style.detail_text),
+            ("of_target", style.map_text),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let (label_pos, label_size) =
+                scaled(layout.label_area(id).unwrap_or_default());
+            let label = create_label(
+                systems,
+                Vec3::new(label_pos.x, label_pos.y, ORDER_VITAL_HP),
+                label_size,
+                Some(Bounds::new(
+                    label_pos.x,
+                    label_pos.y,
+                    label_pos.x + label_size.x,
+                    label_pos.y + label_size.y,
+                )),
+                color,
+            );
+
+            labels[index] = systems.gfx.add_text(
+                label,
+                1,
+                "Target Frame Text",
+                false,
+                CameraView::SubView1,
+            );
+        }
+        let [name, level, distance, of_target] = labels;
+
+        let mut bar_bg = [GfxType::default(); 3];
+        let mut bar = [GfxType::default(); 3];
+        let mut bar_size = [0.0; 3];
+        for (i, (id, color)) in
+            [("hp", style.hp), ("mp", style.mp), ("sp", style.sp)]
+                .into_iter()
+                .enumerate()
+        {
+            let (bar_pos, size) =
+                scaled(layout.rect_area(id).unwrap_or_default());
+
+            let mut bg_rect = Rect::new(
+                &mut systems.renderer,
+                Vec3::new(bar_pos.x, bar_pos.y, ORDER_VITAL_HPBG),
+                size,
+                style.bar_background,
+                0,
+            );
+            bg_rect
+                .set_border_width(systems.theme.border_width)
+                .set_border_color(style.bar_border_color);
+            bar_bg[i] = systems.gfx.add_rect(
+                bg_rect,
+                0,
+                "Target Vital BG",
+                false,
+                CameraView::SubView1,
+            );
+
+            bar_size[i] = size.x - 2.0;
+            let bar_rect = Rect::new(
+                &mut systems.renderer,
+                Vec3::new(bar_pos.x + 1.0, bar_pos.y + 1.0, ORDER_VITAL_HP),
+                size - Vec2::splat(2.0),
+                color,
+                0,
+            );
+            bar[i] = systems.gfx.add_rect(
+                bar_rect,
+                0,
+                "Target Vital Bar",
+                false,
+                CameraView::SubView1,
+            );
+        }
+
+        TargetFrame {
+            bg,
+            portrait_bg,
+            portrait: None,
+            portrait_offset: portrait_area.0 - pos,
+            portrait_size: portrait_area.1,
+            name,
+            level,
+            distance,
+            of_target,
+            bar_bg,
+            bar,
+            bar_size,
+            pos,
+            entity: None,
+            target_of_target: None,
+            shown_level: None,
+            shown_distance: None,
+            shown_of_target: None,
+        }
+    }
+
+    fn gfx(&self) -> impl Iterator<Item = GfxType> + use<> {
+        [
+            self.bg,
+            self.portrait_bg,
+            self.name,
+            self.level,
+            self.distance,
+        ]
+        .into_iter()
+        .chain(self.bar_bg)
+        .chain(self.bar)
+        .chain(self.portrait)
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        for gfx in self.gfx().chain([self.of_target]) {
+            systems.gfx.remove_gfx(&mut systems.renderer, &gfx);
+        }
+    }
+
+    pub fn move_by(&mut self, systems: &mut SystemHolder, offset: Vec2) {
+        for gfx in self.gfx().chain([self.of_target]) {
+            systems.gfx.move_by(&gfx, offset);
+        }
+        self.pos += offset;
+    }
+
+    fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        for gfx in self.gfx() {
+            systems.gfx.set_visible(&gfx, visible);
+        }
+        systems.gfx.set_visible(
+            &self.of_target,
+            visible && self.shown_of_target.is_some(),
+        );
+    }
+
+    /// Follows the current `target`, vitals come from `set_vitals` as the
+    /// server sends them.
+    pub fn update(
+        &mut self,
+        world: &World,
+        systems: &mut SystemHolder,
+        target: Option<GlobalKey>,
+        myentity: Option<GlobalKey>,
+    ) {
+        if self.entity != target {
+            self.show_entity(world, systems, target);
+        }
+
+        let Some(entity) = self.entity else {
+            return;
+        };
+        let Some((level, pos, _)) = entity_state(world, entity) else {
+            return;
+        };
+
+        if self.shown_level != Some(level) {
+            self.shown_level = Some(level);
+
+            let text = systems
+                .locale
+                .format("target_frame.level", &[("level", &level)]);
+            systems.gfx.set_text(&self.level, text.as_ref());
+        }
+
+        let distance = myentity
+            .and_then(|myentity| entity_state(world, myentity))
+            .and_then(|(_, my_pos, _)| target_distance(my_pos, pos));
+        if self.shown_distance != Some(distance) {
+            self.shown_distance = Some(distance);
+
+            let text = match distance {
+                Some(distance) => systems.locale.plural(
+                    "target_frame.distance",
+                    distance as u64,
+                    &[],
+                ),
+                None => systems.locale.text("target_frame.far"),
+            };
+            systems.gfx.set_text(&self.distance, text.as_ref());
+        }
+
+        let of_target = self
+            .target_of_target
+            .filter(|entity| world.entities.contains_key(*entity))
+            .map(|entity| combat_name(world, systems, entity));
+        if self.shown_of_target != of_target {
+            if let Some(name) = &of_target {
+                let text = systems
+                    .locale
+                    .format("target_frame.of_target", &[("name", name)]);
+                systems.gfx.set_text(&self.of_target, text.as_ref());
+            }
+            systems
+                .gfx
+                .set_visible(&self.of_target, of_target.is_some());
+            self.shown_of_target = of_target;
+        }
+    }
+
+    fn show_entity(
+        &mut self,
+        world: &World,
+        systems: &mut SystemHolder,
+        target: Option<GlobalKey>,
+    ) {
+        if let Some(portrait) = self.portrait.take() {
+            systems.gfx.remove_gfx(&mut systems.renderer, &portrait);
+        }
+
+        self.entity = target;
+        self.target_of_target = None;
+        self.shown_level = None;
+        self.shown_distance = None;
+        self.shown_of_target = None;
+
+        let Some((entity, (_, _, vitals))) = target
+            .and_then(|entity| Some((entity, entity_state(world, entity)?)))
+        else {
+            self.entity = None;
+            self.set_visible(systems, false);
+            return;
+        };
+
+        let allocation = match world.entities.get(entity) {
+            Some(Entity::Player(p_data)) => systems
+                .resource
+                .players
+                .get(p_data.sprite.0 as usize)
+                .map(|texture| texture.allocation),
+            Some(Entity::Npc(n_data)) => systems
+                .base
+                .npc
+                .get(n_data.entity_index as usize)
+                .and_then(|npc| systems.resource.npcs.get(npc.sprite as usize))
+                .map(|texture| texture.allocation),
+            _ => None,
+        };
+
+        if let Some(allocation) = allocation {
+            let pos = self.pos + self.portrait_offset;
+            let image = Image::new(
+                Some(allocation),
+                &mut systems.renderer,
+                Vec3::new(pos.x, pos.y, ORDER_VITAL_HP),
+                self.portrait_size,
+                Vec4::new(0.0, 0.0, PORTRAIT_SIZE, PORTRAIT_SIZE),
+                0,
+            );
+
+            self.portrait = Some(systems.gfx.add_image(
+                image,
+                0,
+                "Target Portrait",
+                false,
+                CameraView::SubView1,
+            ));
+        }
+
+        let name = combat_name(world, systems, entity);
+        systems.gfx.set_text(&self.name, &name);
+        self.set_vitals(systems, entity, &vitals.vital, &vitals.vitalmax);
+        self.set_visible(systems, true);
+    }
+
+    /// Sets the bars when `entity` is the one shown.
+    pub fn set_vitals(
+        &mut self,
+        systems: &mut SystemHolder,
+        entity: GlobalKey,
+        vitals: &[i32],
+        vitalmax: &[i32],
+    ) {
+        if self.entity != Some(entity) {
+            return;
+        }
+
+        for (index, bar) in self.bar.iter().enumerate() {
+            let mut size = systems.gfx.get_size(bar);
+            size.x = get_percent(
+                vitals[index],
+                vitalmax[index],
+                self.bar_size[index] as i32,
+            ) as f32;
+            systems.gfx.set_size(bar, size);
+        }
+    }
+
+    /// Who `entity` targets, shown when it is the one in the frame.
+    pub fn set_target_of_target(
+        &mut self,
+        entity: GlobalKey,
+        target: Option<GlobalKey>,
+    ) {
+        if self.entity == Some(entity) {
+            self.target_of_target = target;
+        }
+    }
+}
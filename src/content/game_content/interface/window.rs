@@ -0,0 +1,505 @@
+use std::any::Any;
+
+use ascending_graphics::*;
+
+use crate::{
+    Result, SystemHolder, data_types::*, is_within_area, logic::*,
+    systems::Poller, widget::*,
+};
+
+use super::{ItemDescription, SelectedTextbox, Window};
+
+/// Position, stacking and drag state shared by every interface window.
+pub struct WindowFrame {
+    pub visible: bool,
+    pub pos: Vec2,
+    pub default_pos: Vec2,
+    pub size: Vec2,
+    pub z_order: f32,
+    pub order_index: usize,
+    pub did_button_click: bool,
+    in_hold: bool,
+    hold_pos: Vec2,
+
+    edge: f32,
+    min_bound: Vec2,
+    max_bound: Vec2,
+}
+
+impl WindowFrame {
+    /// `edge` is the gap kept between the window and the screen border while
+    /// it is dragged around.
+    pub fn new(
+        systems: &SystemHolder,
+        pos: Vec2,
+        size: Vec2,
+        edge: f32,
+    ) -> Self {
+        WindowFrame {
+            visible: false,
+            pos,
+            default_pos: pos,
+            size,
+            z_order: 0.0,
+            order_index: 0,
+            did_button_click: false,
+            in_hold: false,
+            hold_pos: Vec2::ZERO,
+
+            edge,
+            min_bound: Vec2::new(
+                systems.size.width - size.x - edge,
+                systems.size.height - size.y - edge,
+            ),
+            max_bound: Vec2::new(edge, edge),
+        }
+    }
+
+    /// Z position the window background sits on, its details are layered
+    /// just in front of it.
+    pub fn detail_origin(&self) -> f32 {
+        ORDER_GUI_WINDOW.sub_f32(self.z_order, 3)
+    }
+
+    pub fn in_window(&self, screen_pos: Vec2) -> bool {
+        if !self.visible {
+            return false;
+        }
+        is_within_area(screen_pos, self.pos, self.size)
+    }
+
+    pub fn in_hold(&self) -> bool {
+        self.in_hold
+    }
+
+    pub fn hold_window(&mut self, screen_pos: Vec2) {
+        if self.in_hold {
+            return;
+        }
+        self.in_hold = true;
+        self.hold_pos = screen_pos - self.pos;
+    }
+
+    pub fn release_window(&mut self) {
+        self.in_hold = false;
+        self.hold_pos = Vec2::ZERO;
+    }
+
+    /// Places the window at `pos`, pulled back inside the screen when the
+    /// window would end up outside of it.
+    pub fn place_at(&mut self, pos: Vec2) {
+        self.pos = pos.max(self.max_bound).min(self.min_bound);
+    }
+
+    /// Keeps the window at the same distance from the screen edges it is
+    /// closest to after the screen was resized from `old_screen`.
+    pub fn anchor_to(&mut self, systems: &SystemHolder, old_screen: Vec2) {
+        let screen = Vec2::new(systems.size.width, systems.size.height);
+
+        self.min_bound = screen - self.size - self.edge;
+        self.default_pos = (self.default_pos
+            + anchor_offset(self.default_pos, self.size, old_screen, screen))
+        .max(self.max_bound)
+        .min(self.min_bound);
+        self.place_at(
+            self.pos + anchor_offset(self.pos, self.size, old_screen, screen),
+        );
+    }
+
+    /// Moves a held window under the cursor while keeping it on screen.
+    /// Returns false when the window is not being held.
+    pub fn drag_to(&mut self, screen_pos: Vec2) -> bool {
+        if !self.in_hold {
+            return false;
+        }
+        self.pos = (screen_pos - self.hold_pos)
+            .max(self.max_bound)
+            .min(self.min_bound);
+        true
+    }
+}
+
+fn anchor_offset(
+    pos: Vec2,
+    size: Vec2,
+    old_screen: Vec2,
+    screen: Vec2,
+) -> Vec2 {
+    let center = pos + size * 0.5;
+    let offset = screen - old_screen;
+
+    Vec2::new(
+        if center.x > old_screen.x * 0.5 {
+            offset.x
+        } else {
+            0.0
+        },
+        if center.y > old_screen.y * 0.5 {
+            offset.y
+        } else {
+            0.0
+        },
+    )
+}
+
+/// What the interface does after a window handled a button or a click.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WindowAction {
+    #[default]
+    None,
+    /// Closes the window, the server is told for windows it opened.
+    Close,
+    /// Ends the click without raising or dragging the window.
+    Stop,
+    /// Moves every window back to its default place and stacking order.
+    ResetLayout,
+    /// Rebuilds the interface once the current input is handled.
+    Rebuild,
+    /// Shows or hides the ping and frame statistics after their settings
+    /// changed.
+    ShowStatistics,
+}
+
+/// Behaviour shared by the draggable interface windows. The interface drives
+/// hovering, clicking, dragging and stacking through this trait so each
+/// window only has to describe its own widgets.
+pub trait GameWindow: Any {
+    fn id(&self) -> Window;
+
+    /// Name the window is stored under in the window state files.
+    fn name(&self) -> &'static str;
+
+    fn frame(&self) -> &WindowFrame;
+
+    fn frame_mut(&mut self) -> &mut WindowFrame;
+
+    fn buttons(&mut self) -> &mut [Button];
+
+    fn unload(&mut self, systems: &mut SystemHolder);
+
+    /// Builds the window again at the current scale, theme and language.
+    fn recreate(&mut self, systems: &mut SystemHolder);
+
+    fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool);
+
+    /// Whether a press at `screen_pos` starts dragging the window.
+    fn can_hold(&self, systems: &SystemHolder, screen_pos: Vec2) -> bool;
+
+    /// Moves every widget after the frame position changed.
+    fn update_pos(&mut self, systems: &mut SystemHolder);
+
+    /// Moves every widget onto a new z position.
+    fn update_z_order(
+        &mut self,
+        systems: &mut SystemHolder,
+        detail_origin: f32,
+    );
+
+    /// Whether the window comes back open on login. Windows opened by the
+    /// server stay closed.
+    fn restores_visibility(&self) -> bool {
+        false
+    }
+
+    /// Whether Escape closes the window while it is the topmost open one.
+    fn closes_on_escape(&self) -> bool {
+        true
+    }
+
+    /// Tells the server the window was closed, for windows it opened.
+    fn send_close(&self, _socket: &mut Poller) -> Result<()> {
+        Ok(())
+    }
+
+    /// Textbox that is selected by a click on it.
+    fn textbox(&self) -> SelectedTextbox {
+        SelectedTextbox::None
+    }
+
+    /// Whether the window has content such as item slots that a press picks
+    /// up, the window is then only dragged by its header.
+    fn holds_content(&self) -> bool {
+        false
+    }
+
+    fn hold_content(&mut self, _systems: &mut SystemHolder, _screen_pos: Vec2) {
+    }
+
+    /// Whether a list such as a dropdown is open over the other windows, it
+    /// takes the next click wherever that lands.
+    fn popup_open(&self) -> bool {
+        false
+    }
+
+    fn close_popup(&mut self, _systems: &mut SystemHolder) {}
+
+    /// Handles a press on the button at `index` of `buttons`.
+    fn trigger_button(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        _index: usize,
+    ) -> Result<WindowAction> {
+        Ok(WindowAction::None)
+    }
+
+    /// Hover effects of the widgets other than the buttons.
+    fn hover_content(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _tooltip: &mut Tooltip,
+        _item_desc: &mut ItemDescription,
+        _screen_pos: Vec2,
+    ) {
+    }
+
+    /// Handles a click on the window that was not taken by a button.
+    fn click_content(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        _alert: &mut Alert,
+        _screen_pos: Vec2,
+    ) -> Result<WindowAction> {
+        Ok(WindowAction::None)
+    }
+
+    /// Handles a double click while the window is the topmost one.
+    fn double_click(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _socket: &mut Poller,
+        _screen_pos: Vec2,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Moves held sliders and scrollbars with the cursor, returns true while
+    /// one of them is held.
+    fn move_content(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _screen_pos: Vec2,
+    ) -> bool {
+        false
+    }
+
+    /// Lets go of held sliders and scrollbars.
+    fn release_content(
+        &mut self,
+        _systems: &mut SystemHolder,
+        _screen_pos: Vec2,
+    ) -> WindowAction {
+        WindowAction::None
+    }
+
+    fn in_window(&self, _systems: &SystemHolder, screen_pos: Vec2) -> bool {
+        self.frame().in_window(screen_pos)
+    }
+
+    fn set_z_order(
+        &mut self,
+        systems: &mut SystemHolder,
+        z_order: f32,
+        order_index: usize,
+    ) {
+        let frame = self.frame_mut();
+        if frame.z_order == z_order {
+            return;
+        }
+        frame.z_order = z_order;
+        frame.order_index = order_index;
+
+        let detail_origin = frame.detail_origin();
+        self.update_z_order(systems, detail_origin);
+    }
+
+    fn hold_window(&mut self, screen_pos: Vec2) {
+        self.frame_mut().hold_window(screen_pos);
+    }
+
+    fn release_window(&mut self) {
+        self.frame_mut().release_window();
+    }
+
+    fn place_at(&mut self, systems: &mut SystemHolder, pos: Vec2) {
+        self.frame_mut().place_at(pos);
+        self.update_pos(systems);
+    }
+
+    fn resize(&mut self, systems: &mut SystemHolder, old_screen: Vec2) {
+        self.frame_mut().anchor_to(systems, old_screen);
+        self.update_pos(systems);
+    }
+
+    fn move_window(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        if self.frame_mut().drag_to(screen_pos) {
+            self.update_pos(systems);
+        }
+    }
+
+    fn hover_buttons(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        if !self.frame().visible {
+            return;
+        }
+
+        for button in self.buttons().iter_mut() {
+            let state = in_button(systems, button, screen_pos);
+            button.set_hover(systems, state);
+        }
+    }
+
+    fn click_buttons(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        if !self.frame().visible {
+            return None;
+        }
+
+        let mut button_found = None;
+        for (index, button) in self.buttons().iter_mut().enumerate() {
+            if in_button(systems, button, screen_pos) {
+                button.set_click(systems, true);
+                button_found = Some(index)
+            }
+        }
+        button_found
+    }
+
+    fn reset_buttons(&mut self, systems: &mut SystemHolder) {
+        let frame = self.frame_mut();
+        if !frame.did_button_click || !frame.visible {
+            return;
+        }
+        frame.did_button_click = false;
+
+        self.buttons().iter_mut().for_each(|button| {
+            button.set_click(systems, false);
+        });
+    }
+}
+
+fn in_button(
+    systems: &SystemHolder,
+    button: &Button,
+    screen_pos: Vec2,
+) -> bool {
+    button.is_visible()
+        && is_within_area(
+            screen_pos,
+            button.base_pos
+                + (button.adjust_pos * systems.scale as f32).floor(),
+            (button.size * systems.scale as f32).floor(),
+        )
+}
+
+/// The draggable windows of the interface, kept in stacking order with the
+/// topmost window first.
+pub struct WindowRegistry {
+    windows: Vec<Box<dyn GameWindow>>,
+    default_order: Vec<Window>,
+}
+
+impl WindowRegistry {
+    /// `windows` are given in the order they stack in by default.
+    pub fn new(windows: Vec<Box<dyn GameWindow>>) -> Self {
+        let default_order = windows.iter().map(|window| window.id()).collect();
+
+        WindowRegistry {
+            windows,
+            default_order,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn GameWindow> {
+        self.windows.iter().map(|window| window.as_ref())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut dyn GameWindow> {
+        self.windows.iter_mut().map(|window| window.as_mut())
+    }
+
+    pub fn window(&self, id: Window) -> &dyn GameWindow {
+        self.iter()
+            .find(|window| window.id() == id)
+            .expect("every window is registered")
+    }
+
+    pub fn window_mut(&mut self, id: Window) -> &mut dyn GameWindow {
+        self.iter_mut()
+            .find(|window| window.id() == id)
+            .expect("every window is registered")
+    }
+
+    /// The registered window of type `T`.
+    pub fn get<T: GameWindow>(&self) -> &T {
+        self.windows
+            .iter()
+            .find_map(|window| (window.as_ref() as &dyn Any).downcast_ref())
+            .expect("every window is registered")
+    }
+
+    pub fn get_mut<T: GameWindow>(&mut self) -> &mut T {
+        self.windows
+            .iter_mut()
+            .find_map(|window| (window.as_mut() as &mut dyn Any).downcast_mut())
+            .expect("every window is registered")
+    }
+
+    /// Topmost window under the cursor.
+    pub fn topmost_at(
+        &self,
+        systems: &SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<Window> {
+        self.iter()
+            .find(|window| window.in_window(systems, screen_pos))
+            .map(|window| window.id())
+    }
+
+    /// Puts `id` on top, returns false when it already was.
+    pub fn move_to_front(&mut self, id: Window) -> bool {
+        match self.windows.iter().position(|window| window.id() == id) {
+            Some(0) | None => false,
+            Some(index) => {
+                let window = self.windows.remove(index);
+                self.windows.insert(0, window);
+                true
+            }
+        }
+    }
+
+    /// Puts `id` below every other window, returns false when it already
+    /// was.
+    pub fn move_to_back(&mut self, id: Window) -> bool {
+        let last = self.windows.len().saturating_sub(1);
+
+        match self.windows.iter().position(|window| window.id() == id) {
+            Some(index) if index != last => {
+                let window = self.windows.remove(index);
+                self.windows.push(window);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Stacks the windows by `key`, the lowest key on top. Windows with the
+    /// same key keep their order.
+    pub fn sort_by_key<K: Ord>(
+        &mut self,
+        mut key: impl FnMut(&dyn GameWindow) -> K,
+    ) {
+        self.windows.sort_by_key(|window| key(window.as_ref()));
+    }
+
+    pub fn reset_order(&mut self) {
+        let order = &self.default_order;
+
+        self.windows.sort_by_key(|window| {
+            order.iter().position(|id| *id == window.id())
+        });
+    }
+}
@@ -0,0 +1,65 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+pub const WINDOW_STATE_FOLDER: &str = "./window_state";
+
+/// Saved placement of a single window.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct WindowState {
+    pub pos: [f32; 2],
+    pub visible: bool,
+    pub order_index: usize,
+}
+
+/// Window placement of one character, stored in
+/// `window_state/<character>.toml` and keyed by the window name.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowStates {
+    pub window: BTreeMap<String, WindowState>,
+}
+
+impl WindowStates {
+    fn path(character: &str) -> PathBuf {
+        let name: String = character
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        PathBuf::from(WINDOW_STATE_FOLDER).join(format!("{name}.toml"))
+    }
+
+    /// Missing or broken files give an empty state so every window keeps
+    /// its default placement.
+    pub fn load(character: &str) -> Self {
+        let path = Self::path(character);
+        let Ok(data) = fs::read_to_string(&path) else {
+            return WindowStates::default();
+        };
+
+        toml::from_str(&data).unwrap_or_else(|e| {
+            warn!("Failed to parse {}: {e}", path.display());
+            WindowStates::default()
+        })
+    }
+
+    pub fn save(&self, character: &str) {
+        let path = Self::path(character);
+
+        let result = fs::create_dir_all(WINDOW_STATE_FOLDER)
+            .map_err(|e| e.to_string())
+            .and_then(|_| toml::to_string(self).map_err(|e| e.to_string()))
+            .and_then(|data| fs::write(&path, data).map_err(|e| e.to_string()));
+
+        if let Err(e) = result {
+            warn!("Failed to save {}: {e}", path.display());
+        }
+    }
+}
@@ -1,41 +1,40 @@
-use bit_op::{BitOp, bit_u8::*};
 use ascending_graphics::*;
 
 use crate::{
-    Direction, MapAttribute, SystemHolder, content::game_content::player::*,
-    content::game_content::*, data_types::*, database::map::*,
+    MapAttribute, SystemHolder, content::game_content::*, database::map::*,
 };
 
+pub mod effects;
 pub mod item;
 
+pub use effects::*;
 pub use item::*;
 
-const MAX_MAP_ITEMS: usize = 30;
 pub const MAP_SIZE: Vec2 = Vec2 {
     x: 640.0, // 32 x TEXTURE_SIZE
     y: 640.0, // 32 x TEXTURE_SIZE
 };
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct MapAttributes {
     pub attribute: Vec<MapAttribute>,
 }
 
-impl MapAttributes {
-    pub fn default() -> Self {
+impl Default for MapAttributes {
+    fn default() -> Self {
         MapAttributes {
             attribute: Vec::with_capacity(1024),
         }
     }
 }
 
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct MapDirBlock {
     pub dir: Vec<u8>,
 }
 
-impl MapDirBlock {
-    pub fn default() -> Self {
+impl Default for MapDirBlock {
+    fn default() -> Self {
         MapDirBlock {
             dir: Vec::with_capacity(1024),
         }
@@ -67,12 +66,13 @@ impl MapContent {
     }
 }
 
-pub fn find_entity(
-    world: &mut World,
+/// Tile under `screen_pos`, on the map next to the center one when it is
+/// outside of it.
+pub fn find_tile(
     systems: &mut SystemHolder,
-    content: &mut GameContent,
+    content: &GameContent,
     screen_pos: Vec2,
-) -> Option<GlobalKey> {
+) -> Option<Position> {
     let center_pos = get_map_render_pos(systems, content.map.map_pos)?;
     let adjusted_pos = screen_pos - center_pos;
     let tile_pos = Vec2::new(
@@ -105,6 +105,17 @@ pub fn find_entity(
         target_pos.map.y -= 1;
     }
 
+    Some(target_pos)
+}
+
+pub fn find_entity(
+    world: &mut World,
+    systems: &mut SystemHolder,
+    content: &mut GameContent,
+    screen_pos: Vec2,
+) -> Option<GlobalKey> {
+    let target_pos = find_tile(systems, content, screen_pos)?;
+
     world.entities.iter().find_map(|(key, entity_data)| {
         match entity_data {
             Entity::Player(p_data) => {
@@ -116,10 +127,8 @@ pub fn find_entity(
                 }
             }
 
-            Entity::Npc(n_data) => {
-                if n_data.pos == target_pos {
-                    return Some(key);
-                }
+            Entity::Npc(n_data) if n_data.pos == target_pos => {
+                return Some(key);
             }
             _ => {}
         }
@@ -127,6 +136,23 @@ pub fn find_entity(
     })
 }
 
+/// Map item lying on the tile under `screen_pos`.
+pub fn find_map_item(
+    world: &World,
+    systems: &mut SystemHolder,
+    content: &GameContent,
+    screen_pos: Vec2,
+) -> Option<GlobalKey> {
+    let target_pos = find_tile(systems, content, screen_pos)?;
+
+    content.mapitems.borrow().iter().copied().find(|key| {
+        matches!(
+            world.entities.get(*key),
+            Some(Entity::MapItem(i_data)) if i_data.pos == target_pos
+        )
+    })
+}
+
 pub fn get_map_loc(mx: i32, my: i32, index: usize) -> (i32, i32) {
     match index {
         1 => (mx - 1, my - 1), // Top Left
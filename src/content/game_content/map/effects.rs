@@ -0,0 +1,211 @@
+use ascending_graphics::*;
+
+use crate::{
+    Entity, GameContent, MAP_LAYER_COUNT, MAP_SIZE, MapSlotData, Position,
+    Result, SystemHolder, World, data_types::*, database::map::*,
+};
+
+/// Roof alpha change per second.
+const ROOF_FADE_SPEED: f32 = 4.0;
+/// How quickly the parallax anchor follows the player into a new map.
+const PARALLAX_ANCHOR_SPEED: f32 = 4.0;
+
+pub fn update_map_effects(
+    world: &mut World,
+    systems: &mut SystemHolder,
+    content: &mut GameContent,
+    seconds: f32,
+    delta: f32,
+) -> Result<()> {
+    update_tile_animations(systems, content, seconds);
+
+    let (pos, pos_offset) = if let Some(entity) = content.myentity
+        && let Some(Entity::Player(p_data)) = world.entities.get(entity)
+    {
+        (p_data.pos, p_data.pos_offset)
+    } else {
+        return Ok(());
+    };
+
+    update_roof_fade(systems, content, pos, delta);
+
+    if let Some(start_pos) = get_map_render_pos(systems, pos.map) {
+        let player_pos = start_pos
+            + (Vec2::new(pos.x as f32, pos.y as f32) * TILE_SIZE as f32)
+            + pos_offset;
+
+        update_parallax(systems, content, player_pos, delta);
+    }
+
+    Ok(())
+}
+
+fn update_tile_animations(
+    systems: &mut SystemHolder,
+    content: &GameContent,
+    seconds: f32,
+) {
+    let effects = &systems.base.map_effects;
+
+    if effects.animations.is_empty() {
+        return;
+    }
+
+    for key in content.map.mapindex.iter() {
+        let Some(mapslotdata) = systems.base.mapdata.get_mut(*key) else {
+            continue;
+        };
+
+        for i in 0..mapslotdata.animated.len() {
+            let animated = mapslotdata.animated[i];
+            let Some(animation) = effects.animations.get(&animated.tile) else {
+                continue;
+            };
+
+            let frame = animation.frame_at(seconds);
+
+            if frame == animated.current {
+                continue;
+            }
+
+            mapslotdata.animated[i].current = frame;
+
+            let layer = animated.pos.z as usize;
+            let map = mapslotdata.layer_map_mut(effects.overlay_index(layer));
+            let mut tile = map.get_tile(animated.pos);
+            tile.id = frame as usize;
+            map.set_tile(animated.pos, tile);
+        }
+    }
+}
+
+fn is_under_roof(
+    systems: &SystemHolder,
+    mapslotdata: &MapSlotData,
+    pos: Position,
+) -> bool {
+    let effects = &systems.base.map_effects;
+
+    (0..MAP_LAYER_COUNT)
+        .filter(|layer| effects.layers[*layer].roof)
+        .any(|layer| {
+            let map = mapslotdata.layer_map(effects.overlay_index(layer));
+
+            map.get_tile(UVec3::new(pos.x as u32, pos.y as u32, layer as u32))
+                .id
+                > 0
+        })
+}
+
+fn update_roof_fade(
+    systems: &mut SystemHolder,
+    content: &GameContent,
+    pos: Position,
+    delta: f32,
+) {
+    if !systems.base.map_effects.has_roof() {
+        return;
+    }
+
+    let under_roof = if let Some(key) = systems.base.mappos_key.get(&pos.map)
+        && let Some(mapslotdata) = systems.base.mapdata.get(*key)
+    {
+        is_under_roof(systems, mapslotdata, pos)
+    } else {
+        false
+    };
+
+    let current = systems.base.map_effects.roof_alpha;
+    let target = if under_roof { 0.0 } else { 1.0 };
+    let step = delta * ROOF_FADE_SPEED;
+    let alpha = if current < target {
+        (current + step).min(target)
+    } else {
+        (current - step).max(target)
+    };
+
+    if alpha == current {
+        return;
+    }
+
+    systems.base.map_effects.roof_alpha = alpha;
+
+    let effects = &systems.base.map_effects;
+
+    for key in content.map.mapindex.iter() {
+        let Some(mapslotdata) = systems.base.mapdata.get_mut(*key) else {
+            continue;
+        };
+
+        for layer in 0..MAP_LAYER_COUNT {
+            if !effects.layers[layer].roof {
+                continue;
+            }
+
+            let color = Color::rgba(255, 255, 255, effects.layer_alpha(layer));
+            let map = mapslotdata.layer_map_mut(effects.overlay_index(layer));
+
+            for x in 0..32 {
+                for y in 0..32 {
+                    let pos = UVec3::new(x, y, layer as u32);
+                    let mut tile = map.get_tile(pos);
+
+                    if tile.id > 0 {
+                        tile.color = color;
+                        map.set_tile(pos, tile);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn update_parallax(
+    systems: &mut SystemHolder,
+    content: &GameContent,
+    player_pos: Vec2,
+    delta: f32,
+) {
+    if systems.base.map_effects.parallax.is_empty() {
+        return;
+    }
+
+    let Some(center_pos) = get_map_render_pos(systems, content.map.map_pos)
+    else {
+        return;
+    };
+
+    // Ease the anchor into the new map so layers drift instead of jumping
+    // when the player crosses a border. Warps snap straight to the target.
+    let target = center_pos + (MAP_SIZE * 0.5);
+    let anchor = match systems.base.map_effects.parallax_anchor {
+        Some(anchor) if anchor.distance(target) <= MAP_SIZE.x * 2.0 => {
+            anchor
+                + (target - anchor) * (delta * PARALLAX_ANCHOR_SPEED).min(1.0)
+        }
+        _ => target,
+    };
+
+    systems.base.map_effects.parallax_anchor = Some(anchor);
+
+    let shift = player_pos - anchor;
+    let effects = &systems.base.map_effects;
+
+    for key in content.map.mapindex.iter() {
+        let Some(mapslotdata) = systems.base.mapdata.get_mut(*key) else {
+            continue;
+        };
+
+        let base_pos = mapslotdata.map.pos;
+
+        for (overlay, factor) in
+            mapslotdata.overlays.iter_mut().zip(effects.parallax.iter())
+        {
+            let pos = base_pos + shift * (1.0 - factor);
+
+            if overlay.pos != pos {
+                overlay.set_pos(pos);
+            }
+        }
+    }
+}
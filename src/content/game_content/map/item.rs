@@ -1,8 +1,5 @@
-use bytey::{ByteBufferRead, ByteBufferWrite};
 use ascending_graphics::*;
 
-use serde::{Deserialize, Serialize};
-
 use crate::{
     Item, MapItemEntity, SystemHolder, data_types::*, game_content::*,
 };
@@ -85,6 +82,7 @@ impl MapItem {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_mapitem_position(
     systems: &mut SystemHolder,
     game_light: GfxType,
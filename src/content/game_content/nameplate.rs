@@ -0,0 +1,299 @@
+use ascending_graphics::*;
+use indexmap::IndexSet;
+
+use crate::{
+    AIBehavior, Entity, GfxType, GlobalKey, NpcMode, SystemHolder, World,
+    data_types::*, database::get_map_render_pos,
+};
+
+/// Size of the behaviour icon drawn in front of NPC names.
+const ICON_SIZE: f32 = 8.0;
+/// Space between the behaviour icon and the name.
+const ICON_GAP: f32 = 3.0;
+/// Height of the name label the icon is centered on.
+const NAME_HEIGHT: f32 = 20.0;
+
+/// How an NPC treats players, shown by the icon in front of its name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Disposition {
+    /// Never attacks players.
+    Friendly,
+    /// Fights back once attacked.
+    Reactive,
+    /// Attacks players that come into sight.
+    Aggressive,
+}
+
+impl Disposition {
+    /// Pets and summons follow their owner, so they never start a fight
+    /// whatever their AI.
+    pub fn of(behaviour: AIBehavior, mode: NpcMode) -> Self {
+        if matches!(mode, NpcMode::Pet | NpcMode::Summon) {
+            return Disposition::Friendly;
+        }
+
+        match behaviour {
+            AIBehavior::Friendly | AIBehavior::Healer => Disposition::Friendly,
+            AIBehavior::Reactive
+            | AIBehavior::HelpReactive
+            | AIBehavior::ReactiveHealer => Disposition::Reactive,
+            AIBehavior::Agressive | AIBehavior::AgressiveHealer => {
+                Disposition::Aggressive
+            }
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Disposition::Friendly => Color::rgba(60, 200, 60, 255),
+            Disposition::Reactive => Color::rgba(230, 200, 40, 255),
+            Disposition::Aggressive => Color::rgba(220, 40, 40, 255),
+        }
+    }
+}
+
+/// Name colour of an NPC `level_gap` levels above our player, from grey
+/// for trivial NPCs over green and yellow to red for dangerous ones.
+pub fn level_gap_color(level_gap: i32) -> Color {
+    match level_gap {
+        i32::MIN..=-10 => Color::rgba(150, 150, 150, 255),
+        -9..=-3 => Color::rgba(80, 200, 80, 255),
+        -2..=2 => Color::rgba(240, 220, 90, 255),
+        3..=5 => Color::rgba(255, 140, 40, 255),
+        _ => Color::rgba(230, 40, 40, 255),
+    }
+}
+
+/// Rows of the tiles within `radius` of an NPC as `(y offset, half width)`,
+/// following the distance of `MapPosition::checkdistance` the server
+/// checks sight with.
+pub fn range_rows(radius: i32) -> impl Iterator<Item = (i32, i32)> {
+    (-radius..=radius).map(move |dy| {
+        let half_width = if dy == 0 {
+            radius
+        } else {
+            radius + 1 - dy.abs()
+        };
+
+        (dy, half_width)
+    })
+}
+
+fn with_alpha(color: Color, alpha: u8) -> Color {
+    Color::rgba(color.r(), color.g(), color.b(), alpha)
+}
+
+struct Nameplate {
+    icon: GfxType,
+    level_gap: Option<i32>,
+}
+
+/// Behaviour icons and level colours of the NPC names, and the sight and
+/// follow ranges shown with the `show_npc_ranges` setting.
+#[derive(Default)]
+pub struct NpcNameplates {
+    plates: Vec<(GlobalKey, Nameplate)>,
+    ranges: Vec<GfxType>,
+    shown_ranges: usize,
+}
+
+impl NpcNameplates {
+    pub fn new() -> Self {
+        NpcNameplates::default()
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        for (_, plate) in self.plates.drain(..) {
+            systems.gfx.remove_gfx(&mut systems.renderer, &plate.icon);
+        }
+
+        for gfx in self.ranges.drain(..) {
+            systems.gfx.remove_gfx(&mut systems.renderer, &gfx);
+        }
+
+        self.shown_ranges = 0;
+    }
+
+    fn add_icon(systems: &mut SystemHolder) -> GfxType {
+        let mut rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(0.0, 0.0, ORDER_ENTITY_ICON),
+            Vec2::new(ICON_SIZE, ICON_SIZE),
+            COLOR_WHITE,
+            0,
+        );
+
+        rect.set_radius(ICON_SIZE * 0.5)
+            .set_border_width(1.0)
+            .set_border_color(Color::rgba(10, 10, 10, 255));
+
+        systems.gfx.add_rect(
+            rect,
+            2,
+            "Npc Behaviour Icon",
+            false,
+            CameraView::MainView,
+        )
+    }
+
+    /// Keeps the icons next to the names and the name colours in line with
+    /// our `player_level`, called every frame.
+    pub fn update(
+        &mut self,
+        world: &World,
+        systems: &mut SystemHolder,
+        npcs: &IndexSet<GlobalKey, ahash::RandomState>,
+        player_level: Option<i32>,
+    ) {
+        self.plates.retain(|(entity, plate)| {
+            let keep = npcs.contains(entity);
+
+            if !keep {
+                systems.gfx.remove_gfx(&mut systems.renderer, &plate.icon);
+            }
+            keep
+        });
+
+        let mut ranges = 0;
+
+        for entity in npcs.iter() {
+            let Some(Entity::Npc(n_data)) = world.entities.get(*entity) else {
+                continue;
+            };
+            let Some(npc) = systems.base.npc.get(n_data.entity_index as usize)
+            else {
+                continue;
+            };
+            let (behaviour, sight, follow_sight) =
+                (npc.behaviour, npc.sight, npc.follow_sight);
+
+            let index = match self.plates.iter().position(|(e, _)| e == entity)
+            {
+                Some(index) => index,
+                None => {
+                    let icon = Self::add_icon(systems);
+
+                    self.plates.push((
+                        *entity,
+                        Nameplate {
+                            icon,
+                            level_gap: None,
+                        },
+                    ));
+                    self.plates.len() - 1
+                }
+            };
+            let plate = &mut self.plates[index].1;
+            let name = n_data.name_map.0;
+            let name_color = systems.gfx.get_color(&name);
+
+            let level_gap = player_level.map(|level| n_data.level - level);
+            if plate.level_gap != level_gap {
+                plate.level_gap = level_gap;
+
+                let color = level_gap_color(level_gap.unwrap_or_default());
+                systems
+                    .gfx
+                    .set_color(&name, with_alpha(color, name_color.a()));
+            }
+
+            let icon_color = Disposition::of(behaviour, n_data.mode).color();
+            let alpha = name_color.a();
+            if systems.gfx.get_color(&plate.icon)
+                != with_alpha(icon_color, alpha)
+            {
+                systems
+                    .gfx
+                    .set_color(&plate.icon, with_alpha(icon_color, alpha));
+                systems.gfx.set_border_color(
+                    &plate.icon,
+                    Color::rgba(10, 10, 10, alpha),
+                );
+            }
+
+            let visible = systems.gfx.get_visible(&name);
+            systems.gfx.set_visible(&plate.icon, visible);
+
+            if visible {
+                let name_pos = systems.gfx.get_pos(&name);
+
+                systems.gfx.set_pos(
+                    &plate.icon,
+                    Vec3::new(
+                        name_pos.x - ICON_SIZE - ICON_GAP,
+                        name_pos.y + (NAME_HEIGHT - ICON_SIZE) * 0.5,
+                        ORDER_ENTITY_ICON,
+                    ),
+                );
+            }
+
+            if systems.config.show_npc_ranges
+                && n_data.visible
+                && n_data.death_type.is_targetable()
+                && let Some(start) = get_map_render_pos(systems, n_data.pos.map)
+            {
+                let tile = start
+                    + Vec2::new(n_data.pos.x as f32, n_data.pos.y as f32)
+                        * TILE_SIZE as f32;
+
+                for (radius, color, z) in [
+                    (
+                        follow_sight,
+                        Color::rgba(255, 140, 40, 40),
+                        ORDER_NPC_FOLLOW_RANGE,
+                    ),
+                    (sight, Color::rgba(230, 40, 40, 50), ORDER_NPC_SIGHT),
+                ] {
+                    for (dy, half_width) in range_rows(radius) {
+                        let pos = Vec3::new(
+                            tile.x - half_width as f32 * TILE_SIZE as f32,
+                            tile.y + dy as f32 * TILE_SIZE as f32,
+                            z,
+                        );
+                        let size = Vec2::new(
+                            (half_width * 2 + 1) as f32 * TILE_SIZE as f32,
+                            TILE_SIZE as f32,
+                        );
+
+                        self.set_range_row(systems, ranges, pos, size, color);
+                        ranges += 1;
+                    }
+                }
+            }
+        }
+
+        for gfx in self.ranges[ranges..self.shown_ranges.max(ranges)].iter() {
+            systems.gfx.set_visible(gfx, false);
+        }
+        self.shown_ranges = ranges;
+    }
+
+    fn set_range_row(
+        &mut self,
+        systems: &mut SystemHolder,
+        index: usize,
+        pos: Vec3,
+        size: Vec2,
+        color: Color,
+    ) {
+        if index == self.ranges.len() {
+            let rect = Rect::new(&mut systems.renderer, pos, size, color, 0);
+
+            self.ranges.push(systems.gfx.add_rect(
+                rect,
+                0,
+                "Npc Range",
+                true,
+                CameraView::MainView,
+            ));
+            return;
+        }
+
+        let gfx = self.ranges[index];
+
+        systems.gfx.set_pos(&gfx, pos);
+        systems.gfx.set_size(&gfx, size);
+        systems.gfx.set_color(&gfx, color);
+        systems.gfx.set_visible(&gfx, true);
+    }
+}
@@ -3,8 +3,8 @@ use ascending_graphics::*;
 pub const NPC_SPRITE_FRAME_X: f32 = 6.0;
 
 use crate::{
-    Direction, EntityNameMap, HPBar, NpcEntity, Result, SpriteIndex,
-    SystemHolder, create_label, data_types::*, game_content::*,
+    EntityNameMap, HPBar, NpcEntity, Result, SpriteIndex, SystemHolder,
+    create_label, data_types::*, game_content::*,
 };
 
 pub fn add_npc(
@@ -193,125 +193,7 @@ pub fn unload_npc(
     Ok(())
 }
 
-pub fn move_npc(
-    world: &mut World,
-    systems: &mut SystemHolder,
-    entity: GlobalKey,
-    move_type: MovementType,
-) -> Result<()> {
-    if !world.entities.contains_key(entity) {
-        return Ok(());
-    }
-
-    let (frame, last_frame) = if let Some(Entity::Npc(n_data)) =
-        world.entities.get_mut(entity)
-    {
-        if n_data.attacking.0 || n_data.movement.is_moving {
-            return Ok(());
-        }
-
-        let (dir, end) = match move_type {
-            MovementType::MovementBuffer => {
-                if n_data.movement_buffer.is_empty() {
-                    return Ok(());
-                }
-                let movement_data = n_data.movement_buffer.pop_front();
-
-                if let Some(data) = movement_data {
-                    (dir_to_enum(data.dir), Some(data.end_pos))
-                } else {
-                    return Ok(());
-                }
-            }
-            MovementType::Manual(m_dir, m_end) => (dir_to_enum(m_dir), m_end),
-        };
-
-        if let Some(end_pos) = end {
-            n_data.end_movement = end_pos;
-        } else {
-            let adj = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-            let dir_index = enum_to_dir(dir) as usize;
-            let mut end_move = Position {
-                x: n_data.pos.x + adj[dir_index].0,
-                y: n_data.pos.y + adj[dir_index].1,
-                map: n_data.pos.map,
-            };
-
-            if end_move.x < 0
-                || end_move.x >= 32
-                || end_move.y < 0
-                || end_move.y >= 32
-            {
-                let new_pos = [
-                    (end_move.x, 31),
-                    (0, end_move.y),
-                    (end_move.x, 0),
-                    (31, end_move.y),
-                ];
-
-                end_move.x = new_pos[dir_index].0;
-                end_move.y = new_pos[dir_index].1;
-                end_move.map.x += adj[dir_index].0;
-                end_move.map.y += adj[dir_index].1;
-            }
-
-            n_data.end_movement = end_move;
-        }
-
-        let dir_u8 = enum_to_dir(dir);
-
-        n_data.movement.is_moving = true;
-        n_data.movement.move_direction = dir;
-        n_data.movement.move_speed = 0.2;
-        n_data.dir = dir_u8;
-
-        let last_frame = if n_data.last_move_frame == 1 { 2 } else { 1 };
-
-        n_data.last_move_frame = last_frame;
-
-        (n_data.dir * NPC_SPRITE_FRAME_X as u8, last_frame)
-    } else {
-        return Ok(());
-    };
-
-    set_npc_frame(world, systems, entity, frame as usize + last_frame)
-}
-
-pub fn end_npc_move(
-    world: &mut World,
-    systems: &mut SystemHolder,
-    entity: GlobalKey,
-) -> Result<()> {
-    if !world.entities.contains_key(entity) {
-        return Ok(());
-    }
-
-    let dir = if let Some(Entity::Npc(n_data)) = world.entities.get_mut(entity)
-    {
-        if !n_data.movement.is_moving {
-            return Ok(());
-        }
-        n_data.movement.is_moving = false;
-        n_data.movement.elapsed_time = 0.0;
-
-        n_data.pos.x = n_data.end_movement.x;
-        n_data.pos.y = n_data.end_movement.y;
-        if n_data.pos.map != n_data.end_movement.map {
-            n_data.pos.map = n_data.end_movement.map;
-        }
-
-        n_data.pos_offset = Vec2::ZERO;
-
-        n_data.dir
-    } else {
-        return Ok(());
-    };
-
-    let frame = dir * NPC_SPRITE_FRAME_X as u8;
-
-    set_npc_frame(world, systems, entity, frame as usize)
-}
-
+#[allow(clippy::too_many_arguments)]
 pub fn update_npc_position(
     systems: &mut SystemHolder,
     center_map: MapPosition,
@@ -472,55 +354,65 @@ pub fn process_npc_attack(
     set_npc_frame(world, systems, entity, frame)
 }
 
-pub fn process_npc_movement(
+/// Places an NPC where its snapshot buffer plays it back at `seconds`,
+/// starting a walking frame on every new step.
+pub fn play_npc_snapshots(
     world: &mut World,
     systems: &mut SystemHolder,
     entity: GlobalKey,
     socket: &mut Poller,
     content: &mut GameContent,
-    delta: f32,
+    seconds: f32,
 ) -> Result<()> {
-    if !world.entities.contains_key(entity) {
-        return Ok(());
-    }
-
-    let (move_pos, progress, move_direction) =
+    let frame =
         if let Some(Entity::Npc(n_data)) = world.entities.get_mut(entity) {
-            if !n_data.movement.is_moving {
+            let Some(playback) = n_data.snapshots.sample(seconds) else {
                 return Ok(());
             };
+            let offset = playback.offset();
 
-            n_data.movement.elapsed_time += delta;
+            if n_data.pos == playback.from
+                && n_data.end_movement == playback.to
+                && n_data.pos_offset == offset
+                && n_data.movement.is_moving == playback.moving
+            {
+                return Ok(());
+            }
 
-            let progress =
-                n_data.movement.elapsed_time / n_data.movement.move_speed;
-            let move_pos = TILE_SIZE as f32 * progress;
+            let new_step = playback.moving
+                && (!n_data.movement.is_moving
+                    || n_data.end_movement != playback.to);
+            let stopped = !playback.moving && n_data.movement.is_moving;
 
-            (move_pos, progress, n_data.movement.move_direction)
-        } else {
-            return Ok(());
-        };
+            n_data.pos = playback.from;
+            n_data.end_movement = playback.to;
+            n_data.pos_offset = offset;
+            n_data.movement.is_moving = playback.moving;
 
-    if progress < 1.0 {
-        if let Some(Entity::Npc(n_data)) = world.entities.get_mut(entity) {
-            if !is_map_connected(content.map.map_pos, n_data.pos.map) {
-                return Ok(());
+            if playback.moving {
+                n_data.dir = playback.dir;
+                n_data.movement.move_direction = dir_to_enum(playback.dir);
             }
 
-            let offset = match move_direction {
-                Direction::Up => Vec2::new(0.0, move_pos),
-                Direction::Down => Vec2::new(0.0, -move_pos),
-                Direction::Left => Vec2::new(-move_pos, 0.0),
-                Direction::Right => Vec2::new(move_pos, 0.0),
-            };
+            let frame = n_data.dir * NPC_SPRITE_FRAME_X as u8;
 
-            n_data.pos_offset = offset;
-        }
-    } else {
-        if let Some(Entity::Npc(n_data)) = world.entities.get_mut(entity) {
-            n_data.pos_offset = Vec2::ZERO;
-        }
-        end_npc_move(world, systems, entity)?;
+            if n_data.attacking.0 {
+                None
+            } else if new_step {
+                n_data.last_move_frame =
+                    if n_data.last_move_frame == 1 { 2 } else { 1 };
+                Some(frame as usize + n_data.last_move_frame)
+            } else if stopped {
+                Some(frame as usize)
+            } else {
+                None
+            }
+        } else {
+            return Ok(());
+        };
+
+    if let Some(frame) = frame {
+        set_npc_frame(world, systems, entity, frame)?;
     }
 
     update_npc_camera(world, systems, entity, socket, content)
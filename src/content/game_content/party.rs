@@ -0,0 +1,92 @@
+use mmap_bytey::{MByteBufferRead, MByteBufferWrite};
+
+use crate::{GlobalKey, MapPosition, Position, VITALS_MAX, target_distance};
+
+/// Members further away than this are shown by the map they are on.
+pub const PARTY_NEARBY_RANGE: i32 = 30;
+
+/// A member of our party as the server last reported them. Members in
+/// view are shown from their entity, which is kept current between
+/// reports.
+#[derive(Clone, Debug, PartialEq, Eq, MByteBufferRead, MByteBufferWrite)]
+pub struct PartyMember {
+    pub entity: GlobalKey,
+    pub name: String,
+    pub level: i32,
+    pub vital: [i32; VITALS_MAX],
+    pub vitalmax: [i32; VITALS_MAX],
+    pub pos: Position,
+}
+
+/// Where a party member is, seen from our player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemberLocation {
+    /// Tiles away from us.
+    Nearby(i32),
+    Away(MapPosition),
+}
+
+/// Location shown for a member at `pos`, tiles when they are close to
+/// `my_pos` and their map otherwise.
+pub fn member_location(
+    my_pos: Option<Position>,
+    pos: Position,
+) -> MemberLocation {
+    match my_pos.and_then(|my_pos| target_distance(my_pos, pos)) {
+        Some(distance) if distance <= PARTY_NEARBY_RANGE => {
+            MemberLocation::Nearby(distance)
+        }
+        _ => MemberLocation::Away(pos.map),
+    }
+}
+
+/// The party our player is in, empty while not in one.
+#[derive(Clone, Debug, Default)]
+pub struct Party {
+    members: Vec<PartyMember>,
+    pub leader: Option<GlobalKey>,
+    /// Experience our player got from kills shared with the party since
+    /// joining it.
+    pub shared_exp: u64,
+}
+
+impl Party {
+    pub fn new() -> Self {
+        Party::default()
+    }
+
+    pub fn in_party(&self) -> bool {
+        !self.members.is_empty()
+    }
+
+    pub fn members(&self) -> &[PartyMember] {
+        &self.members
+    }
+
+    pub fn member(&self, entity: GlobalKey) -> Option<&PartyMember> {
+        self.members.iter().find(|member| member.entity == entity)
+    }
+
+    /// Replaces the members with the server's list, the shared experience
+    /// starts over when we join a new party.
+    pub fn set_members(
+        &mut self,
+        leader: Option<GlobalKey>,
+        members: Vec<PartyMember>,
+    ) {
+        if !self.in_party() {
+            self.shared_exp = 0;
+        }
+
+        self.leader = leader;
+        self.members = members;
+    }
+
+    pub fn add_shared_exp(&mut self, amount: u64) {
+        self.shared_exp = self.shared_exp.saturating_add(amount);
+    }
+
+    pub fn clear(&mut self) {
+        *self = Party::default();
+    }
+}
@@ -2,21 +2,29 @@ use crate::{
     EntityNameMap, HPBar, PlayerEntity, Result, SpriteIndex, create_label,
 };
 use ascending_graphics::*;
-use bytey::{ByteBufferError, ByteBufferRead, ByteBufferWrite};
+use bytey::{ByteBufferRead, ByteBufferWrite};
+use mmap_bytey::{MByteBufferRead, MByteBufferWrite};
 
 pub const PLAYER_SPRITE_FRAME_X: f32 = 6.0;
 
 #[derive(
-    Debug, Copy, Clone, PartialEq, Eq, Default, ByteBufferRead, ByteBufferWrite,
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Default,
+    ByteBufferRead,
+    ByteBufferWrite,
+    MByteBufferRead,
+    MByteBufferWrite,
 )]
 pub struct PlayerPvP {
     pub pk: bool,
     pub pvpon: bool,
 }
 
-use crate::{
-    Direction, SystemHolder, data_types::*, fade::*, game_content::*, send_move,
-};
+use crate::{Direction, SystemHolder, data_types::*, game_content::*};
 
 pub fn add_player(
     world: &mut World,
@@ -211,76 +219,33 @@ pub fn move_player(
         return Ok(());
     }
 
-    let (frame, last_frame) = if let Some(Entity::Player(p_data)) =
-        world.entities.get_mut(entity)
-    {
-        if p_data.attacking.0 || p_data.movement.is_moving {
-            return Ok(());
-        }
-
-        let (dir, end) = match move_type {
-            MovementType::MovementBuffer => {
-                if p_data.movement_buffer.is_empty() {
-                    return Ok(());
-                }
-                let movement_data = p_data.movement_buffer.pop_front();
-
-                if let Some(data) = movement_data {
-                    (dir_to_enum(data.dir), Some(data.end_pos))
-                } else {
-                    return Ok(());
-                }
+    let (frame, last_frame) =
+        if let Some(Entity::Player(p_data)) = world.entities.get_mut(entity) {
+            if p_data.attacking.0 || p_data.movement.is_moving {
+                return Ok(());
             }
-            MovementType::Manual(m_dir, m_end) => (dir_to_enum(m_dir), m_end),
-        };
 
-        if let Some(end_pos) = end {
-            p_data.end_movement = end_pos;
-        } else {
-            let adj = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-            let dir_index = enum_to_dir(dir) as usize;
-            let mut end_move = Position {
-                x: p_data.pos.x + adj[dir_index].0,
-                y: p_data.pos.y + adj[dir_index].1,
-                map: p_data.pos.map,
-            };
+            let MovementType::Manual(m_dir, end) = move_type;
+            let dir = dir_to_enum(m_dir);
 
-            if end_move.x < 0
-                || end_move.x >= 32
-                || end_move.y < 0
-                || end_move.y >= 32
-            {
-                let new_pos = [
-                    (end_move.x, 31),
-                    (0, end_move.y),
-                    (end_move.x, 0),
-                    (31, end_move.y),
-                ];
-
-                end_move.x = new_pos[dir_index].0;
-                end_move.y = new_pos[dir_index].1;
-                end_move.map.x += adj[dir_index].0;
-                end_move.map.y += adj[dir_index].1;
-            }
+            p_data.end_movement =
+                end.unwrap_or_else(|| next_pos(p_data.pos, enum_to_dir(dir)));
 
-            p_data.end_movement = end_move;
-        }
+            let dir_u8 = enum_to_dir(dir);
 
-        let dir_u8 = enum_to_dir(dir);
+            p_data.movement.is_moving = true;
+            p_data.movement.move_direction = dir;
+            p_data.movement.move_speed = 0.2;
+            p_data.dir = dir_u8;
 
-        p_data.movement.is_moving = true;
-        p_data.movement.move_direction = dir;
-        p_data.movement.move_speed = 0.2;
-        p_data.dir = dir_u8;
+            let last_frame = if p_data.last_move_frame == 1 { 2 } else { 1 };
 
-        let last_frame = if p_data.last_move_frame == 1 { 2 } else { 1 };
+            p_data.last_move_frame = last_frame;
 
-        p_data.last_move_frame = last_frame;
-
-        (p_data.dir * PLAYER_SPRITE_FRAME_X as u8, last_frame)
-    } else {
-        return Ok(());
-    };
+            (p_data.dir * PLAYER_SPRITE_FRAME_X as u8, last_frame)
+        } else {
+            return Ok(());
+        };
 
     set_player_frame(world, systems, entity, frame as usize + last_frame)
 }
@@ -339,6 +304,7 @@ pub fn end_player_move(
     set_player_frame(world, systems, entity, frame as usize)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_player_position(
     systems: &mut SystemHolder,
     center_map: MapPosition,
@@ -498,6 +464,7 @@ pub fn process_player_attack(
     set_player_frame(world, systems, entity, frame)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn process_player_movement(
     world: &mut World,
     systems: &mut SystemHolder,
@@ -569,6 +536,70 @@ pub fn process_player_movement(
     Ok(())
 }
 
+/// Places a remote player where its snapshot buffer plays it back at
+/// `seconds`, starting a walking frame on every new step.
+pub fn play_player_snapshots(
+    world: &mut World,
+    systems: &mut SystemHolder,
+    socket: &mut Poller,
+    entity: GlobalKey,
+    content: &mut GameContent,
+    seconds: f32,
+) -> Result<()> {
+    let frame =
+        if let Some(Entity::Player(p_data)) = world.entities.get_mut(entity) {
+            let Some(playback) = p_data.snapshots.sample(seconds) else {
+                return Ok(());
+            };
+            let offset = playback.offset();
+
+            if p_data.pos == playback.from
+                && p_data.end_movement == playback.to
+                && p_data.pos_offset == offset
+                && p_data.movement.is_moving == playback.moving
+            {
+                return Ok(());
+            }
+
+            let new_step = playback.moving
+                && (!p_data.movement.is_moving
+                    || p_data.end_movement != playback.to);
+            let stopped = !playback.moving && p_data.movement.is_moving;
+
+            p_data.pos = playback.from;
+            p_data.end_movement = playback.to;
+            p_data.pos_offset = offset;
+            p_data.movement.is_moving = playback.moving;
+
+            if playback.moving {
+                p_data.dir = playback.dir;
+                p_data.movement.move_direction = dir_to_enum(playback.dir);
+            }
+
+            let frame = p_data.dir * PLAYER_SPRITE_FRAME_X as u8;
+
+            if p_data.attacking.0 {
+                None
+            } else if new_step {
+                p_data.last_move_frame =
+                    if p_data.last_move_frame == 1 { 2 } else { 1 };
+                Some(frame as usize + p_data.last_move_frame)
+            } else if stopped {
+                Some(frame as usize)
+            } else {
+                None
+            }
+        } else {
+            return Ok(());
+        };
+
+    if let Some(frame) = frame {
+        set_player_frame(world, systems, entity, frame)?;
+    }
+
+    update_player_camera(world, systems, socket, entity, content)
+}
+
 pub fn update_player_camera(
     world: &mut World,
     systems: &mut SystemHolder,
@@ -584,6 +615,12 @@ pub fn update_player_camera(
         return Ok(());
     };
 
+    let correction = if content.myentity == Some(entity) {
+        content.prediction.correction()
+    } else {
+        Vec2::ZERO
+    };
+
     if let Some(Entity::Player(p_data)) = world.entities.get_mut(entity) {
         update_player_position(
             systems,
@@ -591,7 +628,7 @@ pub fn update_player_camera(
             content.game_lights,
             p_data.sprite_index.0,
             &p_data.pos,
-            p_data.pos_offset,
+            p_data.pos_offset + correction,
             &p_data.hp_bar,
             p_data.name_map.0,
             light,
@@ -628,29 +665,34 @@ pub fn update_player_camera(
     Ok(())
 }
 
+/// Experience needed to go from `level` to the next one.
+pub fn next_level_exp(level: i32) -> u64 {
+    let exp_per_level = match level {
+        1..=10 => 100,
+        11..=20 => 250,
+        21..=30 => 400,
+        31..=40 => 550,
+        41..=50 => 700,
+        51..=60 => 850,
+        61..=70 => 1000,
+        71..=80 => 1150,
+        81..=90 => 1300,
+        91..=100 => 1450,
+        101..=120 => 2000,
+        121..=150 => 3000,
+        151..=199 => 4000,
+        _ => 0,
+    };
+
+    level.max(0) as u64 * exp_per_level
+}
+
 pub fn player_get_next_lvl_exp(
     world: &mut World,
     entity: GlobalKey,
 ) -> Result<u64> {
     if let Some(Entity::Player(p_data)) = world.entities.get(entity) {
-        let exp_per_level = match p_data.level {
-            1..=10 => 100,
-            11..=20 => 250,
-            21..=30 => 400,
-            31..=40 => 550,
-            41..=50 => 700,
-            51..=60 => 850,
-            61..=70 => 1000,
-            71..=80 => 1150,
-            81..=90 => 1300,
-            91..=100 => 1450,
-            101..=120 => 2000,
-            121..=150 => 3000,
-            151..=199 => 4000,
-            _ => 0,
-        };
-
-        Ok(p_data.level as u64 * exp_per_level as u64)
+        Ok(next_level_exp(p_data.level))
     } else {
         Ok(0)
     }
@@ -0,0 +1,397 @@
+use ascending_graphics::*;
+use bit_op::{BitOp, bit_u8::*};
+use std::collections::VecDeque;
+
+use crate::{
+    Entity, GfxType, MapAttribute, MapAttributes, MapDirBlock, ORDER_TARGET,
+    Position, SystemHolder, TILE_SIZE, World, database::*,
+};
+
+/// Steps the client may run ahead of the server, further key presses wait
+/// until older steps are confirmed.
+pub const MAX_PENDING_MOVES: usize = 4;
+/// Share of a position correction that is slid off per second.
+pub const CORRECTION_SPEED: f32 = 12.0;
+/// Corrections over more tiles than this snap instead of sliding.
+pub const MAX_CORRECTION_TILES: i32 = 3;
+
+/// Tile one step from `pos` towards `dir`, onto the next map at the edges.
+pub fn next_pos(pos: Position, dir: u8) -> Position {
+    let adj = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+    let dir_index = dir as usize % 4;
+    let mut end_move = Position {
+        x: pos.x + adj[dir_index].0,
+        y: pos.y + adj[dir_index].1,
+        map: pos.map,
+    };
+
+    if end_move.x < 0 || end_move.x >= 32 || end_move.y < 0 || end_move.y >= 32
+    {
+        let new_pos = [
+            (end_move.x, 31),
+            (0, end_move.y),
+            (end_move.x, 0),
+            (31, end_move.y),
+        ];
+
+        end_move.x = new_pos[dir_index].0;
+        end_move.y = new_pos[dir_index].1;
+        end_move.map.x += adj[dir_index].0;
+        end_move.map.y += adj[dir_index].1;
+    }
+
+    end_move
+}
+
+/// Tiles walked between `from` and `to` without diagonal steps, None when
+/// they are in different map groups.
+pub fn tile_distance(from: Position, to: Position) -> Option<i32> {
+    if from.map.group != to.map.group {
+        return None;
+    }
+
+    let (dx, dy) = tile_delta(from, to);
+    Some(dx.abs() + dy.abs())
+}
+
+/// Tiles from `from` to `to` along x and y, across map borders.
+pub fn tile_delta(from: Position, to: Position) -> (i32, i32) {
+    (
+        (to.map.x - from.map.x) * 32 + to.x - from.x,
+        (to.map.y - from.map.y) * 32 + to.y - from.y,
+    )
+}
+
+/// Direction of a step from `from` that gets closer to `to`, along the
+/// longer distance first. None when `can_move` refuses both directions or
+/// there is nowhere to go.
+pub fn step_towards(
+    from: Position,
+    to: Position,
+    mut can_move: impl FnMut(Position, u8) -> bool,
+) -> Option<u8> {
+    tile_distance(from, to)?;
+
+    let (dx, dy) = tile_delta(from, to);
+    let x_dir = (dx != 0).then_some(if dx > 0 { 1 } else { 3 });
+    let y_dir = (dy != 0).then_some(if dy > 0 { 2 } else { 0 });
+    let dirs = if dx.abs() >= dy.abs() {
+        [x_dir, y_dir]
+    } else {
+        [y_dir, x_dir]
+    };
+
+    dirs.into_iter().flatten().find(|dir| can_move(from, *dir))
+}
+
+/// Direction `from` has to face to look at the tile next to it, `to`.
+pub fn facing_dir(from: Position, to: Position) -> Option<u8> {
+    match tile_delta(from, to) {
+        (0, -1) => Some(0),
+        (1, 0) => Some(1),
+        (0, 1) => Some(2),
+        (-1, 0) => Some(3),
+        _ => None,
+    }
+}
+
+/// Whether the tile at `pos` blocks leaving it towards `dir`. Bit `dir` of
+/// the tile is set when that side is blocked.
+pub fn is_dir_blocked(dir_block: &MapDirBlock, pos: Position, dir: u8) -> bool {
+    let bit = [B0, B1, B2, B3][dir as usize % 4];
+
+    dir_block
+        .dir
+        .get(get_tile_pos(pos.x, pos.y))
+        .is_some_and(|tile| tile.get(bit) == bit)
+}
+
+/// Whether players may not stand on the tile at `pos`.
+pub fn is_tile_blocked(attributes: &MapAttributes, pos: Position) -> bool {
+    matches!(
+        attributes.attribute.get(get_tile_pos(pos.x, pos.y)),
+        Some(MapAttribute::Blocked)
+    )
+}
+
+/// Whether our player can step from `pos` towards `dir` as far as the
+/// client knows. Maps that are not loaded count as blocked.
+pub fn can_move(
+    world: &World,
+    systems: &SystemHolder,
+    pos: Position,
+    dir: u8,
+) -> bool {
+    let to = next_pos(pos, dir);
+    let map_slot = |map| {
+        systems
+            .base
+            .mappos_key
+            .get(&map)
+            .and_then(|key| systems.base.mapdata.get(*key))
+    };
+
+    let (Some(from_slot), Some(to_slot)) =
+        (map_slot(pos.map), map_slot(to.map))
+    else {
+        return false;
+    };
+
+    if is_dir_blocked(&from_slot.dir_block, pos, dir)
+        || is_tile_blocked(&to_slot.attributes, to)
+    {
+        return false;
+    }
+
+    !world.entities.iter().any(|(_, entity)| match entity {
+        Entity::Npc(n_data) => {
+            !n_data.death_type.is_dead()
+                && (n_data.pos == to
+                    || (n_data.movement.is_moving && n_data.end_movement == to))
+        }
+        _ => false,
+    })
+}
+
+/// A step walked by the client before the server confirmed it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PredictedMove {
+    pub seq: u32,
+    pub dir: u8,
+    /// Tile the step ends on.
+    pub pos: Position,
+}
+
+/// Client side prediction of our own player's steps. Every step gets a
+/// sequence number, the server answers each with the position it accepted
+/// and any mismatch is replayed from there.
+#[derive(Clone, Debug, Default)]
+pub struct MovePredictor {
+    next_seq: u32,
+    pending: VecDeque<PredictedMove>,
+    confirmed: Option<Position>,
+    /// Pixels between where the player was drawn and where a correction
+    /// put it, slid back to zero over time.
+    correction: Vec2,
+}
+
+impl MovePredictor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &PredictedMove> {
+        self.pending.iter()
+    }
+
+    /// Last position the server confirmed.
+    pub fn confirmed(&self) -> Option<Position> {
+        self.confirmed
+    }
+
+    /// Where our player ends up once every pending step is confirmed.
+    pub fn predicted(&self) -> Option<Position> {
+        self.pending.back().map(|step| step.pos).or(self.confirmed)
+    }
+
+    pub fn can_predict(&self) -> bool {
+        self.pending.len() < MAX_PENDING_MOVES
+    }
+
+    /// Starts a step from `pos` towards `dir`, the returned step is sent to
+    /// the server.
+    pub fn predict(&mut self, pos: Position, dir: u8) -> PredictedMove {
+        self.confirmed.get_or_insert(pos);
+
+        let step = PredictedMove {
+            seq: self.next_seq,
+            dir,
+            pos: next_pos(pos, dir),
+        };
+
+        self.next_seq = self.next_seq.wrapping_add(1);
+        self.pending.push_back(step);
+        step
+    }
+
+    /// The server accepted our steps up to `seq` and put us on `pos`. Steps
+    /// that are still pending are replayed from `pos` when it differs from
+    /// our prediction, dropping those `can_move` refuses. Returns the new
+    /// predicted position when a correction is needed.
+    pub fn acknowledge(
+        &mut self,
+        seq: u32,
+        pos: Position,
+        mut can_move: impl FnMut(Position, u8) -> bool,
+    ) -> Option<Position> {
+        let mut expected = None;
+
+        while let Some(step) = self
+            .pending
+            .pop_front_if(|step| seq.wrapping_sub(step.seq) as i32 >= 0)
+        {
+            expected = Some(step.pos);
+        }
+
+        self.confirmed = Some(pos);
+
+        if expected.is_none_or(|expected| expected == pos) {
+            return None;
+        }
+
+        let mut at = pos;
+
+        self.pending.retain_mut(|step| {
+            if !can_move(at, step.dir) {
+                return false;
+            }
+
+            at = next_pos(at, step.dir);
+            step.pos = at;
+            true
+        });
+
+        Some(at)
+    }
+
+    /// Forgets every pending step, after the server moved us on its own.
+    pub fn reset(&mut self, pos: Option<Position>) {
+        self.pending.clear();
+        self.confirmed = pos;
+        self.correction = Vec2::ZERO;
+    }
+
+    pub fn correction(&self) -> Vec2 {
+        self.correction
+    }
+
+    /// Slides the player from `from`, drawn `offset` pixels off its tile,
+    /// to `to`. Far or cross group corrections snap.
+    pub fn start_correction(
+        &mut self,
+        from: Position,
+        offset: Vec2,
+        to: Position,
+    ) {
+        let tiles =
+            |pos: Position| (pos.map.x * 32 + pos.x, pos.map.y * 32 + pos.y);
+        let (from_x, from_y) = tiles(from);
+        let (to_x, to_y) = tiles(to);
+        let (dx, dy) = (from_x - to_x, from_y - to_y);
+
+        self.correction = if from.map.group != to.map.group
+            || dx.abs() > MAX_CORRECTION_TILES
+            || dy.abs() > MAX_CORRECTION_TILES
+        {
+            Vec2::ZERO
+        } else {
+            Vec2::new(dx as f32, dy as f32) * TILE_SIZE as f32 + offset
+        };
+    }
+
+    /// Slides the correction towards zero, returns true while it moved.
+    pub fn update(&mut self, delta: f32) -> bool {
+        if self.correction == Vec2::ZERO {
+            return false;
+        }
+
+        self.correction *= 1.0 - (delta * CORRECTION_SPEED).min(1.0);
+
+        if self.correction.length() < 0.5 {
+            self.correction = Vec2::ZERO;
+        }
+
+        true
+    }
+}
+
+/// Debug outlines of the predicted and the confirmed tile of our player,
+/// shown with the `show_move_ghosts` setting.
+pub struct MoveGhosts {
+    predicted: GfxType,
+    confirmed: GfxType,
+}
+
+impl MoveGhosts {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        MoveGhosts {
+            predicted: Self::add_ghost(
+                systems,
+                [80, 160, 255],
+                "Predicted Ghost",
+            ),
+            confirmed: Self::add_ghost(
+                systems,
+                [255, 150, 40],
+                "Confirmed Ghost",
+            ),
+        }
+    }
+
+    fn add_ghost(
+        systems: &mut SystemHolder,
+        [r, g, b]: [u8; 3],
+        name: &'static str,
+    ) -> GfxType {
+        let mut rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(0.0, 0.0, ORDER_TARGET),
+            Vec2::new(TILE_SIZE as f32, TILE_SIZE as f32),
+            Color::rgba(r, g, b, 50),
+            0,
+        );
+
+        rect.set_border_width(2.0)
+            .set_border_color(Color::rgba(r, g, b, 255));
+
+        systems
+            .gfx
+            .add_rect(rect, 0, name, false, CameraView::MainView)
+    }
+
+    pub fn recreate(&mut self, systems: &mut SystemHolder) {
+        *self = MoveGhosts::new(systems);
+    }
+
+    pub fn unload(&self, systems: &mut SystemHolder) {
+        systems
+            .gfx
+            .remove_gfx(&mut systems.renderer, &self.predicted);
+        systems
+            .gfx
+            .remove_gfx(&mut systems.renderer, &self.confirmed);
+    }
+
+    /// Places the outlines, `fallback` is used before the first step.
+    pub fn update(
+        &self,
+        systems: &mut SystemHolder,
+        predictor: &MovePredictor,
+        fallback: Option<Position>,
+    ) {
+        for (gfx, pos) in [
+            (self.predicted, predictor.predicted()),
+            (self.confirmed, predictor.confirmed()),
+        ] {
+            let start = if systems.config.show_move_ghosts
+                && let Some(pos) = pos.or(fallback)
+            {
+                get_map_render_pos(systems, pos.map).map(|start| {
+                    start
+                        + Vec2::new(pos.x as f32, pos.y as f32)
+                            * TILE_SIZE as f32
+                })
+            } else {
+                None
+            };
+
+            systems.gfx.set_visible(&gfx, start.is_some());
+
+            if let Some(start) = start {
+                systems
+                    .gfx
+                    .set_pos(&gfx, Vec3::new(start.x, start.y, ORDER_TARGET));
+            }
+        }
+    }
+}
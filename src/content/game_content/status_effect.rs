@@ -0,0 +1,206 @@
+use ascending_graphics::*;
+use indexmap::IndexMap;
+use mmap_bytey::{MByteBufferRead, MByteBufferWrite};
+
+use crate::{Entity, GlobalKey, SystemHolder, World};
+
+/// Timed effect the server puts on a player or NPC, from consumables or
+/// NPC attacks.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, Hash, MByteBufferRead, MByteBufferWrite,
+)]
+pub enum EffectKind {
+    Regeneration,
+    Haste,
+    Might,
+    Shield,
+    Poison,
+    Burn,
+    Slow,
+    Stun,
+}
+
+impl EffectKind {
+    /// Locale key of the name, the description is under `<key>_desc`.
+    pub fn locale_key(self) -> &'static str {
+        match self {
+            EffectKind::Regeneration => "status.regeneration",
+            EffectKind::Haste => "status.haste",
+            EffectKind::Might => "status.might",
+            EffectKind::Shield => "status.shield",
+            EffectKind::Poison => "status.poison",
+            EffectKind::Burn => "status.burn",
+            EffectKind::Slow => "status.slow",
+            EffectKind::Stun => "status.stun",
+        }
+    }
+
+    pub fn is_harmful(self) -> bool {
+        matches!(
+            self,
+            EffectKind::Poison
+                | EffectKind::Burn
+                | EffectKind::Slow
+                | EffectKind::Stun
+        )
+    }
+
+    pub fn icon_color(self) -> Color {
+        match self {
+            EffectKind::Regeneration => Color::rgba(70, 170, 70, 255),
+            EffectKind::Haste => Color::rgba(220, 200, 60, 255),
+            EffectKind::Might => Color::rgba(200, 90, 50, 255),
+            EffectKind::Shield => Color::rgba(80, 130, 210, 255),
+            EffectKind::Poison => Color::rgba(90, 160, 40, 255),
+            EffectKind::Burn => Color::rgba(230, 110, 30, 255),
+            EffectKind::Slow => Color::rgba(90, 150, 220, 255),
+            EffectKind::Stun => Color::rgba(200, 200, 90, 255),
+        }
+    }
+
+    /// Colour the sprite is multiplied with, None for effects that leave
+    /// the sprite as it is.
+    pub fn tint(self) -> Option<[u8; 3]> {
+        match self {
+            EffectKind::Regeneration | EffectKind::Haste => None,
+            EffectKind::Might => Some([255, 200, 190]),
+            EffectKind::Shield => Some([200, 225, 255]),
+            EffectKind::Poison => Some([150, 255, 140]),
+            EffectKind::Burn => Some([255, 170, 120]),
+            EffectKind::Slow => Some([160, 190, 255]),
+            EffectKind::Stun => Some([255, 255, 150]),
+        }
+    }
+}
+
+/// Locale key and count of the countdown shown under an effect icon,
+/// seconds below a minute and whole minutes above.
+pub fn effect_time_left(left: f32) -> (&'static str, u32) {
+    let left = left.max(0.0).ceil() as u32;
+
+    if left < 60 {
+        ("status.seconds", left)
+    } else {
+        ("status.minutes", left.div_ceil(60))
+    }
+}
+
+/// `color` multiplied with `tint`, keeping its alpha.
+pub fn tint_color(color: [u8; 4], tint: [u8; 3]) -> [u8; 4] {
+    let mul = |a: u8, b: u8| ((a as u16 * b as u16) / 255) as u8;
+
+    [
+        mul(color[0], tint[0]),
+        mul(color[1], tint[1]),
+        mul(color[2], tint[2]),
+        color[3],
+    ]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ActiveEffect {
+    pub kind: EffectKind,
+    /// Time in seconds the effect runs out at.
+    pub ends_at: f32,
+}
+
+/// Effects on every entity in view, in the order they were applied.
+#[derive(Default)]
+pub struct StatusEffects {
+    entities: IndexMap<GlobalKey, Vec<ActiveEffect>, ahash::RandomState>,
+    /// Entities drawn tinted, so their colour is restored once the effects
+    /// are gone.
+    tinted: Vec<GlobalKey>,
+}
+
+impl StatusEffects {
+    pub fn new() -> Self {
+        StatusEffects::default()
+    }
+
+    /// Puts `kind` on `entity` for `duration` seconds, applying it again
+    /// restarts the timer.
+    pub fn apply(
+        &mut self,
+        entity: GlobalKey,
+        kind: EffectKind,
+        duration: f32,
+        seconds: f32,
+    ) {
+        let effects = self.entities.entry(entity).or_default();
+
+        effects.retain(|effect| effect.kind != kind);
+        effects.push(ActiveEffect {
+            kind,
+            ends_at: seconds + duration,
+        });
+    }
+
+    pub fn remove(&mut self, entity: GlobalKey, kind: EffectKind) {
+        if let Some(effects) = self.entities.get_mut(&entity) {
+            effects.retain(|effect| effect.kind != kind);
+        }
+    }
+
+    pub fn effects(&self, entity: GlobalKey) -> &[ActiveEffect] {
+        self.entities.get(&entity).map_or(&[], Vec::as_slice)
+    }
+
+    /// Drops the effects that ran out, in case the server's removal is
+    /// late or lost.
+    pub fn process(&mut self, seconds: f32) {
+        self.entities.retain(|_, effects| {
+            effects.retain(|effect| effect.ends_at > seconds);
+            !effects.is_empty()
+        });
+    }
+
+    /// Tint of the most recent effect on `entity` that has one.
+    pub fn tint(&self, entity: GlobalKey) -> Option<[u8; 3]> {
+        self.effects(entity)
+            .iter()
+            .rev()
+            .find_map(|effect| effect.kind.tint())
+    }
+
+    /// Tints the sprites of affected entities over their death look, and
+    /// forgets entities that left the view. Sprites in a death fade are
+    /// left to it until it is done.
+    pub fn update_tints(&mut self, world: &World, systems: &mut SystemHolder) {
+        self.entities
+            .retain(|entity, _| world.entities.contains_key(*entity));
+
+        let mut tinted = Vec::with_capacity(self.entities.len());
+
+        for entity in self.entities.keys().chain(self.tinted.iter()) {
+            let (sprite, fade) = match world.entities.get(*entity) {
+                Some(Entity::Player(p_data)) => {
+                    (p_data.sprite_index.0, &p_data.death_fade)
+                }
+                Some(Entity::Npc(n_data)) => {
+                    (n_data.sprite_index.0, &n_data.death_fade)
+                }
+                _ => continue,
+            };
+
+            if fade.active || tinted.contains(entity) {
+                continue;
+            }
+
+            let color = match self.tint(*entity) {
+                Some(tint) => {
+                    tinted.push(*entity);
+                    tint_color(fade.color, tint)
+                }
+                None => fade.color,
+            };
+
+            let color = Color::rgba(color[0], color[1], color[2], color[3]);
+            if systems.gfx.get_color(&sprite) != color {
+                systems.gfx.set_color(&sprite, color);
+            }
+        }
+
+        self.tinted = tinted;
+    }
+}
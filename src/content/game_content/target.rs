@@ -1,9 +1,41 @@
 use crate::{
-    GlobalKey, HPBar, ORDER_TARGET, Result, SystemHolder, data_types::*,
-    send_settarget, systems::Poller,
+    GlobalKey, HPBar, MapPosition, ORDER_TARGET, Position, Result,
+    SystemHolder, data_types::*, send_settarget, systems::Poller, tile_delta,
 };
 use ascending_graphics::*;
 
+/// Furthest NPC in tiles Tab picks as a target.
+pub const TAB_TARGET_RANGE: i32 = 12;
+
+/// Tiles between `from` and `to` as `MapPosition::checkdistance` counts them,
+/// None when they are in different map groups.
+pub fn target_distance(from: Position, to: Position) -> Option<i32> {
+    if from.map.group != to.map.group {
+        return None;
+    }
+
+    let (dx, dy) = tile_delta(from, to);
+    Some(MapPosition::new(dx, dy, 0).checkdistance(MapPosition::default()))
+}
+
+/// Target Tab switches to from `current`, out of `candidates` with their
+/// distance. The nearest comes first and every press moves one further out,
+/// wrapping back to the nearest.
+pub fn next_tab_target(
+    mut candidates: Vec<(GlobalKey, i32)>,
+    current: Option<GlobalKey>,
+) -> Option<GlobalKey> {
+    candidates.sort_by_key(|&(entity, distance)| (distance, entity));
+
+    let next = current
+        .and_then(|current| {
+            candidates.iter().position(|(entity, _)| *entity == current)
+        })
+        .map_or(0, |index| (index + 1) % candidates.len());
+
+    candidates.get(next).map(|(entity, _)| *entity)
+}
+
 pub struct Target {
     pub entity: Option<GlobalKey>,
     img_index: GfxType,
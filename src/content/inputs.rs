@@ -1,20 +1,35 @@
-use ascending_camera::controls::FlatControls;
 use ascending_graphics::*;
 
 use ascending_input::Key;
 use winit::{event_loop::ActiveEventLoop, keyboard::NamedKey};
 
 use crate::{
-    Alert, Direction, EntityKind, Result, SystemHolder, Tooltip,
-    content::{menu_content::content_input::*, *},
-    socket::*,
-    systems::State,
+    Alert, EntityKind, Result, SystemHolder, Tooltip, content::*, socket::*,
+    systems::KeyBindings,
 };
 
+/// Game actions bound to a key through `key_bindings` in `settings.toml`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyAction {
+    ToggleCombatLog,
+}
+
+impl KeyAction {
+    pub fn from_key(bindings: &KeyBindings, key: &Key) -> Option<Self> {
+        let Key::Character(ch) = key else {
+            return None;
+        };
+
+        ch.eq_ignore_ascii_case(&bindings.toggle_combat_log)
+            .then_some(KeyAction::ToggleCombatLog)
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 #[allow(clippy::enum_variant_names)]
 pub enum MouseInputType {
     MouseLeftDown,
+    MouseRightDown,
     MouseDoubleLeftDown,
     MouseLeftDownMove,
     MouseMove,
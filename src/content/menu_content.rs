@@ -1,16 +1,13 @@
-use ascending_graphics::{cosmic_text::Attrs, *};
+use ascending_graphics::*;
 
-pub mod content_input;
+mod content_input;
 pub mod login;
 pub mod register;
 
-pub use content_input::*;
 pub use login::*;
 pub use register::*;
 
-use crate::{
-    SystemHolder, content::*, data_types::*, is_within_area, widget::*,
-};
+use crate::{SystemHolder, content::*};
 
 pub enum WindowType {
     None,
@@ -35,11 +32,12 @@ pub struct MenuContent {
 
 impl MenuContent {
     pub fn new(systems: &mut SystemHolder) -> Self {
+        let (pos, size) = menu_bg_area(systems);
         let bg_image = Image::new(
             Some(systems.resource.menu_bg.allocation),
             &mut systems.renderer,
-            Vec3::new(0.0, 0.0, ORDER_MENU_BG),
-            Vec2::new(800.0, 600.0),
+            Vec3::new(pos.x, pos.y, ORDER_MENU_BG),
+            size,
             Vec4::new(0.0, 0.0, 800.0, 600.0),
             0,
         );
@@ -76,6 +74,19 @@ impl MenuContent {
         self.clear_window(systems)
     }
 
+    /// Keeps the background covering the screen and the windows centered
+    /// after the screen was resized.
+    pub fn resize(&mut self, systems: &mut SystemHolder) {
+        let (pos, size) = menu_bg_area(systems);
+        systems.gfx.set_size(&self.bg, size);
+        systems
+            .gfx
+            .set_pos(&self.bg, Vec3::new(pos.x, pos.y, ORDER_MENU_BG));
+
+        self.login.center(systems);
+        self.register.center(systems);
+    }
+
     pub fn clear_window(&mut self, systems: &mut SystemHolder) {
         self.login.set_visible(systems, false);
         self.register.set_visible(systems, false);
@@ -84,6 +95,15 @@ impl MenuContent {
     }
 }
 
+/// Scales the 800x600 background up until it covers the whole screen.
+fn menu_bg_area(systems: &SystemHolder) -> (Vec2, Vec2) {
+    let screen = Vec2::new(systems.size.width, systems.size.height);
+    let bg_size = Vec2::new(800.0, 600.0);
+    let size = (bg_size * (screen / bg_size).max_element()).ceil();
+
+    (((screen - size) * 0.5).floor(), size)
+}
+
 pub fn create_window(
     systems: &mut SystemHolder,
     content: &mut MenuContent,
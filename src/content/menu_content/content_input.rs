@@ -1,12 +1,10 @@
 use ascending_graphics::*;
 
 use ascending_input::Key;
-use winit::{event_loop::ActiveEventLoop, keyboard::NamedKey};
+use winit::event_loop::ActiveEventLoop;
 
 use crate::{
-    Alert, ContentType, MouseInputType, SystemHolder, Tooltip,
-    content::*,
-    socket::{self, *},
+    Alert, MouseInputType, SystemHolder, Tooltip, content::*, socket::*,
 };
 
 mod login_input;
@@ -16,6 +14,7 @@ use login_input::*;
 use register_input::*;
 
 impl MenuContent {
+    #[allow(clippy::too_many_arguments)]
     pub fn mouse_input(
         content: &mut Content,
         world: &mut World,
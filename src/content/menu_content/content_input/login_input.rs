@@ -5,14 +5,11 @@ use log::error;
 use winit::keyboard::NamedKey;
 
 use crate::{
-    APP_MAJOR, APP_MINOR, APP_REV, Alert, AlertIndex, AlertType, ContentType,
-    MouseInputType, SystemHolder, Tooltip, alert, button,
-    content::*,
-    fade::*,
-    logic::FloatFix,
-    socket::{self, *},
+    APP_MAJOR, APP_MINOR, APP_REV, Alert, AlertIndex, AlertType, FocusEvent,
+    FocusTarget, MouseInputType, SystemHolder, Tooltip, content::*, socket::*,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn login_mouse_input(
     menu_content: &mut MenuContent,
     _world: &mut World,
@@ -70,58 +67,47 @@ pub fn login_key_input(
     key: &Key,
     pressed: bool,
 ) {
-    if pressed {
-        match key {
-            Key::Named(NamedKey::Tab) => match menu_content.selected_textbox {
-                None => {
-                    menu_content.login.textbox[0].set_select(systems, true);
-                    menu_content.selected_textbox = Some(0);
-                }
-                Some(index) => {
-                    menu_content.login.textbox[index]
-                        .set_select(systems, false);
-
-                    let mut next_index = index + 1;
-
-                    if next_index >= menu_content.login.textbox.len() {
-                        next_index = 0;
-                    }
+    if let Some(event) = menu_content.login.focus.key_input(key, pressed) {
+        match event {
+            FocusEvent::Moved(target) => {
+                move_login_focus(menu_content, systems, target);
+            }
+            FocusEvent::Activate(FocusTarget::Button(index)) => {
+                trigger_button(menu_content, systems, socket, alert, index);
+            }
+            FocusEvent::Activate(FocusTarget::Checkbox(index)) => {
+                let value = !menu_content.login.checkbox.value;
+                menu_content.login.checkbox.set_value(systems, value);
+                trigger_checkbox(menu_content, systems, index);
+            }
+            FocusEvent::Activate(_) => {}
+        }
+        return;
+    }
 
-                    menu_content.login.textbox[next_index]
-                        .set_select(systems, true);
-                    menu_content.selected_textbox = Some(next_index);
-                }
-            },
-            Key::Named(NamedKey::Enter) => {
-                match menu_content.selected_textbox {
-                    None => {
-                        menu_content.login.textbox[0].set_select(systems, true);
-                        menu_content.selected_textbox = Some(0);
-                    }
-                    Some(index) => {
-                        menu_content.login.textbox[index]
-                            .set_select(systems, false);
-
-                        let next_index = index + 1;
-
-                        if next_index >= menu_content.login.textbox.len() {
-                            menu_content.selected_textbox = None;
-                            trigger_button(
-                                menu_content,
-                                systems,
-                                socket,
-                                alert,
-                                0,
-                            );
-                        } else {
-                            menu_content.login.textbox[next_index]
-                                .set_select(systems, true);
-                            menu_content.selected_textbox = Some(next_index);
-                        }
-                    }
-                }
+    // Enter moves on to the next field and submits from the last one.
+    if pressed && let Key::Named(NamedKey::Enter) = key {
+        match menu_content.selected_textbox {
+            None => {
+                move_login_focus(
+                    menu_content,
+                    systems,
+                    FocusTarget::Textbox(0),
+                );
+            }
+            Some(index) if index + 1 < menu_content.login.textbox.len() => {
+                move_login_focus(
+                    menu_content,
+                    systems,
+                    FocusTarget::Textbox(index + 1),
+                );
+            }
+            Some(index) => {
+                menu_content.login.textbox[index].set_select(systems, false);
+                menu_content.selected_textbox = None;
+                menu_content.login.focus.clear(systems);
+                trigger_button(menu_content, systems, socket, alert, 0);
             }
-            _ => {}
         }
     }
 
@@ -131,6 +117,29 @@ pub fn login_key_input(
     }
 }
 
+/// Moves the keyboard focus to `target`, selecting it when it is a textbox.
+fn move_login_focus(
+    menu_content: &mut MenuContent,
+    systems: &mut SystemHolder,
+    target: FocusTarget,
+) {
+    if let Some(index) = menu_content.selected_textbox {
+        menu_content.login.textbox[index].set_select(systems, false);
+    }
+
+    menu_content.selected_textbox = match target {
+        FocusTarget::Textbox(index) => {
+            menu_content.login.textbox[index].set_select(systems, true);
+            Some(index)
+        }
+        _ => None,
+    };
+
+    let area = menu_content.login.focus_area(systems, target);
+    menu_content.login.focus.set_focus(systems, Some(target));
+    menu_content.login.focus.show_ring(systems, area);
+}
+
 fn trigger_button(
     menu_content: &mut MenuContent,
     systems: &mut SystemHolder,
@@ -161,8 +170,8 @@ fn trigger_button(
                     alert.show_alert(
                         systems,
                         AlertType::Inform,
-                        "Server is offline",
-                        "Alert Message",
+                        "alert.server_offline",
+                        "alert.header",
                         250,
                         AlertIndex::None,
                         false,
@@ -6,11 +6,12 @@ use regex::Regex;
 use winit::keyboard::NamedKey;
 
 use crate::{
-    APP_MAJOR, APP_MINOR, APP_REV, Alert, AlertIndex, AlertType, ContentType,
-    MouseInputType, SystemHolder, Tooltip, button, content::*, logic::*,
+    APP_MAJOR, APP_MINOR, APP_REV, Alert, AlertIndex, AlertType, FocusEvent,
+    FocusTarget, MouseInputType, SystemHolder, Tooltip, content::*, logic::*,
     socket::*,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn register_mouse_input(
     menu_content: &mut MenuContent,
     _world: &mut World,
@@ -60,60 +61,42 @@ pub fn register_key_input(
     key: &Key,
     pressed: bool,
 ) {
-    if pressed {
-        match key {
-            Key::Named(NamedKey::Tab) => match menu_content.selected_textbox {
-                None => {
-                    menu_content.register.textbox[0].set_select(systems, true);
-                    menu_content.selected_textbox = Some(0);
-                }
-                Some(index) => {
-                    menu_content.register.textbox[index]
-                        .set_select(systems, false);
-
-                    let mut next_index = index + 1;
-
-                    if next_index >= menu_content.register.textbox.len() {
-                        next_index = 0;
-                    }
-
-                    menu_content.register.textbox[next_index]
-                        .set_select(systems, true);
-                    menu_content.selected_textbox = Some(next_index);
-                }
-            },
-            Key::Named(NamedKey::Enter) => {
-                match menu_content.selected_textbox {
-                    None => {
-                        menu_content.register.textbox[0]
-                            .set_select(systems, true);
-                        menu_content.selected_textbox = Some(0);
-                    }
-                    Some(index) => {
-                        menu_content.register.textbox[index]
-                            .set_select(systems, false);
-
-                        let next_index = index + 1;
-
-                        if next_index >= menu_content.register.textbox.len() {
-                            menu_content.selected_textbox = None;
-                            trigger_button(
-                                menu_content,
-                                systems,
-                                socket,
-                                alert,
-                                0,
-                            );
-                        } else {
-                            menu_content.register.textbox[next_index]
-                                .set_select(systems, true);
+    if let Some(event) = menu_content.register.focus.key_input(key, pressed) {
+        match event {
+            FocusEvent::Moved(target) => {
+                move_register_focus(menu_content, systems, target);
+            }
+            FocusEvent::Activate(FocusTarget::Button(index)) => {
+                trigger_button(menu_content, systems, socket, alert, index);
+            }
+            FocusEvent::Activate(_) => {}
+        }
+        return;
+    }
 
-                            menu_content.selected_textbox = Some(next_index);
-                        }
-                    }
-                }
+    // Enter moves on to the next field and submits from the last one.
+    if pressed && let Key::Named(NamedKey::Enter) = key {
+        match menu_content.selected_textbox {
+            None => {
+                move_register_focus(
+                    menu_content,
+                    systems,
+                    FocusTarget::Textbox(0),
+                );
+            }
+            Some(index) if index + 1 < menu_content.register.textbox.len() => {
+                move_register_focus(
+                    menu_content,
+                    systems,
+                    FocusTarget::Textbox(index + 1),
+                );
+            }
+            Some(index) => {
+                menu_content.register.textbox[index].set_select(systems, false);
+                menu_content.selected_textbox = None;
+                menu_content.register.focus.clear(systems);
+                trigger_button(menu_content, systems, socket, alert, 0);
             }
-            _ => {}
         }
     }
 
@@ -123,6 +106,29 @@ pub fn register_key_input(
     }
 }
 
+/// Moves the keyboard focus to `target`, selecting it when it is a textbox.
+fn move_register_focus(
+    menu_content: &mut MenuContent,
+    systems: &mut SystemHolder,
+    target: FocusTarget,
+) {
+    if let Some(index) = menu_content.selected_textbox {
+        menu_content.register.textbox[index].set_select(systems, false);
+    }
+
+    menu_content.selected_textbox = match target {
+        FocusTarget::Textbox(index) => {
+            menu_content.register.textbox[index].set_select(systems, true);
+            Some(index)
+        }
+        _ => None,
+    };
+
+    let area = menu_content.register.focus_area(systems, target);
+    menu_content.register.focus.set_focus(systems, Some(target));
+    menu_content.register.focus.show_ring(systems, area);
+}
+
 fn trigger_button(
     menu_content: &mut MenuContent,
     systems: &mut SystemHolder,
@@ -143,8 +149,8 @@ fn trigger_button(
                 alert.show_alert(
                     systems,
                     AlertType::Inform,
-                    "Email did not match",
-                    "Alert Message",
+                    "register.email_mismatch",
+                    "alert.header",
                     250,
                     AlertIndex::None,
                     false,
@@ -158,8 +164,8 @@ fn trigger_button(
                 alert.show_alert(
                     systems,
                     AlertType::Inform,
-                    "Password did not match",
-                    "Alert Message",
+                    "register.password_mismatch",
+                    "alert.header",
                     250,
                     AlertIndex::None,
                     false,
@@ -177,8 +183,8 @@ fn trigger_button(
                 alert.show_alert(
                     systems,
                     AlertType::Inform,
-                    "Username or Password contains unaccepted Characters",
-                    "Alert Message",
+                    "register.invalid_characters",
+                    "alert.header",
                     250,
                     AlertIndex::None,
                     false,
@@ -187,11 +193,14 @@ fn trigger_button(
             }
 
             if username.len() >= 64 {
+                let msg = systems
+                    .locale
+                    .format("register.username_too_long", &[("max", &64)]);
                 alert.show_alert(
                     systems,
                     AlertType::Inform,
-                    "Username has too many Characters, 64 Characters Max",
-                    "Alert Message",
+                    msg.as_ref(),
+                    "alert.header",
                     250,
                     AlertIndex::None,
                     false,
@@ -200,11 +209,14 @@ fn trigger_button(
             }
 
             if password.len() >= 128 {
+                let msg = systems
+                    .locale
+                    .format("register.password_too_long", &[("max", &128)]);
                 alert.show_alert(
                     systems,
                     AlertType::Inform,
-                    "Password has too many Characters, 128 Characters Max",
-                    "Alert Message",
+                    msg.as_ref(),
+                    "alert.header",
                     250,
                     AlertIndex::None,
                     false,
@@ -216,8 +228,8 @@ fn trigger_button(
                 alert.show_alert(
                     systems,
                     AlertType::Inform,
-                    "Email must be an actual email.",
-                    "Alert Message",
+                    "register.invalid_email",
+                    "alert.header",
                     250,
                     AlertIndex::None,
                     false,
@@ -239,8 +251,8 @@ fn trigger_button(
                     alert.show_alert(
                         systems,
                         AlertType::Inform,
-                        "Server is offline",
-                        "Alert Message",
+                        "alert.server_offline",
+                        "alert.header",
                         250,
                         AlertIndex::None,
                         false,
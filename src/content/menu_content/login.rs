@@ -1,9 +1,6 @@
 use ascending_graphics::*;
 
-use crate::{
-    Config, SCREEN_WIDTH, SystemHolder, content::*, data_types::*,
-    is_within_area, widget::*,
-};
+use crate::{SystemHolder, content::*, is_within_area, widget::*};
 
 pub struct Login {
     window: Vec<GfxType>,
@@ -11,6 +8,7 @@ pub struct Login {
     button: Vec<crate::widget::Button>,
     pub checkbox: Checkbox,
     pub textbox: Vec<Textbox>,
+    pub focus: FocusManager,
 }
 
 impl Login {
@@ -19,23 +17,26 @@ impl Login {
         let mut label = Vec::with_capacity(3);
         let mut button = Vec::with_capacity(2);
         let mut textbox = Vec::with_capacity(2);
+        let menu_style = systems.theme.menu;
+        let textbox_style = systems.theme.textbox;
+        let checkbox_style = systems.theme.checkbox;
         let size = Vec2::new(
             348.0 * systems.scale as f32,
             226.0 * systems.scale as f32,
         )
         .floor();
-        let pos = Vec2::new((SCREEN_WIDTH as f32 - size.x) * 0.5, 80.0).floor();
+        let pos = Vec2::new((systems.size.width - size.x) * 0.5, 80.0).floor();
         let mut menu_rect = Rect::new(
             &mut systems.renderer,
             Vec3::new(pos.x - 1.0, pos.y - 1.0, ORDER_MENU_WINDOW),
             size + 2.0,
-            Color::rgba(160, 160, 160, 255),
+            menu_style.color,
             0,
         );
 
         menu_rect
-            .set_border_color(Color::rgba(10, 10, 10, 255))
-            .set_border_width(1.0);
+            .set_border_color(menu_style.border_color)
+            .set_border_width(systems.theme.border_width);
         window.push(systems.gfx.add_rect(
             menu_rect,
             0,
@@ -52,7 +53,7 @@ impl Login {
                 ORDER_MENU_WINDOW_CONTENT,
             ),
             Vec2::new(size.x, (30.0 * systems.scale as f32).floor()),
-            Color::rgba(120, 120, 120, 255),
+            menu_style.header_color,
             0,
         );
 
@@ -78,7 +79,7 @@ impl Login {
                 pos.x + size.x,
                 pos.y + (219.0 * systems.scale as f32).floor(),
             )),
-            Color::rgba(240, 240, 240, 255),
+            menu_style.header_text,
         );
         let text_index = systems.gfx.add_text(
             header_text,
@@ -88,7 +89,9 @@ impl Login {
             CameraView::SubView1,
         );
 
-        systems.gfx.set_text(&text_index, "Login Window");
+        systems
+            .gfx
+            .set_text(&text_index, systems.locale.get("login.title"));
         systems.gfx.center_text(&mut systems.renderer, &text_index);
         label.push(text_index);
 
@@ -105,7 +108,7 @@ impl Login {
                     ORDER_MENU_WINDOW_CONTENT,
                 ),
                 (Vec2::new(116.0, 24.0) * systems.scale as f32).floor(),
-                Color::rgba(208, 208, 208, 255),
+                menu_style.label_color,
                 0,
             );
             let textbox_bg = Rect::new(
@@ -116,7 +119,7 @@ impl Login {
                     ORDER_MENU_WINDOW_CONTENT,
                 ),
                 (Vec2::new(184.0, 24.0) * systems.scale as f32).floor(),
-                Color::rgba(90, 90, 90, 255),
+                menu_style.field_color,
                 0,
             );
             window.push(systems.gfx.add_rect(
@@ -148,7 +151,7 @@ impl Login {
                     tpos.x + (110.0 * systems.scale as f32).floor(),
                     tpos.y + (20.0 * systems.scale as f32).floor(),
                 )),
-                Color::rgba(100, 100, 100, 255),
+                menu_style.label_text,
             );
             let textindex = systems.gfx.add_text(
                 text,
@@ -159,17 +162,17 @@ impl Login {
             );
             let (msg, disable_option) = match index {
                 1 => (
-                    "Password",
+                    "login.password",
                     vec![
                         TextDisable::Selection,
                         TextDisable::Copy,
                         TextDisable::Paste,
                     ],
                 ),
-                _ => ("Email", vec![]),
+                _ => ("login.email", vec![]),
             };
 
-            systems.gfx.set_text(&textindex, msg);
+            systems.gfx.set_text(&textindex, systems.locale.get(msg));
             label.push(textindex);
 
             let is_hidden = index == 1;
@@ -179,11 +182,11 @@ impl Login {
                 Vec2::new(142.0, addy + 2.0),
                 (0.01, 2),
                 Vec2::new(180.0, 20.0),
-                Color::rgba(240, 240, 240, 255),
+                menu_style.field_text,
                 1,
                 255,
-                Color::rgba(120, 120, 120, 255),
-                Color::rgba(10, 10, 150, 255),
+                textbox_style.background,
+                textbox_style.selection,
                 is_hidden,
                 true,
                 None,
@@ -198,32 +201,18 @@ impl Login {
             textbox.push(txtbox);
         }
 
+        let [login_text, register_text, remember_text] =
+            ["login.login", "login.register", "login.remember"]
+                .map(|key| systems.locale.text(key));
+
         button.push(Button::new(
             systems,
-            ButtonType::Rect(ButtonRect {
-                rect_color: Color::rgba(100, 100, 100, 255),
-                got_border: true,
-                border_color: Color::rgba(70, 70, 70, 255),
-                border_radius: 0.0,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    180, 180, 180, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    40, 40, 40, 255,
-                )),
-            }),
-            ButtonContentType::Text(ButtonContentText {
-                text: "Login".into(),
-                pos: Vec2::new(0.0, 7.0),
-                color: Color::rgba(230, 230, 230, 255),
-                render_layer: 1,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    80, 80, 80, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    170, 170, 170, 255,
-                )),
-            }),
+            ButtonType::Rect(menu_style.button.rect()),
+            ButtonContentType::Text(menu_style.button.text(
+                login_text,
+                Vec2::new(0.0, 7.0),
+                1,
+            )),
             Vec2::new(pos.x, pos.y),
             Vec2::new(104.0, 45.0),
             ORDER_MENU_WINDOW_CONTENT,
@@ -236,18 +225,11 @@ impl Login {
         button.push(Button::new(
             systems,
             ButtonType::None,
-            ButtonContentType::Text(ButtonContentText {
-                text: "Register".into(),
-                pos: Vec2::ZERO,
-                color: Color::rgba(80, 80, 80, 255),
-                render_layer: 1,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    240, 240, 240, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    80, 80, 80, 255,
-                )),
-            }),
+            ButtonContentType::Text(menu_style.link.button_text(
+                register_text,
+                Vec2::ZERO,
+                1,
+            )),
             Vec2::new(pos.x, pos.y),
             Vec2::new(104.0, 19.0),
             ORDER_MENU_WINDOW_CONTENT,
@@ -260,57 +242,90 @@ impl Login {
 
         let mut checkbox = Checkbox::new(
             systems,
-            CheckboxType::Rect(CheckboxRect {
-                rect_color: Color::rgba(100, 100, 100, 255),
-                got_border: true,
-                border_color: Color::rgba(50, 50, 50, 255),
-                border_radius: 2.0,
-                hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                    140, 140, 140, 255,
-                )),
-                click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                    70, 70, 70, 255,
-                )),
-            }),
-            CheckType::SetRect(CheckRect {
-                rect_color: Color::rgba(200, 200, 200, 255),
-                got_border: false,
-                border_color: Color::rgba(255, 255, 255, 255),
-                border_radius: 2.0,
-                pos: Vec2::new(5.0, 5.0),
-                size: Vec2::new(14.0, 14.0),
-            }),
+            CheckboxType::Rect(checkbox_style.rect()),
+            CheckType::SetRect(
+                checkbox_style
+                    .check(Vec2::new(5.0, 5.0), Vec2::new(14.0, 14.0)),
+            ),
             Vec2::new(pos.x, pos.y),
             Vec2::new(116.0, 92.0),
             ORDER_MENU_WINDOW_CONTENT,
             (0.01, 2),
             Vec2::new(24.0, 24.0),
             0,
-            Some(CheckboxText {
-                text: "Remember account?".into(),
-                offset_pos: Vec2::new(3.0, 2.0),
-                render_layer: 1,
-                label_size: Vec2::new(180.0, 20.0),
-                color: Color::rgba(80, 80, 80, 255),
-                hover_change: CheckboxChangeType::ColorChange(Color::rgba(
-                    240, 240, 240, 255,
-                )),
-                click_change: CheckboxChangeType::ColorChange(Color::rgba(
-                    80, 80, 80, 255,
-                )),
-            }),
+            Some(menu_style.link.checkbox_text(
+                remember_text,
+                Vec2::new(3.0, 2.0),
+                Vec2::new(180.0, 20.0),
+                1,
+            )),
             true,
             None,
         );
 
         checkbox.set_value(systems, systems.config.save_password);
 
+        let focus = FocusManager::new(
+            systems,
+            vec![
+                FocusTarget::Textbox(0),
+                FocusTarget::Textbox(1),
+                FocusTarget::Checkbox(0),
+                FocusTarget::Button(0),
+                FocusTarget::Button(1),
+            ],
+            ORDER_MENU_WINDOW_CONTENT_DETAIL2,
+            1,
+        );
+
         Login {
             window,
             label,
             button,
             checkbox,
             textbox,
+            focus,
+        }
+    }
+
+    /// Centers the window horizontally on the screen.
+    pub fn center(&mut self, systems: &mut SystemHolder) {
+        // The first rect is the window border, one pixel around the window.
+        let (pos, size) = systems.gfx.get_pos_and_size(&self.window[0]);
+        let x = ((systems.size.width - (size.x - 2.0)) * 0.5).floor();
+
+        self.move_by(systems, Vec2::new(x - (pos.x + 1.0), 0.0));
+    }
+
+    pub fn move_by(&mut self, systems: &mut SystemHolder, offset: Vec2) {
+        for gfx in self.window.iter().chain(self.label.iter()) {
+            systems.gfx.move_by(gfx, offset);
+        }
+        for button in self.button.iter_mut() {
+            button.set_pos(systems, button.base_pos + offset);
+        }
+        for textbox in self.textbox.iter_mut() {
+            let pos = Vec2::new(textbox.base_pos.x, textbox.base_pos.y);
+            textbox.set_pos(systems, pos + offset);
+        }
+        self.checkbox
+            .set_pos(systems, self.checkbox.base_pos + offset);
+        self.focus.move_by(systems, offset);
+    }
+
+    pub fn focus_area(
+        &self,
+        systems: &SystemHolder,
+        target: FocusTarget,
+    ) -> (Vec2, Vec2) {
+        match target {
+            FocusTarget::Textbox(index) => {
+                self.textbox[index].screen_area(systems)
+            }
+            FocusTarget::Button(index) => {
+                self.button[index].screen_area(systems)
+            }
+            FocusTarget::Checkbox(_) => self.checkbox.screen_area(systems),
         }
     }
 
@@ -328,6 +343,7 @@ impl Login {
             textbox.set_visible(systems, visible);
         });
         self.checkbox.set_visible(systems, visible);
+        self.focus.clear(systems);
     }
 
     pub fn hover_buttons(
@@ -492,6 +508,10 @@ pub fn click_login_textbox(
     }
 
     menu_content.selected_textbox = textbox_found;
+    menu_content
+        .login
+        .focus
+        .set_focus(systems, textbox_found.map(FocusTarget::Textbox));
 }
 
 pub fn release_login_textbox(menu_content: &mut MenuContent) {
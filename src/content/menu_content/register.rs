@@ -1,9 +1,6 @@
 use ascending_graphics::*;
 
-use crate::{
-    SCREEN_WIDTH, SystemHolder, content::*, data_types::*, is_within_area,
-    widget::*,
-};
+use crate::{SystemHolder, content::*, is_within_area, widget::*};
 
 pub struct Register {
     window: Vec<GfxType>,
@@ -12,6 +9,7 @@ pub struct Register {
     button: Vec<crate::widget::Button>,
     pub textbox: Vec<Textbox>,
     pub image: GfxType,
+    pub focus: FocusManager,
 }
 
 impl Register {
@@ -20,19 +18,21 @@ impl Register {
         let mut label = Vec::with_capacity(7);
         let mut button = Vec::with_capacity(4);
         let mut textbox = Vec::with_capacity(5);
+        let menu_style = systems.theme.menu;
+        let textbox_style = systems.theme.textbox;
         let size = (Vec2::new(348.0, 375.0) * systems.scale as f32).floor();
-        let pos = Vec2::new((SCREEN_WIDTH as f32 - size.x) * 0.5, 20.0).floor();
+        let pos = Vec2::new((systems.size.width - size.x) * 0.5, 20.0).floor();
         let mut menu_rect = Rect::new(
             &mut systems.renderer,
             Vec3::new(pos.x - 1.0, pos.y - 1.0, ORDER_MENU_WINDOW),
             size + 2.0,
-            Color::rgba(160, 160, 160, 255),
+            menu_style.color,
             0,
         );
 
         menu_rect
-            .set_border_color(Color::rgba(10, 10, 10, 255))
-            .set_border_width(1.0);
+            .set_border_color(menu_style.border_color)
+            .set_border_width(systems.theme.border_width);
         window.push(systems.gfx.add_rect(
             menu_rect,
             0,
@@ -49,7 +49,7 @@ impl Register {
                 ORDER_MENU_WINDOW_CONTENT,
             ),
             Vec2::new(size.x, (30.0 * systems.scale as f32).floor()),
-            Color::rgba(120, 120, 120, 255),
+            menu_style.header_color,
             0,
         );
 
@@ -75,7 +75,7 @@ impl Register {
                 pos.x + size.x,
                 pos.y + (368.0 * systems.scale as f32).floor(),
             )),
-            Color::rgba(240, 240, 240, 255),
+            menu_style.header_text,
         );
 
         let text_index = systems.gfx.add_text(
@@ -86,7 +86,9 @@ impl Register {
             CameraView::SubView1,
         );
 
-        systems.gfx.set_text(&text_index, "Register Window");
+        systems
+            .gfx
+            .set_text(&text_index, systems.locale.get("register.title"));
         systems.gfx.center_text(&mut systems.renderer, &text_index);
         label.push(text_index);
 
@@ -106,7 +108,7 @@ impl Register {
                     ORDER_MENU_WINDOW_CONTENT,
                 ),
                 (Vec2::new(116.0, 24.0) * systems.scale as f32).floor(),
-                Color::rgba(208, 208, 208, 255),
+                menu_style.label_color,
                 0,
             );
             let textbox_bg = Rect::new(
@@ -117,7 +119,7 @@ impl Register {
                     ORDER_MENU_WINDOW_CONTENT,
                 ),
                 (Vec2::new(184.0, 24.0) * systems.scale as f32).floor(),
-                Color::rgba(90, 90, 90, 255),
+                menu_style.field_color,
                 0,
             );
 
@@ -150,7 +152,7 @@ impl Register {
                     tpos.x + (110.0 * systems.scale as f32).floor(),
                     tpos.y + (20.0 * systems.scale as f32).floor(),
                 )),
-                Color::rgba(100, 100, 100, 255),
+                menu_style.label_text,
             );
             let textindex = systems.gfx.add_text(
                 text,
@@ -160,20 +162,18 @@ impl Register {
                 CameraView::SubView1,
             );
             let (msg, hide_content) = match index {
-                1 => ("Retype", false),
-                2 => ("Password", true),
-                3 => ("Retype", true),
-                4 => ("Username", false),
-                _ => ("Email", false),
+                1 => ("register.retype", false),
+                2 => ("register.password", true),
+                3 => ("register.retype", true),
+                4 => ("register.username", false),
+                _ => ("register.email", false),
             };
 
-            systems.gfx.set_text(&textindex, msg);
+            systems.gfx.set_text(&textindex, systems.locale.get(msg));
             label.push(textindex);
 
             let tooltip = match index {
-                0 | 1 => {
-                    Some("This email will be used for password reset".into())
-                }
+                0 | 1 => Some(systems.locale.text("register.email_tooltip")),
                 _ => None,
             };
 
@@ -183,11 +183,11 @@ impl Register {
                 Vec2::new(142.0, addy + 2.0),
                 (0.01, 2),
                 Vec2::new(180.0, 20.0),
-                Color::rgba(240, 240, 240, 255),
+                menu_style.field_text,
                 1,
                 255,
-                Color::rgba(120, 120, 120, 255),
-                Color::rgba(10, 10, 150, 255),
+                textbox_style.background,
+                textbox_style.selection,
                 hide_content,
                 true,
                 tooltip,
@@ -205,7 +205,7 @@ impl Register {
                 ORDER_MENU_WINDOW_CONTENT,
             ),
             (Vec2::new(80.0, 80.0) * systems.scale as f32).floor(),
-            Color::rgba(120, 120, 120, 255),
+            menu_style.preview_color,
             0,
         );
 
@@ -252,7 +252,7 @@ impl Register {
                 pos.x + (306.0 * systems.scale as f32).floor(),
                 pos.y + (168.0 * systems.scale as f32).floor(),
             )),
-            Color::rgba(80, 80, 80, 255),
+            menu_style.text,
         );
         let sprite_index = systems.gfx.add_text(
             sprite_label,
@@ -262,38 +262,25 @@ impl Register {
             CameraView::SubView1,
         );
 
-        systems.gfx.set_text(&sprite_index, "Sprite Selection");
+        systems
+            .gfx
+            .set_text(&sprite_index, systems.locale.get("register.sprite"));
         systems
             .gfx
             .center_text(&mut systems.renderer, &sprite_index);
         label.push(sprite_index);
 
+        let register_text = systems.locale.text("register.register");
+        let sign_in_text = systems.locale.text("register.sign_in");
+
         button.push(Button::new(
             systems,
-            ButtonType::Rect(ButtonRect {
-                rect_color: Color::rgba(100, 100, 100, 255),
-                got_border: true,
-                border_color: Color::rgba(70, 70, 70, 255),
-                border_radius: 0.0,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    180, 180, 180, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    40, 40, 40, 255,
-                )),
-            }),
-            ButtonContentType::Text(ButtonContentText {
-                text: "Register".into(),
-                pos: Vec2::new(0.0, 7.0),
-                color: Color::rgba(230, 230, 230, 255),
-                render_layer: 1,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    80, 80, 80, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    170, 170, 170, 255,
-                )),
-            }),
+            ButtonType::Rect(menu_style.button.rect()),
+            ButtonContentType::Text(menu_style.button.text(
+                register_text,
+                Vec2::new(0.0, 7.0),
+                1,
+            )),
             Vec2::new(pos.x, pos.y),
             Vec2::new(104.0, 45.0),
             ORDER_MENU_WINDOW_CONTENT,
@@ -306,18 +293,11 @@ impl Register {
         button.push(Button::new(
             systems,
             ButtonType::None,
-            ButtonContentType::Text(ButtonContentText {
-                text: "Sign In".into(),
-                pos: Vec2::ZERO,
-                color: Color::rgba(80, 80, 80, 255),
-                render_layer: 1,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    240, 240, 240, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    80, 80, 80, 255,
-                )),
-            }),
+            ButtonContentType::Text(menu_style.link.button_text(
+                sign_in_text,
+                Vec2::ZERO,
+                1,
+            )),
             Vec2::new(pos.x, pos.y),
             Vec2::new(104.0, 19.0),
             ORDER_MENU_WINDOW_CONTENT,
@@ -329,18 +309,7 @@ impl Register {
         ));
         button.push(Button::new(
             systems,
-            ButtonType::Rect(ButtonRect {
-                rect_color: Color::rgba(100, 100, 100, 255),
-                got_border: true,
-                border_color: Color::rgba(70, 70, 70, 255),
-                border_radius: 0.0,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    180, 180, 180, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    40, 40, 40, 255,
-                )),
-            }),
+            ButtonType::Rect(menu_style.button.rect()),
             ButtonContentType::Image(ButtonContentImg {
                 res: systems.resource.horizontal_arrow.allocation,
                 pos: Vec2::ZERO,
@@ -360,18 +329,7 @@ impl Register {
         ));
         button.push(Button::new(
             systems,
-            ButtonType::Rect(ButtonRect {
-                rect_color: Color::rgba(100, 100, 100, 255),
-                got_border: true,
-                border_color: Color::rgba(70, 70, 70, 255),
-                border_radius: 0.0,
-                hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                    180, 180, 180, 255,
-                )),
-                click_change: ButtonChangeType::ColorChange(Color::rgba(
-                    40, 40, 40, 255,
-                )),
-            }),
+            ButtonType::Rect(menu_style.button.rect()),
             ButtonContentType::Image(ButtonContentImg {
                 res: systems.resource.horizontal_arrow.allocation,
                 pos: Vec2::ZERO,
@@ -404,7 +362,7 @@ impl Register {
                 pos.x + (278.0 * systems.scale as f32).floor(),
                 pos.y + (140.0 * systems.scale as f32).floor(),
             )),
-            Color::rgba(80, 80, 80, 255),
+            menu_style.text,
         );
         let unique_label = systems.gfx.add_text(
             sprite_number_text,
@@ -419,6 +377,18 @@ impl Register {
             .gfx
             .center_text(&mut systems.renderer, &unique_label);
 
+        // Fields first, then the sprite arrows, Register and Sign In.
+        let focus_order = (0..textbox.len())
+            .map(FocusTarget::Textbox)
+            .chain([2, 3, 0, 1].map(FocusTarget::Button))
+            .collect();
+        let focus = FocusManager::new(
+            systems,
+            focus_order,
+            ORDER_MENU_WINDOW_CONTENT_DETAIL2,
+            1,
+        );
+
         Register {
             window,
             label,
@@ -426,6 +396,49 @@ impl Register {
             button,
             textbox,
             image,
+            focus,
+        }
+    }
+
+    /// Centers the window horizontally on the screen.
+    pub fn center(&mut self, systems: &mut SystemHolder) {
+        // The first rect is the window border, one pixel around the window.
+        let (pos, size) = systems.gfx.get_pos_and_size(&self.window[0]);
+        let x = ((systems.size.width - (size.x - 2.0)) * 0.5).floor();
+
+        self.move_by(systems, Vec2::new(x - (pos.x + 1.0), 0.0));
+    }
+
+    pub fn move_by(&mut self, systems: &mut SystemHolder, offset: Vec2) {
+        for gfx in self.window.iter().chain(self.label.iter()) {
+            systems.gfx.move_by(gfx, offset);
+        }
+        for button in self.button.iter_mut() {
+            button.set_pos(systems, button.base_pos + offset);
+        }
+        for textbox in self.textbox.iter_mut() {
+            let pos = Vec2::new(textbox.base_pos.x, textbox.base_pos.y);
+            textbox.set_pos(systems, pos + offset);
+        }
+        systems.gfx.move_by(&self.unique_label, offset);
+        systems.gfx.move_by(&self.image, offset);
+        self.focus.move_by(systems, offset);
+    }
+
+    pub fn focus_area(
+        &self,
+        systems: &SystemHolder,
+        target: FocusTarget,
+    ) -> (Vec2, Vec2) {
+        match target {
+            FocusTarget::Textbox(index) => {
+                self.textbox[index].screen_area(systems)
+            }
+            FocusTarget::Button(index) => {
+                self.button[index].screen_area(systems)
+            }
+            // The window has no checkboxes.
+            FocusTarget::Checkbox(_) => (Vec2::ZERO, Vec2::ZERO),
         }
     }
 
@@ -444,6 +457,7 @@ impl Register {
         });
         systems.gfx.set_visible(&self.unique_label, visible);
         systems.gfx.set_visible(&self.image, visible);
+        self.focus.clear(systems);
     }
 
     pub fn hover_buttons(
@@ -553,6 +567,10 @@ pub fn click_register_textbox(
     }
 
     menu_content.selected_textbox = textbox_found;
+    menu_content
+        .register
+        .focus
+        .set_focus(systems, textbox_found.map(FocusTarget::Textbox));
 }
 
 pub fn release_register_textbox(menu_content: &mut MenuContent) {
@@ -1,10 +1,9 @@
 use std::cmp::Ordering;
-use std::fs::{self, DirEntry, ReadDir};
-use std::path::Path;
+use std::fs::{self, DirEntry};
 
 use ascending_graphics::*;
 
-use crate::{ClientError, TILE_SIZE};
+use crate::TILE_SIZE;
 
 pub struct TextureData {
     pub allocation: usize,
@@ -1,7 +1,7 @@
 use crate::socket::*;
 use ascending_graphics::*;
 use serde::{Deserialize, Serialize};
-use speedy::{Endianness, Readable, Writable};
+use speedy::{Readable, Writable};
 
 #[derive(
     Copy,
@@ -1,5 +1,5 @@
 use ascending_graphics::*;
-use snafu::{Backtrace, Whatever, prelude::*};
+use snafu::Backtrace;
 use std::sync::{PoisonError, TryLockError};
 pub type Result<T> = std::result::Result<T, ClientError>;
 
@@ -209,6 +209,24 @@ pub enum ClientError {
         #[snafu(backtrace)]
         backtrace: Backtrace,
     },
+    #[snafu(transparent)]
+    TomlSer {
+        source: toml::ser::Error,
+        #[snafu(backtrace)]
+        backtrace: Backtrace,
+    },
+    #[snafu(transparent)]
+    SerdeJson {
+        source: serde_json::Error,
+        #[snafu(backtrace)]
+        backtrace: Backtrace,
+    },
+    #[snafu(transparent)]
+    Speedy {
+        source: speedy::Error,
+        #[snafu(backtrace)]
+        backtrace: Backtrace,
+    },
     #[snafu(display("Missing Kind. BACKTRACE: {backtrace:?}"))]
     MissingKind {
         #[snafu(backtrace)]
@@ -240,6 +258,22 @@ pub enum ClientError {
         #[snafu(backtrace)]
         backtrace: Backtrace,
     },
+    #[snafu(display(
+        "Map {name} failed its integrity check. BACKTRACE: {backtrace:?}"
+    ))]
+    MapIntegrity {
+        name: String,
+        #[snafu(backtrace)]
+        backtrace: Backtrace,
+    },
+    #[snafu(display(
+        "Failed to convert data: {message}. BACKTRACE: {backtrace:?}"
+    ))]
+    DataConvert {
+        message: String,
+        #[snafu(backtrace)]
+        backtrace: Backtrace,
+    },
 }
 
 impl<T> From<TryLockError<T>> for ClientError {
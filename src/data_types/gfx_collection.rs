@@ -1,7 +1,7 @@
 use std::borrow::Cow;
 
 use ascending_graphics::*;
-use cosmic_text::Attrs;
+use cosmic_text::{Attrs, Metrics};
 use indexmap::IndexSet;
 use slotmap::SlotMap;
 
@@ -333,6 +333,23 @@ impl GfxCollection {
         }
     }
 
+    /// Shifts a gfx by `offset`, text bounds are moved along with it.
+    pub fn move_by(&mut self, index: &GfxType, offset: Vec2) {
+        let pos = self.get_pos(index);
+        self.set_pos(index, pos + Vec3::new(offset.x, offset.y, 0.0));
+
+        if let Some(data) = self.storage.get_mut(index.0)
+            && let GfxEnum::Text(gfx) = &mut data.gfx
+            && let Some(bounds) = &mut gfx.bounds
+        {
+            bounds.left += offset.x;
+            bounds.right += offset.x;
+            bounds.bottom += offset.y;
+            bounds.top += offset.y;
+            gfx.changed = true;
+        }
+    }
+
     pub fn set_override_pos(&mut self, index: &GfxType, pos: Vec3) {
         if let Some(data) = self.storage.get_mut(index.0) {
             match &mut data.gfx {
@@ -410,6 +427,15 @@ impl GfxCollection {
         }
     }
 
+    pub fn set_text_metrics(&mut self, index: &GfxType, metrics: Metrics) {
+        if let Some(data) = self.storage.get_mut(index.0)
+            && let GfxEnum::Text(gfx) = &mut data.gfx
+        {
+            gfx.get_text_buffer().set_metrics(metrics);
+            gfx.changed = true;
+        }
+    }
+
     pub fn set_uv(&mut self, index: &GfxType, uv: Vec4) {
         if let Some(data) = self.storage.get_mut(index.0)
             && let GfxEnum::Image(gfx) = &mut data.gfx
@@ -1,6 +1,6 @@
 use crate::socket::*;
 use serde::{Deserialize, Serialize};
-use speedy::{Endianness, Readable, Writable};
+use speedy::{Readable, Writable};
 
 #[derive(
     Clone,
@@ -1,8 +1,9 @@
-use crate::{GlobalKey, data_types::*, socket::*};
+use crate::socket::*;
 use ascending_graphics::*;
 use serde::{Deserialize, Serialize};
 
 pub const TILE_SIZE: usize = 20;
+/// Default and smallest size of the game window.
 pub const SCREEN_WIDTH: usize = 800;
 pub const SCREEN_HEIGHT: usize = 600;
 
@@ -133,6 +134,7 @@ pub const COLOR_WHITE: Color = Color::rgba(255, 255, 255, 255);
 pub const COLOR_RED: Color = Color::rgba(230, 30, 30, 255);
 pub const COLOR_BLUE: Color = Color::rgba(30, 30, 230, 255);
 pub const COLOR_GREEN: Color = Color::rgba(40, 255, 40, 255);
+pub const COLOR_PARTY: Color = Color::rgba(90, 200, 230, 255);
 
 pub const VITALS_MAX: usize = VitalTypes::Count as usize;
 pub const MAX_INV: usize = 30;
@@ -155,15 +157,20 @@ pub const ORDER_SERVER_STATUS: f32 = 10.8;
 pub const ORDER_MAP_ITEM: f32 = 8.5;
 pub const ORDER_PLAYER: f32 = 8.0;
 pub const ORDER_NPC: f32 = 8.0;
+pub const ORDER_NPC_FOLLOW_RANGE: f32 = 7.95;
+pub const ORDER_NPC_SIGHT: f32 = 7.94;
 pub const ORDER_HPBAR_BG: f32 = 7.9;
 pub const ORDER_HPBAR: f32 = 7.8;
 pub const ORDER_TARGET: f32 = 7.7;
 // Upper Map Order 5.1 - 5.0
 pub const ORDER_LIGHT: f32 = 4.9;
 pub const ORDER_ENTITY_NAME: f32 = 4.8;
+pub const ORDER_ENTITY_ICON: f32 = 4.79;
 pub const ORDER_FLOAT_TEXT_BG: f32 = 4.7;
 pub const ORDER_FLOAT_TEXT: f32 = 4.6;
 pub const ORDER_MAP_FADE: f32 = 4.0;
+pub const ORDER_DEATH_SCREEN_BG: f32 = 3.99;
+pub const ORDER_DEATH_SCREEN_TEXT: f32 = 3.98;
 pub const ORDER_VITAL_BG: f32 = 3.9;
 pub const ORDER_VITAL_HPBG: f32 = 3.8;
 pub const ORDER_VITAL_HP: f32 = 3.7;
@@ -181,6 +188,9 @@ pub const ORDER_ALERT_TEXT: f32 = 1.57;
 pub const ORDER_ALERT_TEXTBOX_BG: f32 = 1.57;
 pub const ORDER_ALERT_TEXTBOX: f32 = 1.56;
 pub const ORDER_ALERT_BUTTON: f32 = 1.57;
+pub const ORDER_CONTEXT_MENU: f32 = 1.09;
+pub const ORDER_CONTEXT_MENU_SELECT: f32 = 1.08;
+pub const ORDER_CONTEXT_MENU_TEXT: f32 = 1.07;
 pub const ORDER_TOOLTIP: f32 = 1.05;
 pub const ORDER_TOOLTIP_TEXT: f32 = 1.0;
 pub const ORDER_FADE: f32 = 0.9;
@@ -1,9 +1,17 @@
+pub mod content_db;
+pub mod data_convert;
 pub mod items;
 pub mod map;
+pub mod map_cache;
+pub mod map_effects;
 pub mod npc;
 pub mod shops;
 
+pub use content_db::*;
+pub use data_convert::*;
 pub use items::*;
 pub use map::*;
+pub use map_cache::*;
+pub use map_effects::*;
 pub use npc::*;
 pub use shops::*;
@@ -0,0 +1,551 @@
+use log::{info, warn};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use speedy::{Endianness, Readable};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Read;
+use std::path::Path;
+use tokio::sync::mpsc;
+
+use crate::{
+    DatabaseHolder, ItemData, NpcData, ShopData, content::get_dir_files,
+    data_types::*,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContentKind {
+    Item,
+    Npc,
+    Shop,
+}
+
+impl ContentKind {
+    pub const ALL: [ContentKind; 3] =
+        [ContentKind::Item, ContentKind::Npc, ContentKind::Shop];
+
+    pub fn folder(self) -> &'static str {
+        match self {
+            ContentKind::Item => "items",
+            ContentKind::Npc => "npcs",
+            ContentKind::Shop => "shops",
+        }
+    }
+
+    pub fn max(self) -> usize {
+        match self {
+            ContentKind::Item => MAX_ITEMS,
+            ContentKind::Npc => MAX_NPCS,
+            ContentKind::Shop => MAX_SHOPS,
+        }
+    }
+
+    pub fn file_name(self, id: usize) -> String {
+        format!("./data/{}/{id}.bin", self.folder())
+    }
+}
+
+impl fmt::Display for ContentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentKind::Item => write!(f, "Item"),
+            ContentKind::Npc => write!(f, "Npc"),
+            ContentKind::Shop => write!(f, "Shop"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContentIssue {
+    pub kind: ContentKind,
+    pub id: usize,
+    pub message: String,
+}
+
+impl ContentIssue {
+    pub fn new(kind: ContentKind, id: usize, message: String) -> Self {
+        Self { kind, id, message }
+    }
+}
+
+impl fmt::Display for ContentIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.kind, self.id, self.message)
+    }
+}
+
+pub enum ContentFile<T> {
+    Loaded(T),
+    Missing,
+    Malformed(String),
+}
+
+pub fn read_content_file<T>(
+    kind: ContentKind,
+    id: usize,
+    buffer: &mut Vec<u8>,
+) -> ContentFile<T>
+where
+    T: for<'a> Readable<'a, Endianness>,
+{
+    buffer.clear();
+
+    match OpenOptions::new().read(true).open(kind.file_name(id)) {
+        Ok(mut file) => {
+            if let Err(e) = file.read_to_end(buffer) {
+                return ContentFile::Malformed(e.to_string());
+            }
+
+            match T::read_from_buffer_with_ctx(Endianness::LittleEndian, buffer)
+            {
+                Ok(data) => ContentFile::Loaded(data),
+                Err(e) => ContentFile::Malformed(e.to_string()),
+            }
+        }
+        Err(_) => ContentFile::Missing,
+    }
+}
+
+/// Reads every file of a kind so the vector index is the file id. Missing
+/// files are `None` and malformed files are swapped for a default entry.
+fn read_all<T>(
+    kind: ContentKind,
+    issues: &mut Vec<ContentIssue>,
+) -> Vec<Option<T>>
+where
+    T: for<'a> Readable<'a, Endianness> + Default,
+{
+    let mut data = Vec::with_capacity(kind.max());
+    let mut buffer = Vec::with_capacity(2048);
+
+    for id in 0..kind.max() {
+        match read_content_file::<T>(kind, id, &mut buffer) {
+            ContentFile::Loaded(entry) => data.push(Some(entry)),
+            ContentFile::Missing => {
+                issues.push(ContentIssue::new(
+                    kind,
+                    id,
+                    "file not found".to_string(),
+                ));
+                data.push(None);
+            }
+            ContentFile::Malformed(e) => {
+                issues.push(ContentIssue::new(
+                    kind,
+                    id,
+                    format!("malformed file: {e}"),
+                ));
+                data.push(Some(T::default()));
+            }
+        }
+    }
+
+    data
+}
+
+/// Loads every file of a kind, indexed by file id. Files that could not be
+/// read are logged.
+pub fn load_content<T>(kind: ContentKind) -> Vec<Option<T>>
+where
+    T: for<'a> Readable<'a, Endianness> + Default,
+{
+    let mut issues = Vec::new();
+    let data = read_all(kind, &mut issues);
+
+    for issue in issues {
+        warn!("{issue}");
+    }
+
+    data
+}
+
+/// Fills the gaps left by missing files with a default entry so the game can
+/// keep indexing the content by id.
+pub fn with_placeholders<T: Default>(data: Vec<Option<T>>) -> Vec<T> {
+    data.into_iter().map(Option::unwrap_or_default).collect()
+}
+
+fn has_entry<T>(data: &[Option<T>], id: usize) -> bool {
+    matches!(data.get(id), Some(Some(_)))
+}
+
+/// The amount of assets the content is allowed to point at.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ContentLimits {
+    pub item_sprites: usize,
+    pub npc_sprites: usize,
+    pub sounds: usize,
+}
+
+impl ContentLimits {
+    pub fn from_dirs() -> Self {
+        Self {
+            item_sprites: get_dir_files("./images/items/").len(),
+            npc_sprites: get_dir_files("./images/npc/").len(),
+            sounds: get_dir_files("./audio/").len(),
+        }
+    }
+}
+
+pub fn validate_item(
+    id: usize,
+    item: &ItemData,
+    limits: &ContentLimits,
+) -> Vec<ContentIssue> {
+    let mut issues = Vec::new();
+    let kind = ContentKind::Item;
+
+    if item.sprite as usize >= limits.item_sprites {
+        issues.push(ContentIssue::new(
+            kind,
+            id,
+            format!("unknown sprite {}", item.sprite),
+        ));
+    }
+
+    if item.soundid as usize >= limits.sounds && item.soundid > 0 {
+        issues.push(ContentIssue::new(
+            kind,
+            id,
+            format!("unknown soundid {}", item.soundid),
+        ));
+    }
+
+    if let Some(sound) = &item.sound_index
+        && !Path::new(&format!("./audio/{sound}")).exists()
+    {
+        issues.push(ContentIssue::new(
+            kind,
+            id,
+            format!("missing sound file {sound}"),
+        ));
+    }
+
+    if item.stackable && item.stacklimit == 0 {
+        issues.push(ContentIssue::new(
+            kind,
+            id,
+            "stackable with a stack limit of 0".to_string(),
+        ));
+    }
+
+    issues
+}
+
+pub fn validate_npc(
+    id: usize,
+    npc: &NpcData,
+    has_item: impl Fn(usize) -> bool,
+    limits: &ContentLimits,
+) -> Vec<ContentIssue> {
+    let mut issues = Vec::new();
+    let kind = ContentKind::Npc;
+
+    if npc.sprite < 0 || npc.sprite as usize >= limits.npc_sprites {
+        issues.push(ContentIssue::new(
+            kind,
+            id,
+            format!("unknown sprite {}", npc.sprite),
+        ));
+    }
+
+    if npc.mindamage > npc.maxdamage {
+        issues.push(ContentIssue::new(
+            kind,
+            id,
+            format!(
+                "mindamage {} is above maxdamage {}",
+                npc.mindamage, npc.maxdamage
+            ),
+        ));
+    }
+
+    for drop in npc.drops.iter() {
+        for drop_item in drop.items.iter() {
+            if drop_item.amount > 0 && !has_item(drop_item.item as usize) {
+                issues.push(ContentIssue::new(
+                    kind,
+                    id,
+                    format!("drop points at missing item {}", drop_item.item),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+pub fn validate_shop(
+    id: usize,
+    shop: &ShopData,
+    has_item: impl Fn(usize) -> bool,
+) -> Vec<ContentIssue> {
+    let mut issues = Vec::new();
+    let kind = ContentKind::Shop;
+
+    if shop.max_item as usize > MAX_SHOP_ITEM {
+        issues.push(ContentIssue::new(
+            kind,
+            id,
+            format!("max_item {} is above {MAX_SHOP_ITEM}", shop.max_item),
+        ));
+    }
+
+    for (slot, shop_item) in
+        shop.item.iter().take(shop.max_item as usize).enumerate()
+    {
+        if !has_item(shop_item.index as usize) {
+            issues.push(ContentIssue::new(
+                kind,
+                id,
+                format!(
+                    "slot {slot} points at missing item {}",
+                    shop_item.index
+                ),
+            ));
+        }
+    }
+
+    issues
+}
+
+/// Validates content indexed by file id, where `None` marks a missing file.
+pub fn validate_database(
+    item: &[Option<ItemData>],
+    npc: &[Option<NpcData>],
+    shop: &[Option<ShopData>],
+    limits: &ContentLimits,
+) -> Vec<ContentIssue> {
+    let mut issues = Vec::new();
+    let has_item = |index| has_entry(item, index);
+
+    for (id, data) in item.iter().enumerate() {
+        if let Some(data) = data {
+            issues.extend(validate_item(id, data, limits));
+        }
+    }
+
+    for (id, data) in npc.iter().enumerate() {
+        if let Some(data) = data {
+            issues.extend(validate_npc(id, data, has_item, limits));
+        }
+    }
+
+    for (id, data) in shop.iter().enumerate() {
+        if let Some(data) = data {
+            issues.extend(validate_shop(id, data, has_item));
+        }
+    }
+
+    issues
+}
+
+/// Reads every data file from disk and reports anything that would break the
+/// client. Used by the validator binary.
+pub fn validate_data_dir(limits: &ContentLimits) -> Vec<ContentIssue> {
+    let mut issues = Vec::new();
+    let item = read_all::<ItemData>(ContentKind::Item, &mut issues);
+    let npc = read_all::<NpcData>(ContentKind::Npc, &mut issues);
+    let shop = read_all::<ShopData>(ContentKind::Shop, &mut issues);
+
+    issues.extend(validate_database(&item, &npc, &shop, limits));
+    issues
+}
+
+/// Kind and id of a content data file such as `./data/items/12.bin`.
+pub fn content_file_id(path: &Path) -> Option<(ContentKind, usize)> {
+    if path.extension()? != "bin" {
+        return None;
+    }
+
+    let folder = path.parent()?.file_name()?;
+    let kind = ContentKind::ALL
+        .into_iter()
+        .find(|kind| folder == kind.folder())?;
+    let id = path.file_stem()?.to_str()?.parse::<usize>().ok()?;
+
+    (id < kind.max()).then_some((kind, id))
+}
+
+/// Watches the data folders in the background and reports files that were
+/// created or changed on disk so designers can see edits without restarting.
+pub struct ContentReloader {
+    receiver: Option<mpsc::UnboundedReceiver<(ContentKind, usize)>>,
+    /// Kept alive for as long as changes should be reported.
+    _watcher: Option<RecommendedWatcher>,
+    pub limits: ContentLimits,
+}
+
+impl ContentReloader {
+    pub fn new(enabled: bool) -> Self {
+        let limits = ContentLimits::from_dirs();
+
+        if !enabled {
+            return Self {
+                receiver: None,
+                _watcher: None,
+                limits,
+            };
+        }
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        let watcher =
+            notify::recommended_watcher(move |event: notify::Result<Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+
+                if !matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_)
+                ) {
+                    return;
+                }
+
+                for change in
+                    event.paths.iter().filter_map(|path| content_file_id(path))
+                {
+                    let _ = sender.send(change);
+                }
+            })
+            .and_then(|mut watcher| {
+                for kind in ContentKind::ALL {
+                    watcher.watch(
+                        Path::new(&format!("./data/{}", kind.folder())),
+                        RecursiveMode::NonRecursive,
+                    )?;
+                }
+
+                Ok(watcher)
+            });
+
+        match watcher {
+            Ok(watcher) => {
+                info!("Content hot reload enabled");
+
+                Self {
+                    receiver: Some(receiver),
+                    _watcher: Some(watcher),
+                    limits,
+                }
+            }
+            Err(e) => {
+                warn!("Content hot reload is unavailable, Err: {e}");
+
+                Self {
+                    receiver: None,
+                    _watcher: None,
+                    limits,
+                }
+            }
+        }
+    }
+
+    pub fn changed(&mut self) -> Vec<(ContentKind, usize)> {
+        let mut changed = Vec::new();
+
+        if let Some(receiver) = &mut self.receiver {
+            while let Ok(change) = receiver.try_recv() {
+                if !changed.contains(&change) {
+                    changed.push(change);
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+fn replace_entry<T>(data: &mut Vec<T>, id: usize, entry: T) -> bool {
+    if let Some(slot) = data.get_mut(id) {
+        *slot = entry;
+        true
+    } else if id == data.len() {
+        data.push(entry);
+        true
+    } else {
+        false
+    }
+}
+
+/// Reloads any data files that changed on disk. Bad files are reported and
+/// the previous entry is kept.
+pub fn reload_content(base: &mut DatabaseHolder) {
+    let changed = base.reloader.changed();
+
+    if changed.is_empty() {
+        return;
+    }
+
+    let limits = base.reloader.limits;
+    let mut buffer = Vec::with_capacity(2048);
+
+    for (kind, id) in changed {
+        let (replaced, issues) = match kind {
+            ContentKind::Item => {
+                match read_content_file::<ItemData>(kind, id, &mut buffer) {
+                    ContentFile::Loaded(data) => {
+                        let issues = validate_item(id, &data, &limits);
+                        (replace_entry(&mut base.item, id, data), issues)
+                    }
+                    ContentFile::Missing => continue,
+                    ContentFile::Malformed(e) => {
+                        warn!(
+                            "{kind} {id} is malformed, keeping old data: {e}"
+                        );
+                        continue;
+                    }
+                }
+            }
+            ContentKind::Npc => {
+                match read_content_file::<NpcData>(kind, id, &mut buffer) {
+                    ContentFile::Loaded(data) => {
+                        let item_count = base.item.len();
+                        let issues = validate_npc(
+                            id,
+                            &data,
+                            |index| index < item_count,
+                            &limits,
+                        );
+                        (replace_entry(&mut base.npc, id, data), issues)
+                    }
+                    ContentFile::Missing => continue,
+                    ContentFile::Malformed(e) => {
+                        warn!(
+                            "{kind} {id} is malformed, keeping old data: {e}"
+                        );
+                        continue;
+                    }
+                }
+            }
+            ContentKind::Shop => {
+                match read_content_file::<ShopData>(kind, id, &mut buffer) {
+                    ContentFile::Loaded(data) => {
+                        let item_count = base.item.len();
+                        let issues = validate_shop(id, &data, |index| {
+                            index < item_count
+                        });
+                        (replace_entry(&mut base.shop, id, data), issues)
+                    }
+                    ContentFile::Missing => continue,
+                    ContentFile::Malformed(e) => {
+                        warn!(
+                            "{kind} {id} is malformed, keeping old data: {e}"
+                        );
+                        continue;
+                    }
+                }
+            }
+        };
+
+        if replaced {
+            info!("Reloaded {kind} {id}");
+        } else {
+            warn!("{kind} {id} is out of order and was not reloaded");
+        }
+
+        for issue in issues {
+            warn!("{issue}");
+        }
+    }
+}
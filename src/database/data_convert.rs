@@ -0,0 +1,205 @@
+use snafu::Backtrace;
+use speedy::{Endianness, Readable, Writable};
+use std::fmt;
+use std::path::Path;
+
+use crate::{ClientError, ItemData, MapData, NpcData, Result, ShopData};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataFormat {
+    Bin,
+    Json,
+    Toml,
+}
+
+impl DataFormat {
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "bin" => Some(DataFormat::Bin),
+            "json" => Some(DataFormat::Json),
+            "toml" => Some(DataFormat::Toml),
+            _ => None,
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "bin" => Some(DataFormat::Bin),
+            "json" => Some(DataFormat::Json),
+            "toml" => Some(DataFormat::Toml),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            DataFormat::Bin => "bin",
+            DataFormat::Json => "json",
+            DataFormat::Toml => "toml",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataKind {
+    Item,
+    Npc,
+    Shop,
+    Map,
+}
+
+impl DataKind {
+    pub const ALL: [DataKind; 4] =
+        [DataKind::Item, DataKind::Npc, DataKind::Shop, DataKind::Map];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "item" | "items" => Some(DataKind::Item),
+            "npc" | "npcs" => Some(DataKind::Npc),
+            "shop" | "shops" => Some(DataKind::Shop),
+            "map" | "maps" => Some(DataKind::Map),
+            _ => None,
+        }
+    }
+
+    pub fn folder(self) -> &'static str {
+        match self {
+            DataKind::Item => "./data/items",
+            DataKind::Npc => "./data/npcs",
+            DataKind::Shop => "./data/shops",
+            DataKind::Map => "./data/maps",
+        }
+    }
+}
+
+impl fmt::Display for DataKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataKind::Item => write!(f, "items"),
+            DataKind::Npc => write!(f, "npcs"),
+            DataKind::Shop => write!(f, "shops"),
+            DataKind::Map => write!(f, "maps"),
+        }
+    }
+}
+
+fn convert_as<T>(
+    data: &[u8],
+    from: DataFormat,
+    to: DataFormat,
+) -> Result<Vec<u8>>
+where
+    T: for<'a> Readable<'a, Endianness>
+        + Writable<Endianness>
+        + serde::Serialize
+        + serde::de::DeserializeOwned,
+{
+    let value: T = match from {
+        DataFormat::Bin => {
+            T::read_from_buffer_with_ctx(Endianness::LittleEndian, data)?
+        }
+        DataFormat::Json => serde_json::from_slice(data)?,
+        DataFormat::Toml => toml::from_str(std::str::from_utf8(data)?)?,
+    };
+
+    Ok(match to {
+        DataFormat::Bin => {
+            value.write_to_vec_with_ctx(Endianness::LittleEndian)?
+        }
+        DataFormat::Json => serde_json::to_vec_pretty(&value)?,
+        DataFormat::Toml => toml::to_string_pretty(&value)?.into_bytes(),
+    })
+}
+
+/// Converts one data file between the speedy .bin layout and pretty
+/// JSON or TOML.
+pub fn convert_data(
+    kind: DataKind,
+    data: &[u8],
+    from: DataFormat,
+    to: DataFormat,
+) -> Result<Vec<u8>> {
+    if from == to {
+        return Err(ClientError::DataConvert {
+            message: format!("{kind} is already {}", to.extension()),
+            backtrace: Backtrace::new(),
+        });
+    }
+
+    match kind {
+        DataKind::Item => convert_as::<ItemData>(data, from, to),
+        DataKind::Npc => convert_as::<NpcData>(data, from, to),
+        DataKind::Shop => convert_as::<ShopData>(data, from, to),
+        DataKind::Map => convert_as::<MapData>(data, from, to),
+    }
+}
+
+pub fn convert_file(kind: DataKind, input: &Path, output: &Path) -> Result<()> {
+    let (Some(from), Some(to)) = (
+        DataFormat::from_extension(input),
+        DataFormat::from_extension(output),
+    ) else {
+        return Err(ClientError::DataConvert {
+            message: format!(
+                "unknown file type for {} or {}",
+                input.display(),
+                output.display()
+            ),
+            backtrace: Backtrace::new(),
+        });
+    };
+
+    let data = std::fs::read(input)?;
+    let converted = convert_data(kind, &data, from, to)?;
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(output, converted)?;
+    Ok(())
+}
+
+/// Converts every file with the `from` extension in `input` into `output`,
+/// keeping the file names. Returns how many files were written.
+pub fn convert_dir(
+    kind: DataKind,
+    input: &Path,
+    output: &Path,
+    from: DataFormat,
+    to: DataFormat,
+) -> Result<usize> {
+    let mut count = 0;
+
+    for entry in std::fs::read_dir(input)? {
+        let path = entry?.path();
+
+        if DataFormat::from_extension(&path) != Some(from) {
+            continue;
+        }
+
+        let Some(stem) = path.file_stem() else {
+            continue;
+        };
+
+        let target = output.join(stem).with_extension(to.extension());
+
+        convert_file(kind, &path, &target)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Converts a .bin file to text and back again, returning true when the
+/// result is byte for byte the same as the original.
+pub fn roundtrip_data(
+    kind: DataKind,
+    data: &[u8],
+    format: DataFormat,
+) -> Result<bool> {
+    let text = convert_data(kind, data, DataFormat::Bin, format)?;
+    let bin = convert_data(kind, &text, format, DataFormat::Bin)?;
+
+    Ok(bin == data)
+}
@@ -1,11 +1,8 @@
-use crate::{Result, data_types::*, socket::*};
-use log::warn;
+use crate::{ContentKind, Result, data_types::*, load_content};
 use serde::{Deserialize, Serialize};
-use speedy::{Endianness, Readable, Writable};
-use std::fs::{File, OpenOptions};
-use std::io::{BufReader, Read};
+use speedy::{Readable, Writable};
 
-#[derive(Clone, Debug, Deserialize, Serialize, Readable, Writable)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Readable, Writable)]
 pub struct ItemData {
     pub name: String,
     pub levelreq: u16,
@@ -24,31 +21,6 @@ pub struct ItemData {
     pub sound_index: Option<String>,
 }
 
-pub fn load_items() -> Result<Vec<ItemData>> {
-    let mut item_data: Vec<ItemData> = Vec::with_capacity(MAX_ITEMS);
-    let mut buffer = Vec::with_capacity(2048);
-
-    for i in 0..MAX_ITEMS {
-        if let Some(data) = load_file(i, &mut buffer)? {
-            item_data.push(data);
-        }
-    }
-
-    Ok(item_data)
-}
-
-fn load_file(id: usize, buffer: &mut Vec<u8>) -> Result<Option<ItemData>> {
-    let name = format!("./data/items/{id}.bin");
-    buffer.clear();
-
-    match OpenOptions::new().read(true).open(name) {
-        Ok(mut file) => {
-            file.read_to_end(buffer)?;
-            Ok(Some(ItemData::read_from_buffer(buffer).unwrap()))
-        }
-        Err(e) => {
-            warn!("Item Load File Num {id} Err: {e}");
-            Ok(None)
-        }
-    }
+pub fn load_items() -> Result<Vec<Option<ItemData>>> {
+    Ok(load_content(ContentKind::Item))
 }
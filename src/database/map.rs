@@ -1,15 +1,15 @@
 use crate::content::MAP_SIZE;
 use crate::{
-    BufferTask, BufferTaskEnum, MapAttributes, MapDirBlock, MapPosition,
-    Result, SystemHolder, data_types::*, socket::*,
+    BufferTask, BufferTaskEnum, MapAssetCache, MapAttributes, MapDirBlock,
+    MapPosition, Result, SystemHolder, data_types::*, map_hash, socket::*,
 };
 use ascending_graphics::*;
 use log::{error, info};
 use serde::{Deserialize, Serialize};
 use snafu::Backtrace;
-use speedy::{Endianness, Readable, Writable};
-use std::fs::{File, OpenOptions};
-use std::io::{BufReader, Read};
+use speedy::{Readable, Writable};
+use std::fs::OpenOptions;
+use std::io::Read;
 use std::path::Path;
 
 #[derive(
@@ -107,16 +107,56 @@ pub enum Weather {
     Windy,
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct AnimatedTile {
+    pub pos: UVec3,
+    pub tile: u32,
+    pub current: u32,
+}
+
 #[derive(Clone, Debug)]
 pub struct MapSlotData {
     pub mappos: MapPosition,
     pub map: Map,
+    /// One map per parallax factor holding the layers that scroll at that rate.
+    pub overlays: Vec<Map>,
+    pub animated: Vec<AnimatedTile>,
     pub enable: bool,
     pub dir_block: MapDirBlock,
     pub attributes: MapAttributes,
     pub music: Option<String>,
 }
 
+impl MapSlotData {
+    pub fn layer_map(&self, overlay: Option<usize>) -> &Map {
+        match overlay {
+            Some(index) if index < self.overlays.len() => &self.overlays[index],
+            _ => &self.map,
+        }
+    }
+
+    pub fn layer_map_mut(&mut self, overlay: Option<usize>) -> &mut Map {
+        match overlay {
+            Some(index) if index < self.overlays.len() => {
+                &mut self.overlays[index]
+            }
+            _ => &mut self.map,
+        }
+    }
+
+    pub fn unload(
+        &self,
+        renderer: &mut GpuRenderer,
+        map_renderer: &mut MapRenderer,
+    ) {
+        self.map.unload(renderer, map_renderer);
+
+        for overlay in &self.overlays {
+            overlay.unload(renderer, map_renderer);
+        }
+    }
+}
+
 #[derive(
     Clone,
     Debug,
@@ -149,11 +189,74 @@ pub struct MapData {
     pub dir_block: Vec<u8>,
     pub attribute: Vec<MapAttribute>,
     pub zonespawns: [Vec<(u16, u16)>; 5],
+    /// Max npcs to spawn and the npc ids of each zone.
+    #[serde(with = "zone_slots")]
     pub zones: [(u64, [Option<u64>; 5]); 5],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub music: Option<String>,
     pub weather: Weather,
 }
 
+/// TOML has no null, so zones are written with only their filled npc slots
+/// as `(slot, npc)` pairs.
+mod zone_slots {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    type Zones = [(u64, [Option<u64>; 5]); 5];
+
+    #[derive(Serialize, Deserialize)]
+    struct Zone {
+        max_npcs: u64,
+        #[serde(default)]
+        npcs: Vec<(usize, u64)>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        zones: &Zones,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        zones
+            .iter()
+            .map(|(max_npcs, slots)| Zone {
+                max_npcs: *max_npcs,
+                npcs: slots
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(slot, npc)| npc.map(|npc| (slot, npc)))
+                    .collect(),
+            })
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Zones, D::Error> {
+        let list = Vec::<Zone>::deserialize(deserializer)?;
+        let mut zones = Zones::default();
+
+        if list.len() != zones.len() {
+            return Err(D::Error::invalid_length(list.len(), &"5 zones"));
+        }
+
+        for (zone, data) in zones.iter_mut().zip(list) {
+            zone.0 = data.max_npcs;
+
+            for (slot, npc) in data.npcs {
+                let Some(entry) = zone.1.get_mut(slot) else {
+                    return Err(D::Error::custom(format!(
+                        "zone npc slot {slot} is above 4"
+                    )));
+                };
+
+                *entry = Some(npc);
+            }
+        }
+
+        Ok(zones)
+    }
+}
+
 impl MapData {
     pub fn new(x: i32, y: i32, group: u64) -> Self {
         Self {
@@ -175,26 +278,64 @@ pub fn load_map_data(
     key: Index,
     mappos: MapPosition,
 ) -> Result<()> {
-    if let Some(mapslotdata) = systems.base.mapdata.get_mut(key) {
+    if systems.base.mapdata.contains_key(key) {
         let mut buffer = Vec::with_capacity(131_072);
-        let mapdata =
-            load_file(mappos.x, mappos.y, mappos.group as u64, &mut buffer)?;
+        let mapdata = load_file(
+            mappos.x,
+            mappos.y,
+            mappos.group as u64,
+            &mut buffer,
+            &mut systems.base.map_assets,
+        )?;
+
+        apply_map_data(systems, key, mapdata);
+    }
+    Ok(())
+}
+
+pub fn apply_map_data(
+    systems: &mut SystemHolder,
+    key: Index,
+    mapdata: MapData,
+) {
+    let effects = &systems.base.map_effects;
+
+    if let Some(mapslotdata) = systems.base.mapdata.get_mut(key) {
+        mapslotdata.animated.clear();
 
         (0..32).for_each(|x| {
             (0..32).for_each(|y| {
                 let tile_num = get_tile_pos(x, y);
                 (0..9).for_each(|layer| {
-                    let id = mapdata.tile[layer].id[tile_num] as usize;
-                    if id > 0 {
-                        mapslotdata.map.set_tile(
-                            UVec3::new(x as u32, y as u32, layer as u32),
+                    let id = mapdata.tile[layer].id[tile_num];
+                    let pos = UVec3::new(x as u32, y as u32, layer as u32);
+                    let map =
+                        mapslotdata.layer_map_mut(effects.overlay_index(layer));
+
+                    // Maps can be reapplied after a download so old tiles must be cleared too.
+                    if id > 0 || map.get_tile(pos).id > 0 {
+                        map.set_tile(
+                            pos,
                             TileData {
-                                id,
-                                color: Color::rgba(255, 255, 255, 255),
+                                id: id as usize,
+                                color: Color::rgba(
+                                    255,
+                                    255,
+                                    255,
+                                    effects.layer_alpha(layer),
+                                ),
                                 anim_time: 250,
                             },
                         );
                     }
+
+                    if id > 0 && effects.animations.contains_key(&id) {
+                        mapslotdata.animated.push(AnimatedTile {
+                            pos,
+                            tile: id,
+                            current: id,
+                        });
+                    }
                 });
             });
         });
@@ -207,7 +348,6 @@ pub fn load_map_data(
         };
         mapslotdata.music = mapdata.music;
     }
-    Ok(())
 }
 
 pub fn create_map_data(
@@ -225,9 +365,36 @@ pub fn create_map_data(
     ) {
         map.can_render = true;
 
+        let mut overlays =
+            Vec::with_capacity(systems.base.map_effects.parallax.len());
+
+        for _ in 0..systems.base.map_effects.parallax.len() {
+            match Map::new(
+                &mut systems.renderer,
+                map_renderer,
+                TILE_SIZE as u32,
+                world_pos,
+                MapZLayers::default(),
+            ) {
+                Some(overlay) => overlays.push(overlay),
+                None => {
+                    map.unload(&mut systems.renderer, map_renderer);
+                    for overlay in &overlays {
+                        overlay.unload(&mut systems.renderer, map_renderer);
+                    }
+
+                    return Err(ClientError::MapCreationFailed {
+                        backtrace: Backtrace::new(),
+                    });
+                }
+            }
+        }
+
         let mapslotdata = MapSlotData {
             mappos,
             map,
+            overlays,
+            animated: Vec::new(),
             enable: false,
             dir_block: MapDirBlock::default(),
             attributes: MapAttributes::default(),
@@ -274,7 +441,13 @@ pub fn get_map_key(
         if let Some(mapdata) = systems.base.mapdata.get_mut(*index)
             && mapdata.map.pos != world_pos
         {
+            let offset = world_pos - mapdata.map.pos;
             mapdata.map.set_pos(world_pos);
+
+            for overlay in mapdata.overlays.iter_mut() {
+                let pos = overlay.pos + offset;
+                overlay.set_pos(pos);
+            }
         }
 
         return Ok(*index);
@@ -282,17 +455,16 @@ pub fn get_map_key(
 
     if systems.base.map_cache.len() > 60 {
         let keydata = systems.base.map_cache.pop_lru();
-        if let Some(key) = keydata {
-            if let Some(mapdata) = systems.base.mapdata.get(key.0) {
-                let mappos = mapdata.mappos;
-                systems.base.mappos_key.remove(&mappos);
-            }
-
-            systems.base.mapdata.remove(key.0);
+        if let Some(key) = keydata
+            && let Some(mapdata) = systems.base.mapdata.remove(key.0)
+        {
+            systems.base.mappos_key.remove(&mapdata.mappos);
+            mapdata.unload(&mut systems.renderer, map_renderer);
         }
     }
 
     let key = create_map_data(systems, map_renderer, mappos, world_pos)?;
+    systems.base.map_assets.queue_revision(mappos);
     systems.base.mappos_key.insert(mappos, key);
     systems.base.map_cache.push(key, key);
     buffer.add_task(BufferTaskEnum::ApplyMap(mappos, key));
@@ -304,10 +476,7 @@ pub fn clear_map_data(
     map_renderer: &mut MapRenderer,
 ) {
     for mapslotdata in systems.base.mapdata.iter_mut() {
-        mapslotdata
-            .1
-            .map
-            .unload(&mut systems.renderer, map_renderer);
+        mapslotdata.1.unload(&mut systems.renderer, map_renderer);
     }
 
     systems.base.mapdata.clear();
@@ -319,6 +488,10 @@ pub fn set_map_visible(systems: &mut SystemHolder, key: Index, visible: bool) {
         mapslotdata
             .map
             .set_visibility(&mut systems.renderer, visible);
+
+        for overlay in mapslotdata.overlays.iter_mut() {
+            overlay.set_visibility(&mut systems.renderer, visible);
+        }
     }
 }
 
@@ -354,14 +527,24 @@ pub fn get_map_pos(
     None
 }
 
-pub fn load_file(
-    x: i32,
-    y: i32,
-    group: u64,
+pub enum MapFileStatus {
+    Loaded(Box<MapData>),
+    Missing,
+    Corrupt,
+}
+
+/// Reads and decodes a map file without touching any shared state so it can
+/// also be run from the background map loader.
+pub fn read_map_file(
+    mappos: MapPosition,
     buffer: &mut Vec<u8>,
-) -> Result<MapData> {
+    expected_hash: Option<u64>,
+) -> MapFileStatus {
+    let (x, y, group) = (mappos.x, mappos.y, mappos.group as u64);
+
     if !is_map_exist(x, y, group) {
-        return Ok(MapData::new(x, y, group));
+        info!("Map {x}_{y}_{group} is missing, waiting on download");
+        return MapFileStatus::Missing;
     }
 
     buffer.clear();
@@ -370,16 +553,67 @@ pub fn load_file(
 
     match OpenOptions::new().read(true).open(&name) {
         Ok(mut file) => {
-            file.read_to_end(buffer)?;
-            Ok(MapData::read_from_buffer(buffer).unwrap())
+            if let Err(e) = file.read_to_end(buffer) {
+                error!("Failed to read {name}, Err {e:?}");
+                return MapFileStatus::Corrupt;
+            }
+
+            if let Some(hash) = expected_hash
+                && hash != map_hash(buffer)
+            {
+                error!("Map {name} failed its integrity check");
+                return MapFileStatus::Corrupt;
+            }
+
+            match MapData::read_from_buffer(buffer) {
+                Ok(mapdata) => MapFileStatus::Loaded(Box::new(mapdata)),
+                Err(e) => {
+                    error!("Map {name} is corrupt, Err {e:?}");
+                    MapFileStatus::Corrupt
+                }
+            }
         }
         Err(e) => {
             error!("Failed to load {name}, Err {e:?}");
-            Ok(MapData::new(x, y, group))
+            MapFileStatus::Missing
+        }
+    }
+}
+
+/// Turns a read result into usable map data, flagging corrupt files so the
+/// next revision check downloads them again.
+pub fn resolve_map_file(
+    cache: &mut MapAssetCache,
+    mappos: MapPosition,
+    status: MapFileStatus,
+) -> MapData {
+    match status {
+        MapFileStatus::Loaded(mapdata) => *mapdata,
+        MapFileStatus::Missing => {
+            MapData::new(mappos.x, mappos.y, mappos.group as u64)
+        }
+        MapFileStatus::Corrupt => {
+            cache.invalidate(mappos);
+            cache.queue_revision(mappos);
+            MapData::new(mappos.x, mappos.y, mappos.group as u64)
         }
     }
 }
 
+pub fn load_file(
+    x: i32,
+    y: i32,
+    group: u64,
+    buffer: &mut Vec<u8>,
+    cache: &mut MapAssetCache,
+) -> Result<MapData> {
+    let mappos = MapPosition::new(x, y, group as i32);
+    let expected_hash = cache.get_entry(mappos).map(|entry| entry.hash);
+    let status = read_map_file(mappos, buffer, expected_hash);
+
+    Ok(resolve_map_file(cache, mappos, status))
+}
+
 pub fn is_map_exist(x: i32, y: i32, group: u64) -> bool {
     let name = format!("./data/maps/{x}_{y}_{group}.bin");
     Path::new(&name).exists()
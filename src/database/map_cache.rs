@@ -0,0 +1,205 @@
+use crate::{ClientError, MapPosition, Result, socket::*};
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use snafu::Backtrace;
+use speedy::Readable;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use xxhash_rust::xxh3::xxh3_64;
+
+use super::MapData;
+
+pub const MAP_CACHE_INDEX: &str = "./data/maps/cache.toml";
+/// Times a map that failed its download is requested again before it is
+/// left missing until the next login.
+pub const MAP_DOWNLOAD_RETRIES: u8 = 1;
+
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq,
+)]
+pub struct MapCacheEntry {
+    pub revision: u64,
+    pub hash: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MapCacheIndex {
+    pub maps: HashMap<String, MapCacheEntry>,
+}
+
+/// Tracks the revision and content hash of every map file stored locally,
+/// along with the revision checks and downloads still waiting on the server.
+#[derive(Debug, Default)]
+pub struct MapAssetCache {
+    pub index: MapCacheIndex,
+    pub revision_requests: VecDeque<MapPosition>,
+    pub downloads: HashSet<MapPosition, ahash::RandomState>,
+    /// Failed downloads of each map since its last good one.
+    pub failed_downloads: HashMap<MapPosition, u8, ahash::RandomState>,
+}
+
+impl MapAssetCache {
+    pub fn load() -> Self {
+        let index = match fs::read_to_string(MAP_CACHE_INDEX) {
+            Ok(data) => match toml::from_str::<MapCacheIndex>(&data) {
+                Ok(index) => index,
+                Err(e) => {
+                    warn!("Map cache index is corrupt, rebuilding. Err {e:?}");
+                    MapCacheIndex::default()
+                }
+            },
+            Err(_) => MapCacheIndex::default(),
+        };
+
+        Self {
+            index,
+            ..Default::default()
+        }
+    }
+
+    pub fn save(&self) {
+        let data = match toml::to_string(&self.index) {
+            Ok(data) => data,
+            Err(e) => {
+                error!("Failed to serialize map cache index. Err {e:?}");
+                return;
+            }
+        };
+
+        if let Err(e) = write_atomic(MAP_CACHE_INDEX, data.as_bytes()) {
+            error!("Failed to save map cache index. Err {e:?}");
+        }
+    }
+
+    pub fn get_entry(&self, mappos: MapPosition) -> Option<MapCacheEntry> {
+        self.index.maps.get(&map_cache_key(mappos)).copied()
+    }
+
+    pub fn set_entry(&mut self, mappos: MapPosition, entry: MapCacheEntry) {
+        self.index.maps.insert(map_cache_key(mappos), entry);
+        self.save();
+    }
+
+    /// Drops the stored entry so the next revision check downloads the map again.
+    pub fn invalidate(&mut self, mappos: MapPosition) {
+        if self.index.maps.remove(&map_cache_key(mappos)).is_some() {
+            self.save();
+        }
+    }
+
+    /// Records a failed download of `mappos` and drops its stale entry.
+    /// Returns true while the map should be requested again.
+    pub fn download_failed(&mut self, mappos: MapPosition) -> bool {
+        self.invalidate(mappos);
+
+        let failures = self.failed_downloads.entry(mappos).or_default();
+        *failures = failures.saturating_add(1);
+
+        *failures <= MAP_DOWNLOAD_RETRIES
+    }
+
+    pub fn download_succeeded(&mut self, mappos: MapPosition) {
+        self.failed_downloads.remove(&mappos);
+    }
+
+    /// Forgets downloads of the last session, so maps that failed are
+    /// tried again after a login.
+    pub fn reset_downloads(&mut self) {
+        self.downloads.clear();
+        self.failed_downloads.clear();
+    }
+
+    pub fn queue_revision(&mut self, mappos: MapPosition) {
+        if !self.revision_requests.contains(&mappos) {
+            self.revision_requests.push_back(mappos);
+        }
+    }
+
+    /// Returns true when the local copy already matches the server's revision.
+    pub fn is_current(
+        &self,
+        mappos: MapPosition,
+        revision: u64,
+        hash: u64,
+    ) -> bool {
+        self.get_entry(mappos).is_some_and(|entry| {
+            entry.revision == revision && entry.hash == hash
+        }) && is_map_file_exist(mappos)
+    }
+}
+
+pub fn map_cache_key(mappos: MapPosition) -> String {
+    format!("{}_{}_{}", mappos.x, mappos.y, mappos.group)
+}
+
+pub fn map_file_name(mappos: MapPosition) -> String {
+    format!("./data/maps/{}.bin", map_cache_key(mappos))
+}
+
+pub fn is_map_file_exist(mappos: MapPosition) -> bool {
+    Path::new(&map_file_name(mappos)).exists()
+}
+
+pub fn map_hash(data: &[u8]) -> u64 {
+    xxh3_64(data)
+}
+
+/// Checks the bytes against the expected hash and makes sure they decode into
+/// map data for the requested position before anything touches the disk.
+pub fn verify_map_bytes(
+    mappos: MapPosition,
+    data: &[u8],
+    hash: u64,
+) -> Result<MapData> {
+    let name = map_file_name(mappos);
+
+    if map_hash(data) != hash {
+        return Err(ClientError::MapIntegrity {
+            name,
+            backtrace: Backtrace::new(),
+        });
+    }
+
+    match MapData::read_from_buffer(data) {
+        Ok(mapdata) if mapdata.position == mappos => Ok(mapdata),
+        _ => Err(ClientError::MapIntegrity {
+            name,
+            backtrace: Backtrace::new(),
+        }),
+    }
+}
+
+/// Writes into a temporary file first and renames it over the target so a
+/// crash mid write can never leave a truncated file behind.
+pub fn write_atomic(name: &str, data: &[u8]) -> Result<()> {
+    let tmp_name = format!("{name}.tmp");
+
+    if let Some(parent) = Path::new(name).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&tmp_name)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+
+    fs::rename(&tmp_name, name)?;
+    Ok(())
+}
+
+pub fn process_map_requests(
+    socket: &mut Poller,
+    cache: &mut MapAssetCache,
+) -> Result<()> {
+    while let Some(mappos) = cache.revision_requests.pop_front() {
+        let revision = cache.get_entry(mappos).unwrap_or_default().revision;
+        send_maprevision(socket, mappos, revision)?;
+    }
+
+    Ok(())
+}
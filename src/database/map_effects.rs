@@ -0,0 +1,168 @@
+use ascending_graphics::*;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+pub const TILE_ANIMATION_FILE: &str = "./data/tile_animations.toml";
+pub const MAP_LAYER_FILE: &str = "./data/map_layers.toml";
+pub const MAP_LAYER_COUNT: usize = 9;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TileAnimation {
+    /// Tile id as stored in the map files that starts the animation.
+    pub tile: u32,
+    pub frames: Vec<u32>,
+    /// Time each frame is shown in milliseconds.
+    pub frame_time: u32,
+}
+
+impl TileAnimation {
+    pub fn frame_at(&self, seconds: f32) -> u32 {
+        if self.frames.is_empty() || self.frame_time == 0 {
+            return self.tile;
+        }
+
+        let frame = (seconds * 1000.0 / self.frame_time as f32) as usize;
+        self.frames[frame % self.frames.len()]
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TileAnimationTable {
+    #[serde(default)]
+    pub animation: Vec<TileAnimation>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LayerEffect {
+    pub layer: usize,
+    #[serde(default = "default_layer_factor")]
+    pub opacity: f32,
+    /// 1.0 scrolls with the map, lower values lag behind the camera.
+    #[serde(default = "default_layer_factor")]
+    pub parallax: f32,
+    /// Roof layers fade out while the player stands beneath them.
+    #[serde(default)]
+    pub roof: bool,
+}
+
+fn default_layer_factor() -> f32 {
+    1.0
+}
+
+impl LayerEffect {
+    pub fn new(layer: usize) -> Self {
+        Self {
+            layer,
+            opacity: 1.0,
+            parallax: 1.0,
+            roof: false,
+        }
+    }
+
+    pub fn has_parallax(&self) -> bool {
+        (self.parallax - 1.0).abs() > f32::EPSILON
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MapLayerTable {
+    #[serde(default)]
+    pub layer: Vec<LayerEffect>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MapEffects {
+    pub animations: HashMap<u32, TileAnimation, ahash::RandomState>,
+    pub layers: [LayerEffect; MAP_LAYER_COUNT],
+    /// Distinct parallax factors, each one gets its own overlay map per slot.
+    pub parallax: Vec<f32>,
+    pub roof_alpha: f32,
+    pub parallax_anchor: Option<Vec2>,
+}
+
+impl Default for MapEffects {
+    fn default() -> Self {
+        Self {
+            animations: HashMap::default(),
+            layers: std::array::from_fn(LayerEffect::new),
+            parallax: Vec::new(),
+            roof_alpha: 1.0,
+            parallax_anchor: None,
+        }
+    }
+}
+
+impl MapEffects {
+    pub fn layer_alpha(&self, layer: usize) -> u8 {
+        let effect = &self.layers[layer];
+        let alpha = if effect.roof {
+            effect.opacity * self.roof_alpha
+        } else {
+            effect.opacity
+        };
+
+        (alpha.clamp(0.0, 1.0) * 255.0) as u8
+    }
+
+    /// Returns which overlay the layer renders into, None for the base map.
+    pub fn overlay_index(&self, layer: usize) -> Option<usize> {
+        let effect = &self.layers[layer];
+
+        if !effect.has_parallax() {
+            return None;
+        }
+
+        self.parallax
+            .iter()
+            .position(|factor| (*factor - effect.parallax).abs() < f32::EPSILON)
+    }
+
+    pub fn has_roof(&self) -> bool {
+        self.layers.iter().any(|effect| effect.roof)
+    }
+}
+
+pub fn load_map_effects() -> MapEffects {
+    let mut effects = MapEffects::default();
+
+    match fs::read_to_string(TILE_ANIMATION_FILE) {
+        Ok(data) => match toml::from_str::<TileAnimationTable>(&data) {
+            Ok(table) => {
+                for animation in table.animation {
+                    effects.animations.insert(animation.tile, animation);
+                }
+            }
+            Err(e) => warn!("Failed to parse {TILE_ANIMATION_FILE}, Err {e}"),
+        },
+        Err(e) => warn!("Failed to load {TILE_ANIMATION_FILE}, Err {e}"),
+    }
+
+    match fs::read_to_string(MAP_LAYER_FILE) {
+        Ok(data) => match toml::from_str::<MapLayerTable>(&data) {
+            Ok(table) => {
+                for effect in table.layer {
+                    if effect.layer >= MAP_LAYER_COUNT {
+                        warn!("Map layer {} does not exist", effect.layer);
+                        continue;
+                    }
+
+                    if effect.has_parallax()
+                        && !effects.parallax.iter().any(|factor| {
+                            (*factor - effect.parallax).abs() < f32::EPSILON
+                        })
+                    {
+                        effects.parallax.push(effect.parallax);
+                    }
+
+                    effects.layers[effect.layer] = effect;
+                }
+            }
+            Err(e) => warn!("Failed to parse {MAP_LAYER_FILE}, Err {e}"),
+        },
+        Err(e) => warn!("Failed to load {MAP_LAYER_FILE}, Err {e}"),
+    }
+
+    effects
+}
@@ -1,23 +1,20 @@
-use crate::{Result, data_types::*, socket::*};
-use log::warn;
+use crate::{ContentKind, Result, data_types::*, load_content};
 use serde::{Deserialize, Serialize};
-use speedy::{Endianness, Readable, Writable};
-use std::fs::{File, OpenOptions};
-use std::io::{BufReader, Read};
+use speedy::{Readable, Writable};
 
-#[derive(Clone, Debug, Deserialize, Serialize, Readable, Writable)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Readable, Writable)]
 pub struct DropItem {
     pub item: u32,
     pub amount: u32,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Readable, Writable)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Readable, Writable)]
 pub struct NpcDrop {
     pub items: [DropItem; 5],
     pub shares: u32,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Readable, Writable)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, Readable, Writable)]
 pub struct NpcData {
     pub name: String,
     pub level: i32,
@@ -64,31 +61,6 @@ pub struct NpcData {
     pub exp: i64,
 }
 
-pub fn load_npcs() -> Result<Vec<NpcData>> {
-    let mut npc_data: Vec<NpcData> = Vec::with_capacity(MAX_NPCS);
-    let mut buffer = Vec::with_capacity(2048);
-    for i in 0..MAX_NPCS {
-        if let Some(data) = load_file(i, &mut buffer)? {
-            npc_data.push(data);
-        }
-    }
-
-    Ok(npc_data)
-}
-
-fn load_file(id: usize, buffer: &mut Vec<u8>) -> Result<Option<NpcData>> {
-    let name = format!("./data/npcs/{id}.bin");
-
-    buffer.clear();
-
-    match OpenOptions::new().read(true).open(name) {
-        Ok(mut file) => {
-            file.read_to_end(buffer)?;
-            Ok(Some(NpcData::read_from_buffer(buffer).unwrap()))
-        }
-        Err(e) => {
-            warn!("Npc Load File Num {id} Err: {e}");
-            Ok(None)
-        }
-    }
+pub fn load_npcs() -> Result<Vec<Option<NpcData>>> {
+    Ok(load_content(ContentKind::Npc))
 }
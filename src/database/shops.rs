@@ -1,15 +1,12 @@
-use crate::{Result, data_types::*, socket::*};
-use educe::Educe;
-use log::warn;
+use crate::{ContentKind, Result, data_types::*, load_content, socket::*};
 use serde::{Deserialize, Serialize};
-use speedy::{Endianness, Readable, Writable};
-use std::fs::{File, OpenOptions};
-use std::io::{BufReader, Read};
+use speedy::{Readable, Writable};
 
 #[derive(
     Clone,
     Copy,
     Debug,
+    Default,
     Deserialize,
     Serialize,
     Readable,
@@ -26,6 +23,7 @@ pub struct ShopItem {
 #[derive(
     Clone,
     Debug,
+    Default,
     Deserialize,
     Serialize,
     Readable,
@@ -39,32 +37,6 @@ pub struct ShopData {
     pub item: [ShopItem; MAX_SHOP_ITEM],
 }
 
-pub fn load_shops() -> Result<Vec<ShopData>> {
-    let mut shop_data: Vec<ShopData> = Vec::with_capacity(MAX_SHOPS);
-    let mut buffer = Vec::with_capacity(2048);
-
-    for i in 0..MAX_SHOPS {
-        if let Some(data) = load_file(i, &mut buffer)? {
-            shop_data.push(data);
-        }
-    }
-
-    Ok(shop_data)
-}
-
-fn load_file(id: usize, buffer: &mut Vec<u8>) -> Result<Option<ShopData>> {
-    let name = format!("./data/shops/{id}.bin");
-
-    buffer.clear();
-
-    match OpenOptions::new().read(true).open(name) {
-        Ok(mut file) => {
-            file.read_to_end(buffer)?;
-            Ok(Some(ShopData::read_from_buffer(buffer).unwrap()))
-        }
-        Err(e) => {
-            warn!("Shop Load File Num {id} Err: {e}");
-            Ok(None)
-        }
-    }
+pub fn load_shops() -> Result<Vec<Option<ShopData>>> {
+    Ok(load_content(ContentKind::Shop))
 }
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+pub mod container;
+pub mod content;
+pub mod data_types;
+pub mod database;
+pub mod runner;
+pub mod systems;
+pub mod widget;
+
+pub use container::*;
+use content::*;
+pub use data_types::*;
+use database::*;
+use systems::*;
+pub use widget::*;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    Quit,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Axis {
+    Forward,
+    Sideward,
+    Yaw,
+    Pitch,
+}
+
+pub enum MouseEvent {
+    None,
+    Click,
+    Release,
+}
+
+pub const APP_MAJOR: u16 = 0;
+pub const APP_MINOR: u16 = 2;
+pub const APP_REV: u16 = 0;
+pub const SERVER_ID: &str = "127.0.0.1";
+pub const SERVER_PORT: u16 = 7010;
+pub const TLS_SERVER_PORT: u16 = 7011;
@@ -1,83 +1,16 @@
-#![allow(
-    dead_code,
-    clippy::collapsible_match,
-    unused_imports,
-    clippy::too_many_arguments
-)]
-use ascending_camera::{
-    Projection,
-    controls::{Controls, FlatControls, FlatSettings},
-};
 use ascending_graphics::*;
 use backtrace::Backtrace;
-use cosmic_text::{Attrs, Metrics};
 
-use ascending_input::{Bindings, InputHandler, Key};
 use ascending_time::Updater;
-use log::{LevelFilter, Metadata, Record, error, info, warn};
-use lru::LruCache;
-use serde::{Deserialize, Serialize};
-use slotmap::SlotMap;
-use std::{collections::HashMap, env, num::NonZeroUsize};
-use std::{
-    fs::{self, File},
-    io::{Read, Write, prelude::*},
-    iter, panic,
-    sync::Arc,
-    time::{Duration, Instant},
-};
-use wgpu::{Backends, Dx12Compiler, InstanceDescriptor, InstanceFlags};
-use winit::{
-    dpi::{PhysicalPosition, PhysicalSize},
-    event::*,
-    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
-    keyboard::NamedKey,
-    platform::windows::WindowAttributesExtWindows,
-    window::{WindowAttributes, WindowButtons},
-};
-
-mod container;
-mod content;
-mod data_types;
-mod database;
-mod runner;
-mod systems;
-mod widget;
-
-pub use container::*;
-use content::*;
-pub use data_types::*;
-use database::*;
-use systems::*;
-pub use widget::*;
-
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
-enum Action {
-    Quit,
-}
-
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
-enum Axis {
-    Forward,
-    Sideward,
-    Yaw,
-    Pitch,
-}
+use log::{Metadata, Record, error, info};
+use std::env;
+use std::{fs::File, io::Write, panic};
+use winit::event_loop::EventLoop;
 
-enum MouseEvent {
-    None,
-    Click,
-    Release,
-}
+use client::{Result, runner, systems::Config};
 
 // creates a static global logger type for setting the logger
 static MY_LOGGER: MyLogger = MyLogger(log::Level::Trace);
-pub const APP_MAJOR: u16 = 0;
-pub const APP_MINOR: u16 = 2;
-pub const APP_REV: u16 = 0;
-pub const SERVER_ID: &str = "127.0.0.1";
-pub const SERVER_PORT: u16 = 7010;
-pub const TLS_SERVER_PORT: u16 = 7011;
 
 struct MyLogger(pub log::Level);
 
@@ -8,40 +8,30 @@ use crate::{
 };
 use ascending_camera::{
     Projection,
-    controls::{Controls, FlatControls, FlatSettings},
+    controls::{FlatControls, FlatSettings},
 };
 use ascending_graphics::{
     wgpu::{
-        BackendOptions, Dx12BackendOptions, Dx12SwapchainKind,
-        ExperimentalFeatures, ForceShaderModelToken, GlDebugFns,
-        MemoryBudgetThresholds, NoopBackendOptions,
+        BackendOptions, Dx12SwapchainKind, ExperimentalFeatures,
+        ForceShaderModelToken, GlDebugFns, MemoryBudgetThresholds,
+        NoopBackendOptions,
     },
     *,
 };
 use ascending_input::{Axis, Bindings, InputHandler, Key, MouseAxis};
 use ascending_time::{FrameTime, Instant};
-use backtrace::Backtrace;
-use cosmic_text::{Attrs, Metrics};
-use log::{LevelFilter, Metadata, Record, error, info, warn};
+use cosmic_text::Attrs;
+use log::{error, info, warn};
 use lru::LruCache;
-use serde::{Deserialize, Serialize};
 use slotmap::SlotMap;
-use std::{collections::HashMap, env, num::NonZeroUsize};
-use std::{
-    fs::{self, File},
-    io::{Read, Write, prelude::*},
-    iter, panic,
-    sync::Arc,
-    time::Duration,
-};
-use wgpu::{Backends, Dx12Compiler, InstanceDescriptor, InstanceFlags};
+use std::{collections::HashMap, num::NonZeroUsize};
+use std::{sync::Arc, time::Duration};
+use wgpu::{Dx12Compiler, InstanceDescriptor};
 use winit::{
     dpi::{PhysicalPosition, PhysicalSize},
     event::*,
-    event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
-    keyboard::NamedKey,
-    platform::windows::WindowAttributesExtWindows,
-    window::{WindowAttributes, WindowButtons},
+    event_loop::{ActiveEventLoop, ControlFlow},
+    window::WindowAttributes,
 };
 
 #[allow(clippy::large_enum_variant)]
@@ -65,6 +55,8 @@ pub enum Runner {
         reset_timer: f32,
         fps: u32,
         loop_timer: LoopTimer,
+        /// When the next frame may be drawn while the frame rate is capped.
+        next_frame: std::time::Instant,
         mouse_pos: PhysicalPosition<f64>,
         mouse_press: bool,
         instance: Box<wgpu::Instance>,
@@ -81,13 +73,19 @@ impl winit::application::ApplicationHandler for Runner {
             let win_attrs = WindowAttributes::default()
                 .with_active(false)
                 .with_visible(false)
-                .with_inner_size(PhysicalSize::new(800, 600))
-                .with_title("Client")
-                .with_enabled_buttons({
-                    let mut buttons = WindowButtons::all();
-                    buttons.remove(WindowButtons::MAXIMIZE);
-                    buttons
-                });
+                .with_inner_size(PhysicalSize::new(
+                    config.window_size[0].max(SCREEN_WIDTH as u32),
+                    config.window_size[1].max(SCREEN_HEIGHT as u32),
+                ))
+                .with_min_inner_size(PhysicalSize::new(
+                    SCREEN_WIDTH as u32,
+                    SCREEN_HEIGHT as u32,
+                ))
+                .with_resizable(true)
+                .with_fullscreen(
+                    config.window_mode.fullscreen(event_loop.primary_monitor()),
+                )
+                .with_title("Client");
 
             // Builds the Windows that will be rendered too.
             let window = Arc::new(
@@ -103,7 +101,7 @@ impl winit::application::ApplicationHandler for Runner {
             // play the game basically.
             let instance = wgpu::Instance::new(InstanceDescriptor {
                 backends: backend,
-                flags: InstanceFlags::empty(),
+                flags: config.gpu_instance.to_flag(),
                 backend_options: BackendOptions {
                     gl: wgpu::GlBackendOptions {
                         gles_minor_version: wgpu::Gles3MinorVersion::Automatic,
@@ -141,8 +139,8 @@ impl winit::application::ApplicationHandler for Runner {
                     window,
                     //used to find adapters
                     AdapterOptions {
-                        allowed_backends: Backends::all(),
-                        power: AdapterPowerSettings::HighPower,
+                        allowed_backends: backend,
+                        power: config.power_settings.parse_enum(),
                         compatible_surface: Some(compatible_surface),
                     },
                     // used to deturmine which adapters support our special limits or features for our backends.
@@ -160,6 +158,10 @@ impl winit::application::ApplicationHandler for Runner {
                 ))
                 .unwrap();
 
+            let theme = Theme::load(&config.theme);
+            theme.apply_font(&mut renderer.font_sys);
+            let locale = Locale::load(&config.language);
+
             info!("after renderer initiation");
             // we print the GPU it decided to use here for testing purposes.
             println!("{:?}", renderer.adapter().get_info());
@@ -204,12 +206,13 @@ impl winit::application::ApplicationHandler for Runner {
             });
 
             // get the Scale factor the pc currently is using for upscaling or downscaling the rendering.
-            let scale = renderer
+            let os_scale = renderer
                 .window()
                 .current_monitor()
                 .unwrap()
                 .scale_factor()
                 .clamp(1.0, 1.5);
+            let scale = os_scale * config.ui_scale_factor();
 
             // Load textures image
             let resource =
@@ -220,13 +223,26 @@ impl winit::application::ApplicationHandler for Runner {
             let volume = config.bgm_volume as f32 * 0.01;
             audio.set_music_volume(volume);
 
+            let item = load_items().unwrap();
+            let shop = load_shops().unwrap();
+            let npc = load_npcs().unwrap();
+            let reloader = ContentReloader::new(config.content_hot_reload);
+
+            for issue in validate_database(&item, &npc, &shop, &reloader.limits)
+            {
+                warn!("{issue}");
+            }
+
             let database_holder = DatabaseHolder {
-                item: load_items().unwrap(),
-                shop: load_shops().unwrap(),
-                npc: load_npcs().unwrap(),
+                item: with_placeholders(item),
+                shop: with_placeholders(shop),
+                npc: with_placeholders(npc),
                 mapdata: SlotMap::with_key(),
                 mappos_key: HashMap::default(),
                 map_cache: LruCache::new(NonZeroUsize::new(64).unwrap()),
+                map_assets: MapAssetCache::load(),
+                map_effects: load_map_effects(),
+                reloader,
             };
 
             // Compile all rendering data in one type for quick access and passing
@@ -235,10 +251,13 @@ impl winit::application::ApplicationHandler for Runner {
                 renderer,
                 size,
                 scale,
+                os_scale,
                 resource,
                 fade: Fade::new(),
                 map_fade: MapFade::new(),
                 config,
+                theme,
+                locale,
                 base: database_holder,
                 audio,
                 caret: TextCaret {
@@ -383,6 +402,7 @@ impl winit::application::ApplicationHandler for Runner {
                 reset_timer: 0.0f32,
                 fps: 0u32,
                 loop_timer: LoopTimer::default(),
+                next_frame: std::time::Instant::now(),
                 mouse_pos: PhysicalPosition::new(0.0, 0.0),
                 mouse_press: false,
                 instance: Box::new(instance),
@@ -414,6 +434,7 @@ impl winit::application::ApplicationHandler for Runner {
             reset_timer,
             fps,
             loop_timer,
+            next_frame: _,
             mouse_pos,
             mouse_press,
             instance,
@@ -430,8 +451,8 @@ impl winit::application::ApplicationHandler for Runner {
                             alert.show_alert(
                                 systems,
                                 AlertType::Confirm,
-                                "Do you want to return to title screen?",
-                                "Exit Game",
+                                "alert.exit",
+                                "alert.exit_header",
                                 250,
                                 AlertIndex::ExitGame,
                                 false,
@@ -441,19 +462,17 @@ impl winit::application::ApplicationHandler for Runner {
                         }
                         return;
                     }
-                    WindowEvent::Focused(focused) => {
-                        if !focused {
-                            content.game_content.keyinput.iter_mut().for_each(
-                                |key| {
-                                    *key = false;
-                                },
-                            );
+                    WindowEvent::Focused(focused) if !focused => {
+                        content.game_content.keyinput.iter_mut().for_each(
+                            |key| {
+                                *key = false;
+                            },
+                        );
 
-                            content
-                                .game_content
-                                .reset_key_input(world, socket)
-                                .unwrap();
-                        }
+                        content
+                            .game_content
+                            .reset_key_input(world, socket)
+                            .unwrap();
                     }
                     _ => {}
                 }
@@ -508,6 +527,22 @@ impl winit::application::ApplicationHandler for Runner {
                                 .unwrap();
                                 *mouse_press = false;
                             }
+                        } else if button == MouseButton::Right && pressed {
+                            handle_mouse_input(
+                                world,
+                                systems,
+                                socket,
+                                event_loop,
+                                MouseInputType::MouseRightDown,
+                                &Vec2::new(
+                                    mouse_pos.x as f32,
+                                    mouse_pos.y as f32,
+                                ),
+                                content,
+                                alert,
+                                tooltip,
+                            )
+                            .unwrap();
                         }
                     }
                     input::InputEvent::MousePosition { x, y } => {
@@ -541,49 +576,43 @@ impl winit::application::ApplicationHandler for Runner {
                             .unwrap();
                         }
                     }
-                    input::InputEvent::MouseButtonAction(action) => {
-                        if let input::MouseButtonAction::Double(_) = action {
-                            handle_mouse_input(
-                                world,
-                                systems,
-                                socket,
-                                event_loop,
-                                MouseInputType::MouseDoubleLeftDown,
-                                &Vec2::new(
-                                    mouse_pos.x as f32,
-                                    mouse_pos.y as f32,
-                                ),
-                                content,
-                                alert,
-                                tooltip,
-                            )
-                            .unwrap();
-                        }
+                    input::InputEvent::MouseButtonAction(
+                        input::MouseButtonAction::Double(_),
+                    ) => {
+                        handle_mouse_input(
+                            world,
+                            systems,
+                            socket,
+                            event_loop,
+                            MouseInputType::MouseDoubleLeftDown,
+                            &Vec2::new(mouse_pos.x as f32, mouse_pos.y as f32),
+                            content,
+                            alert,
+                            tooltip,
+                        )
+                        .unwrap();
                     }
-                    input::InputEvent::MouseWheel { amount, axis } => {
-                        if axis == MouseAxis::Vertical && amount != 0.0 {
-                            // Test Feature: Map View Zoom
-                            let new_zoom = (if amount > 0.0 {
-                                content.game_content.zoom + 0.1
-                            } else {
-                                content.game_content.zoom - 0.1
-                            })
-                            .clamp(1.0, 3.0);
-                            content.game_content.zoom = new_zoom;
-                            graphics
-                                .system
-                                .controls_mut()
-                                .settings_mut()
-                                .zoom = new_zoom;
-
-                            update_camera(
-                                world,
-                                &mut content.game_content,
-                                systems,
-                                graphics,
-                            )
-                            .unwrap();
-                        }
+                    input::InputEvent::MouseWheel { amount, axis }
+                        if axis == MouseAxis::Vertical && amount != 0.0 =>
+                    {
+                        // Test Feature: Map View Zoom
+                        let new_zoom = (if amount > 0.0 {
+                            content.game_content.zoom + 0.1
+                        } else {
+                            content.game_content.zoom - 0.1
+                        })
+                        .clamp(1.0, 3.0);
+                        content.game_content.zoom = new_zoom;
+                        graphics.system.controls_mut().settings_mut().zoom =
+                            new_zoom;
+
+                        update_camera(
+                            world,
+                            &mut content.game_content,
+                            systems,
+                            graphics,
+                        )
+                        .unwrap();
                     }
                     _ => {}
                 }
@@ -598,6 +627,7 @@ impl winit::application::ApplicationHandler for Runner {
             let new_size = systems.renderer.size();
 
             if systems.size != new_size {
+                let old_size = systems.size;
                 systems.size = new_size;
 
                 // Reset screen size for the Surface here.
@@ -611,6 +641,8 @@ impl winit::application::ApplicationHandler for Runner {
                 });
 
                 systems.renderer.update_depth_texture();
+
+                content.resize(world, systems, graphics, old_size).unwrap();
             }
 
             frame_time.update_recent();
@@ -716,8 +748,8 @@ impl winit::application::ApplicationHandler for Runner {
                             alert.show_alert(
                                 systems,
                                 AlertType::Inform,
-                                "You have been disconnected",
-                                "Alert Message",
+                                "alert.disconnected",
+                                "alert.header",
                                 250,
                                 AlertIndex::Disconnect,
                                 false,
@@ -764,7 +796,7 @@ impl winit::application::ApplicationHandler for Runner {
                     &systems.fps,
                     [
                         (
-                            "FPS: ",
+                            systems.locale.get("stats.fps"),
                             Attrs::new().color(Color::rgba(200, 100, 100, 255)),
                         ),
                         (
@@ -822,7 +854,13 @@ impl winit::application::ApplicationHandler for Runner {
                         let average: u64 = sum / count as u64;
                         systems.gfx.set_text(
                             &content.game_content.interface.frame_loop,
-                            &format!("Frame Jitter: {average:?}"),
+                            systems
+                                .locale
+                                .format(
+                                    "stats.frame_jitter",
+                                    &[("jitter", &average)],
+                                )
+                                .as_ref(),
                         );
                     }
                     if count >= 20 {
@@ -866,6 +904,7 @@ impl winit::application::ApplicationHandler for Runner {
             reset_timer: _,
             fps: _,
             loop_timer: _,
+            next_frame: _,
             mouse_pos: _,
             mouse_press: _,
             instance: _,
@@ -875,7 +914,7 @@ impl winit::application::ApplicationHandler for Runner {
         }
     }
 
-    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         if let Self::Ready {
             content: _,
             systems,
@@ -894,12 +933,30 @@ impl winit::application::ApplicationHandler for Runner {
             reset_timer: _,
             fps: _,
             loop_timer: _,
+            next_frame,
             mouse_pos: _,
             mouse_press: _,
             instance: _,
         } = self
         {
-            systems.renderer.window().request_redraw();
+            let Some(interval) = systems.config.frame_interval() else {
+                event_loop.set_control_flow(ControlFlow::Wait);
+                systems.renderer.window().request_redraw();
+                return;
+            };
+
+            let now = std::time::Instant::now();
+
+            if now >= *next_frame {
+                // Keep the pace of the cap but never try to catch up on
+                // frames that were missed.
+                let target = *next_frame + interval;
+                *next_frame =
+                    if target < now { now + interval } else { target };
+                systems.renderer.window().request_redraw();
+            }
+
+            event_loop.set_control_flow(ControlFlow::WaitUntil(*next_frame));
         }
     }
 }
@@ -1,15 +1,19 @@
 pub mod audio;
 pub mod config;
+pub mod locale;
 pub mod logic;
 pub mod mainloop;
+pub mod map_stream;
 pub mod renderer;
 pub mod socket;
 pub mod system_tasks;
 
 pub use audio::*;
 pub use config::*;
+pub use locale::*;
 pub use logic::*;
 pub use mainloop::*;
+pub use map_stream::*;
 pub use renderer::*;
 pub use socket::*;
 pub use system_tasks::*;
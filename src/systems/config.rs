@@ -1,17 +1,35 @@
-use ascending_graphics::wgpu::{Backend, Backends};
+use ascending_graphics::wgpu::Backends;
 use log::{LevelFilter, debug};
 use pki_types::pem::PemObject;
 use rustls::{
-    ClientConfig, RootCertStore, ServerConfig,
-    client::danger,
+    ClientConfig, RootCertStore,
     crypto::{CryptoProvider, ring as provider},
-    pki_types::{CertificateDer, PrivateKeyDer},
-    server::WebPkiClientVerifier,
+    pki_types::CertificateDer,
 };
 use serde::{Deserialize, Serialize};
-use std::{fs, io::BufReader, sync::Arc};
+use std::{fs, sync::Arc, time::Duration};
 
-use crate::{Result, renderer::*};
+use crate::{
+    DEFAULT_LANGUAGE, DEFAULT_THEME, Result, SCREEN_HEIGHT, SCREEN_WIDTH,
+    renderer::*,
+};
+
+/// Smallest and largest UI scale in percent the player can pick.
+pub const UI_SCALE_MIN: u16 = 75;
+pub const UI_SCALE_MAX: u16 = 200;
+
+/// Backend choices offered in the Setting window, as the key of a name and
+/// the `graphic_backend` value it stands for.
+pub const GRAPHIC_BACKENDS: [(&str, &str); 5] = [
+    ("setting.backends.auto", "OpenGL|DX12|Vulkan|Metal"),
+    ("setting.backends.vulkan", "Vulkan"),
+    ("setting.backends.dx12", "DX12"),
+    ("setting.backends.metal", "Metal"),
+    ("setting.backends.opengl", "OpenGL"),
+];
+
+/// Frame rate caps offered in the Setting window, 0 is unlimited.
+pub const FPS_LIMITS: [u16; 7] = [0, 30, 60, 120, 144, 165, 240];
 
 #[derive(Serialize, Deserialize, Debug)]
 pub enum ClientLevelFilter {
@@ -42,7 +60,24 @@ impl ClientLevelFilter {
     }
 }
 
+/// Keys of the game actions the player can remap, letters match in either
+/// case.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub toggle_combat_log: char,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            toggle_combat_log: 'l',
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
 pub struct Config {
     pub host: Option<String>,
     pub port: Option<u16>,
@@ -59,9 +94,28 @@ pub struct Config {
     pub show_ping: bool,
     pub show_average_ping: bool,
     pub show_frame_loop: bool,
+    /// Debug outlines of our predicted and server confirmed tile.
+    pub show_move_ghosts: bool,
+    /// Debug overlay of the sight and follow range of every NPC.
+    pub show_npc_ranges: bool,
     pub power_settings: ClientAdapterPowerSettings,
     pub present_mode: ClientPresentMode,
     pub gpu_instance: ClientGPUInstances,
+    pub content_hot_reload: bool,
+    /// UI scale in percent, applied on top of the monitor scale factor.
+    pub ui_scale: u16,
+    pub window_mode: ClientWindowMode,
+    /// Size of the window when it is opened in windowed mode.
+    pub window_size: [u32; 2],
+    /// Highest frames drawn per second, 0 leaves it to the present mode.
+    pub fps_limit: u16,
+    /// Id of the theme file in `data/themes` the interface is drawn with.
+    pub theme: String,
+    /// Id of the string table in `data/lang` the interface is written in.
+    pub language: String,
+    /// Players whose chat messages are hidden, set from the context menu.
+    pub ignored_players: Vec<String>,
+    pub key_bindings: KeyBindings,
 }
 
 impl Config {
@@ -96,6 +150,38 @@ impl Config {
         debug!("Backends: {backends:?}",);
         backends
     }
+
+    pub fn ui_scale_factor(&self) -> f64 {
+        self.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX) as f64 * 0.01
+    }
+
+    pub fn is_ignored(&self, name: &str) -> bool {
+        self.ignored_players
+            .iter()
+            .any(|ignored| ignored.eq_ignore_ascii_case(name))
+    }
+
+    /// Ignores `name` or stops ignoring it, returns true when it is now
+    /// ignored.
+    pub fn toggle_ignored(&mut self, name: &str) -> bool {
+        let count = self.ignored_players.len();
+
+        self.ignored_players
+            .retain(|ignored| !ignored.eq_ignore_ascii_case(name));
+
+        if self.ignored_players.len() != count {
+            return false;
+        }
+
+        self.ignored_players.push(name.to_string());
+        true
+    }
+
+    /// Time between two frames when the frame rate is capped.
+    pub fn frame_interval(&self) -> Option<Duration> {
+        (self.fps_limit > 0)
+            .then(|| Duration::from_secs_f64(1.0 / self.fps_limit as f64))
+    }
 }
 
 impl Default for Config {
@@ -116,9 +202,20 @@ impl Default for Config {
             show_ping: false,
             show_average_ping: false,
             show_frame_loop: false,
+            show_move_ghosts: false,
+            show_npc_ranges: false,
             power_settings: ClientAdapterPowerSettings::HighPower,
             present_mode: ClientPresentMode::AutoVsync,
             gpu_instance: ClientGPUInstances::None,
+            content_hot_reload: false,
+            ui_scale: 100,
+            window_mode: ClientWindowMode::Windowed,
+            window_size: [SCREEN_WIDTH as u32, SCREEN_HEIGHT as u32],
+            fps_limit: 0,
+            theme: DEFAULT_THEME.to_string(),
+            language: DEFAULT_LANGUAGE.to_string(),
+            ignored_players: Vec::new(),
+            key_bindings: KeyBindings::default(),
         }
     }
 }
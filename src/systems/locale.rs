@@ -0,0 +1,294 @@
+use log::warn;
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, fs};
+
+use crate::TString;
+
+pub const LANG_FOLDER: &str = "./data/lang";
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+const DEFAULT_LANGUAGE_DATA: &str = include_str!("../../data/lang/en.toml");
+
+/// How a language picks the plural form of a count.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluralRule {
+    /// 1 is `one`, everything else is `other`, as in English or German.
+    #[default]
+    OneOther,
+    /// 0 and 1 are `one`, as in French or Portuguese.
+    ZeroOneOther,
+    /// `one`, `few` and `many` by the last digits, as in Russian.
+    EastSlavic,
+    /// Every count is `other`, as in Chinese or Japanese.
+    None,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PluralForm {
+    One,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralRule {
+    pub fn form(&self, count: u64) -> PluralForm {
+        match self {
+            PluralRule::OneOther if count == 1 => PluralForm::One,
+            PluralRule::ZeroOneOther if count <= 1 => PluralForm::One,
+            PluralRule::EastSlavic => {
+                let (rem10, rem100) = (count % 10, count % 100);
+
+                if rem10 == 1 && rem100 != 11 {
+                    PluralForm::One
+                } else if (2..=4).contains(&rem10)
+                    && !(12..=14).contains(&rem100)
+                {
+                    PluralForm::Few
+                } else {
+                    PluralForm::Many
+                }
+            }
+            _ => PluralForm::Other,
+        }
+    }
+}
+
+/// A string with a form per plural category, `zero` is used for a count
+/// of 0 when given and missing forms fall back to `other`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LocalePlural {
+    pub zero: Option<String>,
+    pub one: Option<String>,
+    pub few: Option<String>,
+    pub many: Option<String>,
+    pub other: String,
+}
+
+impl LocalePlural {
+    pub fn get(&self, rule: PluralRule, count: u64) -> &str {
+        let form = match rule.form(count) {
+            _ if count == 0 && self.zero.is_some() => &self.zero,
+            PluralForm::One => &self.one,
+            PluralForm::Few => &self.few,
+            PluralForm::Many => &self.many,
+            PluralForm::Other => &None,
+        };
+
+        form.as_deref().unwrap_or(&self.other)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum LocaleEntry {
+    Text(String),
+    Plural(LocalePlural),
+}
+
+#[derive(Deserialize)]
+struct LocaleFile {
+    name: String,
+    #[serde(default)]
+    plural: PluralRule,
+    #[serde(flatten)]
+    text: toml::Table,
+}
+
+/// Flattens nested tables into dotted keys. A table with an `other` key
+/// is a plural entry instead of a group of keys.
+fn flatten_entries(
+    prefix: &str,
+    table: toml::Table,
+    entries: &mut HashMap<String, LocaleEntry>,
+) -> Result<(), toml::de::Error> {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+
+        match value {
+            toml::Value::String(text) => {
+                entries.insert(key, LocaleEntry::Text(text));
+            }
+            toml::Value::Table(table) if table.contains_key("other") => {
+                let plural = table.try_into::<LocalePlural>()?;
+                entries.insert(key, LocaleEntry::Plural(plural));
+            }
+            toml::Value::Table(table) => flatten_entries(&key, table, entries)?,
+            _ => warn!("Locale key {key} is not a string or table"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Replaces every `{name}` in `text` with the matching argument, unknown
+/// names are left in place.
+pub fn fill_args(text: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let arg = rest.find('}').and_then(|end| {
+            args.iter()
+                .find(|(name, _)| *name == &rest[1..end])
+                .map(|(_, value)| (end, value))
+        });
+
+        match arg {
+            Some((end, value)) => {
+                output.push_str(&value.to_string());
+                rest = &rest[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// String table of one language, loaded from `data/lang/<id>.toml`. Keys
+/// the language lacks come from the English table built into the client.
+#[derive(Clone, Debug)]
+pub struct Locale {
+    pub id: String,
+    pub name: String,
+    pub plural: PluralRule,
+    entries: HashMap<String, LocaleEntry>,
+    fallback: HashMap<String, LocaleEntry>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::parse(DEFAULT_LANGUAGE, DEFAULT_LANGUAGE_DATA)
+            .expect("built in language must be valid")
+    }
+}
+
+impl Locale {
+    /// Parses a string table without any fallback.
+    pub fn parse(id: &str, data: &str) -> Result<Self, toml::de::Error> {
+        let file: LocaleFile = toml::from_str(data)?;
+        let mut entries = HashMap::new();
+
+        flatten_entries("", file.text, &mut entries)?;
+
+        Ok(Locale {
+            id: id.into(),
+            name: file.name,
+            plural: file.plural,
+            entries,
+            fallback: HashMap::new(),
+        })
+    }
+
+    /// Loads the language `id`, falling back to English when the file is
+    /// missing or broken.
+    pub fn load(id: &str) -> Self {
+        let default = Locale::default();
+
+        if id == DEFAULT_LANGUAGE {
+            return default;
+        }
+
+        let path = format!("{LANG_FOLDER}/{id}.toml");
+
+        match fs::read_to_string(&path) {
+            Ok(data) => match Locale::parse(id, &data) {
+                Ok(mut locale) => {
+                    locale.fallback = default.entries;
+                    return locale;
+                }
+                Err(e) => warn!("Failed to parse {path}, Err {e}"),
+            },
+            Err(e) => warn!("Failed to load {path}, Err {e}"),
+        }
+
+        default
+    }
+
+    /// Ids of every language file in the language folder, English first.
+    pub fn list() -> Vec<String> {
+        let mut ids: Vec<String> = fs::read_dir(LANG_FOLDER)
+            .map(|dir| {
+                dir.flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.extension().is_some_and(|ext| ext == "toml")
+                    })
+                    .filter_map(|path| {
+                        path.file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .map(String::from)
+                    })
+                    .filter(|id| id != DEFAULT_LANGUAGE)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        ids.sort();
+        ids.insert(0, DEFAULT_LANGUAGE.into());
+        ids
+    }
+
+    /// Keys of this language's own table, without the fallback.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    pub fn entry(&self, key: &str) -> Option<&LocaleEntry> {
+        self.entries.get(key).or_else(|| self.fallback.get(key))
+    }
+
+    /// The text of `key`, or the key itself when no table has it.
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        match self.entry(key) {
+            Some(LocaleEntry::Text(text)) => text,
+            Some(LocaleEntry::Plural(plural)) => &plural.other,
+            None => key,
+        }
+    }
+
+    pub fn text(&self, key: &str) -> TString {
+        self.get(key).to_string().into()
+    }
+
+    /// The text of `key` with its `{name}` arguments filled in.
+    pub fn format(
+        &self,
+        key: &str,
+        args: &[(&str, &dyn fmt::Display)],
+    ) -> TString {
+        fill_args(self.get(key), args).into()
+    }
+
+    /// The plural form of `key` for `count`, with `{count}` and the other
+    /// arguments filled in.
+    pub fn plural(
+        &self,
+        key: &str,
+        count: u64,
+        args: &[(&str, &dyn fmt::Display)],
+    ) -> TString {
+        let text = match self.entry(key) {
+            Some(LocaleEntry::Plural(plural)) => plural.get(self.plural, count),
+            Some(LocaleEntry::Text(text)) => text,
+            None => key,
+        };
+        let mut all_args: Vec<(&str, &dyn fmt::Display)> =
+            vec![("count", &count)];
+
+        all_args.extend_from_slice(args);
+        fill_args(text, &all_args).into()
+    }
+}
@@ -1,13 +1,9 @@
 use ascending_camera::controls::FlatControls;
-use ascending_graphics::MapRenderer;
 use ascending_time::Instant;
-use log::info;
 
 use crate::{
-    BufferTask, Entity, EntityKind, Position, Result, SystemHolder, World,
-    content::{game_content::map, *},
-    dir_to_enum, send_gameping,
-    systems::State,
+    BufferTask, Entity, EntityKind, Result, SystemHolder, World, content::*,
+    process_map_requests, reload_content, send_gameping, systems::State,
 };
 
 use super::Poller;
@@ -20,6 +16,7 @@ pub struct LoopTimer {
     ping_tmr: f32,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn game_loop(
     socket: &mut Poller,
     world: &mut World,
@@ -31,15 +28,50 @@ pub fn game_loop(
     delta: f32,
     loop_timer: &mut LoopTimer,
 ) -> Result<()> {
+    reload_content(&mut systems.base);
+
     match content.content_type {
         ContentType::Game => {
             if seconds > loop_timer.maprefresh_tmr {
                 update_map_refresh(world, systems, &mut content.game_content)?;
+                process_map_requests(socket, &mut systems.base.map_assets)?;
                 loop_timer.maprefresh_tmr = seconds + 0.5;
             }
 
             if seconds > loop_timer.entity_tmr {
                 float_text_loop(systems, &mut content.game_content, seconds)?;
+                content.game_content.death_screen.update(systems, seconds);
+                content.game_content.combat_log.process(seconds);
+                content
+                    .game_content
+                    .interface
+                    .windows
+                    .get_mut::<CombatLogWindow>()
+                    .update(
+                        systems,
+                        &mut content.game_content.combat_log,
+                        seconds,
+                    );
+                content.game_content.status_effects.process(seconds);
+                if let Some(myentity) = content.game_content.myentity {
+                    content.game_content.interface.effect_bar.update(
+                        systems,
+                        content.game_content.status_effects.effects(myentity),
+                        seconds,
+                    );
+                }
+                content.game_content.interface.target_frame.update(
+                    world,
+                    systems,
+                    content.game_content.target.entity,
+                    content.game_content.myentity,
+                );
+                content.game_content.interface.party_frames.update(
+                    world,
+                    systems,
+                    &content.game_content.party,
+                    content.game_content.myentity,
+                );
 
                 loop_timer.entity_tmr = seconds + 0.025;
             }
@@ -60,6 +92,16 @@ pub fn game_loop(
                 socket,
                 &mut content.game_content,
                 seconds,
+            )?;
+            content
+                .game_content
+                .status_effects
+                .update_tints(world, systems);
+            update_map_effects(
+                world,
+                systems,
+                &mut content.game_content,
+                seconds,
                 delta,
             )?;
 
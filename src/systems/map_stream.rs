@@ -0,0 +1,126 @@
+use ascending_graphics::Index;
+use std::collections::HashMap;
+use tokio::{runtime::Handle, sync::mpsc};
+
+use crate::{
+    MapFileStatus, MapPosition, Result, SystemHolder, apply_map_data,
+    read_map_file, resolve_map_file,
+};
+
+/// How many finished maps get pushed into the renderer each frame.
+const MAX_MAP_APPLY_PER_FRAME: usize = 2;
+
+pub struct MapLoadResult {
+    pub key: Index,
+    pub mappos: MapPosition,
+    pub ticket: u64,
+    pub status: MapFileStatus,
+}
+
+/// Reads and decodes map files on the tokio blocking pool and hands them back
+/// to the main loop, which is the only place allowed to touch the renderer.
+pub struct MapStream {
+    handle: Option<Handle>,
+    sender: mpsc::UnboundedSender<MapLoadResult>,
+    receiver: mpsc::UnboundedReceiver<MapLoadResult>,
+    pending: HashMap<Index, u64, ahash::RandomState>,
+    next_ticket: u64,
+}
+
+impl Default for MapStream {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        MapStream {
+            handle: Handle::try_current().ok(),
+            sender,
+            receiver,
+            pending: HashMap::default(),
+            next_ticket: 0,
+        }
+    }
+}
+
+impl MapStream {
+    pub fn new() -> Self {
+        MapStream::default()
+    }
+
+    pub fn is_async(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    pub fn is_loading(&self, key: Index) -> bool {
+        self.pending.contains_key(&key)
+    }
+
+    /// Queues a background load for the map slot. Returns false when no
+    /// runtime is available so the caller can load it synchronously instead.
+    pub fn request(
+        &mut self,
+        key: Index,
+        mappos: MapPosition,
+        expected_hash: Option<u64>,
+    ) -> bool {
+        let Some(handle) = &self.handle else {
+            return false;
+        };
+
+        // Newer requests for the same slot win, older results get dropped in poll.
+        self.next_ticket = self.next_ticket.wrapping_add(1);
+        let ticket = self.next_ticket;
+        self.pending.insert(key, ticket);
+
+        let sender = self.sender.clone();
+        handle.spawn_blocking(move || {
+            let mut buffer = Vec::with_capacity(131_072);
+            let status = read_map_file(mappos, &mut buffer, expected_hash);
+
+            let _ = sender.send(MapLoadResult {
+                key,
+                mappos,
+                ticket,
+                status,
+            });
+        });
+
+        true
+    }
+
+    pub fn poll(&mut self, systems: &mut SystemHolder) -> Result<()> {
+        for _ in 0..MAX_MAP_APPLY_PER_FRAME {
+            let Ok(result) = self.receiver.try_recv() else {
+                break;
+            };
+
+            if self.pending.get(&result.key) != Some(&result.ticket) {
+                continue;
+            }
+
+            self.pending.remove(&result.key);
+
+            // The slot may have been evicted or reused while we were loading.
+            if !systems
+                .base
+                .mapdata
+                .get(result.key)
+                .is_some_and(|slot| slot.mappos == result.mappos)
+            {
+                continue;
+            }
+
+            let mapdata = resolve_map_file(
+                &mut systems.base.map_assets,
+                result.mappos,
+                result.status,
+            );
+            apply_map_data(systems, result.key, mapdata);
+        }
+
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+}
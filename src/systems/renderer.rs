@@ -6,26 +6,36 @@ use ascending_graphics::{
 };
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
-use slotmap::{Key, SlotMap};
-use winit::dpi::PhysicalSize;
+use slotmap::SlotMap;
+use winit::{dpi::PhysicalSize, monitor::MonitorHandle, window::Fullscreen};
 
 pub mod fade;
 
 pub use fade::*;
 
 use crate::{
-    Audio, Config, ItemData, MapData, MapPosition, MapSlotData, NpcData,
-    ShopData, TextureAllocation, content::Content, data_types::*,
-    game_content::*,
+    Audio, Config, ContentReloader, ItemData, Locale, MapAssetCache,
+    MapEffects, MapPosition, MapSlotData, NpcData, ShopData, TextureAllocation,
+    Theme, content::Content, data_types::*,
 };
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClientAdapterPowerSettings {
     LowPower,
     HighPower,
 }
 
 impl ClientAdapterPowerSettings {
+    pub const ALL: [Self; 2] = [Self::HighPower, Self::LowPower];
+
+    /// Key of the option name in the string tables.
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            ClientAdapterPowerSettings::HighPower => "setting.power_modes.high",
+            ClientAdapterPowerSettings::LowPower => "setting.power_modes.low",
+        }
+    }
+
     pub fn parse_enum(&self) -> AdapterPowerSettings {
         match self {
             ClientAdapterPowerSettings::HighPower => {
@@ -38,7 +48,7 @@ impl ClientAdapterPowerSettings {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClientPresentMode {
     AutoVsync,
     AutoNoVsync,
@@ -49,6 +59,31 @@ pub enum ClientPresentMode {
 }
 
 impl ClientPresentMode {
+    pub const ALL: [Self; 6] = [
+        Self::AutoVsync,
+        Self::AutoNoVsync,
+        Self::Fifo,
+        Self::FifoRelaxed,
+        Self::Immediate,
+        Self::Mailbox,
+    ];
+
+    /// Key of the option name in the string tables.
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            ClientPresentMode::AutoVsync => "setting.present_modes.auto_vsync",
+            ClientPresentMode::AutoNoVsync => {
+                "setting.present_modes.auto_no_vsync"
+            }
+            ClientPresentMode::Fifo => "setting.present_modes.fifo",
+            ClientPresentMode::FifoRelaxed => {
+                "setting.present_modes.fifo_relaxed"
+            }
+            ClientPresentMode::Immediate => "setting.present_modes.immediate",
+            ClientPresentMode::Mailbox => "setting.present_modes.mailbox",
+        }
+    }
+
     pub fn parse_enum(&self) -> PresentMode {
         match self {
             ClientPresentMode::AutoVsync => PresentMode::AutoVsync,
@@ -61,7 +96,7 @@ impl ClientPresentMode {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClientGPUInstances {
     None,
     Debug,
@@ -70,6 +105,21 @@ pub enum ClientGPUInstances {
 }
 
 impl ClientGPUInstances {
+    pub const ALL: [Self; 4] =
+        [Self::None, Self::Debug, Self::Validation, Self::All];
+
+    /// Key of the option name in the string tables.
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            ClientGPUInstances::None => "setting.gpu_debug_modes.none",
+            ClientGPUInstances::Debug => "setting.gpu_debug_modes.debug",
+            ClientGPUInstances::Validation => {
+                "setting.gpu_debug_modes.validation"
+            }
+            ClientGPUInstances::All => "setting.gpu_debug_modes.all",
+        }
+    }
+
     pub fn to_flag(&self) -> InstanceFlags {
         match self {
             ClientGPUInstances::None => InstanceFlags::empty(),
@@ -80,6 +130,56 @@ impl ClientGPUInstances {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientWindowMode {
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl ClientWindowMode {
+    pub const ALL: [Self; 3] =
+        [Self::Windowed, Self::Borderless, Self::Fullscreen];
+
+    /// Key of the option name in the string tables.
+    pub fn text_key(&self) -> &'static str {
+        match self {
+            ClientWindowMode::Windowed => "setting.window_modes.windowed",
+            ClientWindowMode::Borderless => "setting.window_modes.borderless",
+            ClientWindowMode::Fullscreen => "setting.window_modes.fullscreen",
+        }
+    }
+
+    /// Exclusive fullscreen uses the largest video mode of `monitor` and
+    /// falls back to borderless when the monitor reports none.
+    pub fn fullscreen(
+        &self,
+        monitor: Option<MonitorHandle>,
+    ) -> Option<Fullscreen> {
+        match self {
+            ClientWindowMode::Windowed => None,
+            ClientWindowMode::Borderless => {
+                Some(Fullscreen::Borderless(monitor))
+            }
+            ClientWindowMode::Fullscreen => {
+                let video_mode = monitor.as_ref().and_then(|monitor| {
+                    monitor.video_modes().max_by_key(|mode| {
+                        (
+                            mode.size().width * mode.size().height,
+                            mode.refresh_rate_millihertz(),
+                        )
+                    })
+                });
+
+                Some(match video_mode {
+                    Some(mode) => Fullscreen::Exclusive(mode),
+                    None => Fullscreen::Borderless(monitor),
+                })
+            }
+        }
+    }
+}
+
 pub struct TextCaret {
     pub visible: bool,
     pub index: Option<GfxType>,
@@ -93,17 +193,25 @@ pub struct DatabaseHolder {
     pub mapdata: SlotMap<Index, MapSlotData>,
     pub mappos_key: HashMap<MapPosition, Index, ahash::RandomState>,
     pub map_cache: LruCache<Index, Index>,
+    pub map_assets: MapAssetCache,
+    pub map_effects: MapEffects,
+    pub reloader: ContentReloader,
 }
 
 pub struct SystemHolder {
     pub gfx: GfxCollection,
     pub renderer: GpuRenderer,
     pub size: PhysicalSize<f32>,
+    /// Monitor scale factor multiplied by the UI scale from the config.
     pub scale: f64,
+    /// Scale factor of the monitor alone.
+    pub os_scale: f64,
     pub resource: TextureAllocation,
     pub fade: Fade,
     pub map_fade: MapFade,
     pub config: Config,
+    pub theme: Theme,
+    pub locale: Locale,
     pub base: DatabaseHolder,
     pub audio: Audio,
     pub caret: TextCaret,
@@ -273,6 +381,15 @@ pub fn add_image_to_buffer<Controls>(
                 &mut graphics.map_atlas,
                 [0, 1],
             );
+
+            for overlay in mapslotdata.overlays.iter_mut() {
+                graphics.map_renderer.update(
+                    overlay,
+                    &mut systems.renderer,
+                    &mut graphics.map_atlas,
+                    [0, 1],
+                );
+            }
         }
     }
 }
@@ -138,7 +138,7 @@ impl Fade {
 #[derive(Default)]
 pub struct MapFade {
     show: bool,
-    f_image: GfxType,
+    pub f_image: GfxType,
     f_tmr: f32,
     pub f_alpha: isize,
     f_type: FadeType,
@@ -1,33 +1,16 @@
 use crate::systems::State;
-use crate::{
-    Alert, BufferTask, ClientError, Content, Result, SERVER_ID, SERVER_PORT,
-    SystemHolder, World, config::*,
-};
+use crate::{Alert, BufferTask, Content, Result, SystemHolder, World};
 pub use bytey::{ByteBuffer, ByteBufferError, ByteBufferRead, ByteBufferWrite};
 
 use ascending_camera::controls::FlatControls;
-use ascending_graphics::MapRenderer;
-use log::{info, trace, warn};
-use mio::net::TcpStream;
-use mio::{Events, Interest, Poll, Token};
+use log::warn;
+use mio::{Events, Poll, Token};
 pub use mmap_bytey::{
     MByteBuffer, MByteBufferError, MByteBufferRead, MByteBufferWrite,
 };
 use notls_socket::Socket;
-use pki_types::ServerName;
-use serde::{Deserialize, Serialize};
 use states::ClientState;
-use std::io::ErrorKind;
-use std::os;
-use std::{
-    collections::VecDeque,
-    convert::TryFrom,
-    io::{self, Read, Write},
-    net::ToSocketAddrs,
-    str,
-    sync::Arc,
-    time::Duration,
-};
+use std::{str, time::Duration};
 use tls_socket::TlsSocket;
 
 pub mod handledata;
@@ -86,18 +69,6 @@ impl Poller {
         }
     }
 
-    fn process(
-        &mut self,
-        event: &mio::event::Event,
-        is_tls: bool,
-    ) -> Result<()> {
-        if is_tls {
-            self.tls_socket.process(event, &self.poll)
-        } else {
-            self.socket.process(event, &self.poll)
-        }
-    }
-
     #[inline]
     #[allow(dead_code)]
     pub fn send(&mut self, buf: MByteBuffer, is_tls: bool) -> Result<()> {
@@ -132,6 +103,7 @@ impl Poller {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn process_packets(
         &mut self,
         world: &mut World,
@@ -1,22 +1,9 @@
-use crate::{
-    Alert, BufferTask, ClientError, Content, Result, SERVER_ID, SERVER_PORT,
-    SystemHolder, config::*,
-};
+use crate::Result;
 pub use bytey::{ByteBuffer, ByteBufferError, ByteBufferRead, ByteBufferWrite};
 pub use mmap_bytey::{
     MByteBuffer, MByteBufferError, MByteBufferRead, MByteBufferWrite,
 };
-use std::io::ErrorKind;
-use std::os;
-use std::{
-    collections::VecDeque,
-    convert::TryFrom,
-    io::{self, Read, Write},
-    net::ToSocketAddrs,
-    str,
-    sync::Arc,
-    time::Duration,
-};
+use std::str;
 
 pub trait MByteBufferExt {
     fn new_packet() -> Result<MByteBuffer>;
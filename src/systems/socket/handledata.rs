@@ -1,6 +1,8 @@
 pub mod handle_entity;
 pub mod handle_general;
 pub mod handle_interface;
+pub mod handle_map;
+pub mod handle_party;
 pub mod handle_player;
 pub mod handle_trade;
 pub mod mapper;
@@ -2,20 +2,18 @@ use ascending_graphics::*;
 use mmap_bytey::MByteBuffer;
 
 use crate::{
-    Alert, DeathType, Entity, EntityKind, Equipment, GlobalKey, Item,
-    MovementData, NpcMode, Position, Result, UserAccess, VITALS_MAX, World,
+    DeathType, EffectKind, Entity, EntityKind, Equipment, GlobalKey, Item,
+    NpcMode, Position, Result, UserAccess, VITALS_MAX,
     content::{
-        Content, MapItem, NPC_SPRITE_FRAME_X, PLAYER_SPRITE_FRAME_X, add_npc,
-        add_player, create_npc_light, create_player_light, finalize_entity,
-        init_npc_attack, init_player_attack, is_map_connected, npc_finalized,
-        player_finalized, set_npc_frame, set_player_frame, unload_mapitems,
-        unload_npc, unload_player, update_camera, update_mapitem_position,
-        update_npc_camera, update_player_camera,
-    },
-    systems::{
-        BufferTask, FadeData, FadeType, Poller, SystemHolder, get_percent,
-        mapper::PacketPasser,
+        MapItem, NPC_SPRITE_FRAME_X, PLAYER_SPRITE_FRAME_X, add_npc,
+        add_player, can_move, create_npc_light, create_player_light,
+        finalize_entity, init_npc_attack, init_player_attack, is_map_connected,
+        npc_finalized, player_finalized, set_entity_death, set_npc_frame,
+        set_player_frame, unload_mapitems, unload_npc, unload_player,
+        update_camera, update_mapitem_position, update_npc_camera,
+        update_player_camera,
     },
+    systems::{FadeData, FadeType, get_percent, mapper::PacketPasser},
 };
 
 pub fn handle_playerspawn(
@@ -98,6 +96,17 @@ pub fn handle_playerspawn(
                 p_data.vitals.vitalmax = vitalmax;
             }
 
+            set_entity_death(
+                passer.world,
+                passer.systems,
+                passer.socket,
+                &mut passer.content.game_content,
+                entity,
+                deathtype,
+                passer.seconds,
+                deathtype == DeathType::Spawning,
+            )?;
+
             if passer.content.game_content.finalized {
                 player_finalized(
                     passer.world,
@@ -197,6 +206,17 @@ pub fn handle_npcdata(
                 n_data.vitals.vitalmax = vitalmax;
             }
 
+            set_entity_death(
+                passer.world,
+                passer.systems,
+                passer.socket,
+                &mut passer.content.game_content,
+                entity,
+                deathtype,
+                passer.seconds,
+                deathtype == DeathType::Spawning,
+            )?;
+
             if passer.content.game_content.finalized {
                 npc_finalized(
                     passer.world,
@@ -298,6 +318,11 @@ pub fn handle_move(
         let _switch = data.read::<bool>()?;
         let dir = data.read::<u8>()?;
 
+        // Our own steps are predicted and confirmed by `handle_moveack`.
+        if passer.content.game_content.myentity == Some(entity) {
+            continue;
+        }
+
         if let Some(myentity) = passer.content.game_content.myentity
             && passer.world.entities.contains_key(entity)
         {
@@ -312,29 +337,19 @@ pub fn handle_move(
             if is_map_connected(player_pos.map, pos.map) {
                 if let Some(entity_data) = passer.world.entities.get_mut(entity)
                 {
-                    let movement_data = MovementData { end_pos: pos, dir };
-
                     match entity_data {
-                        Entity::Player(p_data) => {
-                            if p_data.movement_buffer.is_empty() {
-                                p_data.movement_buffer.push_back(movement_data);
-                            } else if let Some(data) =
-                                p_data.movement_buffer.back()
-                                && *data != movement_data
-                            {
-                                p_data.movement_buffer.push_back(movement_data);
-                            }
-                        }
-                        Entity::Npc(n_data) => {
-                            if n_data.movement_buffer.is_empty() {
-                                n_data.movement_buffer.push_back(movement_data);
-                            } else if let Some(data) =
-                                n_data.movement_buffer.back()
-                                && *data != movement_data
-                            {
-                                n_data.movement_buffer.push_back(movement_data);
-                            }
-                        }
+                        Entity::Player(p_data) => p_data.snapshots.push(
+                            passer.seconds,
+                            p_data.pos,
+                            pos,
+                            dir,
+                        ),
+                        Entity::Npc(n_data) => n_data.snapshots.push(
+                            passer.seconds,
+                            n_data.pos,
+                            pos,
+                            dir,
+                        ),
                         _ => {}
                     }
                 }
@@ -399,7 +414,7 @@ pub fn handle_warp(
                 match entity_data {
                     Entity::Player(p_data) => {
                         let old_pos = p_data.pos;
-                        p_data.movement_buffer.clear();
+                        p_data.snapshots.reset(passer.seconds, pos, dir);
                         p_data.movement.is_moving = false;
                         p_data.pos = pos;
                         p_data.pos_offset = Vec2::ZERO;
@@ -409,7 +424,7 @@ pub fn handle_warp(
                     }
                     Entity::Npc(n_data) => {
                         let old_pos = n_data.pos;
-                        n_data.movement_buffer.clear();
+                        n_data.snapshots.reset(passer.seconds, pos, dir);
                         n_data.movement.is_moving = false;
                         n_data.pos = pos;
                         n_data.pos_offset = Vec2::ZERO;
@@ -457,6 +472,8 @@ pub fn handle_warp(
             };
 
             if myentity == entity {
+                passer.content.game_content.prediction.reset(Some(pos));
+
                 //Removed Sends clear...
                 //socket.client.sends.clear();
 
@@ -606,6 +623,84 @@ pub fn handle_warp(
     Ok(())
 }
 
+/// Answer to one of our predicted steps with the position the server put
+/// us on. When it is not where we predicted, our player is moved to the
+/// replayed prediction and slid there from where it was drawn.
+pub fn handle_moveack(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let seq = data.read::<u32>()?;
+    let pos = data.read::<Position>()?;
+
+    let Some(myentity) = passer.content.game_content.myentity else {
+        return Ok(());
+    };
+
+    let (world, systems) = (&*passer.world, &*passer.systems);
+    let Some(corrected) = passer.content.game_content.prediction.acknowledge(
+        seq,
+        pos,
+        |at, dir| can_move(world, systems, at, dir),
+    ) else {
+        return Ok(());
+    };
+
+    let (from, offset, dir) = match passer.world.entities.get_mut(myentity) {
+        Some(Entity::Player(p_data)) => {
+            let from = (p_data.pos, p_data.pos_offset, p_data.dir);
+
+            p_data.movement.is_moving = false;
+            p_data.movement.elapsed_time = 0.0;
+            p_data.pos = corrected;
+            p_data.end_movement = corrected;
+            p_data.pos_offset = Vec2::ZERO;
+
+            from
+        }
+        _ => return Ok(()),
+    };
+
+    passer
+        .content
+        .game_content
+        .prediction
+        .start_correction(from, offset, corrected);
+
+    if from.map != corrected.map {
+        passer.content.game_content.init_map(
+            passer.systems,
+            &mut passer.graphics.map_renderer,
+            corrected.map,
+            passer.buffer,
+            true,
+        )?;
+        finalize_entity(
+            passer.world,
+            passer.systems,
+            passer.content.game_content.game_lights,
+            corrected.map,
+        )?;
+        passer.content.game_content.refresh_map = true;
+    }
+
+    let frame = dir * PLAYER_SPRITE_FRAME_X as u8;
+    set_player_frame(passer.world, passer.systems, myentity, frame as usize)?;
+    update_player_camera(
+        passer.world,
+        passer.systems,
+        passer.socket,
+        myentity,
+        &mut passer.content.game_content,
+    )?;
+    update_camera(
+        passer.world,
+        &mut passer.content.game_content,
+        passer.systems,
+        passer.graphics,
+    )
+}
+
 pub fn handle_dir(
     data: &mut MByteBuffer,
     passer: &mut PacketPasser,
@@ -708,6 +803,13 @@ pub fn handle_vitals(
             size.x = get_percent(vitals[0], vitalmax[0], 18) as f32;
             passer.systems.gfx.set_size(&hpbar.bar_index, size);
 
+            passer
+                .content
+                .game_content
+                .interface
+                .target_frame
+                .set_vitals(passer.systems, entity, &vitals, &vitalmax);
+
             let entity_kind = passer.world.get_kind(entity)?;
 
             if entity_kind == EntityKind::Player
@@ -748,6 +850,69 @@ pub fn handle_vitals(
     Ok(())
 }
 
+/// Who the entity we target is targeting, sent after we pick a target and
+/// whenever its own target changes.
+pub fn handle_targetoftarget(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let entity = data.read::<GlobalKey>()?;
+    let target = data.read::<Option<GlobalKey>>()?;
+
+    passer
+        .content
+        .game_content
+        .interface
+        .target_frame
+        .set_target_of_target(entity, target);
+
+    Ok(())
+}
+
+/// Effects put on entities, with the milliseconds they have left.
+pub fn handle_applyeffects(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let count = data.read::<u32>()?;
+
+    for _ in 0..count {
+        let entity = data.read::<GlobalKey>()?;
+        let kind = data.read::<EffectKind>()?;
+        let duration = data.read::<u32>()?;
+
+        passer.content.game_content.status_effects.apply(
+            entity,
+            kind,
+            duration as f32 / 1000.0,
+            passer.seconds,
+        );
+    }
+
+    Ok(())
+}
+
+/// Effects that ended early, such as a cured poison.
+pub fn handle_removeeffects(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let count = data.read::<u32>()?;
+
+    for _ in 0..count {
+        let entity = data.read::<GlobalKey>()?;
+        let kind = data.read::<EffectKind>()?;
+
+        passer
+            .content
+            .game_content
+            .status_effects
+            .remove(entity, kind);
+    }
+
+    Ok(())
+}
+
 pub fn handle_attack(
     data: &mut MByteBuffer,
     passer: &mut PacketPasser,
@@ -791,13 +956,29 @@ pub fn handle_attack(
 
 pub fn handle_death(
     data: &mut MByteBuffer,
-    _passer: &mut PacketPasser,
+    passer: &mut PacketPasser,
 ) -> Result<()> {
     let count = data.read::<u32>()?;
 
     for _ in 0..count {
-        let _entity = data.read::<GlobalKey>()?;
-        let _deathtype = data.read::<DeathType>()?;
+        let entity = data.read::<GlobalKey>()?;
+        let deathtype = data.read::<DeathType>()?;
+
+        passer.content.game_content.combat_log.death(
+            entity,
+            deathtype,
+            passer.seconds,
+        );
+        set_entity_death(
+            passer.world,
+            passer.systems,
+            passer.socket,
+            &mut passer.content.game_content,
+            entity,
+            deathtype,
+            passer.seconds,
+            true,
+        )?;
     }
 
     Ok(())
@@ -3,12 +3,10 @@ use ascending_time::Instant;
 use mmap_bytey::MByteBuffer;
 
 use crate::{
-    Alert, GlobalKey, Result, World,
-    content::Content,
+    GlobalKey, Result,
     systems::{
-        BufferTask, FADE_SWITCH_TO_GAME, FadeData, FadeType, Poller,
-        SystemHolder, mapper::PacketPasser, send_handshake, send_login_ok,
-        send_tls_handshake,
+        FADE_SWITCH_TO_GAME, FadeData, FadeType, mapper::PacketPasser,
+        send_handshake, send_login_ok, send_tls_handshake,
     },
 };
 
@@ -41,7 +39,11 @@ pub fn handle_ping(
                 let average = sum / count as u64;
                 passer.systems.gfx.set_text(
                     &passer.content.game_content.interface.average_ping,
-                    &format!("Av. Ping: {average:?}"),
+                    passer
+                        .systems
+                        .locale
+                        .format("stats.average_ping", &[("ping", &average)])
+                        .as_ref(),
                 );
             }
             if count >= 20 {
@@ -63,7 +65,11 @@ pub fn handle_ping(
 
     passer.systems.gfx.set_text(
         &passer.content.game_content.interface.ping_text,
-        &format!("Ping: {elapse_time:?}"),
+        passer
+            .systems
+            .locale
+            .format("stats.ping", &[("ping", &elapse_time)])
+            .as_ref(),
     );
 
     Ok(())
@@ -88,6 +94,8 @@ pub fn handle_loginok(
     let _hour = data.read::<u32>()?;
     let _min = data.read::<u32>()?;
 
+    passer.systems.base.map_assets.reset_downloads();
+
     passer.systems.fade.init_fade(
         &mut passer.systems.gfx,
         FadeType::In,
@@ -1,13 +1,11 @@
 use mmap_bytey::MByteBuffer;
 
 use crate::{
-    Alert, AlertIndex, AlertType, COLOR_BLUE, COLOR_GREEN, COLOR_RED,
+    AlertIndex, AlertType, COLOR_BLUE, COLOR_GREEN, COLOR_PARTY, COLOR_RED,
     COLOR_WHITE, FtlType, GlobalKey, IsUsingType, MessageChannel, Position,
-    Result, UserAccess, World,
-    content::{Content, Window, add_float_text, open_interface},
-    systems::{
-        BufferTask, ChatTask, Poller, SystemHolder, mapper::PacketPasser,
-    },
+    Result, Shop, UserAccess,
+    content::{Window, add_float_text, open_interface},
+    systems::{ChatTask, mapper::PacketPasser},
 };
 
 pub fn handle_alertmsg(
@@ -21,7 +19,7 @@ pub fn handle_alertmsg(
         passer.systems,
         AlertType::Inform,
         &message,
-        "Alert Message",
+        "alert.header",
         250,
         AlertIndex::None,
         false,
@@ -51,12 +49,19 @@ pub fn handle_chatmsg(
         let head_string = data.read::<String>()?;
         let msg_string = data.read::<String>()?;
         let _useraccess = data.read::<Option<UserAccess>>()?;
+        // None for server and system messages.
+        let sender = data.read::<Option<String>>()?;
+
+        if sender.is_some_and(|name| passer.systems.config.is_ignored(&name)) {
+            continue;
+        }
 
         let header = if !head_string.is_empty() {
             let color = match channel {
                 MessageChannel::Global => COLOR_GREEN,
                 MessageChannel::Map => COLOR_BLUE,
                 MessageChannel::Private => COLOR_RED,
+                MessageChannel::Party => COLOR_PARTY,
                 _ => COLOR_WHITE,
             };
             Some((head_string.into(), color))
@@ -113,7 +118,8 @@ pub fn handle_openshop(
         .content
         .game_content
         .interface
-        .shop
+        .windows
+        .get_mut::<Shop>()
         .set_shop(passer.systems, shop_index as usize);
 
     passer
@@ -136,10 +142,19 @@ pub fn handle_damage(
     let count = data.read::<u32>()?;
 
     for _ in 0..count {
-        let _entity = data.read::<GlobalKey>()?;
+        let entity = data.read::<GlobalKey>()?;
         let amount = data.read::<u16>()?;
         let pos = data.read::<Position>()?;
         let is_damage = data.read::<bool>()?;
+        let game_content = &mut passer.content.game_content;
+
+        game_content.combat_log.damage(
+            entity,
+            game_content.myentity == Some(entity),
+            amount,
+            is_damage,
+            passer.seconds,
+        );
 
         let (text, color) = if is_damage {
             (format!("-{amount}"), COLOR_RED)
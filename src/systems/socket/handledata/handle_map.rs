@@ -0,0 +1,70 @@
+use log::error;
+use mmap_bytey::MByteBuffer;
+
+use crate::{
+    BufferTaskEnum, MapCacheEntry, MapPosition, Result, map_file_name,
+    send_requestmapdata, systems::mapper::PacketPasser, verify_map_bytes,
+    write_atomic,
+};
+
+pub fn handle_maprevision(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let mappos = data.read::<MapPosition>()?;
+    let revision = data.read::<u64>()?;
+    let hash = data.read::<u64>()?;
+
+    let cache = &mut passer.systems.base.map_assets;
+
+    if cache.is_current(mappos, revision, hash)
+        || !cache.downloads.insert(mappos)
+    {
+        return Ok(());
+    }
+
+    send_requestmapdata(passer.socket, mappos)
+}
+
+pub fn handle_mapdata(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let mappos = data.read::<MapPosition>()?;
+    let revision = data.read::<u64>()?;
+    let hash = data.read::<u64>()?;
+    let bytes = data.read::<Vec<u8>>()?;
+
+    let cache = &mut passer.systems.base.map_assets;
+    cache.downloads.remove(&mappos);
+
+    // A bad download only costs us this map, so log it instead of dropping
+    // the connection, and ask for it once more in case it was corrupted on
+    // the way.
+    if let Err(e) = verify_map_bytes(mappos, &bytes, hash) {
+        error!("Rejected map download, Err {e}");
+
+        if cache.download_failed(mappos) && cache.downloads.insert(mappos) {
+            send_requestmapdata(passer.socket, mappos)?;
+        }
+
+        return Ok(());
+    }
+
+    if let Err(e) = write_atomic(&map_file_name(mappos), &bytes) {
+        error!("Failed to store map download, Err {e}");
+        cache.invalidate(mappos);
+        return Ok(());
+    }
+
+    cache.download_succeeded(mappos);
+    cache.set_entry(mappos, MapCacheEntry { revision, hash });
+
+    if let Some(key) = passer.systems.base.mappos_key.get(&mappos) {
+        passer
+            .buffer
+            .add_task(BufferTaskEnum::ApplyMap(mappos, *key));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use mmap_bytey::MByteBuffer;
+
+use crate::{
+    AlertIndex, AlertType, COLOR_PARTY, Chatbox, GlobalKey, MessageChannel,
+    Result, content::PartyMember, send_declineparty,
+    systems::mapper::PacketPasser,
+};
+
+pub fn handle_partyinvite(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let name = data.read::<String>()?;
+
+    if passer.systems.config.is_ignored(&name) {
+        return send_declineparty(passer.socket);
+    }
+
+    let header = passer
+        .systems
+        .locale
+        .format("party.invite_header", &[("name", &name)]);
+
+    passer.alert.show_alert(
+        passer.systems,
+        AlertType::Confirm,
+        "party.invite",
+        header.as_ref(),
+        250,
+        AlertIndex::PartyInvite,
+        false,
+    );
+
+    Ok(())
+}
+
+/// Every member of our party, sent when we join and whenever a member
+/// joins, leaves or changes.
+pub fn handle_partydata(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let leader = data.read::<Option<GlobalKey>>()?;
+    let count = data.read::<u32>()?;
+    let mut members = Vec::with_capacity(count as usize);
+
+    for _ in 0..count {
+        members.push(data.read::<PartyMember>()?);
+    }
+
+    passer
+        .content
+        .game_content
+        .party
+        .set_members(leader, members);
+
+    Ok(())
+}
+
+/// We left the party or it was disbanded.
+pub fn handle_partyleft(
+    _data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    if !passer.content.game_content.party.in_party() {
+        return Ok(());
+    }
+
+    passer.content.game_content.party.clear();
+
+    let text = passer.systems.locale.text("party.left");
+    passer
+        .content
+        .game_content
+        .interface
+        .windows
+        .get_mut::<Chatbox>()
+        .add_chat(
+            passer.systems,
+            (text, COLOR_PARTY),
+            None,
+            MessageChannel::Party,
+        );
+
+    Ok(())
+}
+
+/// Our share of the experience of a kill made by the party.
+pub fn handle_partyexp(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let amount = data.read::<u64>()?;
+
+    passer.content.game_content.party.add_shared_exp(amount);
+
+    Ok(())
+}
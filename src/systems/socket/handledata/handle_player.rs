@@ -1,14 +1,15 @@
 use mmap_bytey::MByteBuffer;
 
 use crate::{
-    Alert, DeathType, Entity, Equipment, GlobalKey, IsUsingType, Item,
-    MAX_EQPT, Position, Result, UserAccess, VITALS_MAX, World,
+    DeathType, Entity, Equipment, GlobalKey, InspectData, InspectWindow,
+    Inventory, IsUsingType, Item, MAX_EQPT, Position, Profile, Result, Storage,
+    Trade, UserAccess, VITALS_MAX,
     content::{
-        Content, ProfileLabel, Window, add_player, close_interface,
-        create_player_light, player_get_armor_defense, player_get_next_lvl_exp,
-        player_get_weapon_damage,
+        ProfileLabel, Window, add_player, close_interface, create_player_light,
+        open_interface, player_get_armor_defense, player_get_next_lvl_exp,
+        player_get_weapon_damage, set_entity_death,
     },
-    systems::{BufferTask, Poller, SystemHolder, mapper::PacketPasser},
+    systems::mapper::PacketPasser,
 };
 
 pub fn handle_playerdata(
@@ -84,6 +85,17 @@ pub fn handle_playerdata(
             p_data.vitals.vital = vitals;
             p_data.vitals.vitalmax = vitalmax;
         }
+
+        set_entity_death(
+            passer.world,
+            passer.systems,
+            passer.socket,
+            &mut passer.content.game_content,
+            entity,
+            deathtype,
+            passer.seconds,
+            false,
+        )?;
     }
     Ok(())
 }
@@ -103,7 +115,8 @@ pub fn handle_playerinv(
                 .content
                 .game_content
                 .interface
-                .inventory
+                .windows
+                .get_mut::<Inventory>()
                 .update_inv_slot(passer.systems, index, item);
         }
     }
@@ -122,7 +135,8 @@ pub fn handle_playerinvslot(
         .content
         .game_content
         .interface
-        .inventory
+        .windows
+        .get_mut::<Inventory>()
         .update_inv_slot(passer.systems, index, &item);
 
     Ok(())
@@ -148,7 +162,8 @@ pub fn handle_playerstorage(
                 .content
                 .game_content
                 .interface
-                .storage
+                .windows
+                .get_mut::<Storage>()
                 .update_storage_slot(passer.systems, index + start, item);
         }
     }
@@ -167,7 +182,8 @@ pub fn handle_playerstorageslot(
         .content
         .game_content
         .interface
-        .storage
+        .windows
+        .get_mut::<Storage>()
         .update_storage_slot(passer.systems, index, &item);
 
     Ok(())
@@ -201,7 +217,8 @@ pub fn handle_playerequipment(
                     .content
                     .game_content
                     .interface
-                    .profile
+                    .windows
+                    .get_mut::<Profile>()
                     .update_equipment_slot(
                         passer.systems,
                         i,
@@ -220,7 +237,8 @@ pub fn handle_playerequipment(
             .content
             .game_content
             .interface
-            .profile
+            .windows
+            .get_mut::<Profile>()
             .set_profile_label_value(
                 passer.systems,
                 ProfileLabel::Damage,
@@ -234,7 +252,8 @@ pub fn handle_playerequipment(
             .content
             .game_content
             .interface
-            .profile
+            .windows
+            .get_mut::<Profile>()
             .set_profile_label_value(
                 passer.systems,
                 ProfileLabel::Defense,
@@ -253,6 +272,11 @@ pub fn handle_playerlevel(
     let levelexp = data.read::<u64>()?;
 
     passer.content.game_content.player_data.levelexp = levelexp;
+    passer.content.game_content.combat_log.level(
+        level,
+        levelexp,
+        passer.seconds,
+    );
 
     if let Some(myentity) = passer.content.game_content.myentity
         && passer.world.entities.contains_key(myentity)
@@ -282,7 +306,8 @@ pub fn handle_playerlevel(
                 .content
                 .game_content
                 .interface
-                .profile
+                .windows
+                .get_mut::<Profile>()
                 .set_profile_label_value(
                     passer.systems,
                     ProfileLabel::Level,
@@ -305,7 +330,8 @@ pub fn handle_playermoney(
         .content
         .game_content
         .interface
-        .profile
+        .windows
+        .get_mut::<Profile>()
         .set_profile_label_value(passer.systems, ProfileLabel::Money, vals);
 
     Ok(())
@@ -318,6 +344,22 @@ pub fn handle_playerpk(
     Ok(())
 }
 
+pub fn handle_inspectdata(
+    data: &mut MByteBuffer,
+    passer: &mut PacketPasser,
+) -> Result<()> {
+    let inspect = data.read::<InspectData>()?;
+    let interface = &mut passer.content.game_content.interface;
+
+    interface
+        .windows
+        .get_mut::<InspectWindow>()
+        .set_data(passer.systems, &inspect);
+    open_interface(interface, passer.systems, Window::Inspect);
+
+    Ok(())
+}
+
 pub fn handle_clearisusingtype(
     data: &mut MByteBuffer,
     passer: &mut PacketPasser,
@@ -345,7 +387,8 @@ pub fn handle_clearisusingtype(
                 .content
                 .game_content
                 .interface
-                .trade
+                .windows
+                .get_mut::<Trade>()
                 .clear_trade_items(passer.systems);
         }
         _ => {}
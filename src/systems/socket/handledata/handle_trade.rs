@@ -1,10 +1,10 @@
 use mmap_bytey::MByteBuffer;
 
 use crate::{
-    Alert, AlertIndex, AlertType, Entity, GlobalKey, IsUsingType, Item, Result,
-    TradeStatus, World,
-    content::{Content, Window, open_interface},
-    systems::{BufferTask, Poller, SystemHolder, mapper::PacketPasser},
+    AlertIndex, AlertType, Entity, GlobalKey, IsUsingType, Item, Result, Trade,
+    TradeStatus,
+    content::{Window, open_interface},
+    systems::mapper::PacketPasser,
 };
 
 pub fn handle_updatetradeitem(
@@ -19,7 +19,8 @@ pub fn handle_updatetradeitem(
         .content
         .game_content
         .interface
-        .trade
+        .windows
+        .get_mut::<Trade>()
         .update_trade_slot(
             passer.systems,
             trade_slot as usize,
@@ -40,7 +41,8 @@ pub fn handle_updatetrademoney(
         .content
         .game_content
         .interface
-        .trade
+        .windows
+        .get_mut::<Trade>()
         .update_trade_money(passer.systems, amount);
 
     Ok(())
@@ -64,7 +66,8 @@ pub fn handle_inittrade(
         .content
         .game_content
         .interface
-        .trade
+        .windows
+        .get_mut::<Trade>()
         .clear_trade_items(passer.systems);
 
     passer
@@ -84,17 +87,31 @@ pub fn handle_tradestatus(
     let my_status = data.read::<TradeStatus>()?;
     let their_status = data.read::<TradeStatus>()?;
 
-    passer.content.game_content.interface.trade.trade_status = my_status;
+    passer
+        .content
+        .game_content
+        .interface
+        .windows
+        .get_mut::<Trade>()
+        .trade_status = my_status;
 
     if my_status == TradeStatus::Accepted
         && their_status == TradeStatus::Accepted
     {
-        passer.content.game_content.interface.trade.button[1]
-            .change_text(passer.systems, "Confirm".into());
-        passer.content.game_content.interface.trade.update_status(
-            passer.systems,
-            "Click the 'Confirm' Button to proceed".into(),
-        );
+        passer
+            .content
+            .game_content
+            .interface
+            .windows
+            .get_mut::<Trade>()
+            .set_confirm_text(passer.systems, "trade.confirm");
+        passer
+            .content
+            .game_content
+            .interface
+            .windows
+            .get_mut::<Trade>()
+            .update_status(passer.systems, "trade.confirm_hint");
     }
 
     match my_status {
@@ -103,27 +120,27 @@ pub fn handle_tradestatus(
                 .content
                 .game_content
                 .interface
-                .trade
-                .update_my_status(
-                    passer.systems,
-                    "My Trade: Preparing...".into(),
-                );
+                .windows
+                .get_mut::<Trade>()
+                .update_my_status(passer.systems, "trade.my_preparing");
         }
         TradeStatus::Accepted => {
             passer
                 .content
                 .game_content
                 .interface
-                .trade
-                .update_my_status(passer.systems, "My Trade: Submitted".into());
+                .windows
+                .get_mut::<Trade>()
+                .update_my_status(passer.systems, "trade.my_submitted");
         }
         TradeStatus::Submitted => {
             passer
                 .content
                 .game_content
                 .interface
-                .trade
-                .update_my_status(passer.systems, "My Trade: Confirmed".into());
+                .windows
+                .get_mut::<Trade>()
+                .update_my_status(passer.systems, "trade.my_confirmed");
         }
     }
     match their_status {
@@ -132,33 +149,27 @@ pub fn handle_tradestatus(
                 .content
                 .game_content
                 .interface
-                .trade
-                .update_their_status(
-                    passer.systems,
-                    "Their Trade: Preparing...".into(),
-                );
+                .windows
+                .get_mut::<Trade>()
+                .update_their_status(passer.systems, "trade.their_preparing");
         }
         TradeStatus::Accepted => {
             passer
                 .content
                 .game_content
                 .interface
-                .trade
-                .update_their_status(
-                    passer.systems,
-                    "Their Trade: Submitted".into(),
-                );
+                .windows
+                .get_mut::<Trade>()
+                .update_their_status(passer.systems, "trade.their_submitted");
         }
         TradeStatus::Submitted => {
             passer
                 .content
                 .game_content
                 .interface
-                .trade
-                .update_their_status(
-                    passer.systems,
-                    "Their Trade: Confirmed".into(),
-                );
+                .windows
+                .get_mut::<Trade>()
+                .update_their_status(passer.systems, "trade.their_confirmed");
         }
     }
 
@@ -182,11 +193,16 @@ pub fn handle_traderequest(
         return Ok(());
     };
 
+    let header = passer
+        .systems
+        .locale
+        .format("trade.request_header", &[("name", name)]);
+
     passer.alert.show_alert(
         passer.systems,
         AlertType::Confirm,
-        "Would you like to accept this trade request?",
-        &format!("{name} would like to trade with you"),
+        "trade.request",
+        header.as_ref(),
         250,
         AlertIndex::TradeRequest,
         false,
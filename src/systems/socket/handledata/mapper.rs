@@ -1,12 +1,10 @@
-use crate::{BufferTask, data_types::*, fade::*, socket::*, systems::State};
-use ahash::AHashMap;
+use crate::{BufferTask, data_types::*, socket::*, systems::State};
 use ascending_camera::controls::FlatControls;
-use ascending_graphics::MapRenderer;
 use serde::{Deserialize, Serialize};
 
 use super::{
-    handle_entity::*, handle_general::*, handle_interface::*, handle_player::*,
-    handle_trade::*,
+    handle_entity::*, handle_general::*, handle_interface::*, handle_map::*,
+    handle_party::*, handle_player::*, handle_trade::*,
 };
 
 pub struct PacketPasser<'a> {
@@ -76,6 +74,17 @@ pub enum ServerPackets {
     Ping,
     TlsHandShake,
     ClearData,
+    MapRevision,
+    MapData,
+    MoveAck,
+    InspectData,
+    TargetOfTarget,
+    ApplyEffects,
+    RemoveEffects,
+    PartyInvite,
+    PartyData,
+    PartyLeft,
+    PartyExp,
 }
 
 pub fn run_packet(packet: &ServerPackets) -> Option<PacketFunction> {
@@ -150,6 +159,29 @@ pub fn run_packet(packet: &ServerPackets) -> Option<PacketFunction> {
             Some(handle_tls_handshake as PacketFunction)
         }
         ServerPackets::ClearData => Some(handle_clear_data as PacketFunction),
+        ServerPackets::MapRevision => {
+            Some(handle_maprevision as PacketFunction)
+        }
+        ServerPackets::MapData => Some(handle_mapdata as PacketFunction),
+        ServerPackets::MoveAck => Some(handle_moveack as PacketFunction),
+        ServerPackets::InspectData => {
+            Some(handle_inspectdata as PacketFunction)
+        }
+        ServerPackets::TargetOfTarget => {
+            Some(handle_targetoftarget as PacketFunction)
+        }
+        ServerPackets::ApplyEffects => {
+            Some(handle_applyeffects as PacketFunction)
+        }
+        ServerPackets::RemoveEffects => {
+            Some(handle_removeeffects as PacketFunction)
+        }
+        ServerPackets::PartyInvite => {
+            Some(handle_partyinvite as PacketFunction)
+        }
+        ServerPackets::PartyData => Some(handle_partydata as PacketFunction),
+        ServerPackets::PartyLeft => Some(handle_partyleft as PacketFunction),
+        ServerPackets::PartyExp => Some(handle_partyexp as PacketFunction),
         ServerPackets::OnlineCheck => None,
     }
 }
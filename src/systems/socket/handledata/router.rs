@@ -1,11 +1,10 @@
 use ascending_camera::controls::FlatControls;
-use log::{error, trace};
+use log::error;
 use snafu::Backtrace;
 
 use crate::{
     BufferTask,
     data_types::*,
-    fade::*,
     socket::*,
     systems::{
         State,
@@ -1,28 +1,19 @@
-use crate::{
-    Alert, BufferTask, ClientError, Content, Result, SERVER_ID, SERVER_PORT,
-    SystemHolder, config::*,
-};
+use crate::{ClientError, Result, SERVER_ID, SERVER_PORT};
 pub use bytey::{ByteBuffer, ByteBufferError, ByteBufferRead, ByteBufferWrite};
 
-use log::{info, trace, warn};
+use log::{info, trace};
+use mio::Poll;
 use mio::net::TcpStream;
-use mio::{Events, Interest, Poll};
 pub use mmap_bytey::{
     MByteBuffer, MByteBufferError, MByteBufferRead, MByteBufferWrite,
 };
-use pki_types::ServerName;
-use serde::{Deserialize, Serialize};
 use snafu::Backtrace;
 use std::io::ErrorKind;
-use std::os;
 use std::{
     collections::VecDeque,
-    convert::TryFrom,
     io::{self, Read, Write},
     net::ToSocketAddrs,
     str,
-    sync::Arc,
-    time::Duration,
 };
 
 use super::states::PollState;
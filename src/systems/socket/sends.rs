@@ -1,4 +1,4 @@
-use crate::{GlobalKey, Position, data_types::*, socket::*};
+use crate::{GlobalKey, MapPosition, Position, data_types::*, socket::*};
 
 use super::bufer_ext::MByteBufferExt;
 
@@ -52,6 +52,14 @@ enum ClientPacket {
     Reconnect,
     Disconnect,
     LoginOk,
+    MapRevision,
+    RequestMapData,
+    MoveStep,
+    RequestInspect,
+    PartyInvite,
+    AcceptParty,
+    DeclineParty,
+    LeaveParty,
 }
 
 pub fn send_register(
@@ -108,11 +116,28 @@ pub fn send_handshake(socket: &mut Poller, handshake: &str) -> Result<()> {
     socket.send(buf, false)
 }
 
-pub fn send_move(socket: &mut Poller, dir: Option<u8>) -> Result<()> {
+/// One predicted step, the server answers with a `MoveAck` for `seq`.
+pub fn send_movestep(socket: &mut Poller, dir: u8, seq: u32) -> Result<()> {
     let mut buf = MByteBuffer::new_packet()?;
 
-    buf.write(ClientPacket::Move)?;
+    buf.write(ClientPacket::MoveStep)?;
     buf.write(dir)?;
+    buf.write(seq)?;
+    buf.finish()?;
+
+    socket.send(buf, false)
+}
+
+/// Asks for the equipment and details of another player, the server answers
+/// with `InspectData`.
+pub fn send_requestinspect(
+    socket: &mut Poller,
+    entity: GlobalKey,
+) -> Result<()> {
+    let mut buf = MByteBuffer::new_packet()?;
+
+    buf.write(ClientPacket::RequestInspect)?;
+    buf.write(entity)?;
     buf.finish()?;
 
     socket.send(buf, false)
@@ -434,6 +459,43 @@ pub fn send_declinetrade(socket: &mut Poller) -> Result<()> {
     socket.send(buf, false)
 }
 
+pub fn send_partyinvite(socket: &mut Poller, entity: GlobalKey) -> Result<()> {
+    let mut buf = MByteBuffer::new_packet()?;
+
+    buf.write(ClientPacket::PartyInvite)?;
+    buf.write(entity)?;
+    buf.finish()?;
+
+    socket.send(buf, false)
+}
+
+pub fn send_acceptparty(socket: &mut Poller) -> Result<()> {
+    let mut buf = MByteBuffer::new_packet()?;
+
+    buf.write(ClientPacket::AcceptParty)?;
+    buf.finish()?;
+
+    socket.send(buf, false)
+}
+
+pub fn send_declineparty(socket: &mut Poller) -> Result<()> {
+    let mut buf = MByteBuffer::new_packet()?;
+
+    buf.write(ClientPacket::DeclineParty)?;
+    buf.finish()?;
+
+    socket.send(buf, false)
+}
+
+pub fn send_leaveparty(socket: &mut Poller) -> Result<()> {
+    let mut buf = MByteBuffer::new_packet()?;
+
+    buf.write(ClientPacket::LeaveParty)?;
+    buf.finish()?;
+
+    socket.send(buf, false)
+}
+
 pub fn send_ping(socket: &mut Poller) -> Result<()> {
     let mut buf = MByteBuffer::new_packet()?;
 
@@ -506,3 +568,31 @@ pub fn send_login_ok(socket: &mut Poller, code: &str) -> Result<()> {
 
     socket.send(buf, false)
 }
+
+pub fn send_maprevision(
+    socket: &mut Poller,
+    mappos: MapPosition,
+    revision: u64,
+) -> Result<()> {
+    let mut buf = MByteBuffer::new_packet()?;
+
+    buf.write(ClientPacket::MapRevision)?;
+    buf.write(mappos)?;
+    buf.write(revision)?;
+    buf.finish()?;
+
+    socket.send(buf, false)
+}
+
+pub fn send_requestmapdata(
+    socket: &mut Poller,
+    mappos: MapPosition,
+) -> Result<()> {
+    let mut buf = MByteBuffer::new_packet()?;
+
+    buf.write(ClientPacket::RequestMapData)?;
+    buf.write(mappos)?;
+    buf.finish()?;
+
+    socket.send(buf, false)
+}
@@ -1,27 +1,18 @@
-use crate::{
-    Alert, BufferTask, ClientError, Content, Result, SERVER_ID, SystemHolder,
-    TLS_SERVER_PORT, config::*,
-};
+use crate::{Result, SERVER_ID, TLS_SERVER_PORT, config::*};
 pub use bytey::{ByteBuffer, ByteBufferError, ByteBufferRead, ByteBufferWrite};
 
-use log::{info, trace, warn};
+use log::trace;
+use mio::Poll;
 use mio::net::TcpStream;
-use mio::{Events, Interest, Poll};
 pub use mmap_bytey::{
     MByteBuffer, MByteBufferError, MByteBufferRead, MByteBufferWrite,
 };
 use pki_types::ServerName;
-use serde::{Deserialize, Serialize};
 use std::io::ErrorKind;
-use std::os;
 use std::{
     collections::VecDeque,
     convert::TryFrom,
     io::{self, Read, Write},
-    net::ToSocketAddrs,
-    str,
-    sync::Arc,
-    time::Duration,
 };
 
 use super::notls_socket::connect;
@@ -1,10 +1,9 @@
 use ascending_graphics::*;
 use indexmap::IndexMap;
-use rustls::internal::msgs;
 use std::collections::VecDeque;
 
 use crate::{
-    Content, MapAttributes, MapDirBlock, MapPosition, MessageChannel, Result,
+    Chatbox, Content, MapPosition, MapStream, MessageChannel, Result,
     SystemHolder, data_types::*, database::map::*,
 };
 
@@ -20,6 +19,7 @@ pub struct BufferTask {
     pub task: VecDeque<BufferTaskEnum>,
     pub storage: StoredData,
     pub chatbuffer: ChatBufferTask,
+    pub map_stream: MapStream,
 }
 
 impl Default for BufferTask {
@@ -30,6 +30,7 @@ impl Default for BufferTask {
                 map_data: IndexMap::default(),
             },
             chatbuffer: ChatBufferTask::new(),
+            map_stream: MapStream::new(),
         }
     }
 }
@@ -42,6 +43,7 @@ impl BufferTask {
     pub fn clear_buffer(&mut self) {
         self.task.clear();
         self.storage.map_data.clear();
+        self.map_stream.clear();
     }
 
     pub fn process_buffer(
@@ -50,14 +52,21 @@ impl BufferTask {
         content: &mut Content,
     ) -> Result<()> {
         self.chatbuffer.process_buffer(systems, content);
-
-        if !self.task.is_empty() {
-            let task_data = self.task.pop_front();
-
-            if let Some(task) = task_data {
-                match task {
-                    BufferTaskEnum::ApplyMap(mappos, index) => {
+        self.map_stream.poll(systems)?;
+
+        while let Some(task) = self.task.pop_front() {
+            match task {
+                BufferTaskEnum::ApplyMap(mappos, index) => {
+                    let expected_hash = systems
+                        .base
+                        .map_assets
+                        .get_entry(mappos)
+                        .map(|entry| entry.hash);
+
+                    if !self.map_stream.request(index, mappos, expected_hash) {
+                        // No runtime to stream with so keep to one map per frame.
                         load_map_data(systems, index, mappos)?;
+                        break;
                     }
                 }
             }
@@ -112,12 +121,12 @@ impl ChatBufferTask {
         let task_data = self.task.pop_front();
 
         if let Some(task) = task_data {
-            content.game_content.interface.chatbox.add_chat(
-                systems,
-                task.msg,
-                task.header_msg,
-                task.channel,
-            );
+            content
+                .game_content
+                .interface
+                .windows
+                .get_mut::<Chatbox>()
+                .add_chat(systems, task.msg, task.header_msg, task.channel);
         }
     }
 
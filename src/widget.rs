@@ -1,15 +1,31 @@
 pub mod alert;
 pub mod button;
 pub mod checkbox;
+pub mod context_menu;
+pub mod dropdown;
+pub mod focus;
 pub mod label;
+pub mod layout;
+pub mod list_view;
 pub mod scrollbar;
+pub mod slider;
+pub mod tab_container;
 pub mod textbox;
+pub mod theme;
 pub mod tooltip;
 
 pub use alert::*;
 pub use button::*;
 pub use checkbox::*;
+pub use context_menu::*;
+pub use dropdown::*;
+pub use focus::*;
 pub use label::*;
+pub use layout::*;
+pub use list_view::*;
 pub use scrollbar::*;
+pub use slider::*;
+pub use tab_container::*;
 pub use textbox::*;
+pub use theme::*;
 pub use tooltip::*;
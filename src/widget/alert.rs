@@ -3,9 +3,10 @@ use crate::{
     content::{Content, ContentType},
     data_types::*,
     logic::*,
-    send_accepttrade, send_addtradeitem, send_declinetrade, send_deposititem,
-    send_dropitem, send_removetradeitem, send_sellitem, send_switchinvslot,
-    send_switchstorageslot, send_withdrawitem, socket,
+    send_acceptparty, send_accepttrade, send_addtradeitem, send_declineparty,
+    send_declinetrade, send_deposititem, send_dropitem, send_removetradeitem,
+    send_sellitem, send_switchinvslot, send_switchstorageslot,
+    send_withdrawitem,
     systems::{
         FADE_SWITCH_TO_TITLE, FadeData, FadeType, Poller, send_disconnect,
     },
@@ -13,7 +14,7 @@ use crate::{
 };
 use ascending_graphics::{cosmic_text::Attrs, *};
 use ascending_input::Key;
-use winit::{event_loop::ActiveEventLoop, keyboard::NamedKey};
+use winit::event_loop::ActiveEventLoop;
 
 #[derive(PartialEq, Eq)]
 pub enum AlertType {
@@ -33,6 +34,7 @@ pub enum AlertIndex {
     Deposit(u16, u16),
     Withdraw(u16, u16),
     TradeRequest,
+    PartyInvite,
     Offline,
     ExitGame,
     Disconnect,
@@ -76,6 +78,9 @@ impl Alert {
         Alert::default()
     }
 
+    /// `msg` and `header` are looked up in the string tables, text that is
+    /// not a key, like a message from the server, is shown as is.
+    #[allow(clippy::too_many_arguments)]
     pub fn show_alert(
         &mut self,
         systems: &mut SystemHolder,
@@ -122,12 +127,19 @@ impl Alert {
             Some(128.0),
         )
         .set_wrap(cosmic_text::Wrap::Word);
-        text.set_text(msg, &Attrs::new(), Shaping::Advanced, None);
+        let msg = systems.locale.text(msg);
+        text.set_text(msg.as_ref(), &Attrs::new(), Shaping::Advanced, None);
 
         let text_size = text.measure(&mut systems.renderer.font_sys).floor();
         let mut header_text = create_empty_label(systems);
 
-        header_text.set_text(header, &Attrs::new(), Shaping::Advanced, None);
+        let header = systems.locale.text(header);
+        header_text.set_text(
+            header.as_ref(),
+            &Attrs::new(),
+            Shaping::Advanced,
+            None,
+        );
 
         let header_text_size =
             header_text.measure(&mut systems.renderer.font_sys).floor();
@@ -204,11 +216,12 @@ impl Alert {
 
         self.text.push(header_text_index);
 
+        let style = systems.theme.alert;
         let bg = Rect::new(
             &mut systems.renderer,
             Vec3::new(0.0, 0.0, ORDER_ALERT_BG),
             Vec2::new(systems.size.width, systems.size.height),
-            Color::rgba(10, 10, 10, 140),
+            style.backdrop,
             0,
         );
 
@@ -216,13 +229,13 @@ impl Alert {
             &mut systems.renderer,
             w_pos - Vec3::new(1.0, 1.0, 0.0),
             w_size + Vec2::new(2.0, 2.0),
-            Color::rgba(160, 160, 160, 255),
+            style.color,
             0,
         );
 
         window
-            .set_border_width(1.0)
-            .set_border_color(Color::rgba(40, 40, 40, 255));
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(style.border_color);
         self.window.push(systems.gfx.add_rect(
             bg,
             3,
@@ -272,7 +285,7 @@ impl Alert {
                     ORDER_ALERT_HEADER,
                 ),
                 Vec2::new(w_size.x, (30.0 * systems.scale as f32).floor()),
-                Color::rgba(100, 100, 100, 255),
+                style.header_color,
                 0,
             );
 
@@ -286,28 +299,21 @@ impl Alert {
         }
 
         let button_detail = ButtonRect {
-            rect_color: Color::rgba(70, 70, 70, 255),
             got_border: true,
-            border_color: Color::rgba(40, 40, 40, 255),
-            border_radius: 0.0,
-            hover_change: ButtonChangeType::ColorChange(Color::rgba(
-                150, 150, 150, 255,
-            )),
-            click_change: ButtonChangeType::ColorChange(Color::rgba(
-                200, 200, 200, 255,
-            )),
+            ..systems.theme.button.rect()
         };
 
         match alert_type {
             AlertType::Inform => {
                 let pos = Vec2::new(((orig_size.x - 60.0) * 0.5).floor(), 10.0);
+                let okay = systems.locale.text("alert.okay");
                 self.button.push(Button::new(
                     systems,
                     ButtonType::Rect(button_detail),
                     ButtonContentType::Text(ButtonContentText {
-                        text: "Okay".into(),
+                        text: okay,
                         pos: Vec2::new(0.0, 5.0),
-                        color: Color::rgba(255, 255, 255, 255),
+                        color: style.text,
                         render_layer: 5,
                         hover_change: ButtonChangeType::None,
                         click_change: ButtonChangeType::None,
@@ -325,13 +331,17 @@ impl Alert {
             AlertType::Confirm => {
                 let pos =
                     Vec2::new(((orig_size.x - 130.0) * 0.5).floor(), 10.0);
+                let (yes, no) = (
+                    systems.locale.text("alert.yes"),
+                    systems.locale.text("alert.no"),
+                );
                 self.button.push(Button::new(
                     systems,
                     ButtonType::Rect(button_detail),
                     ButtonContentType::Text(ButtonContentText {
-                        text: "Yes".into(),
+                        text: yes,
                         pos: Vec2::new(0.0, 5.0),
-                        color: Color::rgba(255, 255, 255, 255),
+                        color: style.text,
                         render_layer: 5,
                         hover_change: ButtonChangeType::None,
                         click_change: ButtonChangeType::None,
@@ -349,9 +359,9 @@ impl Alert {
                     systems,
                     ButtonType::Rect(button_detail),
                     ButtonContentType::Text(ButtonContentText {
-                        text: "No".into(),
+                        text: no,
                         pos: Vec2::new(0.0, 5.0),
-                        color: Color::rgba(255, 255, 255, 255),
+                        color: style.text,
                         render_layer: 5,
                         hover_change: ButtonChangeType::None,
                         click_change: ButtonChangeType::None,
@@ -379,13 +389,13 @@ impl Alert {
                         ORDER_ALERT_TEXTBOX_BG,
                     ),
                     (Vec2::new(104.0, 24.0) * systems.scale as f32).floor(),
-                    Color::rgba(120, 120, 120, 255),
+                    style.input_color,
                     0,
                 );
 
                 textbox_bg
-                    .set_border_width(1.0)
-                    .set_border_color(Color::rgba(40, 40, 40, 255));
+                    .set_border_width(systems.theme.border_width)
+                    .set_border_color(style.border_color);
 
                 let textbox = Textbox::new(
                     systems,
@@ -393,11 +403,11 @@ impl Alert {
                     textbox_pos + Vec2::new(2.0, 2.0),
                     (0.001, 3),
                     Vec2::new(100.0, 20.0),
-                    Color::rgba(200, 200, 200, 255),
+                    style.textbox.text,
                     5,
                     10,
-                    Color::rgba(80, 80, 80, 255),
-                    Color::rgba(10, 10, 150, 255),
+                    style.textbox.background,
+                    style.textbox.selection,
                     false,
                     true,
                     None,
@@ -419,14 +429,18 @@ impl Alert {
 
                 let pos =
                     Vec2::new(((orig_size.x - 150.0) * 0.5).floor(), 10.0);
+                let (confirm, cancel) = (
+                    systems.locale.text("alert.confirm"),
+                    systems.locale.text("alert.cancel"),
+                );
 
                 self.button.push(Button::new(
                     systems,
                     ButtonType::Rect(button_detail),
                     ButtonContentType::Text(ButtonContentText {
-                        text: "Confirm".into(),
+                        text: confirm,
                         pos: Vec2::new(0.0, 5.0),
-                        color: Color::rgba(255, 255, 255, 255),
+                        color: style.text,
                         render_layer: 5,
                         hover_change: ButtonChangeType::None,
                         click_change: ButtonChangeType::None,
@@ -444,9 +458,9 @@ impl Alert {
                     systems,
                     ButtonType::Rect(button_detail),
                     ButtonContentType::Text(ButtonContentText {
-                        text: "Cancel".into(),
+                        text: cancel,
                         pos: Vec2::new(0.0, 5.0),
-                        color: Color::rgba(255, 255, 255, 255),
+                        color: style.text,
                         render_layer: 5,
                         hover_change: ButtonChangeType::None,
                         click_change: ButtonChangeType::None,
@@ -545,6 +559,7 @@ impl Alert {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn alert_mouse_input(
         &mut self,
         systems: &mut SystemHolder,
@@ -636,6 +651,10 @@ impl Alert {
                             send_accepttrade(socket)?;
                             self.hide_alert(systems);
                         }
+                        AlertIndex::PartyInvite => {
+                            send_acceptparty(socket)?;
+                            self.hide_alert(systems);
+                        }
                         AlertIndex::ExitGame => {
                             if content.content_type == ContentType::Game {
                                 socket.socket.clear_sends();
@@ -661,6 +680,10 @@ impl Alert {
                             send_declinetrade(socket)?;
                             self.hide_alert(systems);
                         }
+                        AlertIndex::PartyInvite => {
+                            send_declineparty(socket)?;
+                            self.hide_alert(systems);
+                        }
                         _ => self.hide_alert(systems),
                     }, // No
                 }
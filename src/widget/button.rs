@@ -1,5 +1,4 @@
 use ascending_graphics::*;
-use cosmic_text::{Attrs, Metrics};
 
 use crate::{GfxType, SystemHolder, TString, logic::*, widget::*};
 
@@ -110,7 +109,7 @@ impl Button {
                 rect.set_radius(data.border_radius);
 
                 if data.got_border {
-                    rect.set_border_width(1.0)
+                    rect.set_border_width(systems.theme.border_width)
                         .set_border_color(data.border_color);
                 }
 
@@ -238,6 +237,20 @@ impl Button {
         }
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Position and size of the button on the screen.
+    pub fn screen_area(&self, systems: &SystemHolder) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+
+        (
+            self.base_pos + (self.adjust_pos * scale).floor(),
+            (self.size * scale).floor(),
+        )
+    }
+
     pub fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
         if self.visible != visible {
             if !visible {
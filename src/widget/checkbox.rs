@@ -1,7 +1,4 @@
-use std::default;
-
 use ascending_graphics::*;
-use cosmic_text::{Attrs, Metrics};
 
 use crate::{GfxType, SystemHolder, TString, logic::*, widget::*};
 
@@ -125,7 +122,7 @@ impl Checkbox {
                 rect.set_radius(data.border_radius);
 
                 if data.got_border {
-                    rect.set_border_width(1.0)
+                    rect.set_border_width(systems.theme.border_width)
                         .set_border_color(data.border_color);
                 }
 
@@ -178,7 +175,7 @@ impl Checkbox {
                 rect.set_radius(data.border_radius);
 
                 if data.got_border {
-                    rect.set_border_width(1.0)
+                    rect.set_border_width(systems.theme.border_width)
                         .set_border_color(data.border_color);
                 }
 
@@ -279,6 +276,22 @@ impl Checkbox {
         }
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Position and size of the box and its text on the screen.
+    pub fn screen_area(&self, systems: &SystemHolder) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+
+        (
+            self.base_pos + (self.adjust_pos * scale).floor(),
+            (Vec2::new(self.box_size.x + self.adjust_x, self.box_size.y)
+                * scale)
+                .floor(),
+        )
+    }
+
     pub fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
         if self.visible != visible {
             self.visible = visible;
@@ -0,0 +1,247 @@
+use ascending_graphics::*;
+
+use crate::{
+    GfxType, SystemHolder, create_label, data_types::*, is_within_area,
+    measure_string,
+};
+
+const ROW_HEIGHT: f32 = 20.0;
+const PADDING: f32 = 6.0;
+const MIN_WIDTH: f32 = 80.0;
+
+/// List of options opened at the mouse with a right click. It draws with
+/// the tooltip colours above every window and closes once an option is
+/// picked or the mouse clicks anywhere else.
+pub struct ContextMenu {
+    pub visible: bool,
+    bg: GfxType,
+    select: GfxType,
+    rows: Vec<GfxType>,
+    options: Vec<String>,
+    hover_option: Option<usize>,
+
+    pos: Vec2,
+    size: Vec2,
+}
+
+impl ContextMenu {
+    pub fn new(systems: &mut SystemHolder) -> Self {
+        let style = systems.theme.tooltip;
+
+        let mut bg_rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(0.0, 0.0, ORDER_CONTEXT_MENU),
+            Vec2::new(MIN_WIDTH, ROW_HEIGHT),
+            style.color,
+            0,
+        );
+        bg_rect
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(style.border_color);
+        let bg = systems.gfx.add_rect(
+            bg_rect,
+            4,
+            "Context Menu BG",
+            false,
+            CameraView::SubView1,
+        );
+
+        let select_rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(0.0, 0.0, ORDER_CONTEXT_MENU_SELECT),
+            Vec2::new(MIN_WIDTH, ROW_HEIGHT),
+            systems.theme.button.rect.hover,
+            0,
+        );
+        let select = systems.gfx.add_rect(
+            select_rect,
+            4,
+            "Context Menu Select",
+            false,
+            CameraView::SubView1,
+        );
+
+        ContextMenu {
+            visible: false,
+            bg,
+            select,
+            rows: Vec::new(),
+            options: Vec::new(),
+            hover_option: None,
+            pos: Vec2::ZERO,
+            size: Vec2::ZERO,
+        }
+    }
+
+    pub fn recreate(&mut self, systems: &mut SystemHolder) {
+        *self = ContextMenu::new(systems);
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        for gfx in [self.bg, self.select].iter().chain(self.rows.iter()) {
+            systems.gfx.remove_gfx(&mut systems.renderer, gfx);
+        }
+        self.rows.clear();
+    }
+
+    fn row_size(&self, systems: &SystemHolder) -> Vec2 {
+        Vec2::new(self.size.x, (ROW_HEIGHT * systems.scale as f32).floor())
+    }
+
+    fn row_pos(&self, systems: &SystemHolder, index: usize) -> Vec2 {
+        let row_size = self.row_size(systems);
+
+        Vec2::new(
+            self.pos.x,
+            self.pos.y + self.size.y - row_size.y * (index + 1) as f32,
+        )
+    }
+
+    /// Shows `options` below and to the right of `screen_pos`, moved back
+    /// inside the screen when they would leave it.
+    pub fn open(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+        options: Vec<String>,
+    ) {
+        self.close(systems);
+
+        for gfx in self.rows.drain(..) {
+            systems.gfx.remove_gfx(&mut systems.renderer, &gfx);
+        }
+
+        if options.is_empty() {
+            self.options.clear();
+            return;
+        }
+
+        let scale = systems.scale as f32;
+        let padding = (PADDING * scale).floor();
+        let text_width = options
+            .iter()
+            .map(|option| measure_string(systems, option, 16.0, 16.0).x)
+            .fold(0.0, f32::max);
+        let row_height = (ROW_HEIGHT * scale).floor();
+
+        self.size = Vec2::new(
+            (text_width * scale + padding * 2.0).max(MIN_WIDTH * scale),
+            row_height * options.len() as f32,
+        )
+        .floor();
+        self.pos = Vec2::new(
+            screen_pos.x.min(systems.size.width - self.size.x).max(0.0),
+            (screen_pos.y - self.size.y).max(0.0),
+        )
+        .floor();
+        self.options = options;
+
+        let text_color = systems.theme.tooltip.text;
+
+        for index in 0..self.options.len() {
+            let rpos = self.row_pos(systems, index);
+            let tpos =
+                Vec3::new(rpos.x + padding, rpos.y, ORDER_CONTEXT_MENU_TEXT);
+            let tsize = Vec2::new(self.size.x - padding * 2.0, row_height);
+            let label = create_label(
+                systems,
+                tpos,
+                tsize,
+                Some(Bounds::new(
+                    tpos.x,
+                    tpos.y,
+                    tpos.x + tsize.x,
+                    tpos.y + tsize.y,
+                )),
+                text_color,
+            );
+            let gfx = systems.gfx.add_text(
+                label,
+                5,
+                "Context Menu Option",
+                true,
+                CameraView::SubView1,
+            );
+
+            systems.gfx.set_text(&gfx, &self.options[index]);
+            self.rows.push(gfx);
+        }
+
+        systems.gfx.set_pos(
+            &self.bg,
+            Vec3::new(self.pos.x, self.pos.y, ORDER_CONTEXT_MENU),
+        );
+        systems.gfx.set_size(&self.bg, self.size);
+        systems.gfx.set_visible(&self.bg, true);
+        self.visible = true;
+    }
+
+    pub fn close(&mut self, systems: &mut SystemHolder) {
+        if !self.visible {
+            return;
+        }
+        self.visible = false;
+        self.hover_option = None;
+
+        for gfx in [self.bg, self.select].iter().chain(self.rows.iter()) {
+            systems.gfx.set_visible(gfx, false);
+        }
+    }
+
+    pub fn in_menu(&self, screen_pos: Vec2) -> bool {
+        self.visible && is_within_area(screen_pos, self.pos, self.size)
+    }
+
+    /// Option under `screen_pos`.
+    pub fn in_option(
+        &self,
+        systems: &SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        if !self.in_menu(screen_pos) {
+            return None;
+        }
+
+        let row_size = self.row_size(systems);
+
+        (0..self.options.len()).find(|index| {
+            is_within_area(screen_pos, self.row_pos(systems, *index), row_size)
+        })
+    }
+
+    /// Highlights the option under the mouse.
+    pub fn hover(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        let option = self.in_option(systems, screen_pos);
+
+        if self.hover_option == option {
+            return;
+        }
+        self.hover_option = option;
+
+        match option {
+            Some(index) => {
+                let rpos = self.row_pos(systems, index);
+
+                systems.gfx.set_pos(
+                    &self.select,
+                    Vec3::new(rpos.x, rpos.y, ORDER_CONTEXT_MENU_SELECT),
+                );
+                systems.gfx.set_size(&self.select, self.row_size(systems));
+                systems.gfx.set_visible(&self.select, true);
+            }
+            None => systems.gfx.set_visible(&self.select, false),
+        }
+    }
+
+    /// Closes the menu, returns the option under `screen_pos` if any.
+    pub fn click(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        let option = self.in_option(systems, screen_pos);
+
+        self.close(systems);
+        option
+    }
+}
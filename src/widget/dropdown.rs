@@ -0,0 +1,514 @@
+use ascending_graphics::*;
+
+use crate::{
+    GfxType, SystemHolder, TString, is_within_area, logic::*, widget::*,
+};
+
+pub struct DropdownRect {
+    pub color: Color,
+    pub render_layer: usize,
+    pub got_border: bool,
+    pub border_color: Color,
+    pub hover_color: Color,
+    pub radius: f32,
+    pub list_color: Color,
+    pub select_color: Color,
+    pub text_color: Color,
+}
+
+/// A box showing the selected option which opens a list of every option
+/// below it when clicked. The list sits on its own z so it covers the
+/// widgets of the window under it.
+pub struct Dropdown {
+    visible: bool,
+    bg: GfxType,
+    text: GfxType,
+    arrow: GfxType,
+    list_bg: GfxType,
+    select: GfxType,
+    list: Vec<GfxType>,
+    options: Vec<String>,
+    style: DropdownRect,
+
+    pub selected: usize,
+    pub expanded: bool,
+    hover_option: Option<usize>,
+    in_hover: bool,
+
+    pub base_pos: Vec2,
+    pub adjust_pos: Vec2,
+    pub z_order: [f32; 2],
+    pub size: Vec2,
+    z_step: (f32, i32),
+    pub tooltip: Option<TString>,
+}
+
+impl Dropdown {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        systems: &mut SystemHolder,
+        base_pos: Vec2,
+        adjust_pos: Vec2,
+        size: Vec2,
+        z_order: [f32; 2],
+        z_step: (f32, i32),
+        style: DropdownRect,
+        options: Vec<String>,
+        visible: bool,
+        tooltip: Option<TString>,
+    ) -> Self {
+        let mut dropdown = Dropdown {
+            visible,
+            bg: GfxType::default(),
+            text: GfxType::default(),
+            arrow: GfxType::default(),
+            list_bg: GfxType::default(),
+            select: GfxType::default(),
+            list: Vec::new(),
+            options: Vec::new(),
+            style,
+            selected: 0,
+            expanded: false,
+            hover_option: None,
+            in_hover: false,
+            base_pos,
+            adjust_pos,
+            z_order,
+            size,
+            z_step,
+            tooltip,
+        };
+
+        let (pos, box_size) = dropdown.box_area(systems);
+        let detail_z = z_order[0].sub_f32(z_step.0, z_step.1);
+
+        dropdown.bg = dropdown.add_rect(
+            systems,
+            Vec3::new(pos.x, pos.y, z_order[0]),
+            box_size,
+            dropdown.style.color,
+            true,
+            "Dropdown BG",
+            visible,
+        );
+
+        let (tpos, tsize) = dropdown.text_area(systems, pos, false);
+        dropdown.text = dropdown.add_label(
+            systems,
+            Vec3::new(tpos.x, tpos.y, detail_z),
+            tsize,
+            "Dropdown Text",
+            visible,
+        );
+
+        let (tpos, tsize) = dropdown.text_area(systems, pos, true);
+        dropdown.arrow = dropdown.add_label(
+            systems,
+            Vec3::new(tpos.x, tpos.y, detail_z),
+            tsize,
+            "Dropdown Arrow",
+            visible,
+        );
+        systems.gfx.set_text(&dropdown.arrow, "v");
+
+        dropdown.list_bg = dropdown.add_rect(
+            systems,
+            Vec3::new(pos.x, pos.y, z_order[1]),
+            box_size,
+            dropdown.style.list_color,
+            true,
+            "Dropdown List",
+            false,
+        );
+        dropdown.select = dropdown.add_rect(
+            systems,
+            Vec3::new(pos.x, pos.y, z_order[1].sub_f32(z_step.0, z_step.1)),
+            box_size,
+            dropdown.style.select_color,
+            false,
+            "Dropdown Select",
+            false,
+        );
+
+        dropdown.set_options(systems, options);
+        dropdown
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_rect(
+        &self,
+        systems: &mut SystemHolder,
+        pos: Vec3,
+        size: Vec2,
+        color: Color,
+        with_border: bool,
+        name: &'static str,
+        visible: bool,
+    ) -> GfxType {
+        let mut rect = Rect::new(&mut systems.renderer, pos, size, color, 0);
+
+        rect.set_radius(self.style.radius);
+
+        if with_border && self.style.got_border {
+            rect.set_border_width(systems.theme.border_width)
+                .set_border_color(self.style.border_color);
+        }
+
+        systems.gfx.add_rect(
+            rect,
+            self.style.render_layer,
+            name,
+            visible,
+            CameraView::SubView1,
+        )
+    }
+
+    fn add_label(
+        &self,
+        systems: &mut SystemHolder,
+        pos: Vec3,
+        size: Vec2,
+        name: &'static str,
+        visible: bool,
+    ) -> GfxType {
+        let text_data = create_label(
+            systems,
+            pos,
+            size,
+            Some(Bounds::new(pos.x, pos.y, pos.x + size.x, pos.y + size.y)),
+            self.style.text_color,
+        );
+
+        systems.gfx.add_text(
+            text_data,
+            self.style.render_layer + 1,
+            name,
+            visible,
+            CameraView::SubView1,
+        )
+    }
+
+    fn box_area(&self, systems: &SystemHolder) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+
+        (
+            self.base_pos + (self.adjust_pos * scale).floor(),
+            (self.size * scale).floor(),
+        )
+    }
+
+    fn option_pos(&self, systems: &SystemHolder, index: usize) -> Vec2 {
+        let (pos, size) = self.box_area(systems);
+
+        Vec2::new(pos.x, pos.y - size.y * (index + 1) as f32)
+    }
+
+    /// Text area inside a row starting at `pos`, the arrow takes the right
+    /// end of the box.
+    fn text_area(
+        &self,
+        systems: &SystemHolder,
+        pos: Vec2,
+        arrow: bool,
+    ) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+        let arrow_width = (16.0 * scale).floor();
+        let padding = (4.0 * scale).floor();
+        let height = (20.0 * scale).floor();
+        let y = pos.y + ((self.size.y * scale).floor() - height) * 0.5;
+
+        if arrow {
+            (
+                Vec2::new(
+                    pos.x + (self.size.x * scale).floor() - arrow_width,
+                    y,
+                ),
+                Vec2::new(arrow_width, height),
+            )
+        } else {
+            (
+                Vec2::new(pos.x + padding, y),
+                Vec2::new(
+                    (self.size.x * scale).floor() - arrow_width - padding,
+                    height,
+                ),
+            )
+        }
+    }
+
+    fn set_label_area(
+        systems: &mut SystemHolder,
+        gfx: &GfxType,
+        (pos, size): (Vec2, Vec2),
+    ) {
+        let z = systems.gfx.get_pos(gfx).z;
+
+        systems.gfx.set_pos(gfx, Vec3::new(pos.x, pos.y, z));
+        systems.gfx.set_bound(
+            gfx,
+            Some(Bounds::new(pos.x, pos.y, pos.x + size.x, pos.y + size.y)),
+        );
+    }
+
+    /// Replaces the options of the list, keeping the selection when it is
+    /// still in range.
+    pub fn set_options(
+        &mut self,
+        systems: &mut SystemHolder,
+        options: Vec<String>,
+    ) {
+        for gfx in self.list.drain(..) {
+            systems.gfx.remove_gfx(&mut systems.renderer, &gfx);
+        }
+
+        self.options = options;
+        self.hover_option = None;
+
+        let list_z =
+            self.z_order[1].sub_f32(self.z_step.0 * 2.0, self.z_step.1);
+
+        for index in 0..self.options.len() {
+            let opos = self.option_pos(systems, index);
+            let (tpos, tsize) = self.text_area(systems, opos, false);
+            let gfx = self.add_label(
+                systems,
+                Vec3::new(tpos.x, tpos.y, list_z),
+                tsize,
+                "Dropdown Option",
+                self.visible && self.expanded,
+            );
+
+            systems.gfx.set_text(&gfx, &self.options[index]);
+            self.list.push(gfx);
+        }
+
+        let (_, size) = self.box_area(systems);
+        let last = self.options.len().max(1) - 1;
+        let list_pos = self.option_pos(systems, last);
+        let z = systems.gfx.get_pos(&self.list_bg).z;
+
+        systems
+            .gfx
+            .set_pos(&self.list_bg, Vec3::new(list_pos.x, list_pos.y, z));
+        systems.gfx.set_size(
+            &self.list_bg,
+            Vec2::new(size.x, size.y * self.options.len().max(1) as f32),
+        );
+
+        self.selected = self.selected.min(last);
+        systems.gfx.set_text(&self.text, self.selected_text());
+    }
+
+    pub fn set_selected(&mut self, systems: &mut SystemHolder, index: usize) {
+        if self.selected == index {
+            return;
+        }
+        self.selected = index;
+        systems.gfx.set_text(&self.text, self.selected_text());
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.options
+            .get(self.selected)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        for gfx in [self.bg, self.text, self.arrow, self.list_bg, self.select]
+            .iter()
+            .chain(self.list.iter())
+        {
+            systems.gfx.remove_gfx(&mut systems.renderer, gfx);
+        }
+        self.list.clear();
+    }
+
+    pub fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        if self.visible == visible {
+            return;
+        }
+        self.visible = visible;
+
+        for gfx in [self.bg, self.text, self.arrow] {
+            systems.gfx.set_visible(&gfx, visible);
+        }
+
+        if !visible {
+            self.set_hover(systems, false);
+            self.set_expanded(systems, false);
+        }
+    }
+
+    pub fn set_z_order(
+        &mut self,
+        systems: &mut SystemHolder,
+        z_order: [f32; 2],
+    ) {
+        self.z_order = z_order;
+
+        let detail_z = z_order[0].sub_f32(self.z_step.0, self.z_step.1);
+        let select_z = z_order[1].sub_f32(self.z_step.0, self.z_step.1);
+        let list_z = z_order[1].sub_f32(self.z_step.0 * 2.0, self.z_step.1);
+
+        systems.gfx.set_pos_z(&self.bg, z_order[0]);
+        systems.gfx.set_pos_z(&self.text, detail_z);
+        systems.gfx.set_pos_z(&self.arrow, detail_z);
+        systems.gfx.set_pos_z(&self.list_bg, z_order[1]);
+        systems.gfx.set_pos_z(&self.select, select_z);
+
+        for gfx in self.list.iter() {
+            systems.gfx.set_pos_z(gfx, list_z);
+        }
+    }
+
+    pub fn set_pos(&mut self, systems: &mut SystemHolder, new_pos: Vec2) {
+        self.base_pos = new_pos;
+
+        let (pos, size) = self.box_area(systems);
+        let z = systems.gfx.get_pos(&self.bg).z;
+        systems.gfx.set_pos(&self.bg, Vec3::new(pos.x, pos.y, z));
+
+        let area = self.text_area(systems, pos, false);
+        Dropdown::set_label_area(systems, &self.text, area);
+        let area = self.text_area(systems, pos, true);
+        Dropdown::set_label_area(systems, &self.arrow, area);
+
+        for index in 0..self.list.len() {
+            let opos = self.option_pos(systems, index);
+            let area = self.text_area(systems, opos, false);
+            Dropdown::set_label_area(systems, &self.list[index], area);
+        }
+
+        let last = self.options.len().max(1) - 1;
+        let list_pos = self.option_pos(systems, last);
+        let z = systems.gfx.get_pos(&self.list_bg).z;
+        systems
+            .gfx
+            .set_pos(&self.list_bg, Vec3::new(list_pos.x, list_pos.y, z));
+
+        if let Some(index) = self.hover_option {
+            let opos = self.option_pos(systems, index);
+            let z = systems.gfx.get_pos(&self.select).z;
+            systems
+                .gfx
+                .set_pos(&self.select, Vec3::new(opos.x, opos.y, z));
+            systems.gfx.set_size(&self.select, size);
+        }
+    }
+
+    pub fn in_box(&self, systems: &SystemHolder, screen_pos: Vec2) -> bool {
+        if !self.visible {
+            return false;
+        }
+
+        let (pos, size) = self.box_area(systems);
+        is_within_area(screen_pos, pos, size)
+    }
+
+    /// Option of the open list under `screen_pos`.
+    pub fn in_list(
+        &self,
+        systems: &SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        if !self.visible || !self.expanded {
+            return None;
+        }
+
+        let (_, size) = self.box_area(systems);
+
+        (0..self.options.len()).find(|index| {
+            is_within_area(screen_pos, self.option_pos(systems, *index), size)
+        })
+    }
+
+    pub fn set_expanded(&mut self, systems: &mut SystemHolder, expanded: bool) {
+        let expanded = expanded && self.visible;
+
+        if self.expanded == expanded {
+            return;
+        }
+        self.expanded = expanded;
+
+        systems.gfx.set_visible(&self.list_bg, expanded);
+        for gfx in self.list.iter() {
+            systems.gfx.set_visible(gfx, expanded);
+        }
+
+        if !expanded {
+            self.hover_option = None;
+            systems.gfx.set_visible(&self.select, false);
+        }
+    }
+
+    pub fn set_hover(&mut self, systems: &mut SystemHolder, state: bool) {
+        if self.in_hover == state {
+            return;
+        }
+        self.in_hover = state;
+
+        let color = if state {
+            self.style.hover_color
+        } else {
+            self.style.color
+        };
+        systems.gfx.set_color(&self.bg, color);
+    }
+
+    /// Updates the hover of the box and highlights the option of the open
+    /// list under the mouse.
+    pub fn hover(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        let in_box = self.in_box(systems, screen_pos);
+        self.set_hover(systems, in_box);
+
+        let option = self.in_list(systems, screen_pos);
+
+        if self.hover_option == option {
+            return;
+        }
+        self.hover_option = option;
+
+        match option {
+            Some(index) => {
+                let opos = self.option_pos(systems, index);
+                let (_, size) = self.box_area(systems);
+                let z = systems.gfx.get_pos(&self.select).z;
+
+                systems
+                    .gfx
+                    .set_pos(&self.select, Vec3::new(opos.x, opos.y, z));
+                systems.gfx.set_size(&self.select, size);
+                systems.gfx.set_visible(&self.select, true);
+            }
+            None => systems.gfx.set_visible(&self.select, false),
+        }
+    }
+
+    /// Opens or closes the list, or picks the option under `screen_pos`.
+    /// Returns the new selection when it changed.
+    pub fn click(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        if self.expanded {
+            let option = self.in_list(systems, screen_pos);
+            self.set_expanded(systems, false);
+
+            let index = option?;
+            if index == self.selected {
+                return None;
+            }
+
+            self.set_selected(systems, index);
+            return Some(index);
+        }
+
+        if self.in_box(systems, screen_pos) {
+            self.set_expanded(systems, true);
+        }
+
+        None
+    }
+}
@@ -0,0 +1,152 @@
+use ascending_graphics::*;
+
+use ascending_input::Key;
+use winit::keyboard::NamedKey;
+
+use crate::{GfxType, SystemHolder};
+
+/// A widget of the owning window that can take keyboard focus, by its index
+/// in the window's own widget list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusTarget {
+    Textbox(usize),
+    Button(usize),
+    Checkbox(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusEvent {
+    /// Tab or Shift-Tab moved the focus.
+    Moved(FocusTarget),
+    /// Enter or Space was pressed on a focused button or checkbox.
+    Activate(FocusTarget),
+}
+
+/// Keeps track of which widget of a window has keyboard focus and draws a
+/// ring around it. The window owns the widgets and reacts to the returned
+/// [`FocusEvent`]s.
+pub struct FocusManager {
+    order: Vec<FocusTarget>,
+    focused: Option<usize>,
+    ring: GfxType,
+    shift_hold: bool,
+}
+
+impl FocusManager {
+    pub fn new(
+        systems: &mut SystemHolder,
+        order: Vec<FocusTarget>,
+        z_order: f32,
+        render_layer: usize,
+    ) -> Self {
+        let rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(0.0, 0.0, z_order),
+            Vec2::ZERO,
+            Color::rgba(0, 0, 0, 0),
+            0,
+        );
+        let ring = systems.gfx.add_rect(
+            rect,
+            render_layer,
+            "Focus Ring",
+            false,
+            CameraView::SubView1,
+        );
+
+        FocusManager {
+            order,
+            focused: None,
+            ring,
+            shift_hold: false,
+        }
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        systems.gfx.remove_gfx(&mut systems.renderer, &self.ring);
+    }
+
+    pub fn focused(&self) -> Option<FocusTarget> {
+        self.focused.map(|index| self.order[index])
+    }
+
+    /// Focuses `target` without showing the ring, used when the mouse
+    /// picked the widget.
+    pub fn set_focus(
+        &mut self,
+        systems: &mut SystemHolder,
+        target: Option<FocusTarget>,
+    ) {
+        self.focused = target
+            .and_then(|target| self.order.iter().position(|t| *t == target));
+        systems.gfx.set_visible(&self.ring, false);
+    }
+
+    pub fn clear(&mut self, systems: &mut SystemHolder) {
+        self.set_focus(systems, None);
+    }
+
+    /// Draws the ring around the screen area of the focused widget.
+    pub fn show_ring(
+        &mut self,
+        systems: &mut SystemHolder,
+        area: (Vec2, Vec2),
+    ) {
+        let color = systems.theme.focus.color;
+        let width = (systems.theme.focus.width * systems.scale as f32)
+            .floor()
+            .max(1.0);
+        let (pos, size) = area;
+        let z = systems.gfx.get_pos(&self.ring).z;
+
+        systems.gfx.set_border_color(&self.ring, color);
+        systems.gfx.set_border_width(&self.ring, width);
+        systems
+            .gfx
+            .set_pos(&self.ring, Vec3::new(pos.x - width, pos.y - width, z));
+        systems.gfx.set_size(&self.ring, size + width * 2.0);
+        systems.gfx.set_visible(&self.ring, true);
+    }
+
+    pub fn move_by(&mut self, systems: &mut SystemHolder, offset: Vec2) {
+        systems.gfx.move_by(&self.ring, offset);
+    }
+
+    /// Handles Tab, Shift-Tab, Enter and Space. Enter on a textbox is left to
+    /// the window so it can submit or move on to the next field.
+    pub fn key_input(
+        &mut self,
+        key: &Key,
+        pressed: bool,
+    ) -> Option<FocusEvent> {
+        if let Key::Named(NamedKey::Shift) = key {
+            self.shift_hold = pressed;
+        }
+
+        if !pressed || self.order.is_empty() {
+            return None;
+        }
+
+        match key {
+            Key::Named(NamedKey::Tab) => {
+                let len = self.order.len();
+                let next = match self.focused {
+                    None if self.shift_hold => len - 1,
+                    None => 0,
+                    Some(index) if self.shift_hold => (index + len - 1) % len,
+                    Some(index) => (index + 1) % len,
+                };
+
+                self.focused = Some(next);
+                Some(FocusEvent::Moved(self.order[next]))
+            }
+            Key::Named(NamedKey::Enter) | Key::Named(NamedKey::Space) => {
+                match self.focused()? {
+                    FocusTarget::Textbox(_) => None,
+                    target => Some(FocusEvent::Activate(target)),
+                }
+            }
+            _ => None,
+        }
+    }
+}
@@ -12,7 +12,7 @@ pub fn create_label(
 ) -> Text {
     let mut text = Text::new(
         &mut systems.renderer,
-        Some(Metrics::new(16.0, 16.0).scale(systems.scale as f32)),
+        Some(systems.theme.metrics(systems.scale as f32)),
         Vec3::new(pos.x, pos.y, pos.z),
         label_size,
         1.0,
@@ -29,7 +29,7 @@ pub fn create_label(
 pub fn create_empty_label(systems: &mut SystemHolder) -> Text {
     let mut text = Text::new(
         &mut systems.renderer,
-        Some(Metrics::new(16.0, 16.0).scale(systems.scale as f32)),
+        Some(systems.theme.metrics(systems.scale as f32)),
         Vec3::new(0.0, 0.0, 0.0),
         Vec2::ZERO,
         1.0,
@@ -73,7 +73,7 @@ pub fn measure_character(systems: &mut SystemHolder, text: &str) -> Vec<Vec2> {
         &Attrs::new(),
         TextOptions {
             shaping: Shaping::Advanced,
-            metrics: Some(Metrics::new(16.0, 16.0).scale(1.0)),
+            metrics: Some(systems.theme.metrics(1.0)),
             buffer_width: Some(4096.0),
             buffer_height: Some(systems.size.height),
             scale: 1.0,
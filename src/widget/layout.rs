@@ -0,0 +1,2270 @@
+use ascending_graphics::*;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::{
+    GfxType, SystemHolder, data_types::*, is_within_area, logic::*, widget::*,
+};
+
+pub const UI_LAYOUT_FOLDER: &str = "./data/ui";
+
+/// Z step shared by every widget built from a layout.
+const LAYOUT_Z_STEP: (f32, i32) = (0.0001, 4);
+
+fn default_layer() -> u8 {
+    1
+}
+
+fn default_text_layer() -> usize {
+    1
+}
+
+fn default_text_color() -> [u8; 4] {
+    [200, 200, 200, 255]
+}
+
+fn default_list_layer() -> u8 {
+    5
+}
+
+fn default_step() -> usize {
+    1
+}
+
+fn default_row_height() -> f32 {
+    20.0
+}
+
+fn default_scrollbar_width() -> f32 {
+    10.0
+}
+
+fn color(color: [u8; 4]) -> Color {
+    Color::rgba(color[0], color[1], color[2], color[3])
+}
+
+fn vec2(value: [f32; 2]) -> Vec2 {
+    Vec2::new(value[0], value[1])
+}
+
+/// Z position of a detail layer inside a window, layer 0 is the window
+/// background itself.
+pub fn layer_z(origin: f32, layer: u8) -> f32 {
+    origin.sub_f32(layer as f32 / 1000.0, 3)
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum LayoutAnchor {
+    #[default]
+    BottomLeft,
+    BottomRight,
+    TopLeft,
+    TopRight,
+    Center,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayoutChange {
+    #[default]
+    None,
+    ImageFrame(usize),
+    ColorChange([u8; 4]),
+    AdjustY(usize),
+}
+
+impl LayoutChange {
+    fn button(self) -> ButtonChangeType {
+        match self {
+            LayoutChange::None => ButtonChangeType::None,
+            LayoutChange::ImageFrame(frame) => {
+                ButtonChangeType::ImageFrame(frame)
+            }
+            LayoutChange::ColorChange(value) => {
+                ButtonChangeType::ColorChange(color(value))
+            }
+            LayoutChange::AdjustY(value) => ButtonChangeType::AdjustY(value),
+        }
+    }
+
+    fn checkbox(self) -> CheckboxChangeType {
+        match self {
+            LayoutChange::ImageFrame(frame) => {
+                CheckboxChangeType::ImageFrame(frame)
+            }
+            LayoutChange::ColorChange(value) => {
+                CheckboxChangeType::ColorChange(color(value))
+            }
+            _ => CheckboxChangeType::None,
+        }
+    }
+}
+
+/// Widget types a window can look up by id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutKind {
+    Rect,
+    Label,
+    Image,
+    Button,
+    Checkbox,
+    Scrollbar,
+    Slider,
+    Textbox,
+    SlotGrid,
+    Dropdown,
+    ListView,
+}
+
+impl LayoutKind {
+    pub const ALL: [LayoutKind; 11] = [
+        LayoutKind::Rect,
+        LayoutKind::Label,
+        LayoutKind::Image,
+        LayoutKind::Button,
+        LayoutKind::Checkbox,
+        LayoutKind::Scrollbar,
+        LayoutKind::Slider,
+        LayoutKind::Textbox,
+        LayoutKind::SlotGrid,
+        LayoutKind::Dropdown,
+        LayoutKind::ListView,
+    ];
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutWindow {
+    pub size: [f32; 2],
+    #[serde(default)]
+    pub anchor: LayoutAnchor,
+    /// Distance from the anchored screen edges, not affected by ui scale.
+    #[serde(default)]
+    pub offset: [f32; 2],
+    /// Left out to use the window colours of the theme.
+    pub color: Option<[u8; 4]>,
+    pub border_color: Option<[u8; 4]>,
+}
+
+impl LayoutWindow {
+    pub fn scaled_size(&self, systems: &SystemHolder) -> Vec2 {
+        (vec2(self.size) * systems.scale as f32).floor()
+    }
+
+    /// Where the window opens when nothing else has placed it.
+    pub fn default_pos(&self, systems: &SystemHolder) -> Vec2 {
+        let size = self.scaled_size(systems);
+        let screen = Vec2::new(systems.size.width, systems.size.height);
+        let offset = vec2(self.offset);
+
+        match self.anchor {
+            LayoutAnchor::BottomLeft => offset,
+            LayoutAnchor::BottomRight => {
+                Vec2::new(screen.x - size.x - offset.x, offset.y)
+            }
+            LayoutAnchor::TopLeft => {
+                Vec2::new(offset.x, screen.y - size.y - offset.y)
+            }
+            LayoutAnchor::TopRight => screen - size - offset,
+            LayoutAnchor::Center => ((screen - size) * 0.5 + offset).floor(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutHeader {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    /// Left out to use the header colours of the theme.
+    pub color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub text_pos: [f32; 2],
+    pub text_color: Option<[u8; 4]>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LayoutButtonStyle {
+    #[default]
+    None,
+    /// Rect with the button colours of the theme, `border` draws the theme
+    /// border even when the theme leaves it out.
+    Theme {
+        #[serde(default)]
+        border: bool,
+    },
+    Rect {
+        color: [u8; 4],
+        border_color: Option<[u8; 4]>,
+        #[serde(default)]
+        radius: f32,
+        #[serde(default)]
+        hover: LayoutChange,
+        #[serde(default)]
+        click: LayoutChange,
+    },
+    Image {
+        res: String,
+        #[serde(default)]
+        hover: LayoutChange,
+        #[serde(default)]
+        click: LayoutChange,
+    },
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LayoutButtonContent {
+    #[default]
+    None,
+    Image {
+        res: String,
+        #[serde(default)]
+        pos: [f32; 2],
+        #[serde(default)]
+        uv: [f32; 2],
+        size: [f32; 2],
+        #[serde(default)]
+        hover: LayoutChange,
+        #[serde(default)]
+        click: LayoutChange,
+    },
+    /// Left out `color` uses the button text colours of the theme.
+    Text {
+        text: String,
+        #[serde(default)]
+        pos: [f32; 2],
+        color: Option<[u8; 4]>,
+        #[serde(default = "default_text_layer")]
+        render_layer: usize,
+        #[serde(default)]
+        hover: LayoutChange,
+        #[serde(default)]
+        click: LayoutChange,
+    },
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutButton {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    #[serde(default)]
+    pub style: LayoutButtonStyle,
+    #[serde(default)]
+    pub content: LayoutButtonContent,
+    pub tooltip: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutCheckText {
+    pub text: String,
+    #[serde(default)]
+    pub offset: [f32; 2],
+    pub size: [f32; 2],
+    /// Left out to use the checkbox text colours of the theme, hover and
+    /// click are then ignored.
+    pub color: Option<[u8; 4]>,
+    #[serde(default = "default_text_layer")]
+    pub render_layer: usize,
+    #[serde(default)]
+    pub hover: LayoutChange,
+    #[serde(default)]
+    pub click: LayoutChange,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutCheckbox {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    /// Left out to use the checkbox colours of the theme, the border,
+    /// radius, hover and click settings are then ignored.
+    pub color: Option<[u8; 4]>,
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+    #[serde(default)]
+    pub hover: LayoutChange,
+    #[serde(default)]
+    pub click: LayoutChange,
+    pub check_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub check_pos: [f32; 2],
+    pub check_size: [f32; 2],
+    pub text: Option<LayoutCheckText>,
+    pub tooltip: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutScrollbarBackground {
+    pub color: [u8; 4],
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutScrollbar {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub length: f32,
+    pub thickness: f32,
+    #[serde(default)]
+    pub vertical: bool,
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    pub max_value: usize,
+    #[serde(default)]
+    pub min_bar_size: f32,
+    #[serde(default)]
+    pub reverse: bool,
+    /// Left out to use the scrollbar colours of the theme for the handle and
+    /// background, the other colour settings are then ignored.
+    pub color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub hover_color: [u8; 4],
+    #[serde(default)]
+    pub hold_color: [u8; 4],
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+    pub background: Option<LayoutScrollbarBackground>,
+    pub tooltip: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutSliderLabel {
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_text_color")]
+    pub color: [u8; 4],
+    #[serde(default = "default_text_layer")]
+    pub render_layer: usize,
+    #[serde(default)]
+    pub suffix: String,
+}
+
+/// A horizontal slider from `min_value` to `max_value` in `step`s.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutSlider {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub length: f32,
+    pub thickness: f32,
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    #[serde(default)]
+    pub min_value: usize,
+    pub max_value: usize,
+    #[serde(default = "default_step")]
+    pub step: usize,
+    #[serde(default)]
+    pub min_bar_size: f32,
+    /// Left out to use the scrollbar colours of the theme for the handle and
+    /// background, the other colour settings are then ignored.
+    pub color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub hover_color: [u8; 4],
+    #[serde(default)]
+    pub hold_color: [u8; 4],
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+    pub background: Option<LayoutScrollbarBackground>,
+    pub label: Option<LayoutSliderLabel>,
+    pub tooltip: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutLabel {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default = "default_text_layer")]
+    pub render_layer: usize,
+    /// Left out to use the window text colour of the theme, or its
+    /// highlight text colour with `highlight`.
+    pub color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub highlight: bool,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub center: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutRect {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    /// Left out to use the window colour of the theme, or its panel colour
+    /// with `panel`.
+    pub color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub panel: bool,
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+}
+
+/// A picture from one of the client resources, `uv` picks the part of it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutImage {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    pub res: String,
+    #[serde(default)]
+    pub uv: [f32; 2],
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutTextbox {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default = "default_text_layer")]
+    pub render_layer: usize,
+    /// Left out colours come from the textbox colours of the theme.
+    pub text_color: Option<[u8; 4]>,
+    pub bg_color: Option<[u8; 4]>,
+    pub selection_color: Option<[u8; 4]>,
+    pub limit: usize,
+    #[serde(default)]
+    pub hide_content: bool,
+    pub tooltip: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutDropdown {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    /// Layer of the open list, above the other widgets it covers.
+    #[serde(default = "default_list_layer")]
+    pub list_layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    pub color: [u8; 4],
+    pub hover_color: [u8; 4],
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+    pub list_color: [u8; 4],
+    pub select_color: [u8; 4],
+    #[serde(default = "default_text_color")]
+    pub text_color: [u8; 4],
+    /// Options shown until the client sets its own.
+    #[serde(default)]
+    pub options: Vec<String>,
+    pub tooltip: Option<String>,
+}
+
+/// A scrolling list of text rows, the items are set by the client. The
+/// scrollbar takes `scrollbar_width` on the right and uses the theme.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutListView {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub size: [f32; 2],
+    #[serde(default = "default_row_height")]
+    pub row_height: f32,
+    #[serde(default = "default_scrollbar_width")]
+    pub scrollbar_width: f32,
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    pub color: [u8; 4],
+    pub hover_color: [u8; 4],
+    pub select_color: [u8; 4],
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+    #[serde(default = "default_text_color")]
+    pub text_color: [u8; 4],
+}
+
+/// Row of tab headers, one per `[[tab]]` starting at `pos`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutTabBar {
+    pub pos: [f32; 2],
+    pub tab_size: [f32; 2],
+    #[serde(default)]
+    pub spacing: f32,
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    pub color: [u8; 4],
+    pub hover_color: [u8; 4],
+    pub select_color: [u8; 4],
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+    #[serde(default = "default_text_color")]
+    pub text_color: [u8; 4],
+}
+
+/// A group of widgets shown while the tab is open. Widgets that are not
+/// listed in any tab are always shown.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutTab {
+    pub id: String,
+    pub text: String,
+    #[serde(default)]
+    pub widgets: Vec<String>,
+}
+
+/// A grid of equally sized slot backgrounds, rows grow downwards from `pos`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LayoutSlotGrid {
+    pub id: String,
+    pub pos: [f32; 2],
+    pub columns: usize,
+    pub rows: usize,
+    pub slot_size: [f32; 2],
+    #[serde(default)]
+    pub spacing: [f32; 2],
+    #[serde(default = "default_layer")]
+    pub layer: u8,
+    #[serde(default)]
+    pub render_layer: usize,
+    /// Left out to use the slot colour of the theme.
+    pub color: Option<[u8; 4]>,
+    pub border_color: Option<[u8; 4]>,
+    #[serde(default)]
+    pub radius: f32,
+}
+
+impl LayoutSlotGrid {
+    pub fn len(&self) -> usize {
+        self.columns * self.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Unscaled position of the slot relative to the window.
+    pub fn slot_pos(&self, index: usize) -> Vec2 {
+        let column = index % self.columns.max(1);
+        let row = index / self.columns.max(1);
+        let step = vec2(self.slot_size) + vec2(self.spacing);
+
+        Vec2::new(
+            self.pos[0] + column as f32 * step.x,
+            self.pos[1] - row as f32 * step.y,
+        )
+    }
+}
+
+/// Describes a window and its widgets, loaded from `data/ui/<name>.toml`.
+/// Texts, tooltips and options are keys into the `data/lang` string tables.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct UiLayout {
+    pub window: LayoutWindow,
+    pub header: Option<LayoutHeader>,
+    #[serde(default)]
+    pub rect: Vec<LayoutRect>,
+    #[serde(default)]
+    pub label: Vec<LayoutLabel>,
+    #[serde(default)]
+    pub image: Vec<LayoutImage>,
+    #[serde(default)]
+    pub button: Vec<LayoutButton>,
+    #[serde(default)]
+    pub checkbox: Vec<LayoutCheckbox>,
+    #[serde(default)]
+    pub scrollbar: Vec<LayoutScrollbar>,
+    #[serde(default)]
+    pub slider: Vec<LayoutSlider>,
+    #[serde(default)]
+    pub textbox: Vec<LayoutTextbox>,
+    #[serde(default)]
+    pub slot_grid: Vec<LayoutSlotGrid>,
+    #[serde(default)]
+    pub dropdown: Vec<LayoutDropdown>,
+    #[serde(default)]
+    pub list_view: Vec<LayoutListView>,
+    pub tab_bar: Option<LayoutTabBar>,
+    #[serde(default)]
+    pub tab: Vec<LayoutTab>,
+}
+
+impl UiLayout {
+    /// Loads the layout file, falling back to the layout compiled into the
+    /// client when the file is missing, broken or lacks one of the `required`
+    /// widgets the window code depends on.
+    pub fn load(
+        name: &str,
+        fallback: &str,
+        required: &[(LayoutKind, &str)],
+    ) -> Self {
+        let path = format!("{UI_LAYOUT_FOLDER}/{name}.toml");
+
+        match fs::read_to_string(&path) {
+            Ok(data) => match toml::from_str::<UiLayout>(&data) {
+                Ok(layout) => match layout.missing_widget(required) {
+                    Some((kind, id)) => {
+                        warn!("{path} is missing {kind:?} widget {id}")
+                    }
+                    None => return layout,
+                },
+                Err(e) => warn!("Failed to parse {path}, Err {e}"),
+            },
+            Err(e) => warn!("Failed to load {path}, Err {e}"),
+        }
+
+        toml::from_str(fallback).unwrap_or_else(|e| {
+            warn!("Built in layout for {name} is invalid, Err {e}");
+            UiLayout::default()
+        })
+    }
+}
+
+impl UiLayout {
+    pub fn has_widget_of(&self, kind: LayoutKind, id: &str) -> bool {
+        match kind {
+            LayoutKind::Rect => self.rect.iter().any(|data| data.id == id),
+            LayoutKind::Label => self.label.iter().any(|data| data.id == id),
+            LayoutKind::Image => self.image.iter().any(|data| data.id == id),
+            LayoutKind::Button => self.button.iter().any(|data| data.id == id),
+            LayoutKind::Checkbox => {
+                self.checkbox.iter().any(|data| data.id == id)
+            }
+            LayoutKind::Scrollbar => {
+                self.scrollbar.iter().any(|data| data.id == id)
+            }
+            LayoutKind::Slider => self.slider.iter().any(|data| data.id == id),
+            LayoutKind::Textbox => {
+                self.textbox.iter().any(|data| data.id == id)
+            }
+            LayoutKind::SlotGrid => {
+                self.slot_grid.iter().any(|data| data.id == id)
+            }
+            LayoutKind::Dropdown => {
+                self.dropdown.iter().any(|data| data.id == id)
+            }
+            LayoutKind::ListView => {
+                self.list_view.iter().any(|data| data.id == id)
+            }
+        }
+    }
+
+    pub fn has_widget(&self, id: &str) -> bool {
+        LayoutKind::ALL
+            .iter()
+            .any(|kind| self.has_widget_of(*kind, id))
+    }
+
+    /// First of the `required` widgets the layout has no widget of that
+    /// type for.
+    pub fn missing_widget<'a>(
+        &self,
+        required: &[(LayoutKind, &'a str)],
+    ) -> Option<(LayoutKind, &'a str)> {
+        required
+            .iter()
+            .find(|(kind, id)| !self.has_widget_of(*kind, id))
+            .copied()
+    }
+
+    /// Unscaled position and size of the rect `id`, for HUD parts that
+    /// draw their own widgets in the places their layout gives.
+    pub fn rect_area(&self, id: &str) -> Option<(Vec2, Vec2)> {
+        self.rect
+            .iter()
+            .find(|data| data.id == id)
+            .map(|data| (vec2(data.pos), vec2(data.size)))
+    }
+
+    /// Unscaled position and size of the label `id`.
+    pub fn label_area(&self, id: &str) -> Option<(Vec2, Vec2)> {
+        self.label
+            .iter()
+            .find(|data| data.id == id)
+            .map(|data| (vec2(data.pos), vec2(data.size)))
+    }
+
+    /// Whether the widget `id` is shown while tab `tab` is open.
+    pub fn on_tab(&self, tab: usize, id: &str) -> bool {
+        let in_tab = |data: &LayoutTab| data.widgets.iter().any(|w| w == id);
+
+        self.tab.get(tab).is_some_and(in_tab) || !self.tab.iter().any(in_tab)
+    }
+}
+
+/// Looks up a layout tooltip key in the string tables.
+fn layout_tooltip(
+    systems: &SystemHolder,
+    tooltip: &Option<String>,
+) -> Option<TString> {
+    tooltip.as_deref().map(|key| systems.locale.text(key))
+}
+
+pub fn layout_resource(systems: &SystemHolder, name: &str) -> Option<usize> {
+    let resource = &systems.resource;
+
+    Some(match name {
+        "menu_bg" => resource.menu_bg.allocation,
+        "horizontal_arrow" => resource.horizontal_arrow.allocation,
+        "vertical_arrow" => resource.vertical_arrow.allocation,
+        "button_icon" => resource.button_icon.allocation,
+        "target" => resource.target.allocation,
+        "window_button_icon" => resource.window_button_icon.allocation,
+        "shop_currency_icon" => resource.shop_currency_icon.allocation,
+        _ => {
+            warn!("Unknown layout resource {name}");
+            return None;
+        }
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn add_layout_rect(
+    systems: &mut SystemHolder,
+    pos: Vec3,
+    size: Vec2,
+    fill: Color,
+    border: Option<Color>,
+    radius: f32,
+    render_layer: usize,
+    name: &'static str,
+) -> GfxType {
+    let mut rect = Rect::new(&mut systems.renderer, pos, size, fill, 0);
+
+    rect.set_radius(radius);
+
+    if let Some(border) = border {
+        rect.set_border_width(systems.theme.border_width)
+            .set_border_color(border);
+    }
+
+    systems
+        .gfx
+        .add_rect(rect, render_layer, name, false, CameraView::SubView1)
+}
+
+struct LayoutText {
+    gfx: GfxType,
+    pos: Vec2,
+    size: Vec2,
+    center: bool,
+}
+
+impl LayoutText {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        systems: &mut SystemHolder,
+        base_pos: Vec2,
+        pos: Vec2,
+        size: Vec2,
+        z: f32,
+        text_color: Color,
+        render_layer: usize,
+        text: &str,
+        center: bool,
+    ) -> Self {
+        let mut label = LayoutText {
+            gfx: GfxType::default(),
+            pos,
+            size,
+            center,
+        };
+        let (tpos, tsize) = label.area(systems, base_pos);
+
+        let text_data = create_label(
+            systems,
+            Vec3::new(tpos.x, tpos.y, z),
+            tsize,
+            Some(Bounds::new(
+                tpos.x,
+                tpos.y,
+                tpos.x + tsize.x,
+                tpos.y + tsize.y,
+            )),
+            text_color,
+        );
+        label.gfx = systems.gfx.add_text(
+            text_data,
+            render_layer,
+            "Layout Label",
+            false,
+            CameraView::SubView1,
+        );
+        let text = systems.locale.text(text);
+        label.set_text(systems, text.as_ref());
+        label
+    }
+
+    fn area(&self, systems: &SystemHolder, base_pos: Vec2) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+
+        (
+            base_pos + (self.pos * scale).floor(),
+            (self.size * scale).floor(),
+        )
+    }
+
+    fn set_text(&self, systems: &mut SystemHolder, text: &str) {
+        systems.gfx.set_text(&self.gfx, text);
+
+        if self.center {
+            systems.gfx.center_text(&mut systems.renderer, &self.gfx);
+        }
+    }
+
+    fn set_pos(&self, systems: &mut SystemHolder, base_pos: Vec2) {
+        let (tpos, tsize) = self.area(systems, base_pos);
+        let z = systems.gfx.get_pos(&self.gfx).z;
+
+        systems.gfx.set_pos(&self.gfx, Vec3::new(tpos.x, tpos.y, z));
+        systems.gfx.set_bound(
+            &self.gfx,
+            Some(Bounds::new(
+                tpos.x,
+                tpos.y,
+                tpos.x + tsize.x,
+                tpos.y + tsize.y,
+            )),
+        );
+
+        if self.center {
+            systems.gfx.center_text(&mut systems.renderer, &self.gfx);
+        }
+    }
+}
+
+struct LayoutGfx {
+    gfx: GfxType,
+    pos: Vec2,
+    layer: u8,
+}
+
+/// Widgets built from a [`UiLayout`], kept in the order of the file and
+/// looked up by their `id`.
+pub struct LayoutWidgets {
+    pub layout: UiLayout,
+    bg: GfxType,
+    header: Option<(GfxType, LayoutText)>,
+    rect: Vec<LayoutGfx>,
+    label: Vec<(u8, LayoutText)>,
+    image: Vec<LayoutGfx>,
+    pub button: Vec<Button>,
+    pub checkbox: Vec<Checkbox>,
+    pub scrollbar: Vec<Scrollbar>,
+    pub slider: Vec<Slider>,
+    pub textbox: Vec<Textbox>,
+    pub dropdown: Vec<Dropdown>,
+    pub list_view: Vec<ListView>,
+    slot: Vec<Vec<LayoutGfx>>,
+    tab_bar: Option<TabContainer>,
+    visible: bool,
+    pub did_button_click: bool,
+    pub did_checkbox_click: bool,
+}
+
+impl LayoutWidgets {
+    pub fn new(
+        systems: &mut SystemHolder,
+        layout: UiLayout,
+        pos: Vec2,
+        z_order: f32,
+    ) -> Self {
+        let scale = systems.scale as f32;
+        let w_size = layout.window.scaled_size(systems);
+        let (fill, border) = match layout.window.color {
+            Some(fill) => (color(fill), layout.window.border_color.map(color)),
+            None => (
+                systems.theme.window.color,
+                Some(systems.theme.window.border_color),
+            ),
+        };
+
+        let bg = add_layout_rect(
+            systems,
+            Vec3::new(pos.x - 1.0, pos.y - 1.0, z_order),
+            w_size + 2.0,
+            fill,
+            border,
+            0.0,
+            0,
+            "Window BG",
+        );
+
+        let header = layout.header.as_ref().map(|data| {
+            let hpos = pos + (vec2(data.pos) * scale).floor();
+            let gfx = add_layout_rect(
+                systems,
+                Vec3::new(hpos.x, hpos.y, layer_z(z_order, 1)),
+                (vec2(data.size) * scale).floor(),
+                data.color.map_or(systems.theme.window.header_color, color),
+                None,
+                0.0,
+                0,
+                "Window Header",
+            );
+            let text = LayoutText::new(
+                systems,
+                pos,
+                vec2(data.text_pos),
+                Vec2::new(data.size[0], 20.0),
+                layer_z(z_order, 2),
+                data.text_color
+                    .map_or(systems.theme.window.header_text, color),
+                1,
+                &data.text,
+                true,
+            );
+
+            (gfx, text)
+        });
+
+        let rect = layout
+            .rect
+            .iter()
+            .map(|data| {
+                let rpos = pos + (vec2(data.pos) * scale).floor();
+
+                LayoutGfx {
+                    gfx: add_layout_rect(
+                        systems,
+                        Vec3::new(rpos.x, rpos.y, layer_z(z_order, data.layer)),
+                        (vec2(data.size) * scale).floor(),
+                        data.color.map_or(
+                            if data.panel {
+                                systems.theme.window.panel
+                            } else {
+                                systems.theme.window.color
+                            },
+                            color,
+                        ),
+                        data.border_color.map(color),
+                        data.radius,
+                        data.render_layer,
+                        "Layout Rect",
+                    ),
+                    pos: vec2(data.pos),
+                    layer: data.layer,
+                }
+            })
+            .collect();
+
+        let label = layout
+            .label
+            .iter()
+            .map(|data| {
+                (
+                    data.layer,
+                    LayoutText::new(
+                        systems,
+                        pos,
+                        vec2(data.pos),
+                        vec2(data.size),
+                        layer_z(z_order, data.layer),
+                        data.color.map_or(
+                            if data.highlight {
+                                systems.theme.window.highlight_text
+                            } else {
+                                systems.theme.window.text
+                            },
+                            color,
+                        ),
+                        data.render_layer,
+                        &data.text,
+                        data.center,
+                    ),
+                )
+            })
+            .collect();
+
+        let image = layout
+            .image
+            .iter()
+            .map(|data| {
+                let ipos = pos + (vec2(data.pos) * scale).floor();
+                let res = layout_resource(systems, &data.res);
+                let image = Image::new(
+                    res,
+                    &mut systems.renderer,
+                    Vec3::new(ipos.x, ipos.y, layer_z(z_order, data.layer)),
+                    (vec2(data.size) * scale).floor(),
+                    Vec4::new(
+                        data.uv[0],
+                        data.uv[1],
+                        data.size[0],
+                        data.size[1],
+                    ),
+                    0,
+                );
+
+                LayoutGfx {
+                    gfx: systems.gfx.add_image(
+                        image,
+                        data.render_layer,
+                        "Layout Image",
+                        false,
+                        CameraView::SubView1,
+                    ),
+                    pos: vec2(data.pos),
+                    layer: data.layer,
+                }
+            })
+            .collect();
+
+        let button = layout
+            .button
+            .iter()
+            .map(|data| build_button(systems, data, pos, z_order))
+            .collect();
+
+        let checkbox = layout
+            .checkbox
+            .iter()
+            .map(|data| build_checkbox(systems, data, pos, z_order))
+            .collect();
+
+        let scrollbar = layout
+            .scrollbar
+            .iter()
+            .map(|data| build_scrollbar(systems, data, pos, z_order))
+            .collect();
+
+        let textbox = layout
+            .textbox
+            .iter()
+            .map(|data| {
+                let tooltip = layout_tooltip(systems, &data.tooltip);
+                let style = systems.theme.textbox;
+
+                Textbox::new(
+                    systems,
+                    Vec3::new(pos.x, pos.y, layer_z(z_order, data.layer)),
+                    vec2(data.pos),
+                    LAYOUT_Z_STEP,
+                    vec2(data.size),
+                    data.text_color.map_or(style.text, color),
+                    data.render_layer,
+                    data.limit,
+                    data.bg_color.map_or(style.background, color),
+                    data.selection_color.map_or(style.selection, color),
+                    data.hide_content,
+                    false,
+                    tooltip,
+                    vec![],
+                )
+            })
+            .collect();
+
+        let slider = layout
+            .slider
+            .iter()
+            .map(|data| build_slider(systems, data, pos, z_order))
+            .collect();
+
+        let dropdown = layout
+            .dropdown
+            .iter()
+            .map(|data| build_dropdown(systems, data, pos, z_order))
+            .collect();
+
+        let list_view = layout
+            .list_view
+            .iter()
+            .map(|data| build_list_view(systems, data, pos, z_order))
+            .collect();
+
+        let tab_bar = layout.tab_bar.as_ref().map(|data| {
+            let names: Vec<String> = layout
+                .tab
+                .iter()
+                .map(|tab| systems.locale.get(&tab.text).to_string())
+                .collect();
+
+            TabContainer::new(
+                systems,
+                pos,
+                vec2(data.pos),
+                vec2(data.tab_size),
+                data.spacing,
+                layer_z(z_order, data.layer),
+                LAYOUT_Z_STEP,
+                TabRect {
+                    color: color(data.color),
+                    render_layer: data.render_layer,
+                    got_border: data.border_color.is_some(),
+                    border_color: color(data.border_color.unwrap_or_default()),
+                    hover_color: color(data.hover_color),
+                    select_color: color(data.select_color),
+                    text_color: color(data.text_color),
+                    radius: data.radius,
+                },
+                &names,
+                false,
+            )
+        });
+
+        let slot = layout
+            .slot_grid
+            .iter()
+            .map(|data| {
+                (0..data.len())
+                    .map(|index| {
+                        let spos = data.slot_pos(index);
+                        let gpos = pos + (spos * scale).floor();
+
+                        LayoutGfx {
+                            gfx: add_layout_rect(
+                                systems,
+                                Vec3::new(
+                                    gpos.x,
+                                    gpos.y,
+                                    layer_z(z_order, data.layer),
+                                ),
+                                (vec2(data.slot_size) * scale).floor(),
+                                data.color
+                                    .map_or(systems.theme.slot.color, color),
+                                data.border_color.map(color),
+                                data.radius,
+                                data.render_layer,
+                                "Layout Slot",
+                            ),
+                            pos: spos,
+                            layer: data.layer,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        LayoutWidgets {
+            layout,
+            bg,
+            header,
+            rect,
+            label,
+            image,
+            button,
+            checkbox,
+            scrollbar,
+            slider,
+            textbox,
+            dropdown,
+            list_view,
+            slot,
+            tab_bar,
+            visible: false,
+            did_button_click: false,
+            did_checkbox_click: false,
+        }
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        systems.gfx.remove_gfx(&mut systems.renderer, &self.bg);
+
+        if let Some((gfx, text)) = &self.header {
+            systems.gfx.remove_gfx(&mut systems.renderer, gfx);
+            systems.gfx.remove_gfx(&mut systems.renderer, &text.gfx);
+        }
+
+        for data in self.gfx_iter() {
+            systems.gfx.remove_gfx(&mut systems.renderer, &data.gfx);
+        }
+
+        for (_, text) in self.label.iter() {
+            systems.gfx.remove_gfx(&mut systems.renderer, &text.gfx);
+        }
+
+        self.button
+            .iter_mut()
+            .for_each(|button| button.unload(systems));
+        self.checkbox
+            .iter_mut()
+            .for_each(|checkbox| checkbox.unload(systems));
+        self.scrollbar
+            .iter_mut()
+            .for_each(|scrollbar| scrollbar.unload(systems));
+        self.textbox
+            .iter_mut()
+            .for_each(|textbox| textbox.unload(systems));
+        self.slider
+            .iter_mut()
+            .for_each(|slider| slider.unload(systems));
+        self.dropdown
+            .iter_mut()
+            .for_each(|dropdown| dropdown.unload(systems));
+        self.list_view
+            .iter_mut()
+            .for_each(|list_view| list_view.unload(systems));
+
+        if let Some(tab_bar) = &mut self.tab_bar {
+            tab_bar.unload(systems);
+        }
+
+        self.header = None;
+        self.tab_bar = None;
+        self.rect.clear();
+        self.image.clear();
+        self.slot.clear();
+        self.label.clear();
+        self.button.clear();
+        self.checkbox.clear();
+        self.scrollbar.clear();
+        self.slider.clear();
+        self.textbox.clear();
+        self.dropdown.clear();
+        self.list_view.clear();
+    }
+
+    /// Rects, images and slots, the widgets that are a single gfx.
+    fn gfx_iter(&self) -> impl Iterator<Item = &LayoutGfx> {
+        self.rect
+            .iter()
+            .chain(self.image.iter())
+            .chain(self.slot.iter().flatten())
+    }
+
+    pub fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        self.visible = visible;
+        systems.gfx.set_visible(&self.bg, visible);
+
+        if let Some((gfx, text)) = &self.header {
+            systems.gfx.set_visible(gfx, visible);
+            systems.gfx.set_visible(&text.gfx, visible);
+        }
+
+        if let Some(tab_bar) = &mut self.tab_bar {
+            tab_bar.set_visible(systems, visible);
+        }
+
+        self.update_tab_visible(systems);
+    }
+
+    /// Tab that is open, the first one when the layout has no tab bar.
+    pub fn selected_tab(&self) -> usize {
+        self.tab_bar
+            .as_ref()
+            .map(|data| data.selected)
+            .unwrap_or_default()
+    }
+
+    /// Shows the widgets of the open tab while the window is visible.
+    fn update_tab_visible(&mut self, systems: &mut SystemHolder) {
+        let layout = &self.layout;
+        let tab = self.selected_tab();
+        let shown = |id: &str| self.visible && layout.on_tab(tab, id);
+
+        for (data, rect) in layout.rect.iter().zip(&self.rect) {
+            systems.gfx.set_visible(&rect.gfx, shown(&data.id));
+        }
+
+        for (data, slots) in layout.slot_grid.iter().zip(&self.slot) {
+            for slot in slots.iter() {
+                systems.gfx.set_visible(&slot.gfx, shown(&data.id));
+            }
+        }
+
+        for (data, (_, text)) in layout.label.iter().zip(&self.label) {
+            systems.gfx.set_visible(&text.gfx, shown(&data.id));
+        }
+
+        for (data, image) in layout.image.iter().zip(&self.image) {
+            systems.gfx.set_visible(&image.gfx, shown(&data.id));
+        }
+
+        for (data, button) in layout.button.iter().zip(&mut self.button) {
+            button.set_visible(systems, shown(&data.id));
+        }
+
+        for (data, checkbox) in layout.checkbox.iter().zip(&mut self.checkbox) {
+            checkbox.set_visible(systems, shown(&data.id));
+        }
+
+        for (data, scrollbar) in
+            layout.scrollbar.iter().zip(&mut self.scrollbar)
+        {
+            scrollbar.set_visible(systems, shown(&data.id));
+        }
+
+        for (data, slider) in layout.slider.iter().zip(&mut self.slider) {
+            slider.set_visible(systems, shown(&data.id));
+        }
+
+        for (data, textbox) in layout.textbox.iter().zip(&mut self.textbox) {
+            textbox.set_visible(systems, shown(&data.id));
+        }
+
+        for (data, dropdown) in layout.dropdown.iter().zip(&mut self.dropdown) {
+            dropdown.set_visible(systems, shown(&data.id));
+        }
+
+        for (data, list_view) in
+            layout.list_view.iter().zip(&mut self.list_view)
+        {
+            list_view.set_visible(systems, shown(&data.id));
+        }
+    }
+
+    pub fn hover_tab(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        if let Some(tab_bar) = &mut self.tab_bar {
+            tab_bar.hover(systems, screen_pos);
+        }
+    }
+
+    /// Opens the tab under `screen_pos`, returns true when it changed.
+    pub fn click_tab(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> bool {
+        let Some(tab_bar) = &mut self.tab_bar else {
+            return false;
+        };
+
+        if tab_bar.click(systems, screen_pos).is_none() {
+            return false;
+        }
+
+        self.close_dropdowns(systems);
+        self.update_tab_visible(systems);
+        true
+    }
+
+    pub fn set_z_order(&mut self, systems: &mut SystemHolder, z_order: f32) {
+        let set_z = |systems: &mut SystemHolder, gfx: &GfxType, z: f32| {
+            let mut pos = systems.gfx.get_pos(gfx);
+            pos.z = z;
+            systems.gfx.set_pos(gfx, pos);
+        };
+
+        set_z(systems, &self.bg, z_order);
+
+        if let Some((gfx, text)) = &self.header {
+            set_z(systems, gfx, layer_z(z_order, 1));
+            set_z(systems, &text.gfx, layer_z(z_order, 2));
+        }
+
+        for data in self.gfx_iter() {
+            set_z(systems, &data.gfx, layer_z(z_order, data.layer));
+        }
+
+        for (layer, text) in self.label.iter() {
+            set_z(systems, &text.gfx, layer_z(z_order, *layer));
+        }
+
+        for (button, data) in self.button.iter_mut().zip(&self.layout.button) {
+            button.set_z_order(systems, layer_z(z_order, data.layer));
+        }
+
+        for (checkbox, data) in
+            self.checkbox.iter_mut().zip(&self.layout.checkbox)
+        {
+            checkbox.set_z_order(systems, layer_z(z_order, data.layer));
+        }
+
+        for (scrollbar, data) in
+            self.scrollbar.iter_mut().zip(&self.layout.scrollbar)
+        {
+            scrollbar.set_z_order(systems, layer_z(z_order, data.layer));
+        }
+
+        for (slider, data) in self.slider.iter_mut().zip(&self.layout.slider) {
+            slider.set_z_order(systems, layer_z(z_order, data.layer));
+        }
+
+        for (textbox, data) in self.textbox.iter_mut().zip(&self.layout.textbox)
+        {
+            textbox.set_z_order(systems, layer_z(z_order, data.layer));
+        }
+
+        for (list_view, data) in
+            self.list_view.iter_mut().zip(&self.layout.list_view)
+        {
+            list_view.set_z_order(systems, layer_z(z_order, data.layer));
+        }
+
+        if let Some(tab_bar) = &mut self.tab_bar
+            && let Some(data) = &self.layout.tab_bar
+        {
+            tab_bar.set_z_order(systems, layer_z(z_order, data.layer));
+        }
+
+        for (dropdown, data) in
+            self.dropdown.iter_mut().zip(&self.layout.dropdown)
+        {
+            dropdown.set_z_order(
+                systems,
+                [
+                    layer_z(z_order, data.layer),
+                    layer_z(z_order, data.list_layer),
+                ],
+            );
+        }
+    }
+
+    pub fn set_pos(&mut self, systems: &mut SystemHolder, pos: Vec2) {
+        let scale = systems.scale as f32;
+
+        let z = systems.gfx.get_pos(&self.bg).z;
+        systems
+            .gfx
+            .set_pos(&self.bg, Vec3::new(pos.x - 1.0, pos.y - 1.0, z));
+
+        if let Some((gfx, text)) = &self.header
+            && let Some(data) = &self.layout.header
+        {
+            let hpos = pos + (vec2(data.pos) * scale).floor();
+            let z = systems.gfx.get_pos(gfx).z;
+
+            systems.gfx.set_pos(gfx, Vec3::new(hpos.x, hpos.y, z));
+            text.set_pos(systems, pos);
+        }
+
+        for data in self.gfx_iter() {
+            let gpos = pos + (data.pos * scale).floor();
+            let z = systems.gfx.get_pos(&data.gfx).z;
+
+            systems.gfx.set_pos(&data.gfx, Vec3::new(gpos.x, gpos.y, z));
+        }
+
+        for (_, text) in self.label.iter() {
+            text.set_pos(systems, pos);
+        }
+
+        self.button
+            .iter_mut()
+            .for_each(|button| button.set_pos(systems, pos));
+        self.checkbox
+            .iter_mut()
+            .for_each(|checkbox| checkbox.set_pos(systems, pos));
+        self.scrollbar
+            .iter_mut()
+            .for_each(|scrollbar| scrollbar.set_pos(systems, pos));
+        self.slider
+            .iter_mut()
+            .for_each(|slider| slider.set_pos(systems, pos));
+        self.textbox
+            .iter_mut()
+            .for_each(|textbox| textbox.set_pos(systems, pos));
+        self.dropdown
+            .iter_mut()
+            .for_each(|dropdown| dropdown.set_pos(systems, pos));
+        self.list_view
+            .iter_mut()
+            .for_each(|list_view| list_view.set_pos(systems, pos));
+
+        if let Some(tab_bar) = &mut self.tab_bar {
+            tab_bar.set_pos(systems, pos);
+        }
+    }
+
+    /// Screen area of the header, used to drag the window around.
+    pub fn header_area(
+        &self,
+        systems: &SystemHolder,
+        pos: Vec2,
+    ) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+
+        match &self.layout.header {
+            Some(data) => (
+                pos + (vec2(data.pos) * scale).floor(),
+                (vec2(data.size) * scale).floor(),
+            ),
+            None => (pos, Vec2::ZERO),
+        }
+    }
+
+    pub fn button_index(&self, id: &str) -> Option<usize> {
+        self.layout.button.iter().position(|data| data.id == id)
+    }
+
+    pub fn button_id(&self, index: usize) -> &str {
+        self.layout
+            .button
+            .get(index)
+            .map(|data| data.id.as_str())
+            .unwrap_or_default()
+    }
+
+    pub fn checkbox_index(&self, id: &str) -> Option<usize> {
+        self.layout.checkbox.iter().position(|data| data.id == id)
+    }
+
+    pub fn checkbox_id(&self, index: usize) -> &str {
+        self.layout
+            .checkbox
+            .get(index)
+            .map(|data| data.id.as_str())
+            .unwrap_or_default()
+    }
+
+    pub fn scrollbar_index(&self, id: &str) -> Option<usize> {
+        self.layout.scrollbar.iter().position(|data| data.id == id)
+    }
+
+    pub fn slider_index(&self, id: &str) -> Option<usize> {
+        self.layout.slider.iter().position(|data| data.id == id)
+    }
+
+    pub fn textbox_index(&self, id: &str) -> Option<usize> {
+        self.layout.textbox.iter().position(|data| data.id == id)
+    }
+
+    pub fn dropdown_index(&self, id: &str) -> Option<usize> {
+        self.layout.dropdown.iter().position(|data| data.id == id)
+    }
+
+    pub fn dropdown_id(&self, index: usize) -> &str {
+        self.layout
+            .dropdown
+            .get(index)
+            .map(|data| data.id.as_str())
+            .unwrap_or_default()
+    }
+
+    pub fn list_view_index(&self, id: &str) -> Option<usize> {
+        self.layout.list_view.iter().position(|data| data.id == id)
+    }
+
+    pub fn slot_grid(&self, id: &str) -> Option<&LayoutSlotGrid> {
+        self.layout.slot_grid.iter().find(|data| data.id == id)
+    }
+
+    pub fn set_label_text(
+        &mut self,
+        systems: &mut SystemHolder,
+        id: &str,
+        text: &str,
+    ) {
+        if let Some(index) =
+            self.layout.label.iter().position(|data| data.id == id)
+        {
+            self.label[index].1.set_text(systems, text);
+        }
+    }
+
+    /// Shows or hides the rect, label, image or button `id` on its own,
+    /// for parts of a window that only show while they have data. It is
+    /// never shown while the window is hidden.
+    pub fn set_widget_visible(
+        &mut self,
+        systems: &mut SystemHolder,
+        id: &str,
+        visible: bool,
+    ) {
+        let visible = visible && self.visible;
+        let layout = &self.layout;
+
+        if let Some(index) = layout.rect.iter().position(|data| data.id == id) {
+            systems.gfx.set_visible(&self.rect[index].gfx, visible);
+        }
+
+        if let Some(index) = layout.label.iter().position(|data| data.id == id)
+        {
+            systems.gfx.set_visible(&self.label[index].1.gfx, visible);
+        }
+
+        if let Some(index) = layout.image.iter().position(|data| data.id == id)
+        {
+            systems.gfx.set_visible(&self.image[index].gfx, visible);
+        }
+
+        if let Some(index) = self.button_index(id) {
+            self.button[index].set_visible(systems, visible);
+        }
+    }
+
+    /// Shows or hides one slot background of the slot grid `id`.
+    pub fn set_slot_visible(
+        &mut self,
+        systems: &mut SystemHolder,
+        id: &str,
+        index: usize,
+        visible: bool,
+    ) {
+        if let Some(grid) =
+            self.layout.slot_grid.iter().position(|data| data.id == id)
+            && let Some(slot) = self.slot[grid].get(index)
+        {
+            systems.gfx.set_visible(&slot.gfx, visible && self.visible);
+        }
+    }
+
+    /// Colour used by the label from its next `set_label_text`.
+    pub fn set_label_color(
+        &mut self,
+        systems: &mut SystemHolder,
+        id: &str,
+        text_color: Color,
+    ) {
+        if let Some(index) =
+            self.layout.label.iter().position(|data| data.id == id)
+        {
+            systems.gfx.set_color(&self.label[index].1.gfx, text_color);
+        }
+    }
+
+    pub fn set_list_items(
+        &mut self,
+        systems: &mut SystemHolder,
+        id: &str,
+        items: Vec<String>,
+    ) {
+        if let Some(index) = self.list_view_index(id) {
+            self.list_view[index].set_items(systems, items);
+        }
+    }
+
+    pub fn set_checkbox_value(
+        &mut self,
+        systems: &mut SystemHolder,
+        id: &str,
+        value: bool,
+    ) {
+        if let Some(index) = self.checkbox_index(id) {
+            self.checkbox[index].set_value(systems, value);
+        }
+    }
+
+    pub fn set_dropdown_options(
+        &mut self,
+        systems: &mut SystemHolder,
+        id: &str,
+        options: Vec<String>,
+        selected: usize,
+    ) {
+        if let Some(index) = self.dropdown_index(id) {
+            self.dropdown[index].set_options(systems, options);
+            self.dropdown[index].set_selected(systems, selected);
+        }
+    }
+
+    pub fn hover_buttons(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) {
+        for button in self.button.iter_mut() {
+            let state = button.is_visible()
+                && is_within_area(
+                    screen_pos,
+                    button.base_pos
+                        + (button.adjust_pos * systems.scale as f32).floor(),
+                    (button.size * systems.scale as f32).floor(),
+                );
+
+            button.set_hover(systems, state);
+        }
+    }
+
+    pub fn click_buttons(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        let mut button_found = None;
+
+        for (index, button) in self.button.iter_mut().enumerate() {
+            if button.is_visible()
+                && is_within_area(
+                    screen_pos,
+                    button.base_pos
+                        + (button.adjust_pos * systems.scale as f32).floor(),
+                    (button.size * systems.scale as f32).floor(),
+                )
+            {
+                button.set_click(systems, true);
+                button_found = Some(index)
+            }
+        }
+
+        button_found
+    }
+
+    pub fn reset_buttons(&mut self, systems: &mut SystemHolder) {
+        if !self.did_button_click {
+            return;
+        }
+        self.did_button_click = false;
+
+        self.button
+            .iter_mut()
+            .for_each(|button| button.set_click(systems, false));
+    }
+
+    fn checkbox_area(
+        systems: &SystemHolder,
+        checkbox: &Checkbox,
+    ) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+
+        (
+            checkbox.base_pos + (checkbox.adjust_pos * scale).floor(),
+            (Vec2::new(
+                checkbox.box_size.x + checkbox.adjust_x,
+                checkbox.box_size.y,
+            ) * scale)
+                .floor(),
+        )
+    }
+
+    pub fn hover_checkbox(
+        &mut self,
+        systems: &mut SystemHolder,
+        tooltip: &mut Tooltip,
+        screen_pos: Vec2,
+    ) {
+        for checkbox in self.checkbox.iter_mut() {
+            let (pos, size) = LayoutWidgets::checkbox_area(systems, checkbox);
+
+            if checkbox.is_visible() && is_within_area(screen_pos, pos, size) {
+                checkbox.set_hover(systems, true);
+
+                if let Some(msg) = &checkbox.tooltip {
+                    tooltip.init_tooltip(systems, screen_pos, msg.as_ref());
+                }
+            } else {
+                checkbox.set_hover(systems, false);
+            }
+        }
+    }
+
+    pub fn click_checkbox(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        let mut checkbox_found = None;
+
+        for (index, checkbox) in self.checkbox.iter_mut().enumerate() {
+            let (pos, size) = LayoutWidgets::checkbox_area(systems, checkbox);
+
+            if checkbox.is_visible() && is_within_area(screen_pos, pos, size) {
+                checkbox.set_click(systems, true);
+                checkbox_found = Some(index)
+            }
+        }
+
+        checkbox_found
+    }
+
+    pub fn reset_checkbox(&mut self, systems: &mut SystemHolder) {
+        if !self.did_checkbox_click {
+            return;
+        }
+        self.did_checkbox_click = false;
+
+        self.checkbox
+            .iter_mut()
+            .for_each(|checkbox| checkbox.set_click(systems, false));
+    }
+
+    pub fn hover_scrollbar(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) {
+        for scrollbar in self.scrollbar.iter_mut() {
+            let state = scrollbar.in_scroll(screen_pos);
+            scrollbar.set_hover(systems, state);
+        }
+    }
+
+    pub fn hover_slider(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) {
+        for slider in self.slider.iter_mut() {
+            slider.set_hover(systems, screen_pos);
+        }
+    }
+
+    pub fn hover_dropdown(
+        &mut self,
+        systems: &mut SystemHolder,
+        tooltip: &mut Tooltip,
+        screen_pos: Vec2,
+    ) {
+        for dropdown in self.dropdown.iter_mut() {
+            dropdown.hover(systems, screen_pos);
+
+            if !dropdown.expanded
+                && dropdown.in_box(systems, screen_pos)
+                && let Some(msg) = &dropdown.tooltip
+            {
+                tooltip.init_tooltip(systems, screen_pos, msg.as_ref());
+            }
+        }
+    }
+
+    pub fn hover_list_view(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) {
+        for list_view in self.list_view.iter_mut() {
+            list_view.hover(systems, screen_pos);
+        }
+    }
+
+    /// Grabs a list scrollbar or selects a row, returns the list and item
+    /// when the selection changed.
+    pub fn click_list_view(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<(usize, usize)> {
+        let index = self
+            .list_view
+            .iter()
+            .position(|list_view| list_view.in_list(systems, screen_pos))?;
+
+        self.list_view[index]
+            .click(systems, screen_pos)
+            .map(|item| (index, item))
+    }
+
+    pub fn move_list_view(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> bool {
+        let mut in_hold = false;
+
+        for list_view in self.list_view.iter_mut() {
+            list_view.set_move(systems, screen_pos);
+            in_hold |= list_view.scrollbar.in_hold;
+        }
+
+        in_hold
+    }
+
+    pub fn release_list_view(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) {
+        for list_view in self.list_view.iter_mut() {
+            list_view.release(systems, screen_pos);
+        }
+    }
+
+    pub fn dropdown_open(&self) -> bool {
+        self.dropdown.iter().any(|dropdown| dropdown.expanded)
+    }
+
+    pub fn close_dropdowns(&mut self, systems: &mut SystemHolder) {
+        self.dropdown
+            .iter_mut()
+            .for_each(|dropdown| dropdown.set_expanded(systems, false));
+    }
+
+    /// Opens the dropdown under `screen_pos`, or picks from the open list
+    /// and closes it. Returns the index of the dropdown whose selection
+    /// changed.
+    pub fn click_dropdown(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        if let Some(index) =
+            self.dropdown.iter().position(|dropdown| dropdown.expanded)
+        {
+            return self.dropdown[index]
+                .click(systems, screen_pos)
+                .map(|_| index);
+        }
+
+        if let Some(dropdown) = self
+            .dropdown
+            .iter_mut()
+            .find(|dropdown| dropdown.in_box(systems, screen_pos))
+        {
+            dropdown.click(systems, screen_pos);
+        }
+
+        None
+    }
+}
+
+fn build_button(
+    systems: &mut SystemHolder,
+    data: &LayoutButton,
+    pos: Vec2,
+    z_order: f32,
+) -> Button {
+    let button_type = match &data.style {
+        LayoutButtonStyle::None => ButtonType::None,
+        LayoutButtonStyle::Theme { border } => {
+            let rect = systems.theme.button.rect();
+
+            ButtonType::Rect(ButtonRect {
+                got_border: rect.got_border || *border,
+                ..rect
+            })
+        }
+        LayoutButtonStyle::Rect {
+            color: fill,
+            border_color,
+            radius,
+            hover,
+            click,
+        } => ButtonType::Rect(ButtonRect {
+            rect_color: color(*fill),
+            got_border: border_color.is_some(),
+            border_color: color(border_color.unwrap_or_default()),
+            border_radius: *radius,
+            hover_change: hover.button(),
+            click_change: click.button(),
+        }),
+        LayoutButtonStyle::Image { res, hover, click } => {
+            match layout_resource(systems, res) {
+                Some(res) => ButtonType::Image(ButtonImage {
+                    res,
+                    hover_change: hover.button(),
+                    click_change: click.button(),
+                }),
+                None => ButtonType::None,
+            }
+        }
+    };
+
+    let content_type = match &data.content {
+        LayoutButtonContent::None => ButtonContentType::None,
+        LayoutButtonContent::Image {
+            res,
+            pos,
+            uv,
+            size,
+            hover,
+            click,
+        } => match layout_resource(systems, res) {
+            Some(res) => ButtonContentType::Image(ButtonContentImg {
+                res,
+                pos: vec2(*pos),
+                uv: vec2(*uv),
+                size: vec2(*size),
+                hover_change: hover.button(),
+                click_change: click.button(),
+            }),
+            None => ButtonContentType::None,
+        },
+        LayoutButtonContent::Text {
+            text,
+            pos,
+            color: Some(text_color),
+            render_layer,
+            hover,
+            click,
+        } => ButtonContentType::Text(ButtonContentText {
+            text: systems.locale.text(text),
+            pos: vec2(*pos),
+            color: color(*text_color),
+            render_layer: *render_layer,
+            hover_change: hover.button(),
+            click_change: click.button(),
+        }),
+        LayoutButtonContent::Text {
+            text,
+            pos,
+            color: None,
+            render_layer,
+            ..
+        } => ButtonContentType::Text(systems.theme.button.text(
+            systems.locale.text(text),
+            vec2(*pos),
+            *render_layer,
+        )),
+    };
+
+    let tooltip = layout_tooltip(systems, &data.tooltip);
+
+    Button::new(
+        systems,
+        button_type,
+        content_type,
+        pos,
+        vec2(data.pos),
+        layer_z(z_order, data.layer),
+        LAYOUT_Z_STEP,
+        vec2(data.size),
+        data.render_layer,
+        false,
+        tooltip,
+    )
+}
+
+fn build_checkbox(
+    systems: &mut SystemHolder,
+    data: &LayoutCheckbox,
+    pos: Vec2,
+    z_order: f32,
+) -> Checkbox {
+    let (theme, locale) = (&systems.theme, &systems.locale);
+    let box_rect = match data.color {
+        Some(fill) => CheckboxRect {
+            rect_color: color(fill),
+            got_border: data.border_color.is_some(),
+            border_color: color(data.border_color.unwrap_or_default()),
+            border_radius: data.radius,
+            hover_change: data.hover.checkbox(),
+            click_change: data.click.checkbox(),
+        },
+        None => theme.checkbox.rect(),
+    };
+    let mut check_rect = theme
+        .checkbox
+        .check(vec2(data.check_pos), vec2(data.check_size));
+    if let Some(check) = data.check_color {
+        check_rect.rect_color = color(check);
+        check_rect.border_radius = data.radius;
+    }
+    let text = data.text.as_ref().map(|text| match text.color {
+        Some(text_color) => CheckboxText {
+            text: locale.text(&text.text),
+            offset_pos: vec2(text.offset),
+            render_layer: text.render_layer,
+            label_size: vec2(text.size),
+            color: color(text_color),
+            hover_change: text.hover.checkbox(),
+            click_change: text.click.checkbox(),
+        },
+        None => theme.checkbox.text(
+            locale.text(&text.text),
+            vec2(text.offset),
+            vec2(text.size),
+            text.render_layer,
+        ),
+    });
+
+    let tooltip = layout_tooltip(systems, &data.tooltip);
+
+    Checkbox::new(
+        systems,
+        CheckboxType::Rect(box_rect),
+        CheckType::SetRect(check_rect),
+        pos,
+        vec2(data.pos),
+        layer_z(z_order, data.layer),
+        LAYOUT_Z_STEP,
+        vec2(data.size),
+        data.render_layer,
+        text,
+        false,
+        tooltip,
+    )
+}
+
+fn build_scrollbar(
+    systems: &mut SystemHolder,
+    data: &LayoutScrollbar,
+    pos: Vec2,
+    z_order: f32,
+) -> Scrollbar {
+    let (bar, background) = match data.color {
+        Some(fill) => (
+            ScrollbarRect {
+                color: color(fill),
+                render_layer: data.render_layer,
+                got_border: data.border_color.is_some(),
+                border_color: color(data.border_color.unwrap_or_default()),
+                hover_color: color(data.hover_color),
+                hold_color: color(data.hold_color),
+                radius: data.radius,
+            },
+            data.background.as_ref().map(|bg| ScrollbarBackground {
+                color: color(bg.color),
+                render_layer: data.render_layer,
+                got_border: bg.border_color.is_some(),
+                border_color: color(bg.border_color.unwrap_or_default()),
+                radius: bg.radius,
+            }),
+        ),
+        None => (
+            systems.theme.scrollbar.bar(data.render_layer),
+            Some(systems.theme.scrollbar.background_rect(data.render_layer)),
+        ),
+    };
+
+    let tooltip = layout_tooltip(systems, &data.tooltip);
+
+    Scrollbar::new(
+        systems,
+        pos,
+        vec2(data.pos),
+        data.length,
+        data.thickness,
+        data.vertical,
+        layer_z(z_order, data.layer),
+        LAYOUT_Z_STEP,
+        bar,
+        background,
+        data.max_value,
+        data.min_bar_size,
+        data.reverse,
+        false,
+        tooltip,
+    )
+}
+
+fn build_slider(
+    systems: &mut SystemHolder,
+    data: &LayoutSlider,
+    pos: Vec2,
+    z_order: f32,
+) -> Slider {
+    let (bar, background) = match data.color {
+        Some(fill) => (
+            ScrollbarRect {
+                color: color(fill),
+                render_layer: data.render_layer,
+                got_border: data.border_color.is_some(),
+                border_color: color(data.border_color.unwrap_or_default()),
+                hover_color: color(data.hover_color),
+                hold_color: color(data.hold_color),
+                radius: data.radius,
+            },
+            data.background.as_ref().map(|bg| ScrollbarBackground {
+                color: color(bg.color),
+                render_layer: data.render_layer,
+                got_border: bg.border_color.is_some(),
+                border_color: color(bg.border_color.unwrap_or_default()),
+                radius: bg.radius,
+            }),
+        ),
+        None => (
+            systems.theme.scrollbar.bar(data.render_layer),
+            Some(systems.theme.scrollbar.background_rect(data.render_layer)),
+        ),
+    };
+
+    let tooltip = layout_tooltip(systems, &data.tooltip);
+
+    Slider::new(
+        systems,
+        pos,
+        vec2(data.pos),
+        data.length,
+        data.thickness,
+        layer_z(z_order, data.layer),
+        LAYOUT_Z_STEP,
+        bar,
+        background,
+        (data.min_value, data.max_value, data.step),
+        data.min_bar_size,
+        data.label.as_ref().map(|label| SliderLabel {
+            pos: vec2(label.pos),
+            size: vec2(label.size),
+            color: color(label.color),
+            render_layer: label.render_layer,
+            suffix: label.suffix.clone(),
+        }),
+        false,
+        tooltip,
+    )
+}
+
+fn build_dropdown(
+    systems: &mut SystemHolder,
+    data: &LayoutDropdown,
+    pos: Vec2,
+    z_order: f32,
+) -> Dropdown {
+    let options = data
+        .options
+        .iter()
+        .map(|key| systems.locale.get(key).to_string())
+        .collect();
+    let tooltip = layout_tooltip(systems, &data.tooltip);
+
+    Dropdown::new(
+        systems,
+        pos,
+        vec2(data.pos),
+        vec2(data.size),
+        [
+            layer_z(z_order, data.layer),
+            layer_z(z_order, data.list_layer),
+        ],
+        LAYOUT_Z_STEP,
+        DropdownRect {
+            color: color(data.color),
+            render_layer: data.render_layer,
+            got_border: data.border_color.is_some(),
+            border_color: color(data.border_color.unwrap_or_default()),
+            hover_color: color(data.hover_color),
+            radius: data.radius,
+            list_color: color(data.list_color),
+            select_color: color(data.select_color),
+            text_color: color(data.text_color),
+        },
+        options,
+        false,
+        tooltip,
+    )
+}
+
+fn build_list_view(
+    systems: &mut SystemHolder,
+    data: &LayoutListView,
+    pos: Vec2,
+    z_order: f32,
+) -> ListView {
+    let scrollbar = systems.theme.scrollbar.bar(data.render_layer);
+
+    ListView::new(
+        systems,
+        pos,
+        vec2(data.pos),
+        vec2(data.size),
+        data.row_height,
+        layer_z(z_order, data.layer),
+        LAYOUT_Z_STEP,
+        ListViewRect {
+            color: color(data.color),
+            render_layer: data.render_layer,
+            got_border: data.border_color.is_some(),
+            border_color: color(data.border_color.unwrap_or_default()),
+            hover_color: color(data.hover_color),
+            select_color: color(data.select_color),
+            text_color: color(data.text_color),
+            radius: data.radius,
+        },
+        scrollbar,
+        data.scrollbar_width,
+        false,
+    )
+}
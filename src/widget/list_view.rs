@@ -0,0 +1,467 @@
+use ascending_graphics::*;
+
+use crate::{GfxType, SystemHolder, is_within_area, logic::*, widget::*};
+
+pub struct ListViewRect {
+    pub color: Color,
+    pub render_layer: usize,
+    pub got_border: bool,
+    pub border_color: Color,
+    pub hover_color: Color,
+    pub select_color: Color,
+    pub text_color: Color,
+    pub radius: f32,
+}
+
+struct ListRow {
+    bg: GfxType,
+    text: GfxType,
+}
+
+/// A scrolling list that only builds the rows that fit in its area and
+/// fills them from `items` as the [`Scrollbar`] moves.
+pub struct ListView {
+    visible: bool,
+    bg: GfxType,
+    rows: Vec<ListRow>,
+    items: Vec<String>,
+    pub scrollbar: Scrollbar,
+    style: ListViewRect,
+
+    pub selected: Option<usize>,
+    hover: Option<usize>,
+    offset: usize,
+
+    pub base_pos: Vec2,
+    pub adjust_pos: Vec2,
+    pub size: Vec2,
+    pub row_height: f32,
+    scrollbar_width: f32,
+    pub z_order: f32,
+    z_step: (f32, i32),
+}
+
+impl ListView {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        systems: &mut SystemHolder,
+        base_pos: Vec2,
+        adjust_pos: Vec2,
+        size: Vec2,
+        row_height: f32,
+        z_order: f32,
+        z_step: (f32, i32),
+        style: ListViewRect,
+        scrollbar: ScrollbarRect,
+        scrollbar_width: f32,
+        visible: bool,
+    ) -> Self {
+        let scale = systems.scale as f32;
+        let pos = base_pos + (adjust_pos * scale).floor();
+
+        let mut rect = Rect::new(
+            &mut systems.renderer,
+            Vec3::new(pos.x, pos.y, z_order),
+            (size * scale).floor(),
+            style.color,
+            0,
+        );
+        rect.set_radius(style.radius);
+
+        if style.got_border {
+            rect.set_border_width(systems.theme.border_width)
+                .set_border_color(style.border_color);
+        }
+
+        let bg = systems.gfx.add_rect(
+            rect,
+            style.render_layer,
+            "List BG",
+            visible,
+            CameraView::SubView1,
+        );
+
+        let scrollbar = Scrollbar::new(
+            systems,
+            base_pos,
+            Vec2::new(adjust_pos.x + size.x - scrollbar_width, adjust_pos.y),
+            size.y,
+            scrollbar_width,
+            true,
+            z_order.sub_f32(z_step.0, z_step.1),
+            z_step,
+            scrollbar,
+            None,
+            0,
+            (row_height * 0.5).floor(),
+            false,
+            visible,
+            None,
+        );
+
+        let mut list = ListView {
+            visible,
+            bg,
+            rows: Vec::new(),
+            items: Vec::new(),
+            scrollbar,
+            style,
+            selected: None,
+            hover: None,
+            offset: 0,
+            base_pos,
+            adjust_pos,
+            size,
+            row_height,
+            scrollbar_width,
+            z_order,
+            z_step,
+        };
+
+        let row_count = (size.y / row_height.max(1.0)).floor() as usize;
+
+        for index in 0..row_count {
+            let (rpos, rsize) = list.row_area(systems, index);
+
+            let rect = Rect::new(
+                &mut systems.renderer,
+                Vec3::new(rpos.x, rpos.y, z_order.sub_f32(z_step.0, z_step.1)),
+                rsize,
+                list.style.hover_color,
+                0,
+            );
+            let bg = systems.gfx.add_rect(
+                rect,
+                list.style.render_layer,
+                "List Row",
+                false,
+                CameraView::SubView1,
+            );
+
+            let tpos = list.text_pos(systems, rpos, rsize);
+            let text_data = create_label(
+                systems,
+                Vec3::new(
+                    tpos.x,
+                    tpos.y,
+                    z_order.sub_f32(z_step.0 * 2.0, z_step.1),
+                ),
+                rsize,
+                Some(Bounds::new(
+                    tpos.x,
+                    rpos.y,
+                    rpos.x + rsize.x,
+                    rpos.y + rsize.y,
+                )),
+                list.style.text_color,
+            );
+            let text = systems.gfx.add_text(
+                text_data,
+                list.style.render_layer + 1,
+                "List Text",
+                false,
+                CameraView::SubView1,
+            );
+
+            list.rows.push(ListRow { bg, text });
+        }
+
+        list
+    }
+
+    /// Area of the row `index` counted from the top, the scrollbar takes
+    /// the right side of the list.
+    fn row_area(&self, systems: &SystemHolder, index: usize) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+        let pos = self.base_pos + (self.adjust_pos * scale).floor();
+        let height = (self.row_height * scale).floor();
+        let width = ((self.size.x - self.scrollbar_width) * scale).floor();
+
+        (
+            Vec2::new(
+                pos.x,
+                pos.y + (self.size.y * scale).floor()
+                    - height * (index + 1) as f32,
+            ),
+            Vec2::new(width, height),
+        )
+    }
+
+    fn text_pos(&self, systems: &SystemHolder, pos: Vec2, size: Vec2) -> Vec2 {
+        let scale = systems.scale as f32;
+        let height = (16.0 * scale).floor();
+
+        Vec2::new(
+            pos.x + (4.0 * scale).floor(),
+            pos.y + ((size.y - height) * 0.5).floor(),
+        )
+    }
+
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// Number of rows that fit in the list.
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn set_items(
+        &mut self,
+        systems: &mut SystemHolder,
+        items: Vec<String>,
+    ) {
+        self.items = items;
+        self.selected = self.selected.filter(|index| *index < self.items.len());
+        self.update_scroll_range(systems);
+        self.update_rows(systems);
+    }
+
+    /// Adds an item at the end, following it when the list was scrolled to
+    /// the bottom.
+    pub fn push_item(&mut self, systems: &mut SystemHolder, item: String) {
+        let at_bottom = self.scrollbar.value >= self.scrollbar.max_value;
+
+        self.items.push(item);
+        self.update_scroll_range(systems);
+
+        if at_bottom {
+            let max_value = self.scrollbar.max_value;
+            self.scrollbar.set_value(systems, max_value);
+        }
+
+        self.update_rows(systems);
+    }
+
+    fn update_scroll_range(&mut self, systems: &mut SystemHolder) {
+        let max_value = self.items.len().saturating_sub(self.rows.len());
+        self.scrollbar.set_max_value(systems, max_value);
+    }
+
+    /// Fills the rows from the item at the scroll offset.
+    fn update_rows(&mut self, systems: &mut SystemHolder) {
+        self.offset = self.scrollbar.value;
+
+        for (index, row) in self.rows.iter().enumerate() {
+            let item = self.offset + index;
+
+            match self.items.get(item) {
+                Some(text) => {
+                    systems.gfx.set_text(&row.text, text);
+                    systems.gfx.set_visible(&row.text, self.visible);
+                }
+                None => systems.gfx.set_visible(&row.text, false),
+            }
+
+            self.update_row_color(systems, index);
+        }
+    }
+
+    fn update_row_color(&self, systems: &mut SystemHolder, index: usize) {
+        let Some(row) = self.rows.get(index) else {
+            return;
+        };
+        let item = self.offset + index;
+
+        let color = if self.selected == Some(item) {
+            Some(self.style.select_color)
+        } else if self.hover == Some(item) {
+            Some(self.style.hover_color)
+        } else {
+            None
+        };
+
+        match color {
+            Some(color) if self.visible && item < self.items.len() => {
+                systems.gfx.set_color(&row.bg, color);
+                systems.gfx.set_visible(&row.bg, true);
+            }
+            _ => systems.gfx.set_visible(&row.bg, false),
+        }
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        systems.gfx.remove_gfx(&mut systems.renderer, &self.bg);
+        self.scrollbar.unload(systems);
+
+        for row in self.rows.drain(..) {
+            systems.gfx.remove_gfx(&mut systems.renderer, &row.bg);
+            systems.gfx.remove_gfx(&mut systems.renderer, &row.text);
+        }
+    }
+
+    pub fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        if self.visible == visible {
+            return;
+        }
+        self.visible = visible;
+
+        systems.gfx.set_visible(&self.bg, visible);
+        self.scrollbar.set_visible(systems, visible);
+
+        if !visible {
+            self.hover = None;
+            self.scrollbar.set_hold(systems, false, Vec2::ZERO);
+        }
+
+        self.update_rows(systems);
+    }
+
+    pub fn set_z_order(&mut self, systems: &mut SystemHolder, z_order: f32) {
+        self.z_order = z_order;
+
+        let row_z = z_order.sub_f32(self.z_step.0, self.z_step.1);
+        let text_z = z_order.sub_f32(self.z_step.0 * 2.0, self.z_step.1);
+
+        systems.gfx.set_pos_z(&self.bg, z_order);
+        self.scrollbar.set_z_order(systems, row_z);
+
+        for row in self.rows.iter() {
+            systems.gfx.set_pos_z(&row.bg, row_z);
+            systems.gfx.set_pos_z(&row.text, text_z);
+        }
+    }
+
+    pub fn set_pos(&mut self, systems: &mut SystemHolder, new_pos: Vec2) {
+        self.base_pos = new_pos;
+
+        let pos = new_pos + (self.adjust_pos * systems.scale as f32).floor();
+        let z = systems.gfx.get_pos(&self.bg).z;
+        systems.gfx.set_pos(&self.bg, Vec3::new(pos.x, pos.y, z));
+        self.scrollbar.set_pos(systems, new_pos);
+
+        for index in 0..self.rows.len() {
+            let (rpos, rsize) = self.row_area(systems, index);
+            let tpos = self.text_pos(systems, rpos, rsize);
+            let row = &self.rows[index];
+
+            let z = systems.gfx.get_pos(&row.bg).z;
+            systems.gfx.set_pos(&row.bg, Vec3::new(rpos.x, rpos.y, z));
+
+            let z = systems.gfx.get_pos(&row.text).z;
+            systems.gfx.set_pos(&row.text, Vec3::new(tpos.x, tpos.y, z));
+            systems.gfx.set_bound(
+                &row.text,
+                Some(Bounds::new(
+                    tpos.x,
+                    rpos.y,
+                    rpos.x + rsize.x,
+                    rpos.y + rsize.y,
+                )),
+            );
+        }
+    }
+
+    pub fn in_list(&self, systems: &SystemHolder, screen_pos: Vec2) -> bool {
+        if !self.visible {
+            return false;
+        }
+
+        let scale = systems.scale as f32;
+        is_within_area(
+            screen_pos,
+            self.base_pos + (self.adjust_pos * scale).floor(),
+            (self.size * scale).floor(),
+        )
+    }
+
+    /// Item under `screen_pos`.
+    pub fn in_item(
+        &self,
+        systems: &SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        if !self.visible {
+            return None;
+        }
+
+        (0..self.rows.len())
+            .find(|index| {
+                let (pos, size) = self.row_area(systems, *index);
+                is_within_area(screen_pos, pos, size)
+            })
+            .map(|index| self.offset + index)
+            .filter(|item| *item < self.items.len())
+    }
+
+    pub fn hover(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        let state = self.scrollbar.in_scroll(screen_pos);
+        self.scrollbar.set_hover(systems, state);
+
+        let hover = self.in_item(systems, screen_pos);
+
+        if self.hover == hover {
+            return;
+        }
+
+        let old = self.hover;
+        self.hover = hover;
+
+        for item in [old, hover].into_iter().flatten() {
+            if let Some(index) = item.checked_sub(self.offset) {
+                self.update_row_color(systems, index);
+            }
+        }
+    }
+
+    /// Grabs the scrollbar or selects the item under `screen_pos`, returns
+    /// the item when the selection changed.
+    pub fn click(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        if self.scrollbar.in_scroll(screen_pos) {
+            self.scrollbar.set_hold(systems, true, screen_pos);
+            return None;
+        }
+
+        let item = self.in_item(systems, screen_pos)?;
+
+        if self.selected == Some(item) {
+            return None;
+        }
+
+        let old = self.selected;
+        self.selected = Some(item);
+
+        for item in [old, Some(item)].into_iter().flatten() {
+            if let Some(index) = item.checked_sub(self.offset) {
+                self.update_row_color(systems, index);
+            }
+        }
+
+        Some(item)
+    }
+
+    /// Drags the held scrollbar.
+    pub fn set_move(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        if !self.scrollbar.in_hold {
+            return;
+        }
+
+        self.scrollbar.set_move_scroll(systems, screen_pos);
+
+        if self.offset != self.scrollbar.value {
+            self.update_rows(systems);
+        }
+    }
+
+    pub fn release(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        self.scrollbar.set_hold(systems, false, screen_pos);
+    }
+
+    /// Scrolls by `amount` rows, negative values scroll up.
+    pub fn scroll_by(&mut self, systems: &mut SystemHolder, amount: isize) {
+        let value = self
+            .scrollbar
+            .value
+            .saturating_add_signed(amount)
+            .min(self.scrollbar.max_value);
+
+        if value != self.scrollbar.value {
+            self.scrollbar.set_value(systems, value);
+            self.update_rows(systems);
+        }
+    }
+}
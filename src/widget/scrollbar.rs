@@ -89,7 +89,7 @@ impl Scrollbar {
 
             if data.got_border {
                 scrollbg_rect
-                    .set_border_width(1.0)
+                    .set_border_width(systems.theme.border_width)
                     .set_border_color(data.border_color);
             }
 
@@ -169,7 +169,7 @@ impl Scrollbar {
         scroll_rect.set_radius(scrollbar.radius);
         if scrollbar.got_border {
             scroll_rect
-                .set_border_width(1.0)
+                .set_border_width(systems.theme.border_width)
                 .set_border_color(scrollbar.border_color);
         }
         let scroll = systems.gfx.add_rect(
@@ -216,8 +216,9 @@ impl Scrollbar {
         systems.gfx.remove_gfx(&mut systems.renderer, &self.scroll);
     }
 
-    pub fn in_scroll(&mut self, screen_pos: Vec2) -> bool {
-        is_within_area(screen_pos, self.base_pos + self.pos, self.size)
+    pub fn in_scroll(&self, screen_pos: Vec2) -> bool {
+        self.visible
+            && is_within_area(screen_pos, self.base_pos + self.pos, self.size)
     }
 
     pub fn set_hover(&mut self, systems: &mut SystemHolder, in_hover: bool) {
@@ -289,6 +290,7 @@ impl Scrollbar {
 
             if self.in_hold {
                 self.hold_pos = screen_pos - (self.base_pos + self.pos);
+                systems.gfx.set_color(&self.scroll, self.hold_color);
             } else if self.in_hover {
                 systems.gfx.set_color(&self.scroll, self.hover_color);
             }
@@ -394,7 +396,7 @@ impl Scrollbar {
         systems: &mut SystemHolder,
         max_value: usize,
     ) {
-        if self.max_value < max_value {
+        if self.max_value != max_value {
             self.max_value = max_value;
 
             let scrollbar_size = ((self.bar_size
@@ -452,6 +454,11 @@ impl Scrollbar {
                 ),
             );
             systems.gfx.set_size(&self.scroll, self.size);
+
+            self.value = self.value.min(self.max_value);
+            if self.max_value > 0 {
+                self.set_value(systems, self.value);
+            }
         }
     }
 }
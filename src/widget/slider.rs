@@ -0,0 +1,214 @@
+use ascending_graphics::*;
+
+use crate::{GfxType, SystemHolder, TString, widget::*};
+
+pub struct SliderLabel {
+    pub pos: Vec2,
+    pub size: Vec2,
+    pub color: Color,
+    pub render_layer: usize,
+    /// Appended to the value, like `%`.
+    pub suffix: String,
+}
+
+/// A horizontal [`Scrollbar`] stepping from `min_value` to `max_value`, with
+/// an optional label showing the current value.
+pub struct Slider {
+    pub scrollbar: Scrollbar,
+    label: Option<(GfxType, SliderLabel)>,
+    base_pos: Vec2,
+    pub min_value: usize,
+    pub max_value: usize,
+    pub step: usize,
+}
+
+impl Slider {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        systems: &mut SystemHolder,
+        base_pos: Vec2,
+        adjust_pos: Vec2,
+        length: f32,
+        thickness: f32,
+        z_order: f32,
+        z_step: (f32, i32),
+        scrollbar: ScrollbarRect,
+        background: Option<ScrollbarBackground>,
+        (min_value, max_value, step): (usize, usize, usize),
+        min_bar_size: f32,
+        label: Option<SliderLabel>,
+        visible: bool,
+        tooltip: Option<TString>,
+    ) -> Self {
+        let step = step.max(1);
+        let max_value = max_value.max(min_value);
+
+        let scrollbar = Scrollbar::new(
+            systems,
+            base_pos,
+            adjust_pos,
+            length,
+            thickness,
+            false,
+            z_order,
+            z_step,
+            scrollbar,
+            background,
+            (max_value - min_value) / step,
+            min_bar_size,
+            false,
+            visible,
+            tooltip,
+        );
+
+        let label = label.map(|data| {
+            let (pos, size) = Slider::label_area(systems, base_pos, &data);
+            let text_data = create_label(
+                systems,
+                Vec3::new(pos.x, pos.y, z_order),
+                size,
+                Some(Bounds::new(pos.x, pos.y, pos.x + size.x, pos.y + size.y)),
+                data.color,
+            );
+            let gfx = systems.gfx.add_text(
+                text_data,
+                data.render_layer,
+                "Slider Label",
+                visible,
+                CameraView::SubView1,
+            );
+
+            (gfx, data)
+        });
+
+        let slider = Slider {
+            scrollbar,
+            label,
+            base_pos,
+            min_value,
+            max_value,
+            step,
+        };
+        slider.update_label(systems);
+        slider
+    }
+
+    fn label_area(
+        systems: &SystemHolder,
+        base_pos: Vec2,
+        label: &SliderLabel,
+    ) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+
+        (
+            base_pos + (label.pos * scale).floor(),
+            (label.size * scale).floor(),
+        )
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        self.scrollbar.unload(systems);
+
+        if let Some((gfx, _)) = &self.label {
+            systems.gfx.remove_gfx(&mut systems.renderer, gfx);
+        }
+    }
+
+    pub fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        self.scrollbar.set_visible(systems, visible);
+
+        if let Some((gfx, _)) = &self.label {
+            systems.gfx.set_visible(gfx, visible);
+        }
+    }
+
+    pub fn set_z_order(&mut self, systems: &mut SystemHolder, z_order: f32) {
+        self.scrollbar.set_z_order(systems, z_order);
+
+        if let Some((gfx, _)) = &self.label {
+            systems.gfx.set_pos_z(gfx, z_order);
+        }
+    }
+
+    pub fn set_pos(&mut self, systems: &mut SystemHolder, new_pos: Vec2) {
+        self.base_pos = new_pos;
+        self.scrollbar.set_pos(systems, new_pos);
+
+        if let Some((gfx, data)) = &self.label {
+            let (pos, size) = Slider::label_area(systems, new_pos, data);
+            let z = systems.gfx.get_pos(gfx).z;
+
+            systems.gfx.set_pos(gfx, Vec3::new(pos.x, pos.y, z));
+            systems.gfx.set_bound(
+                gfx,
+                Some(Bounds::new(pos.x, pos.y, pos.x + size.x, pos.y + size.y)),
+            );
+        }
+    }
+
+    pub fn value(&self) -> usize {
+        (self.min_value + self.scrollbar.value * self.step).min(self.max_value)
+    }
+
+    /// Moves the handle to `value`, rounded down to the nearest step.
+    pub fn set_value(&mut self, systems: &mut SystemHolder, value: usize) {
+        let value = value.clamp(self.min_value, self.max_value);
+
+        if self.scrollbar.max_value > 0 {
+            self.scrollbar
+                .set_value(systems, (value - self.min_value) / self.step);
+        }
+        self.update_label(systems);
+    }
+
+    fn update_label(&self, systems: &mut SystemHolder) {
+        if let Some((gfx, data)) = &self.label {
+            systems
+                .gfx
+                .set_text(gfx, &format!("{}{}", self.value(), data.suffix));
+        }
+    }
+
+    pub fn in_slider(&self, screen_pos: Vec2) -> bool {
+        self.scrollbar.in_scroll(screen_pos)
+    }
+
+    pub fn in_hold(&self) -> bool {
+        self.scrollbar.in_hold
+    }
+
+    pub fn set_hover(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        let state = self.in_slider(screen_pos);
+        self.scrollbar.set_hover(systems, state);
+    }
+
+    pub fn set_hold(
+        &mut self,
+        systems: &mut SystemHolder,
+        in_hold: bool,
+        screen_pos: Vec2,
+    ) {
+        self.scrollbar.set_hold(systems, in_hold, screen_pos);
+    }
+
+    /// Drags the held handle, returns true when the value changed.
+    pub fn set_move(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> bool {
+        if !self.in_hold() {
+            return false;
+        }
+
+        let value = self.scrollbar.value;
+        self.scrollbar.set_move_scroll(systems, screen_pos);
+
+        if value == self.scrollbar.value {
+            return false;
+        }
+
+        self.update_label(systems);
+        true
+    }
+}
@@ -0,0 +1,279 @@
+use ascending_graphics::*;
+
+use crate::{GfxType, SystemHolder, is_within_area, logic::*, widget::*};
+
+pub struct TabRect {
+    pub color: Color,
+    pub render_layer: usize,
+    pub got_border: bool,
+    pub border_color: Color,
+    pub hover_color: Color,
+    pub select_color: Color,
+    pub text_color: Color,
+    pub radius: f32,
+}
+
+struct Tab {
+    bg: GfxType,
+    text: GfxType,
+}
+
+/// A row of tab headers of which one is selected. The owner shows the
+/// content of the selected tab.
+pub struct TabContainer {
+    visible: bool,
+    tabs: Vec<Tab>,
+    style: TabRect,
+
+    pub selected: usize,
+    hover: Option<usize>,
+
+    pub base_pos: Vec2,
+    pub adjust_pos: Vec2,
+    pub tab_size: Vec2,
+    pub spacing: f32,
+    pub z_order: f32,
+    z_step: (f32, i32),
+}
+
+impl TabContainer {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        systems: &mut SystemHolder,
+        base_pos: Vec2,
+        adjust_pos: Vec2,
+        tab_size: Vec2,
+        spacing: f32,
+        z_order: f32,
+        z_step: (f32, i32),
+        style: TabRect,
+        names: &[String],
+        visible: bool,
+    ) -> Self {
+        let mut container = TabContainer {
+            visible,
+            tabs: Vec::with_capacity(names.len()),
+            style,
+            selected: 0,
+            hover: None,
+            base_pos,
+            adjust_pos,
+            tab_size,
+            spacing,
+            z_order,
+            z_step,
+        };
+
+        for (index, name) in names.iter().enumerate() {
+            let (pos, size) = container.tab_area(systems, index);
+
+            let mut rect = Rect::new(
+                &mut systems.renderer,
+                Vec3::new(pos.x, pos.y, z_order),
+                size,
+                container.tab_color(index),
+                0,
+            );
+            rect.set_radius(container.style.radius);
+
+            if container.style.got_border {
+                rect.set_border_width(systems.theme.border_width)
+                    .set_border_color(container.style.border_color);
+            }
+
+            let bg = systems.gfx.add_rect(
+                rect,
+                container.style.render_layer,
+                "Tab BG",
+                visible,
+                CameraView::SubView1,
+            );
+
+            let tpos = Vec3::new(
+                pos.x,
+                pos.y
+                    + ((size.y - (20.0 * systems.scale as f32).floor()) * 0.5)
+                        .floor(),
+                z_order.sub_f32(z_step.0, z_step.1),
+            );
+            let text_data = create_label(
+                systems,
+                tpos,
+                size,
+                Some(Bounds::new(
+                    tpos.x,
+                    tpos.y,
+                    tpos.x + size.x,
+                    pos.y + size.y,
+                )),
+                container.style.text_color,
+            );
+            let text = systems.gfx.add_text(
+                text_data,
+                container.style.render_layer + 1,
+                "Tab Text",
+                visible,
+                CameraView::SubView1,
+            );
+            systems.gfx.set_text(&text, name);
+            systems.gfx.center_text(&mut systems.renderer, &text);
+
+            container.tabs.push(Tab { bg, text });
+        }
+
+        container
+    }
+
+    fn tab_area(&self, systems: &SystemHolder, index: usize) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+        let offset =
+            Vec2::new((self.tab_size.x + self.spacing) * index as f32, 0.0);
+
+        (
+            self.base_pos + ((self.adjust_pos + offset) * scale).floor(),
+            (self.tab_size * scale).floor(),
+        )
+    }
+
+    fn tab_color(&self, index: usize) -> Color {
+        if index == self.selected {
+            self.style.select_color
+        } else if self.hover == Some(index) {
+            self.style.hover_color
+        } else {
+            self.style.color
+        }
+    }
+
+    fn update_color(&self, systems: &mut SystemHolder, index: usize) {
+        if let Some(tab) = self.tabs.get(index) {
+            systems.gfx.set_color(&tab.bg, self.tab_color(index));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tabs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty()
+    }
+
+    pub fn unload(&mut self, systems: &mut SystemHolder) {
+        for tab in self.tabs.drain(..) {
+            systems.gfx.remove_gfx(&mut systems.renderer, &tab.bg);
+            systems.gfx.remove_gfx(&mut systems.renderer, &tab.text);
+        }
+    }
+
+    pub fn set_visible(&mut self, systems: &mut SystemHolder, visible: bool) {
+        if self.visible == visible {
+            return;
+        }
+        self.visible = visible;
+
+        for tab in self.tabs.iter() {
+            systems.gfx.set_visible(&tab.bg, visible);
+            systems.gfx.set_visible(&tab.text, visible);
+        }
+    }
+
+    pub fn set_z_order(&mut self, systems: &mut SystemHolder, z_order: f32) {
+        self.z_order = z_order;
+
+        for tab in self.tabs.iter() {
+            systems.gfx.set_pos_z(&tab.bg, z_order);
+            systems.gfx.set_pos_z(
+                &tab.text,
+                z_order.sub_f32(self.z_step.0, self.z_step.1),
+            );
+        }
+    }
+
+    pub fn set_pos(&mut self, systems: &mut SystemHolder, new_pos: Vec2) {
+        self.base_pos = new_pos;
+
+        for (index, tab) in self.tabs.iter().enumerate() {
+            let (pos, size) = self.tab_area(systems, index);
+            let z = systems.gfx.get_pos(&tab.bg).z;
+            systems.gfx.set_pos(&tab.bg, Vec3::new(pos.x, pos.y, z));
+
+            let tpos = Vec2::new(
+                pos.x,
+                pos.y
+                    + ((size.y - (20.0 * systems.scale as f32).floor()) * 0.5)
+                        .floor(),
+            );
+            let z = systems.gfx.get_pos(&tab.text).z;
+            systems.gfx.set_pos(&tab.text, Vec3::new(tpos.x, tpos.y, z));
+            systems.gfx.set_bound(
+                &tab.text,
+                Some(Bounds::new(
+                    tpos.x,
+                    tpos.y,
+                    tpos.x + size.x,
+                    pos.y + size.y,
+                )),
+            );
+            systems.gfx.center_text(&mut systems.renderer, &tab.text);
+        }
+    }
+
+    pub fn in_tab(
+        &self,
+        systems: &SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        if !self.visible {
+            return None;
+        }
+
+        (0..self.tabs.len()).find(|index| {
+            let (pos, size) = self.tab_area(systems, *index);
+            is_within_area(screen_pos, pos, size)
+        })
+    }
+
+    pub fn set_selected(&mut self, systems: &mut SystemHolder, index: usize) {
+        if self.selected == index || index >= self.tabs.len() {
+            return;
+        }
+
+        let old = self.selected;
+        self.selected = index;
+        self.update_color(systems, old);
+        self.update_color(systems, index);
+    }
+
+    pub fn hover(&mut self, systems: &mut SystemHolder, screen_pos: Vec2) {
+        let hover = self.in_tab(systems, screen_pos);
+
+        if self.hover == hover {
+            return;
+        }
+
+        let old = self.hover;
+        self.hover = hover;
+
+        for index in [old, hover].into_iter().flatten() {
+            self.update_color(systems, index);
+        }
+    }
+
+    /// Selects the tab under `screen_pos`, returns it when the selection
+    /// changed.
+    pub fn click(
+        &mut self,
+        systems: &mut SystemHolder,
+        screen_pos: Vec2,
+    ) -> Option<usize> {
+        let index = self.in_tab(systems, screen_pos)?;
+
+        if index == self.selected {
+            return None;
+        }
+
+        self.set_selected(systems, index);
+        Some(index)
+    }
+}
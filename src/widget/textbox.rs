@@ -1,6 +1,5 @@
 use arboard::Clipboard;
 use ascending_graphics::*;
-use cosmic_text::{Attrs, Metrics};
 use log::warn;
 use std::cmp;
 
@@ -206,6 +205,17 @@ impl Textbox {
         }
     }
 
+    /// Position and size of the textbox on the screen.
+    pub fn screen_area(&self, systems: &SystemHolder) -> (Vec2, Vec2) {
+        let scale = systems.scale as f32;
+
+        (
+            Vec2::new(self.base_pos.x, self.base_pos.y)
+                + (self.adjust_pos * scale).floor(),
+            (self.size * scale).floor(),
+        )
+    }
+
     pub fn unload(&mut self, systems: &mut SystemHolder) {
         systems.gfx.remove_gfx(&mut systems.renderer, &self.bg);
         systems
@@ -0,0 +1,479 @@
+use ascending_graphics::*;
+use cosmic_text::{Family, FontSystem, Metrics};
+use log::warn;
+use serde::{Deserialize, Deserializer};
+use std::{fs, sync::OnceLock};
+
+use crate::{TString, widget::*};
+
+pub const THEME_FOLDER: &str = "./data/themes";
+pub const DEFAULT_THEME: &str = "default";
+
+const DEFAULT_THEME_DATA: &str = include_str!("../../data/themes/default.toml");
+
+/// Sans serif family of the font system before any theme changed it, so
+/// themes without a font of their own can switch back to it.
+static SYSTEM_FONT_FAMILY: OnceLock<String> = OnceLock::new();
+
+fn deserialize_color<'de, D>(deserializer: D) -> Result<Color, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let [r, g, b, a] = <[u8; 4]>::deserialize(deserializer)?;
+    Ok(Color::rgba(r, g, b, a))
+}
+
+/// Colours of a widget while idle, hovered and clicked or held.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeStates {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub normal: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub hover: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub click: Color,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ThemeFont {
+    pub size: f32,
+    pub line_height: f32,
+    /// Family used for every text, it must be installed on the system or
+    /// come from `file`.
+    pub family: Option<String>,
+    /// Font file loaded into the font system when the theme is applied.
+    pub file: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeWindow {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub header_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub header_text: Color,
+    /// Labels inside a window.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Color,
+    /// Labels that stand out, such as item names and trade states.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub highlight_text: Color,
+    /// Boxes drawn inside a window behind a group of labels.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub panel: Color,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeButton {
+    pub rect: ThemeStates,
+    pub text: ThemeStates,
+    #[serde(default)]
+    pub border: bool,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_color: Color,
+    pub radius: f32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeCheckbox {
+    pub rect: ThemeStates,
+    pub text: ThemeStates,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub check: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_color: Color,
+    pub radius: f32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeScrollbar {
+    /// The `click` colour is used while the bar is held.
+    pub bar: ThemeStates,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub background: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_color: Color,
+    pub radius: f32,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeTextbox {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub background: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub selection: Color,
+}
+
+/// Item slots of the inventory, storage, shop and trade windows.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeSlot {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub amount_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub amount_border_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub amount_text: Color,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeTooltip {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Color,
+}
+
+/// The login and register windows, drawn before entering the game.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeMenu {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub header_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub header_text: Color,
+    /// Background of the field names in front of the textboxes.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub label_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub label_text: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub field_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub field_text: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub preview_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Color,
+    /// Text only buttons and checkbox labels.
+    pub link: ThemeStates,
+    pub button: ThemeButton,
+}
+
+/// Alert windows and the dimmed screen behind them.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeAlert {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub backdrop: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub header_color: Color,
+    /// Message, header and button text.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Color,
+    /// Frame around the amount textbox.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub input_color: Color,
+    pub textbox: ThemeTextbox,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeChat {
+    /// The `click` colour is used by the selected tab.
+    pub tab: ThemeStates,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub tab_text: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub tab_border_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub area_color: Color,
+    /// Frame around the chat textbox.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub input_color: Color,
+    /// Background of selected messages.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub selection: Color,
+    /// Messages without a colour of their own.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Color,
+}
+
+/// Vital bar, target and party frames and the effect icons drawn over the
+/// map.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeHud {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub border_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub bar_background: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub bar_border_color: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub hp: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub mp: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub sp: Color,
+    /// HP bar of the player while dead.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub dead: Color,
+    /// Names inside the frames.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub text: Color,
+    /// Levels, distances and locations inside the frames.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub detail_text: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub leader_text: Color,
+    /// Texts outside of the frames, drawn straight over the map.
+    #[serde(deserialize_with = "deserialize_color")]
+    pub map_text: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub effect_border: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub harmful_effect_border: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub effect_text: Color,
+}
+
+/// Player name colours for each access level.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeAccess {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub player: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub monitor: Color,
+    #[serde(deserialize_with = "deserialize_color")]
+    pub admin: Color,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThemeFocus {
+    #[serde(deserialize_with = "deserialize_color")]
+    pub color: Color,
+    pub width: f32,
+}
+
+/// Colours, border widths and fonts shared by the widgets and windows,
+/// loaded from `data/themes/<id>.toml`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Theme {
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub border_width: f32,
+    pub font: ThemeFont,
+    pub window: ThemeWindow,
+    pub button: ThemeButton,
+    pub checkbox: ThemeCheckbox,
+    pub scrollbar: ThemeScrollbar,
+    pub textbox: ThemeTextbox,
+    pub slot: ThemeSlot,
+    pub tooltip: ThemeTooltip,
+    pub menu: ThemeMenu,
+    pub alert: ThemeAlert,
+    pub chat: ThemeChat,
+    pub hud: ThemeHud,
+    pub access: ThemeAccess,
+    pub focus: ThemeFocus,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        let mut theme: Theme = toml::from_str(DEFAULT_THEME_DATA)
+            .expect("built in theme must be valid");
+        theme.id = DEFAULT_THEME.into();
+        theme
+    }
+}
+
+impl Theme {
+    /// Loads the theme `id`, falling back to the theme compiled into the
+    /// client when the file is missing or broken.
+    pub fn load(id: &str) -> Self {
+        let path = format!("{THEME_FOLDER}/{id}.toml");
+
+        match fs::read_to_string(&path) {
+            Ok(data) => match toml::from_str::<Theme>(&data) {
+                Ok(mut theme) => {
+                    theme.id = id.into();
+                    return theme;
+                }
+                Err(e) => warn!("Failed to parse {path}, Err {e}"),
+            },
+            Err(e) => warn!("Failed to load {path}, Err {e}"),
+        }
+
+        Theme::default()
+    }
+
+    /// Ids of every theme file in the theme folder, the built in default
+    /// first.
+    pub fn list() -> Vec<String> {
+        let mut ids: Vec<String> = fs::read_dir(THEME_FOLDER)
+            .map(|dir| {
+                dir.flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.extension().is_some_and(|ext| ext == "toml")
+                    })
+                    .filter_map(|path| {
+                        path.file_stem()
+                            .and_then(|stem| stem.to_str())
+                            .map(String::from)
+                    })
+                    .filter(|id| id != DEFAULT_THEME)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        ids.sort();
+        ids.insert(0, DEFAULT_THEME.into());
+        ids
+    }
+
+    pub fn metrics(&self, scale: f32) -> Metrics {
+        Metrics::new(self.font.size, self.font.line_height).scale(scale)
+    }
+
+    /// Makes the theme font the default family of every text.
+    pub fn apply_font(&self, font_sys: &mut FontSystem) {
+        let db = font_sys.db_mut();
+        let system_family = SYSTEM_FONT_FAMILY
+            .get_or_init(|| db.family_name(&Family::SansSerif).to_string());
+
+        if let Some(file) = &self.font.file
+            && let Err(e) = db.load_font_file(file)
+        {
+            warn!("Failed to load font {file}, Err {e}");
+        }
+
+        match &self.font.family {
+            Some(family) => db.set_sans_serif_family(family.clone()),
+            None => db.set_sans_serif_family(system_family.clone()),
+        }
+    }
+}
+
+impl ThemeStates {
+    pub fn button_text(
+        &self,
+        text: impl Into<TString>,
+        pos: Vec2,
+        render_layer: usize,
+    ) -> ButtonContentText {
+        ButtonContentText {
+            text: text.into(),
+            pos,
+            color: self.normal,
+            render_layer,
+            hover_change: ButtonChangeType::ColorChange(self.hover),
+            click_change: ButtonChangeType::ColorChange(self.click),
+        }
+    }
+
+    pub fn checkbox_text(
+        &self,
+        text: impl Into<TString>,
+        offset_pos: Vec2,
+        label_size: Vec2,
+        render_layer: usize,
+    ) -> CheckboxText {
+        CheckboxText {
+            text: text.into(),
+            offset_pos,
+            render_layer,
+            label_size,
+            color: self.normal,
+            hover_change: CheckboxChangeType::ColorChange(self.hover),
+            click_change: CheckboxChangeType::ColorChange(self.click),
+        }
+    }
+}
+
+impl ThemeButton {
+    pub fn rect(&self) -> ButtonRect {
+        ButtonRect {
+            rect_color: self.rect.normal,
+            got_border: self.border,
+            border_color: self.border_color,
+            border_radius: self.radius,
+            hover_change: ButtonChangeType::ColorChange(self.rect.hover),
+            click_change: ButtonChangeType::ColorChange(self.rect.click),
+        }
+    }
+
+    pub fn text(
+        &self,
+        text: impl Into<TString>,
+        pos: Vec2,
+        render_layer: usize,
+    ) -> ButtonContentText {
+        self.text.button_text(text, pos, render_layer)
+    }
+}
+
+impl ThemeCheckbox {
+    pub fn rect(&self) -> CheckboxRect {
+        CheckboxRect {
+            rect_color: self.rect.normal,
+            got_border: true,
+            border_color: self.border_color,
+            border_radius: self.radius,
+            hover_change: CheckboxChangeType::ColorChange(self.rect.hover),
+            click_change: CheckboxChangeType::ColorChange(self.rect.click),
+        }
+    }
+
+    pub fn check(&self, pos: Vec2, size: Vec2) -> CheckRect {
+        CheckRect {
+            rect_color: self.check,
+            got_border: false,
+            border_color: self.border_color,
+            border_radius: self.radius,
+            pos,
+            size,
+        }
+    }
+
+    pub fn text(
+        &self,
+        text: impl Into<TString>,
+        offset_pos: Vec2,
+        label_size: Vec2,
+        render_layer: usize,
+    ) -> CheckboxText {
+        self.text
+            .checkbox_text(text, offset_pos, label_size, render_layer)
+    }
+}
+
+impl ThemeScrollbar {
+    pub fn bar(&self, render_layer: usize) -> ScrollbarRect {
+        ScrollbarRect {
+            color: self.bar.normal,
+            render_layer,
+            got_border: true,
+            border_color: self.border_color,
+            hover_color: self.bar.hover,
+            hold_color: self.bar.click,
+            radius: self.radius,
+        }
+    }
+
+    pub fn background_rect(&self, render_layer: usize) -> ScrollbarBackground {
+        ScrollbarBackground {
+            color: self.background,
+            render_layer,
+            got_border: false,
+            border_color: self.border_color,
+            radius: 0.0,
+        }
+    }
+}
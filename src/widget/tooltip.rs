@@ -1,5 +1,4 @@
 use ascending_graphics::*;
-use cosmic_text::{Attrs, Metrics};
 
 use crate::{SystemHolder, create_label, data_types::*};
 
@@ -17,17 +16,18 @@ pub struct Tooltip {
 impl Tooltip {
     pub fn new(systems: &mut SystemHolder) -> Self {
         let visible = false;
+        let style = systems.theme.tooltip;
 
         let mut window_rect = Rect::new(
             &mut systems.renderer,
             Vec3::new(0.0, 0.0, ORDER_TOOLTIP),
             Vec2::new(24.0, 24.0),
-            Color::rgba(130, 130, 130, 255),
+            style.color,
             0,
         );
         window_rect
-            .set_border_width(1.0)
-            .set_border_color(Color::rgba(40, 40, 40, 255));
+            .set_border_width(systems.theme.border_width)
+            .set_border_color(style.border_color);
         let window = systems.gfx.add_rect(
             window_rect,
             4,
@@ -41,7 +41,7 @@ impl Tooltip {
             Vec3::new(2.0, 2.0, ORDER_TOOLTIP_TEXT),
             Vec2::new(20.0, 20.0),
             Some(Bounds::new(2.0, 2.0, 22.0, 22.0)),
-            Color::rgba(255, 255, 255, 255),
+            style.text,
         );
         text_label.set_buffer_size(Some(300.0), Some(systems.size.height));
         let text = systems.gfx.add_text(
@@ -64,6 +64,25 @@ impl Tooltip {
         }
     }
 
+    /// Applies `systems.scale` and the theme after the interface was
+    /// rebuilt.
+    pub fn refresh_style(&self, systems: &mut SystemHolder) {
+        let style = systems.theme.tooltip;
+
+        systems.gfx.set_color(&self.window, style.color);
+        systems
+            .gfx
+            .set_border_color(&self.window, style.border_color);
+        systems
+            .gfx
+            .set_border_width(&self.window, systems.theme.border_width);
+        systems.gfx.set_color(&self.text, style.text);
+        systems.gfx.set_text_metrics(
+            &self.text,
+            systems.theme.metrics(systems.scale as f32),
+        );
+    }
+
     pub fn init_tooltip(
         &mut self,
         systems: &mut SystemHolder,
@@ -0,0 +1,127 @@
+mod common;
+
+use ascending_input::Key;
+use client::{
+    DeathType,
+    content::{
+        KeyAction,
+        game_content::{CombatEvent, CombatLog, next_level_exp},
+    },
+    systems::KeyBindings,
+};
+use common::key;
+
+fn events(log: &CombatLog) -> Vec<CombatEvent> {
+    log.entries().map(|entry| entry.event.clone()).collect()
+}
+
+#[test]
+fn damage_is_credited_to_the_attacked_target() {
+    let (me, slime, other) = (key(1), key(2), key(3));
+    let mut log = CombatLog::new();
+
+    log.attack(slime, "Slime".into(), 10.0);
+    log.damage(other, false, 7, true, 10.2);
+    log.damage(slime, false, 12, true, 10.3);
+    log.damage(me, true, 4, true, 10.4);
+    log.damage(me, true, 9, false, 10.5);
+
+    assert_eq!(
+        events(&log),
+        [
+            CombatEvent::Dealt {
+                target: "Slime".into(),
+                amount: 12
+            },
+            CombatEvent::Taken { amount: 4 },
+        ]
+    );
+    assert_eq!((log.damage_dealt, log.damage_taken), (12, 4));
+    assert_eq!(log.target_damage().collect::<Vec<_>>(), [("Slime", 12)]);
+}
+
+#[test]
+fn misses_and_kills() {
+    let slime = key(2);
+    let mut log = CombatLog::new();
+
+    log.attack(slime, "Slime".into(), 1.0);
+    log.damage(slime, false, 0, true, 1.1);
+    log.attack(slime, "Slime".into(), 2.0);
+    log.process(4.0);
+    log.attack(slime, "Slime".into(), 5.0);
+    log.damage(slime, false, 20, true, 5.2);
+    log.death(slime, DeathType::Dead, 6.0);
+
+    assert_eq!((log.misses, log.kills), (2, 1));
+    assert!(matches!(
+        events(&log).last(),
+        Some(CombatEvent::Kill { target }) if target == "Slime"
+    ));
+
+    // A target that dies long after our last hit is not our kill.
+    let bat = key(4);
+
+    log.attack(bat, "Bat".into(), 10.0);
+    log.damage(bat, false, 5, true, 10.1);
+    log.process(30.0);
+    log.death(bat, DeathType::Dead, 30.0);
+
+    assert_eq!(log.kills, 1);
+}
+
+#[test]
+fn experience_across_level_ups() {
+    let mut log = CombatLog::new();
+
+    log.level(1, 50, 0.0);
+    assert_eq!(log.experience, 0);
+
+    log.level(1, 80, 10.0);
+    log.level(3, 20, 20.0);
+    log.level(3, 10, 30.0);
+
+    let gained = 30 + (next_level_exp(1) - 80) + next_level_exp(2) + 20;
+
+    assert_eq!(log.experience, gained);
+    assert_eq!(
+        events(&log).last(),
+        Some(&CombatEvent::LevelUp { level: 3 })
+    );
+
+    log.process(1800.0);
+    assert_eq!(log.xp_per_hour(), gained * 2);
+
+    log.reset();
+    assert_eq!((log.experience, log.entries().count()), (0, 0));
+    log.level(3, 60, 1800.0);
+    assert_eq!(log.experience, 50);
+}
+
+#[test]
+fn dps_counts_fight_time_only() {
+    let slime = key(2);
+    let mut log = CombatLog::new();
+
+    assert_eq!(log.dps(), 0.0);
+
+    for (at, amount) in [(0.0, 10), (1.0, 10), (2.0, 10), (100.0, 10)] {
+        log.attack(slime, "Slime".into(), at);
+        log.damage(slime, false, amount, true, at + 0.1);
+    }
+
+    // Two seconds of the first fight plus one second each fight starts with.
+    assert!((log.dps() - 40.0 / 4.0).abs() < 0.01);
+}
+
+#[test]
+fn toggle_key_can_be_remapped() {
+    let mut bindings = KeyBindings::default();
+    let toggle = Some(KeyAction::ToggleCombatLog);
+
+    assert_eq!(KeyAction::from_key(&bindings, &Key::Character('L')), toggle);
+
+    bindings.toggle_combat_log = 'k';
+    assert_eq!(KeyAction::from_key(&bindings, &Key::Character('l')), None);
+    assert_eq!(KeyAction::from_key(&bindings, &Key::Character('k')), toggle);
+}
@@ -0,0 +1,24 @@
+// Each test crate only uses some of the fixtures.
+#![allow(dead_code)]
+
+use client::{GlobalKey, MapPosition, Position};
+use slotmap::KeyData;
+
+/// Entity key `id` of the first slotmap generation.
+pub fn key(id: u64) -> GlobalKey {
+    KeyData::from_ffi((1 << 32) | id).into()
+}
+
+/// Tile on the map at 0, 0.
+pub fn pos(x: i32, y: i32) -> Position {
+    pos_on_map(x, y, 0)
+}
+
+/// Tile on the map `map_x` maps to the right of 0, 0.
+pub fn pos_on_map(x: i32, y: i32, map_x: i32) -> Position {
+    Position {
+        x,
+        y,
+        map: MapPosition::new(map_x, 0, 0),
+    }
+}
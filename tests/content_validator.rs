@@ -0,0 +1,217 @@
+use client::{
+    MAX_SHOP_ITEM,
+    database::{
+        ContentIssue, ContentKind, ContentLimits, ItemData, NpcData, ShopData,
+        content_file_id, validate_database, validate_item, validate_npc,
+        validate_shop,
+    },
+};
+use std::path::Path;
+
+const LIMITS: ContentLimits = ContentLimits {
+    item_sprites: 10,
+    npc_sprites: 5,
+    sounds: 3,
+};
+
+fn messages(issues: Vec<ContentIssue>) -> Vec<String> {
+    issues.into_iter().map(|issue| issue.message).collect()
+}
+
+#[test]
+fn valid_content_has_no_issues() {
+    let shop = ShopData {
+        max_item: 1,
+        ..Default::default()
+    };
+
+    assert!(validate_item(0, &ItemData::default(), &LIMITS).is_empty());
+    assert!(validate_npc(0, &NpcData::default(), |_| true, &LIMITS).is_empty());
+    assert!(validate_shop(0, &shop, |_| true).is_empty());
+}
+
+#[test]
+fn item_sprite_must_exist() {
+    let item = ItemData {
+        sprite: 10,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        messages(validate_item(4, &item, &LIMITS)),
+        ["unknown sprite 10"]
+    );
+}
+
+#[test]
+fn item_sound_id_must_exist() {
+    let item = ItemData {
+        soundid: 3,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        messages(validate_item(0, &item, &LIMITS)),
+        ["unknown soundid 3"]
+    );
+}
+
+#[test]
+fn item_sound_file_must_exist() {
+    let item = ItemData {
+        sound_index: Some("missing_sound.ogg".to_string()),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        messages(validate_item(0, &item, &LIMITS)),
+        ["missing sound file missing_sound.ogg"]
+    );
+}
+
+#[test]
+fn stackable_item_needs_a_stack_limit() {
+    let mut item = ItemData {
+        stackable: true,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        messages(validate_item(0, &item, &LIMITS)),
+        ["stackable with a stack limit of 0"]
+    );
+
+    item.stacklimit = 99;
+    assert!(validate_item(0, &item, &LIMITS).is_empty());
+}
+
+#[test]
+fn npc_sprite_must_exist() {
+    for sprite in [-1, 5] {
+        let npc = NpcData {
+            sprite,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            messages(validate_npc(0, &npc, |_| true, &LIMITS)),
+            [format!("unknown sprite {sprite}")]
+        );
+    }
+}
+
+#[test]
+fn npc_damage_range_must_be_ordered() {
+    let npc = NpcData {
+        mindamage: 8,
+        maxdamage: 4,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        messages(validate_npc(0, &npc, |_| true, &LIMITS)),
+        ["mindamage 8 is above maxdamage 4"]
+    );
+}
+
+#[test]
+fn npc_drops_must_point_at_items() {
+    let mut npc = NpcData::default();
+    npc.drops[0].items[0].item = 2;
+    npc.drops[0].items[0].amount = 1;
+    npc.drops[1].items[0].item = 9;
+
+    assert_eq!(
+        messages(validate_npc(3, &npc, |index| index < 2, &LIMITS)),
+        ["drop points at missing item 2"]
+    );
+}
+
+#[test]
+fn shop_max_item_is_capped() {
+    let shop = ShopData {
+        max_item: MAX_SHOP_ITEM as u16 + 1,
+        ..Default::default()
+    };
+
+    let issues = messages(validate_shop(0, &shop, |_| true));
+    assert_eq!(
+        issues[0],
+        format!("max_item {} is above {MAX_SHOP_ITEM}", MAX_SHOP_ITEM + 1)
+    );
+}
+
+#[test]
+fn shop_slots_must_point_at_items() {
+    let mut shop = ShopData {
+        max_item: 2,
+        ..Default::default()
+    };
+    shop.item[1].index = 5;
+    shop.item[2].index = 6;
+
+    assert_eq!(
+        messages(validate_shop(0, &shop, |index| index < 5)),
+        ["slot 1 points at missing item 5"]
+    );
+}
+
+#[test]
+fn database_issues_name_the_entry() {
+    let items = [
+        Some(ItemData::default()),
+        Some(ItemData {
+            sprite: 99,
+            ..Default::default()
+        }),
+    ];
+    let npcs = [
+        None,
+        Some(NpcData {
+            sprite: 7,
+            ..Default::default()
+        }),
+    ];
+
+    let issues = validate_database(&items, &npcs, &[], &LIMITS);
+    let names: Vec<String> =
+        issues.iter().map(|issue| issue.to_string()).collect();
+
+    assert_eq!(
+        names,
+        ["Item 1: unknown sprite 99", "Npc 1: unknown sprite 7"]
+    );
+}
+
+#[test]
+fn references_to_missing_files_are_reported() {
+    let items = [Some(ItemData::default()), None, Some(ItemData::default())];
+    let mut shop = ShopData {
+        max_item: 3,
+        ..Default::default()
+    };
+    shop.item[0].index = 1;
+    shop.item[1].index = 2;
+
+    let issues = validate_database(&items, &[], &[Some(shop)], &LIMITS);
+
+    assert_eq!(messages(issues), ["slot 0 points at missing item 1"]);
+}
+
+#[test]
+fn watched_paths_map_to_content_files() {
+    assert_eq!(
+        content_file_id(Path::new("./data/items/12.bin")),
+        Some((ContentKind::Item, 12))
+    );
+    assert_eq!(
+        content_file_id(Path::new("/game/data/shops/0.bin")),
+        Some((ContentKind::Shop, 0))
+    );
+    assert_eq!(content_file_id(Path::new("./data/npcs/3.bin.tmp")), None);
+    assert_eq!(content_file_id(Path::new("./data/npcs/name.bin")), None);
+    assert_eq!(content_file_id(Path::new("./data/maps/1.bin")), None);
+
+    let too_high = format!("./data/npcs/{}.bin", ContentKind::Npc.max());
+    assert_eq!(content_file_id(Path::new(&too_high)), None);
+}
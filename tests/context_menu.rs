@@ -0,0 +1,97 @@
+mod common;
+
+use client::{
+    MapPosition, Position,
+    content::game_content::{
+        MenuAction, MenuTarget, facing_dir, step_towards, tile_distance,
+    },
+    systems::Config,
+};
+use common::{key, pos};
+
+const DOWN: u8 = 0;
+const RIGHT: u8 = 1;
+const UP: u8 = 2;
+const LEFT: u8 = 3;
+
+#[test]
+fn options_depend_on_the_target() {
+    assert_eq!(
+        MenuAction::for_target(MenuTarget::Player(key(1))),
+        [
+            MenuAction::Trade,
+            MenuAction::InviteParty,
+            MenuAction::Whisper,
+            MenuAction::Inspect,
+            MenuAction::Ignore,
+            MenuAction::CopyName,
+        ]
+    );
+    assert_eq!(
+        MenuAction::for_target(MenuTarget::Npc(key(2))),
+        [MenuAction::Examine]
+    );
+    assert_eq!(
+        MenuAction::for_target(MenuTarget::MapItem(key(3))),
+        [MenuAction::PickUp]
+    );
+
+    assert_eq!(MenuAction::Ignore.text_key(false), "menu.ignore");
+    assert_eq!(MenuAction::Ignore.text_key(true), "menu.unignore");
+}
+
+#[test]
+fn ignore_toggles_without_case() {
+    let mut config = Config::default();
+
+    assert!(config.toggle_ignored("Spammer"));
+    assert!(config.is_ignored("spammer"));
+    assert!(!config.is_ignored("Friend"));
+
+    assert!(!config.toggle_ignored("SPAMMER"));
+    assert!(!config.is_ignored("Spammer"));
+    assert!(config.ignored_players.is_empty());
+}
+
+#[test]
+fn walking_to_an_item_steps_along_the_longer_axis() {
+    assert_eq!(step_towards(pos(5, 5), pos(9, 6), |_, _| true), Some(RIGHT));
+    assert_eq!(step_towards(pos(5, 5), pos(4, 1), |_, _| true), Some(DOWN));
+
+    // A wall on the longer axis sends the player around it.
+    assert_eq!(
+        step_towards(pos(5, 5), pos(9, 6), |_, dir| dir != RIGHT),
+        Some(UP)
+    );
+    assert_eq!(
+        step_towards(pos(5, 5), pos(9, 5), |_, dir| dir != RIGHT),
+        None
+    );
+    assert_eq!(step_towards(pos(5, 5), pos(5, 5), |_, _| true), None);
+
+    // Items on the next map are found across the edge.
+    let next_map = Position {
+        x: 0,
+        y: 5,
+        map: MapPosition {
+            x: 1,
+            ..MapPosition::default()
+        },
+    };
+    assert_eq!(tile_distance(pos(31, 5), next_map), Some(1));
+    assert_eq!(facing_dir(pos(31, 5), next_map), Some(RIGHT));
+}
+
+#[test]
+fn adjacent_items_are_faced() {
+    assert_eq!(facing_dir(pos(5, 5), pos(5, 4)), Some(DOWN));
+    assert_eq!(facing_dir(pos(5, 5), pos(4, 5)), Some(LEFT));
+    assert_eq!(facing_dir(pos(5, 5), pos(5, 5)), None);
+    assert_eq!(facing_dir(pos(5, 5), pos(6, 6)), None);
+
+    let other_group = Position {
+        map: MapPosition::new(0, 0, 1),
+        ..pos(5, 5)
+    };
+    assert_eq!(tile_distance(pos(5, 5), other_group), None);
+}
@@ -0,0 +1,86 @@
+use client::database::{DataFormat, DataKind, convert_data, roundtrip_data};
+use std::path::PathBuf;
+
+fn bin_files(kind: DataKind) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(kind.folder())
+        .expect("data folder should exist")
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            DataFormat::from_extension(path) == Some(DataFormat::Bin)
+        })
+        .collect();
+
+    files.sort();
+    files
+}
+
+fn assert_roundtrip(kind: DataKind, format: DataFormat) {
+    let files = bin_files(kind);
+    assert!(!files.is_empty(), "no {kind} files to test");
+
+    for path in files {
+        let data = std::fs::read(&path).unwrap();
+
+        match roundtrip_data(kind, &data, format) {
+            Ok(same) => assert!(
+                same,
+                "{} changed after the {} round trip",
+                path.display(),
+                format.extension()
+            ),
+            Err(e) => panic!("{} failed to convert: {e}", path.display()),
+        }
+    }
+}
+
+#[test]
+fn items_roundtrip_json() {
+    assert_roundtrip(DataKind::Item, DataFormat::Json);
+}
+
+#[test]
+fn items_roundtrip_toml() {
+    assert_roundtrip(DataKind::Item, DataFormat::Toml);
+}
+
+#[test]
+fn npcs_roundtrip_json() {
+    assert_roundtrip(DataKind::Npc, DataFormat::Json);
+}
+
+#[test]
+fn npcs_roundtrip_toml() {
+    assert_roundtrip(DataKind::Npc, DataFormat::Toml);
+}
+
+#[test]
+fn shops_roundtrip_json() {
+    assert_roundtrip(DataKind::Shop, DataFormat::Json);
+}
+
+#[test]
+fn shops_roundtrip_toml() {
+    assert_roundtrip(DataKind::Shop, DataFormat::Toml);
+}
+
+#[test]
+fn maps_roundtrip_json() {
+    assert_roundtrip(DataKind::Map, DataFormat::Json);
+}
+
+#[test]
+fn maps_roundtrip_toml() {
+    assert_roundtrip(DataKind::Map, DataFormat::Toml);
+}
+
+#[test]
+fn rejects_same_format() {
+    let data = std::fs::read(format!("{}/0.bin", DataKind::Item.folder()))
+        .expect("item 0 should exist");
+
+    assert!(
+        convert_data(DataKind::Item, &data, DataFormat::Bin, DataFormat::Bin)
+            .is_err()
+    );
+}
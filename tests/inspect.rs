@@ -0,0 +1,28 @@
+use client::{
+    UserAccess,
+    content::game_content::{PlayerPvP, access_color, pvp_status_key},
+    widget::Theme,
+};
+
+#[test]
+fn staff_names_stand_out() {
+    for id in Theme::list() {
+        let theme = Theme::load(&id).access;
+        let color = |access| access_color(&theme, access);
+        let player = color(UserAccess::None);
+
+        assert_ne!(color(UserAccess::Monitor), player, "{id}");
+        assert_ne!(color(UserAccess::Admin), player, "{id}");
+        assert_ne!(color(UserAccess::Admin), color(UserAccess::Monitor));
+    }
+}
+
+#[test]
+fn player_killers_are_shown_before_pvp() {
+    let status = |pk, pvpon| pvp_status_key(PlayerPvP { pk, pvpon });
+
+    assert_eq!(status(true, true), "inspect.pk");
+    assert_eq!(status(true, false), "inspect.pk");
+    assert_eq!(status(false, true), "inspect.pvp_on");
+    assert_eq!(status(false, false), "inspect.pvp_off");
+}
@@ -0,0 +1,194 @@
+use std::{collections::HashSet, path::Path};
+
+use client::{
+    systems::{
+        DEFAULT_LANGUAGE, LANG_FOLDER, Locale, LocaleEntry, PluralForm,
+        PluralRule, fill_args,
+    },
+    widget::UI_LAYOUT_FOLDER,
+};
+use regex::Regex;
+
+fn default_keys() -> HashSet<String> {
+    Locale::default().keys().map(String::from).collect()
+}
+
+fn source_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap().flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            source_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+fn layout_keys(value: &toml::Value, keys: &mut Vec<String>) {
+    match value {
+        toml::Value::Table(table) => {
+            for (name, value) in table {
+                match (name.as_str(), value) {
+                    ("text" | "tooltip", toml::Value::String(key)) => {
+                        keys.push(key.clone())
+                    }
+                    ("options", toml::Value::Array(options)) => keys.extend(
+                        options
+                            .iter()
+                            .filter_map(|option| option.as_str())
+                            .map(String::from),
+                    ),
+                    _ => layout_keys(value, keys),
+                }
+            }
+        }
+        toml::Value::Array(array) => {
+            array.iter().for_each(|value| layout_keys(value, keys))
+        }
+        _ => {}
+    }
+}
+
+#[test]
+fn languages_parse_and_cover_default() {
+    let default = default_keys();
+    let mut count = 0;
+
+    for entry in std::fs::read_dir(LANG_FOLDER).unwrap().flatten() {
+        let path = entry.path();
+
+        if path.extension().is_none_or(|ext| ext != "toml") {
+            continue;
+        }
+
+        let id = path.file_stem().unwrap().to_str().unwrap();
+        let data = std::fs::read_to_string(&path).unwrap();
+
+        let locale = match Locale::parse(id, &data) {
+            Ok(locale) => locale,
+            Err(e) => panic!("{} failed to parse: {e}", path.display()),
+        };
+
+        let keys: HashSet<String> = locale.keys().map(String::from).collect();
+        let mut missing: Vec<&String> = default.difference(&keys).collect();
+        let mut unknown: Vec<&String> = keys.difference(&default).collect();
+
+        missing.sort();
+        unknown.sort();
+
+        assert!(missing.is_empty(), "{} lacks {missing:?}", path.display());
+        assert!(
+            unknown.is_empty(),
+            "{} has keys English lacks {unknown:?}",
+            path.display()
+        );
+
+        count += 1;
+    }
+
+    assert!(count > 0, "no languages found in {LANG_FOLDER}");
+}
+
+#[test]
+fn layout_keys_exist() {
+    let default = default_keys();
+
+    for entry in std::fs::read_dir(UI_LAYOUT_FOLDER).unwrap().flatten() {
+        let path = entry.path();
+
+        if path.extension().is_none_or(|ext| ext != "toml") {
+            continue;
+        }
+
+        let data = std::fs::read_to_string(&path).unwrap();
+        let value: toml::Value = toml::from_str(&data).unwrap();
+        let mut keys = Vec::new();
+
+        layout_keys(&value, &mut keys);
+
+        for key in keys {
+            assert!(
+                default.contains(&key),
+                "{} uses unknown key {key}",
+                path.display()
+            );
+        }
+    }
+}
+
+#[test]
+fn source_keys_exist() {
+    let default = default_keys();
+    let groups: HashSet<&str> = default
+        .iter()
+        .filter_map(|key| key.split('.').next())
+        .collect();
+    let key_regex = Regex::new(r#""([a-z_]+(?:\.[a-z0-9_]+)+)""#).unwrap();
+    let mut files = Vec::new();
+
+    source_files(Path::new("src"), &mut files);
+
+    for path in files {
+        let data = std::fs::read_to_string(&path).unwrap();
+
+        for capture in key_regex.captures_iter(&data) {
+            let key = &capture[1];
+            let group = key.split('.').next().unwrap();
+
+            if groups.contains(group) {
+                assert!(
+                    default.contains(key),
+                    "{} uses unknown key {key}",
+                    path.display()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn missing_language_falls_back_to_default() {
+    let locale = Locale::load("missing_language");
+
+    assert_eq!(locale.id, DEFAULT_LANGUAGE);
+    assert_eq!(locale.get("alert.okay"), "Okay");
+    assert_eq!(locale.get("not.a.key"), "not.a.key");
+    assert_eq!(Locale::list().first().map(String::as_str), Some("en"));
+}
+
+#[test]
+fn plurals_and_arguments() {
+    assert_eq!(PluralRule::OneOther.form(1), PluralForm::One);
+    assert_eq!(PluralRule::OneOther.form(0), PluralForm::Other);
+    assert_eq!(PluralRule::ZeroOneOther.form(0), PluralForm::One);
+    assert_eq!(PluralRule::EastSlavic.form(21), PluralForm::One);
+    assert_eq!(PluralRule::EastSlavic.form(3), PluralForm::Few);
+    assert_eq!(PluralRule::EastSlavic.form(12), PluralForm::Many);
+    assert_eq!(PluralRule::None.form(1), PluralForm::Other);
+
+    let args: [(&str, &dyn std::fmt::Display); 2] = [("a", &1), ("b", &"two")];
+
+    assert_eq!(fill_args("{a} and {b} {c}", &args), "1 and two {c}");
+
+    let locale = Locale::default();
+
+    assert!(matches!(
+        locale.entry("amount.withdraw"),
+        Some(LocaleEntry::Plural(_))
+    ));
+    assert_eq!(
+        locale.plural("amount.withdraw", 1, &[]).as_ref(),
+        "Enter the amount to withdraw, 1 is stored"
+    );
+    assert_eq!(
+        locale.plural("amount.withdraw", 5, &[]).as_ref(),
+        "Enter the amount to withdraw, 5 are stored"
+    );
+    assert_eq!(
+        locale
+            .format("trade.request_header", &[("name", &"Ann")])
+            .as_ref(),
+        "Ann would like to trade with you"
+    );
+}
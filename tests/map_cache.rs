@@ -0,0 +1,84 @@
+use client::{
+    MapPosition,
+    database::{
+        MAP_DOWNLOAD_RETRIES, MapAssetCache, MapCacheEntry, MapData,
+        map_cache_key, map_file_name, map_hash, verify_map_bytes,
+    },
+};
+use speedy::Writable;
+
+fn map_bytes(mappos: MapPosition) -> Vec<u8> {
+    MapData::new(mappos.x, mappos.y, mappos.group as u64)
+        .write_to_vec()
+        .unwrap()
+}
+
+#[test]
+fn cache_keys_and_file_names_follow_the_position() {
+    let mappos = MapPosition::new(-2, 5, 1);
+
+    assert_eq!(map_cache_key(mappos), "-2_5_1");
+    assert_eq!(map_file_name(mappos), "./data/maps/-2_5_1.bin");
+}
+
+#[test]
+fn index_entries_are_kept_per_map() {
+    let mut cache = MapAssetCache::default();
+    let entry = MapCacheEntry {
+        revision: 4,
+        hash: 99,
+    };
+
+    cache
+        .index
+        .maps
+        .insert(map_cache_key(MapPosition::new(1, 0, 0)), entry);
+
+    assert_eq!(cache.get_entry(MapPosition::new(1, 0, 0)), Some(entry));
+    assert_eq!(cache.get_entry(MapPosition::new(0, 1, 0)), None);
+    assert!(!cache.is_current(MapPosition::new(0, 1, 0), 4, 99));
+
+    let index = toml::to_string(&cache.index).unwrap();
+    let loaded: client::database::MapCacheIndex =
+        toml::from_str(&index).unwrap();
+    assert_eq!(loaded.maps, cache.index.maps);
+}
+
+#[test]
+fn good_map_bytes_pass_verification() {
+    let mappos = MapPosition::new(3, -1, 0);
+    let bytes = map_bytes(mappos);
+
+    let mapdata = verify_map_bytes(mappos, &bytes, map_hash(&bytes)).unwrap();
+    assert_eq!(mapdata.position, mappos);
+}
+
+#[test]
+fn wrong_hash_or_position_is_rejected() {
+    let mappos = MapPosition::new(3, -1, 0);
+    let bytes = map_bytes(mappos);
+    let hash = map_hash(&bytes);
+
+    assert!(verify_map_bytes(mappos, &bytes, hash ^ 1).is_err());
+    assert!(verify_map_bytes(MapPosition::new(0, 0, 0), &bytes, hash).is_err());
+
+    let truncated = &bytes[..bytes.len() / 2];
+    assert!(verify_map_bytes(mappos, truncated, map_hash(truncated)).is_err());
+}
+
+#[test]
+fn failed_downloads_are_retried_until_login() {
+    let mut cache = MapAssetCache::default();
+    let mappos = MapPosition::new(7, 7, 0);
+
+    for _ in 0..MAP_DOWNLOAD_RETRIES {
+        assert!(cache.download_failed(mappos));
+    }
+    assert!(!cache.download_failed(mappos));
+
+    cache.reset_downloads();
+    assert!(cache.download_failed(mappos));
+
+    cache.download_succeeded(mappos);
+    assert!(cache.download_failed(mappos));
+}
@@ -0,0 +1,133 @@
+mod common;
+
+use client::{
+    MapPosition,
+    content::game_content::{
+        MapAttributes, MapDirBlock, MovePredictor, is_dir_blocked,
+        is_tile_blocked, next_pos,
+    },
+    database::map::MapAttribute,
+};
+use common::pos;
+
+const DOWN: u8 = 0;
+const RIGHT: u8 = 1;
+const UP: u8 = 2;
+const LEFT: u8 = 3;
+
+#[test]
+fn steps_cross_map_edges() {
+    let map = MapPosition::default();
+
+    assert_eq!(next_pos(pos(5, 5), UP), pos(5, 6));
+    assert_eq!(next_pos(pos(5, 5), RIGHT), pos(6, 5));
+
+    let below = next_pos(pos(5, 0), DOWN);
+    assert_eq!((below.x, below.y), (5, 31));
+    assert_eq!(
+        below.map,
+        MapPosition {
+            y: map.y - 1,
+            ..map
+        }
+    );
+
+    let left = next_pos(pos(0, 7), LEFT);
+    assert_eq!((left.x, left.y), (31, 7));
+    assert_eq!(
+        left.map,
+        MapPosition {
+            x: map.x - 1,
+            ..map
+        }
+    );
+}
+
+#[test]
+fn map_collision() {
+    let mut dir_block = MapDirBlock { dir: vec![0; 1024] };
+    let mut attributes = MapAttributes {
+        attribute: vec![MapAttribute::Walkable; 1024],
+    };
+
+    dir_block.dir[5 + 5 * 32] = 1 << UP;
+    attributes.attribute[3 + 4 * 32] = MapAttribute::Blocked;
+    attributes.attribute[4 + 4 * 32] = MapAttribute::NpcBlocked;
+
+    assert!(is_dir_blocked(&dir_block, pos(5, 5), UP));
+    assert!(!is_dir_blocked(&dir_block, pos(5, 5), DOWN));
+    assert!(!is_dir_blocked(&dir_block, pos(5, 6), UP));
+    assert!(is_tile_blocked(&attributes, pos(3, 4)));
+    assert!(!is_tile_blocked(&attributes, pos(4, 4)));
+}
+
+#[test]
+fn confirmed_steps_need_no_correction() {
+    let mut predictor = MovePredictor::new();
+
+    let first = predictor.predict(pos(5, 5), RIGHT);
+    let second = predictor.predict(first.pos, RIGHT);
+
+    assert_eq!(second.seq, first.seq + 1);
+    assert_eq!(predictor.confirmed(), Some(pos(5, 5)));
+    assert_eq!(predictor.predicted(), Some(pos(7, 5)));
+
+    assert_eq!(
+        predictor.acknowledge(first.seq, pos(6, 5), |_, _| true),
+        None
+    );
+    assert_eq!(predictor.confirmed(), Some(pos(6, 5)));
+    assert_eq!(predictor.pending().count(), 1);
+
+    // A late answer for a step already confirmed changes nothing.
+    assert_eq!(
+        predictor.acknowledge(first.seq, pos(6, 5), |_, _| true),
+        None
+    );
+    assert_eq!(predictor.predicted(), Some(pos(7, 5)));
+}
+
+#[test]
+fn rejected_steps_are_replayed() {
+    let mut predictor = MovePredictor::new();
+
+    let first = predictor.predict(pos(5, 5), RIGHT);
+    let second = predictor.predict(first.pos, UP);
+    let third = predictor.predict(second.pos, RIGHT);
+
+    assert_eq!(predictor.predicted(), Some(pos(7, 6)));
+
+    // The server refused the first step, the rest is replayed from (5, 5)
+    // and the last step now runs into a wall.
+    let corrected = predictor.acknowledge(first.seq, pos(5, 5), |at, dir| {
+        !(at == pos(5, 6) && dir == RIGHT)
+    });
+
+    assert_eq!(corrected, Some(pos(5, 6)));
+    assert_eq!(predictor.confirmed(), Some(pos(5, 5)));
+    assert_eq!(
+        predictor
+            .pending()
+            .map(|step| (step.seq, step.pos))
+            .collect::<Vec<_>>(),
+        [(second.seq, pos(5, 6))]
+    );
+    assert!(third.seq > second.seq);
+}
+
+#[test]
+fn corrections_slide_back() {
+    let mut predictor = MovePredictor::new();
+
+    predictor.start_correction(pos(6, 5), Default::default(), pos(5, 5));
+    assert_eq!(predictor.correction().x, 20.0);
+
+    assert!(predictor.update(0.05));
+    assert!(predictor.correction().x > 0.0 && predictor.correction().x < 20.0);
+
+    while predictor.update(0.05) {}
+    assert_eq!(predictor.correction(), Default::default());
+
+    predictor.start_correction(pos(20, 5), Default::default(), pos(5, 5));
+    assert_eq!(predictor.correction(), Default::default());
+}
@@ -0,0 +1,74 @@
+use client::{
+    AIBehavior, MapPosition, NpcMode,
+    content::game_content::{Disposition, level_gap_color, range_rows},
+};
+
+#[test]
+fn names_warm_up_with_the_level_gap() {
+    let colors: Vec<_> = [-12, -5, 0, 4, 8]
+        .into_iter()
+        .map(level_gap_color)
+        .collect();
+
+    for (index, color) in colors.iter().enumerate() {
+        assert!(
+            !colors[index + 1..].contains(color),
+            "level bands share a colour"
+        );
+    }
+
+    assert_eq!(level_gap_color(-2), level_gap_color(2));
+    assert_eq!(level_gap_color(-3), level_gap_color(-9));
+    assert_eq!(level_gap_color(3), level_gap_color(5));
+    assert_eq!(level_gap_color(6), level_gap_color(100));
+    assert_eq!(level_gap_color(-10), level_gap_color(i32::MIN));
+}
+
+#[test]
+fn behaviours_fall_into_three_classes() {
+    let of = |behaviour| Disposition::of(behaviour, NpcMode::Normal);
+
+    assert_eq!(of(AIBehavior::Friendly), Disposition::Friendly);
+    assert_eq!(of(AIBehavior::Healer), Disposition::Friendly);
+    assert_eq!(of(AIBehavior::Reactive), Disposition::Reactive);
+    assert_eq!(of(AIBehavior::HelpReactive), Disposition::Reactive);
+    assert_eq!(of(AIBehavior::ReactiveHealer), Disposition::Reactive);
+    assert_eq!(of(AIBehavior::Agressive), Disposition::Aggressive);
+    assert_eq!(of(AIBehavior::AgressiveHealer), Disposition::Aggressive);
+
+    assert_eq!(
+        Disposition::of(AIBehavior::Agressive, NpcMode::Boss),
+        Disposition::Aggressive
+    );
+    assert_eq!(
+        Disposition::of(AIBehavior::Agressive, NpcMode::Pet),
+        Disposition::Friendly
+    );
+    assert_eq!(
+        Disposition::of(AIBehavior::Agressive, NpcMode::Summon),
+        Disposition::Friendly
+    );
+}
+
+#[test]
+fn range_rows_cover_the_tiles_in_sight() {
+    for radius in 0..8 {
+        let rows: Vec<_> = range_rows(radius).collect();
+        assert_eq!(rows.len(), (radius * 2 + 1) as usize);
+
+        for dy in -radius - 2..=radius + 2 {
+            for dx in -radius - 2..=radius + 2 {
+                let in_sight = MapPosition::new(dx, dy, 0)
+                    .checkdistance(MapPosition::default())
+                    <= radius;
+                let in_rows = rows
+                    .iter()
+                    .any(|(row, half)| *row == dy && dx.abs() <= *half);
+
+                assert_eq!(in_rows, in_sight, "radius {radius} at {dx},{dy}");
+            }
+        }
+    }
+
+    assert_eq!(range_rows(-1).count(), 0);
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use client::{
+    MapPosition, MessageChannel, VITALS_MAX,
+    content::game_content::{
+        MemberLocation, PARTY_NEARBY_RANGE, Party, PartyMember,
+        can_channel_show, member_location,
+    },
+};
+use common::{key, pos_on_map};
+
+fn member(id: u64, name: &str) -> PartyMember {
+    PartyMember {
+        entity: key(id),
+        name: name.to_string(),
+        level: 3,
+        vital: [20; VITALS_MAX],
+        vitalmax: [25; VITALS_MAX],
+        pos: pos_on_map(4, 6, 1),
+    }
+}
+
+#[test]
+fn members_are_shown_by_distance_or_map() {
+    assert_eq!(
+        member_location(Some(pos_on_map(5, 5, 0)), pos_on_map(5, 9, 0)),
+        MemberLocation::Nearby(4)
+    );
+    assert_eq!(
+        member_location(
+            Some(pos_on_map(5, 5, 0)),
+            pos_on_map(5, 5 + PARTY_NEARBY_RANGE, 0)
+        ),
+        MemberLocation::Nearby(PARTY_NEARBY_RANGE)
+    );
+    assert_eq!(
+        member_location(Some(pos_on_map(0, 0, 0)), pos_on_map(0, 10, 3)),
+        MemberLocation::Away(MapPosition::new(3, 0, 0))
+    );
+    assert_eq!(
+        member_location(None, pos_on_map(5, 5, 2)),
+        MemberLocation::Away(MapPosition::new(2, 0, 0))
+    );
+
+    let mut other_group = pos_on_map(5, 5, 0);
+    other_group.map.group = 1;
+    assert_eq!(
+        member_location(Some(pos_on_map(5, 5, 0)), other_group),
+        MemberLocation::Away(other_group.map)
+    );
+}
+
+#[test]
+fn shared_exp_starts_over_in_a_new_party() {
+    let mut party = Party::new();
+    assert!(!party.in_party());
+
+    party.set_members(Some(key(1)), vec![member(1, "Lead"), member(2, "Me")]);
+    party.add_shared_exp(40);
+    party.set_members(Some(key(1)), vec![member(1, "Lead")]);
+    party.add_shared_exp(2);
+
+    assert_eq!(party.shared_exp, 42);
+    assert_eq!(party.member(key(1)).unwrap().name, "Lead");
+    assert!(party.member(key(2)).is_none());
+
+    party.clear();
+    assert!(!party.in_party());
+    assert_eq!(party.leader, None);
+
+    party.set_members(None, vec![member(3, "Other")]);
+    assert_eq!(party.shared_exp, 0);
+}
+
+#[test]
+fn party_chat_shows_on_all_and_party_tabs() {
+    assert!(can_channel_show(MessageChannel::Party, 0));
+    assert!(can_channel_show(MessageChannel::Party, 3));
+    assert!(!can_channel_show(MessageChannel::Party, 1));
+    assert!(!can_channel_show(MessageChannel::Map, 3));
+    assert!(!can_channel_show(MessageChannel::Global, 3));
+}
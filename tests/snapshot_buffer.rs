@@ -0,0 +1,162 @@
+mod common;
+
+use client::{
+    MAX_EXTRAPOLATION, MAX_PLAYBACK_DELAY, MIN_PLAYBACK_DELAY, Playback,
+    STEP_TIME, SnapshotBuffer,
+};
+use common::pos;
+
+const RIGHT: u8 = 1;
+const FRAME: f32 = 1.0 / 60.0;
+
+fn tile_x(playback: &Playback) -> f32 {
+    playback.from.x as f32
+        + (playback.to.x - playback.from.x) as f32 * playback.progress
+}
+
+/// Plays `arrivals` of single steps to the right at 60 frames a second and
+/// returns the drawn x of every frame.
+fn play(buffer: &mut SnapshotBuffer, arrivals: &[f32], until: f32) -> Vec<f32> {
+    let mut next = 0;
+    let mut frames = Vec::new();
+    let mut now = 0.0;
+
+    while now < until {
+        while next < arrivals.len() && arrivals[next] <= now {
+            buffer.push(
+                arrivals[next],
+                pos(next as i32, 0),
+                pos(next as i32 + 1, 0),
+                RIGHT,
+            );
+            next += 1;
+        }
+
+        if let Some(playback) = buffer.sample(now) {
+            frames.push(tile_x(&playback));
+        }
+
+        now += FRAME;
+    }
+
+    frames
+}
+
+/// No frame moves further than `max_step` tiles and the entity never walks
+/// back more than it extrapolated.
+fn assert_smooth(frames: &[f32], max_step: f32) {
+    let overshoot = MAX_EXTRAPOLATION / STEP_TIME;
+    let mut furthest = f32::MIN;
+
+    for pair in frames.windows(2) {
+        let step = pair[1] - pair[0];
+
+        furthest = furthest.max(pair[0]);
+        assert!(step.abs() <= max_step + 1e-4, "jumped: {pair:?}");
+        assert!(
+            pair[1] >= furthest.floor() - 1e-4
+                && pair[1] <= furthest.floor() + 1.0 + overshoot + 1e-4,
+            "walked backwards: {pair:?}"
+        );
+    }
+}
+
+#[test]
+fn steady_packets_play_back_smoothly() {
+    let mut buffer = SnapshotBuffer::new();
+    let arrivals: Vec<f32> =
+        (0..10).map(|i| 1.0 + i as f32 * STEP_TIME).collect();
+
+    let frames = play(&mut buffer, &arrivals, 4.0);
+
+    assert_smooth(&frames, FRAME / STEP_TIME);
+    assert!(buffer.delay() - MIN_PLAYBACK_DELAY < 1e-3);
+    assert_eq!(frames.last().copied(), Some(10.0));
+
+    // Walking never pauses between the first and the last step.
+    let walking: Vec<f32> = frames
+        .iter()
+        .copied()
+        .skip_while(|x| *x < 0.5)
+        .take_while(|x| *x < 9.5)
+        .collect();
+
+    assert!(walking.windows(2).all(|pair| pair[1] > pair[0]));
+}
+
+#[test]
+fn jittered_packets_raise_the_delay() {
+    let jitter = [0.0, 0.09, -0.06, 0.1, 0.0, -0.08, 0.07, -0.05, 0.08, 0.0];
+    let arrivals: Vec<f32> = jitter
+        .iter()
+        .enumerate()
+        .map(|(i, offset)| 1.0 + i as f32 * STEP_TIME + offset)
+        .collect();
+    let mut buffer = SnapshotBuffer::new();
+
+    let frames = play(&mut buffer, &arrivals, 4.0);
+
+    assert!(buffer.delay() > MIN_PLAYBACK_DELAY);
+    assert!(buffer.delay() <= MAX_PLAYBACK_DELAY);
+    assert_smooth(&frames, FRAME / (STEP_TIME * 0.5));
+    assert_eq!(frames.last().copied(), Some(10.0));
+}
+
+#[test]
+fn late_packets_extrapolate_briefly() {
+    let mut buffer = SnapshotBuffer::new();
+
+    for x in 0..3 {
+        buffer.push(x as f32 * STEP_TIME, pos(x, 0), pos(x + 1, 0), RIGHT);
+    }
+
+    // The last step ends at 0.6 plus the playback delay.
+    let end = 3.0 * STEP_TIME + buffer.delay();
+
+    let playback = buffer.sample(end + MAX_EXTRAPOLATION * 0.5).unwrap();
+    assert!(playback.moving);
+    assert_eq!(playback.to, pos(3, 0));
+    assert!(tile_x(&playback) > 3.0);
+
+    // It then walks back and stands on the last tile.
+    let playback = buffer.sample(end + MAX_EXTRAPOLATION * 1.5).unwrap();
+    assert!(playback.moving);
+    assert!(tile_x(&playback) > 3.0);
+
+    let playback = buffer.sample(end + MAX_EXTRAPOLATION * 2.5).unwrap();
+    assert!(!playback.moving);
+    assert_eq!(tile_x(&playback), 3.0);
+}
+
+#[test]
+fn far_moves_snap() {
+    let mut buffer = SnapshotBuffer::new();
+
+    buffer.push(1.0, pos(0, 0), pos(6, 0), RIGHT);
+
+    let playback = buffer
+        .sample(1.0 + STEP_TIME * 0.5 + buffer.delay())
+        .unwrap();
+    assert!(!playback.moving);
+    assert_eq!(playback.from, pos(6, 0));
+    assert_eq!(playback.offset(), Default::default());
+}
+
+#[test]
+fn first_step_after_standing_takes_one_step() {
+    let mut buffer = SnapshotBuffer::new();
+
+    buffer.reset(0.0, pos(0, 0), RIGHT);
+    buffer.push(10.0, pos(0, 0), pos(1, 0), RIGHT);
+
+    let before = buffer.sample(9.9).unwrap();
+    assert!(!before.moving);
+    assert_eq!(tile_x(&before), 0.0);
+
+    let halfway = buffer
+        .sample(10.0 + STEP_TIME * 0.5 + buffer.delay())
+        .unwrap();
+    assert!(halfway.moving);
+    assert!((tile_x(&halfway) - 0.5).abs() < 1e-3);
+    assert!((halfway.offset().x - 10.0).abs() < 1e-2);
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use client::{
+    content::game_content::{
+        EffectKind, StatusEffects, effect_time_left, tint_color,
+    },
+    systems::Locale,
+};
+use common::key;
+
+const KINDS: [EffectKind; 8] = [
+    EffectKind::Regeneration,
+    EffectKind::Haste,
+    EffectKind::Might,
+    EffectKind::Shield,
+    EffectKind::Poison,
+    EffectKind::Burn,
+    EffectKind::Slow,
+    EffectKind::Stun,
+];
+
+#[test]
+fn effects_are_applied_refreshed_and_removed() {
+    let mut effects = StatusEffects::new();
+
+    effects.apply(key(1), EffectKind::Poison, 10.0, 0.0);
+    effects.apply(key(1), EffectKind::Haste, 5.0, 1.0);
+    effects.apply(key(1), EffectKind::Poison, 10.0, 2.0);
+
+    let kinds: Vec<_> =
+        effects.effects(key(1)).iter().map(|e| e.kind).collect();
+    assert_eq!(kinds, [EffectKind::Haste, EffectKind::Poison]);
+    assert_eq!(effects.effects(key(1))[1].ends_at, 12.0);
+    assert!(effects.effects(key(2)).is_empty());
+
+    effects.remove(key(1), EffectKind::Haste);
+    assert_eq!(effects.effects(key(1)).len(), 1);
+
+    effects.process(11.9);
+    assert_eq!(effects.effects(key(1)).len(), 1);
+    effects.process(12.0);
+    assert!(effects.effects(key(1)).is_empty());
+}
+
+#[test]
+fn newest_tinting_effect_colours_the_sprite() {
+    let mut effects = StatusEffects::new();
+
+    effects.apply(key(1), EffectKind::Poison, 10.0, 0.0);
+    effects.apply(key(1), EffectKind::Burn, 10.0, 0.0);
+    effects.apply(key(1), EffectKind::Haste, 10.0, 0.0);
+    assert_eq!(effects.tint(key(1)), EffectKind::Burn.tint());
+
+    effects.remove(key(1), EffectKind::Burn);
+    assert_eq!(effects.tint(key(1)), EffectKind::Poison.tint());
+
+    effects.remove(key(1), EffectKind::Poison);
+    assert_eq!(effects.tint(key(1)), None);
+}
+
+#[test]
+fn tint_keeps_the_death_alpha() {
+    assert_eq!(
+        tint_color([255, 255, 255, 130], [255, 128, 0]),
+        [255, 128, 0, 130]
+    );
+    assert_eq!(
+        tint_color([90, 90, 90, 150], [255, 255, 255]),
+        [90, 90, 90, 150]
+    );
+}
+
+#[test]
+fn countdown_switches_to_minutes() {
+    assert_eq!(effect_time_left(-1.0), ("status.seconds", 0));
+    assert_eq!(effect_time_left(0.2), ("status.seconds", 1));
+    assert_eq!(effect_time_left(59.0), ("status.seconds", 59));
+    assert_eq!(effect_time_left(59.5), ("status.minutes", 1));
+    assert_eq!(effect_time_left(61.0), ("status.minutes", 2));
+}
+
+#[test]
+fn every_effect_has_a_name_and_description() {
+    let locale = Locale::default();
+
+    for kind in KINDS {
+        let key = kind.locale_key();
+        let desc = format!("{key}_desc");
+
+        assert_ne!(locale.get(key), key);
+        assert_ne!(locale.get(&desc), desc);
+    }
+}
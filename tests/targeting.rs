@@ -0,0 +1,51 @@
+mod common;
+
+use client::content::game_content::{next_tab_target, target_distance};
+use common::{key, pos_on_map};
+
+#[test]
+fn distance_counts_tiles_across_maps() {
+    assert_eq!(
+        target_distance(pos_on_map(5, 5, 0), pos_on_map(5, 5, 0)),
+        Some(0)
+    );
+    assert_eq!(
+        target_distance(pos_on_map(5, 5, 0), pos_on_map(5, 9, 0)),
+        Some(4)
+    );
+    assert_eq!(
+        target_distance(pos_on_map(5, 5, 0), pos_on_map(8, 9, 0)),
+        Some(6)
+    );
+    assert_eq!(
+        target_distance(pos_on_map(30, 5, 0), pos_on_map(1, 5, 1)),
+        Some(3)
+    );
+
+    let mut other_group = pos_on_map(5, 5, 0);
+    other_group.map.group = 1;
+    assert_eq!(target_distance(pos_on_map(5, 5, 0), other_group), None);
+}
+
+#[test]
+fn tab_starts_at_the_nearest_target() {
+    let candidates = vec![(key(1), 6), (key(2), 2), (key(3), 4)];
+
+    assert_eq!(next_tab_target(candidates.clone(), None), Some(key(2)));
+    assert_eq!(next_tab_target(candidates, Some(key(9))), Some(key(2)));
+    assert_eq!(next_tab_target(Vec::new(), Some(key(1))), None);
+}
+
+#[test]
+fn tab_cycles_outwards_and_wraps() {
+    let candidates = vec![(key(1), 6), (key(2), 2), (key(3), 4), (key(4), 4)];
+    let mut target = None;
+    let mut order = Vec::new();
+
+    for _ in 0..5 {
+        target = next_tab_target(candidates.clone(), target);
+        order.push(target.unwrap());
+    }
+
+    assert_eq!(order, [key(2), key(3), key(4), key(1), key(2)]);
+}
@@ -0,0 +1,40 @@
+use client::widget::{DEFAULT_THEME, THEME_FOLDER, Theme};
+
+#[test]
+fn themes_parse() {
+    let mut count = 0;
+
+    for entry in std::fs::read_dir(THEME_FOLDER).unwrap().flatten() {
+        let path = entry.path();
+
+        if path.extension().is_none_or(|ext| ext != "toml") {
+            continue;
+        }
+
+        let data = std::fs::read_to_string(&path).unwrap();
+
+        if let Err(e) = toml::from_str::<Theme>(&data) {
+            panic!("{} failed to parse: {e}", path.display());
+        }
+
+        count += 1;
+    }
+
+    assert!(count > 0, "no themes found in {THEME_FOLDER}");
+}
+
+#[test]
+fn theme_list_starts_with_default() {
+    let list = Theme::list();
+
+    assert_eq!(list.first().map(String::as_str), Some(DEFAULT_THEME));
+    assert_eq!(list.iter().filter(|id| *id == DEFAULT_THEME).count(), 1);
+}
+
+#[test]
+fn missing_theme_falls_back_to_default() {
+    let theme = Theme::load("missing_theme");
+
+    assert_eq!(theme.id, DEFAULT_THEME);
+    assert_eq!(theme.name, Theme::default().name);
+}
@@ -0,0 +1,83 @@
+use client::{
+    content::game_content::{
+        COMBAT_LOG_WIDGETS, INSPECT_WIDGETS, INVENTORY_WIDGETS,
+        PARTY_FRAMES_WIDGETS, PROFILE_WIDGETS, SETTING_WIDGETS, SHOP_WIDGETS,
+        STORAGE_WIDGETS, TARGET_FRAME_WIDGETS, TRADE_WIDGETS,
+    },
+    widget::{LayoutKind, UI_LAYOUT_FOLDER, UiLayout},
+};
+
+fn shipped_layout(name: &str) -> UiLayout {
+    let path = format!("{UI_LAYOUT_FOLDER}/{name}.toml");
+    toml::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn layouts_parse() {
+    let mut count = 0;
+
+    for entry in std::fs::read_dir(UI_LAYOUT_FOLDER).unwrap().flatten() {
+        let path = entry.path();
+
+        if path.extension().is_none_or(|ext| ext != "toml") {
+            continue;
+        }
+
+        let data = std::fs::read_to_string(&path).unwrap();
+
+        let layout = match toml::from_str::<UiLayout>(&data) {
+            Ok(layout) => layout,
+            Err(e) => panic!("{} failed to parse: {e}", path.display()),
+        };
+
+        assert!(
+            layout.tab.is_empty() || layout.tab_bar.is_some(),
+            "{} has tabs but no tab_bar",
+            path.display()
+        );
+
+        for tab in layout.tab.iter() {
+            for id in tab.widgets.iter() {
+                assert!(
+                    layout.has_widget(id),
+                    "{} tab {} lists unknown widget {id}",
+                    path.display(),
+                    tab.id
+                );
+            }
+        }
+
+        count += 1;
+    }
+
+    assert!(count > 0, "no layouts found in {UI_LAYOUT_FOLDER}");
+}
+
+#[test]
+fn layouts_have_the_widgets_their_windows_use() {
+    for (name, required) in [
+        ("setting", SETTING_WIDGETS),
+        ("combat_log", COMBAT_LOG_WIDGETS),
+        ("inspect", INSPECT_WIDGETS),
+        ("target_frame", TARGET_FRAME_WIDGETS),
+        ("party_frames", PARTY_FRAMES_WIDGETS),
+        ("inventory", INVENTORY_WIDGETS),
+        ("storage", STORAGE_WIDGETS),
+        ("shop", SHOP_WIDGETS),
+        ("trade", TRADE_WIDGETS),
+        ("profile", PROFILE_WIDGETS),
+    ] {
+        assert_eq!(shipped_layout(name).missing_widget(required), None);
+    }
+}
+
+#[test]
+fn required_widgets_must_have_the_right_type() {
+    let layout = shipped_layout("setting");
+
+    assert!(layout.has_widget("sfx_text"));
+    assert_eq!(
+        layout.missing_widget(&[(LayoutKind::Slider, "sfx_text")]),
+        Some((LayoutKind::Slider, "sfx_text"))
+    );
+}